@@ -0,0 +1,160 @@
+//! Procedural macros for the [`problem_details`](https://crates.io/crates/problem_details) crate.
+//!
+//! The macros are re-exported by `problem_details` behind feature flags and should be
+//! used from there, as the generated code refers to the `problem_details` crate.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::{Error, Expr, ExprLit, Fields, GenericParam, Ident, ItemStruct, Lit, LitStr, Token};
+
+/// Turns a tuple struct wrapping an extractor into an extractor whose rejection is a problem.
+///
+/// See `problem_details::axum::problem_rejection` for details.
+#[proc_macro_attribute]
+pub fn problem_rejection(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = syn::parse_macro_input!(args as RejectionArgs);
+    let item = syn::parse_macro_input!(item as ItemStruct);
+
+    expand_problem_rejection(args, item)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// The settings of [`problem_rejection`], e.g. `type = "...", status = 400, source = Body`.
+#[derive(Default)]
+struct RejectionArgs {
+    r#type: Option<LitStr>,
+    status: Option<Expr>,
+    source: Option<Ident>,
+}
+
+impl Parse for RejectionArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = Self::default();
+
+        while !input.is_empty() {
+            let key = Ident::parse_any(input)?;
+            input.parse::<Token![=]>()?;
+
+            match key.to_string().as_str() {
+                "type" if args.r#type.is_none() => args.r#type = Some(input.parse()?),
+                "status" if args.status.is_none() => args.status = Some(input.parse()?),
+                "source" if args.source.is_none() => args.source = Some(input.parse()?),
+                "type" | "status" | "source" => {
+                    return Err(Error::new(key.span(), format!("duplicate setting `{key}`")))
+                }
+                _ => {
+                    return Err(Error::new(
+                        key.span(),
+                        format!("unknown setting `{key}`, expected `type`, `status` or `source`"),
+                    ))
+                }
+            }
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(args)
+    }
+}
+
+fn expand_problem_rejection(
+    args: RejectionArgs,
+    item: ItemStruct,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let ItemStruct {
+        attrs,
+        vis,
+        ident,
+        generics,
+        fields,
+        ..
+    } = item;
+
+    let field = match &fields {
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0],
+        _ => {
+            return Err(Error::new(
+                fields.span(),
+                "#[problem_rejection] requires a tuple struct with a single field",
+            ))
+        }
+    };
+    let field_vis = &field.vis;
+    let field_ty = &field.ty;
+    if let Some(attr) = field.attrs.first() {
+        return Err(Error::new(
+            attr.span(),
+            "#[problem_rejection] does not support attributes on the wrapped extractor",
+        ));
+    }
+
+    if let Some(where_clause) = &generics.where_clause {
+        return Err(Error::new(
+            where_clause.span(),
+            "#[problem_rejection] does not support where clauses",
+        ));
+    }
+    let params = generics
+        .params
+        .iter()
+        .map(|param| match param {
+            GenericParam::Type(param)
+                if param.bounds.is_empty() && param.default.is_none() && param.attrs.is_empty() =>
+            {
+                Ok(&param.ident)
+            }
+            _ => Err(Error::new(
+                param.span(),
+                "#[problem_rejection] only supports type parameters without bounds",
+            )),
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    let generics = (!params.is_empty()).then(|| quote!(<#(#params),*>));
+
+    let ty = args.r#type.map(|ty| quote!(type: #ty,));
+    let status = args.status.map(status_code).transpose()?;
+    let status = status.map(|status| quote!(status: #status,));
+    let source = args.source.map(|source| quote!(source: #source,));
+
+    Ok(quote! {
+        ::problem_details::problem_rejection! {
+            #(#attrs)*
+            #vis struct #ident #generics (#field_vis #field_ty) {
+                #ty
+                #status
+                #source
+            }
+        }
+    })
+}
+
+/// Converts integer literals into a `StatusCode`, checking them at compile time.
+fn status_code(status: Expr) -> syn::Result<proc_macro2::TokenStream> {
+    let Expr::Lit(ExprLit {
+        lit: Lit::Int(literal),
+        ..
+    }) = &status
+    else {
+        return Ok(quote!(#status));
+    };
+
+    let code = literal.base10_parse::<u16>().ok();
+    if !code.is_some_and(|code| (100..1000).contains(&code)) {
+        return Err(Error::new(
+            literal.span(),
+            "#[problem_rejection] requires a valid status code",
+        ));
+    }
+
+    Ok(quote! {
+        ::problem_details::__private::StatusCode::from_u16(#literal)
+            .expect("#[problem_rejection] requires a valid status code")
+    })
+}
@@ -0,0 +1,159 @@
+//! Conversion between [`ProblemDetails`] and AWS API Gateway proxy integration responses.
+//! Requires feature `aws`.
+//!
+//! Lambda functions behind an API Gateway proxy integration return their response as
+//! [`ProxyResponse`], which serializes to the expected `statusCode`, `headers` and `body`
+//! members. Errors produced by API Gateway itself, e.g. for missing authentication, have
+//! a `{"message": "..."}` body and can be read as problem using [`parse_gateway_error`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use http::StatusCode;
+//! use problem_details::{aws, ProblemDetails};
+//!
+//! let details = ProblemDetails::from_status_code(StatusCode::NOT_FOUND)
+//!     .with_detail("No order with id 42");
+//!
+//! let response = aws::ProxyResponse::from(details);
+//! assert_eq!(
+//!     serde_json::to_value(&response).unwrap(),
+//!     serde_json::json!({
+//!         "statusCode": 404,
+//!         "headers": { "content-type": "application/problem+json" },
+//!         "body": r#"{"status":404,"title":"Not Found","detail":"No order with id 42"}"#,
+//!         "isBase64Encoded": false,
+//!     })
+//! );
+//! ```
+use std::collections::BTreeMap;
+
+use http::{header, StatusCode};
+use serde_json::Value;
+
+use crate::dynamic_extensions::RESERVED_MEMBERS;
+use crate::{JsonExtensions, JsonProblemDetails, ProblemDetails};
+
+/// A response of a Lambda function for an API Gateway proxy integration.
+///
+/// The format is understood by REST APIs, HTTP APIs (payload format 1.0 and 2.0)
+/// and Lambda function URLs.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyResponse {
+    /// The HTTP status code.
+    pub status_code: u16,
+
+    /// The response headers. Headers with multiple values are joined by commas.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+
+    /// The response body.
+    #[serde(default)]
+    pub body: String,
+
+    /// Whether the body is base64 encoded, always false for problem details.
+    #[serde(default)]
+    pub is_base64_encoded: bool,
+}
+
+/// The body is written like [`JsonProblemDetails::to_body_string`], and the headers
+/// of the problem are included. If the problem cannot be serialized, an empty
+/// `500 Internal Server Error` response is returned.
+impl<Ext> From<ProblemDetails<Ext>> for ProxyResponse
+where
+    Ext: serde::Serialize,
+{
+    fn from(details: ProblemDetails<Ext>) -> Self {
+        let mut details = details.with_configured_default_type();
+        let status_code = details.status_or_default().as_u16();
+
+        let mut headers = BTreeMap::new();
+        for (name, value) in details.headers.take().unwrap_or_default().iter() {
+            let Ok(value) = value.to_str() else {
+                continue;
+            };
+            headers
+                .entry(name.as_str().to_string())
+                .and_modify(|existing: &mut String| {
+                    existing.push(',');
+                    existing.push_str(value);
+                })
+                .or_insert_with(|| value.to_string());
+        }
+
+        let Ok(body) = JsonProblemDetails(details).to_body_string() else {
+            return Self {
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+                ..Self::default()
+            };
+        };
+
+        headers.insert(
+            header::CONTENT_TYPE.as_str().to_string(),
+            JsonProblemDetails::<Ext>::CONTENT_TYPE.to_string(),
+        );
+
+        Self {
+            status_code,
+            headers,
+            body,
+            is_base64_encoded: false,
+        }
+    }
+}
+
+/// Reads an error response produced by API Gateway as problem details.
+///
+/// API Gateway reports errors like missing authentication or throttling with a
+/// `{"message": "..."}` body (`Message` for some authorization errors). The message
+/// is used as detail of a problem with the given status, and other members are kept
+/// as extensions. Bodies which already are problem details are parsed as such.
+///
+/// ```rust
+/// use http::StatusCode;
+/// use problem_details::aws;
+///
+/// let details = aws::parse_gateway_error(
+///     StatusCode::FORBIDDEN,
+///     r#"{"message":"Missing Authentication Token"}"#,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(details.status, Some(StatusCode::FORBIDDEN));
+/// assert_eq!(details.title.as_deref(), Some("Forbidden"));
+/// assert_eq!(details.detail.as_deref(), Some("Missing Authentication Token"));
+/// ```
+pub fn parse_gateway_error(
+    status: StatusCode,
+    body: &str,
+) -> serde_json::Result<ProblemDetails<JsonExtensions>> {
+    let mut members = serde_json::from_str::<JsonExtensions>(body)?;
+
+    if RESERVED_MEMBERS
+        .iter()
+        .any(|key| members.contains_key(*key))
+    {
+        let details: ProblemDetails<JsonExtensions> = serde_json::from_value(members.into())?;
+        return Ok(match details.status {
+            Some(_) => details,
+            None => details.with_status(status),
+        });
+    }
+
+    let message = ["message", "Message"]
+        .into_iter()
+        .find_map(|key| match members.get(key) {
+            Some(Value::String(message)) => Some((key, message.clone())),
+            _ => None,
+        });
+
+    let details = ProblemDetails::from_status_code(status);
+    Ok(match message {
+        Some((key, message)) => {
+            members.retain(|existing, _| existing != key);
+            details.with_detail(message).with_extensions(members)
+        }
+        None => details.with_extensions(members),
+    })
+}
@@ -5,6 +5,44 @@
 //! With the `axum` feature enabled, [`ProblemDetails`] implements [`IntoResponse`] using
 //! [`JsonProblemDetails`]. You can also return [`JsonProblemDetails`] to be specific.
 //! If you want to return XML, you can use [`XmlProblemDetails`].
+//! Concise problem details in CBOR are returned using `CborProblemDetails` (requires feature `cbor`).
+//! YAML is returned using `YamlProblemDetails` (requires feature `yaml`).
+//! Browsers can be served an HTML page using `HtmlProblemDetails` (requires feature `html`).
+//! As last resort, [`TextProblemDetails`] returns a plain text body.
+//! Partial failures of batch requests are returned as `207 Multi-Status` using [`MultiStatusProblems`].
+//!
+//! To select the format by the `Accept` request header, extract an [`AcceptedFormat`]
+//! and return a [`NegotiatedProblemDetails`]:
+//!
+//! ```rust
+//! use http::StatusCode;
+//! use problem_details::{AcceptedFormat, NegotiatedProblemDetails, ProblemDetails};
+//!
+//! async fn handler(format: AcceptedFormat) -> Result<&'static str, NegotiatedProblemDetails> {
+//!     Err(format.respond(ProblemDetails::from_status_code(StatusCode::NOT_FOUND)))
+//! }
+//! # let _app: axum::Router = axum::Router::new().route("/", axum::routing::get(handler));
+//! ```
+//!
+//! To negotiate the format of all problems returned by a router instead, add a [`NegotiationLayer`].
+//!
+//! The rejections of the built-in extractors, like `JsonRejection` or `PathRejection`,
+//! convert into problems with a [`RejectionExtension`] describing what failed.
+//! With feature `axum-ws`, failed WebSocket upgrades are rejected with problems
+//! using `ProblemWebSocketUpgrade`. With feature `axum-multipart`, the errors of
+//! file uploads using `Multipart` convert into problems naming the offending part.
+//! Other extractors can be wrapped using [`problem_rejection!`](crate::problem_rejection),
+//! or the `#[problem_rejection]` attribute with feature `axum-macros`, to reject requests
+//! with problems.
+//!
+//! Errors produced by axum itself, like `405 Method Not Allowed`, are plain text responses.
+//! Add a [`ProblemDetailsLayer`] to the router to convert them into problems.
+//! Panics in handlers are converted into `500` problems by a [`CatchPanicProblemLayer`].
+//! Unmatched routes and methods are answered with problems by registering
+//! [`not_found_fallback`] and [`method_not_allowed_fallback`] as fallbacks of the router.
+//! A [`ProblemContextLayer`] fills in the `instance` and the request id of all problems.
+//! How the problems of a router are rendered is configured by adding a [`ProblemDetailsConfig`]
+//! as layer.
 //!
 //! # Example
 //!
@@ -25,31 +63,117 @@
 //!     // build and run server...
 //! }
 //! ```
-use axum::{
-    response::{IntoResponse, Response},
-    Json,
-};
+#[cfg(feature = "serde")]
+use axum::response::{IntoResponse, Response};
+#[cfg(feature = "serde")]
 use http::{header, StatusCode};
 
+#[cfg(feature = "json")]
 use crate::ProblemDetails;
 
 #[cfg(feature = "json")]
-use crate::JsonProblemDetails;
+mod body;
+#[cfg(feature = "json")]
+mod catch_panic;
+#[cfg(feature = "json")]
+mod config;
+#[cfg(feature = "json")]
+mod context;
+#[cfg(feature = "json")]
+mod fallback;
+#[cfg(feature = "json")]
+mod layer;
+#[cfg(feature = "json")]
+mod negotiation;
+#[cfg(feature = "json")]
+mod rejection;
+
+#[cfg(feature = "json")]
+pub use catch_panic::*;
+#[cfg(feature = "json")]
+pub use config::*;
+#[cfg(feature = "json")]
+pub use context::*;
+#[cfg(feature = "json")]
+pub use fallback::*;
+#[cfg(feature = "json")]
+pub use layer::*;
+#[cfg(feature = "json")]
+pub use negotiation::*;
+#[cfg(feature = "json")]
+pub use rejection::*;
+
+/// Attribute version of [`problem_rejection!`](crate::problem_rejection), turning a tuple
+/// struct around an extractor into an extractor whose rejection is converted into
+/// [`ProblemDetails`] with a [`RejectionExtension`].
+///
+/// The settings are given as arguments of the attribute, all of them are optional:
+///
+/// - `type`: A URI string literal used as problem type
+/// - `status`: A status code literal, or a [`StatusCode`] expression, used instead of
+///   the status of the rejection
+/// - `source`: The [`RejectionSource`] variant reported in the extension, `Request` by default
+///
+/// The struct must have a single field and only type parameters without bounds.
+/// The generated code refers to this crate as `problem_details`, so it cannot be renamed
+/// in the `Cargo.toml` of crates using this macro.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{routing::get, Router};
+/// use axum::extract::Query;
+/// use problem_details::axum::problem_rejection;
+///
+/// /// Query string whose rejections are problems.
+/// #[problem_rejection(type = "https://example.com/probs/invalid-query", status = 422)]
+/// pub struct ProblemQuery<T>(pub Query<T>);
+///
+/// async fn handler(ProblemQuery(Query(query)): ProblemQuery<Vec<(String, String)>>) -> String {
+///     format!("{query:?}")
+/// }
+///
+/// let app: Router = Router::new().route("/", get(handler));
+/// ```
+///
+/// Requires feature `axum-macros`.
+#[cfg(feature = "axum-macros")]
+pub use problem_details_macros::problem_rejection;
+
+#[cfg(feature = "json")]
+use crate::{
+    AcceptedFormat, JsonProblemDetails, MultiStatusProblems, NegotiatedProblemDetails,
+    ProblemFormat, TextProblemDetails,
+};
 
 #[cfg(feature = "xml")]
 use crate::XmlProblemDetails;
 
+#[cfg(feature = "cbor")]
+use crate::CborProblemDetails;
+
+#[cfg(feature = "yaml")]
+use crate::YamlProblemDetails;
+
+#[cfg(feature = "html")]
+use crate::HtmlProblemDetails;
+
 #[cfg(feature = "json")]
 impl<Ext> IntoResponse for JsonProblemDetails<Ext>
 where
     Ext: serde::Serialize,
 {
     fn into_response(self) -> Response {
-        let status_code = self.0.status.unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
         let content_type = [(header::CONTENT_TYPE, Self::CONTENT_TYPE)];
-        let content = Json(self.0);
+        let content = match Self(details).to_body_bytes() {
+            Ok(json) => json,
+            Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        };
 
-        (status_code, content_type, content).into_response()
+        (status_code, content_type, headers, content).into_response()
     }
 }
 
@@ -59,14 +183,89 @@ where
     Ext: serde::Serialize,
 {
     fn into_response(self) -> Response {
-        let status_code = self.0.status.unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
         let content_type = [(header::CONTENT_TYPE, Self::CONTENT_TYPE)];
-        let content = match self.to_body_string() {
+        let content = match Self(details).to_body_bytes() {
             Ok(xml) => xml,
             Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
         };
 
-        (status_code, content_type, content).into_response()
+        (status_code, content_type, headers, content).into_response()
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl<Ext> IntoResponse for CborProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn into_response(self) -> Response {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content_type = [(header::CONTENT_TYPE, Self::CONTENT_TYPE)];
+        let content = match Self(details).to_body_vec() {
+            Ok(cbor) => cbor,
+            Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        };
+
+        (status_code, content_type, headers, content).into_response()
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl<Ext> IntoResponse for YamlProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn into_response(self) -> Response {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content_type = [(header::CONTENT_TYPE, Self::CONTENT_TYPE)];
+        let content = match Self(details).to_body_string() {
+            Ok(yaml) => yaml,
+            Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        };
+
+        (status_code, content_type, headers, content).into_response()
+    }
+}
+
+#[cfg(feature = "html")]
+impl<Ext> IntoResponse for HtmlProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn into_response(self) -> Response {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content_type = [(header::CONTENT_TYPE, Self::CONTENT_TYPE)];
+        let content = match Self(details).to_body_string() {
+            Ok(html) => html,
+            Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        };
+
+        (status_code, content_type, headers, content).into_response()
+    }
+}
+
+#[cfg(feature = "json")]
+impl<Ext> IntoResponse for TextProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn into_response(self) -> Response {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content_type = [(header::CONTENT_TYPE, Self::CONTENT_TYPE)];
+        let content = Self(details).to_body_string();
+
+        (status_code, content_type, headers, content).into_response()
     }
 }
 
@@ -79,3 +278,61 @@ where
         JsonProblemDetails(self).into_response()
     }
 }
+
+#[cfg(feature = "json")]
+impl<T, Ext> IntoResponse for MultiStatusProblems<T, Ext>
+where
+    T: serde::Serialize,
+    Ext: serde::Serialize,
+{
+    fn into_response(self) -> Response {
+        let content_type = [(header::CONTENT_TYPE, Self::CONTENT_TYPE)];
+        let content = match self.with_configured_default_type().to_body_string() {
+            Ok(json) => json,
+            Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        };
+
+        (Self::STATUS, content_type, content).into_response()
+    }
+}
+
+#[cfg(feature = "json")]
+impl<Ext> IntoResponse for NegotiatedProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn into_response(self) -> Response {
+        let mut response = match self.format {
+            ProblemFormat::Json => JsonProblemDetails(self.details).into_response(),
+            #[cfg(feature = "xml")]
+            ProblemFormat::Xml => XmlProblemDetails(self.details).into_response(),
+            #[cfg(feature = "html")]
+            ProblemFormat::Html => HtmlProblemDetails(self.details).into_response(),
+            #[cfg(feature = "cbor")]
+            ProblemFormat::Cbor => CborProblemDetails(self.details).into_response(),
+            #[cfg(feature = "yaml")]
+            ProblemFormat::Yaml => YamlProblemDetails(self.details).into_response(),
+            ProblemFormat::Text => TextProblemDetails(self.details).into_response(),
+        };
+
+        response
+            .headers_mut()
+            .append(header::VARY, header::ACCEPT.into());
+        response
+    }
+}
+
+#[cfg(feature = "json")]
+impl<S> axum::extract::FromRequestParts<S> for AcceptedFormat
+where
+    S: Send + Sync,
+{
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(
+        parts: &mut http::request::Parts,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_headers(&parts.headers))
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use axum::body::{Body, Bytes, HttpBody};
+use axum::response::{IntoResponse, Response};
+use http::response::Parts;
+use http::{header, HeaderValue};
+use http_body::Frame;
+
+use crate::{
+    JsonExtensions, JsonProblemDetails, NegotiatedProblemDetails, ProblemDetails, ProblemFormat,
+};
+
+/// Maximum size of the problem bodies read using [`read_problem`].
+const BODY_LIMIT: usize = 64 * 1024;
+
+/// Reads the problem details of an `application/problem+json` response.
+///
+/// Other responses and bodies which are no valid problem details are returned as error.
+/// Bodies larger than [`BODY_LIMIT`] or failing to be read are passed on unchanged.
+pub(super) async fn read_problem(
+    response: Response,
+) -> Result<(Parts, ProblemDetails<JsonExtensions>), Response> {
+    let is_problem = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(is_problem_json);
+    if !is_problem || exceeds_limit(&response) {
+        return Err(response);
+    }
+
+    let (parts, mut body) = response.into_parts();
+    let mut replay = Replay::default();
+    let mut len = 0;
+    while len <= BODY_LIMIT {
+        match std::future::poll_fn(|cx| Pin::new(&mut body).poll_frame(cx)).await {
+            Some(Ok(frame)) => {
+                len += frame.data_ref().map_or(0, Bytes::len);
+                replay.frames.push_back(frame);
+            }
+            Some(Err(err)) => {
+                replay.error = Some(err);
+                return Err(Response::from_parts(parts, Body::new(replay)));
+            }
+            None => break,
+        }
+    }
+    if len > BODY_LIMIT {
+        replay.rest = body;
+        return Err(Response::from_parts(parts, Body::new(replay)));
+    }
+
+    let data = replay
+        .frames
+        .iter()
+        .filter_map(Frame::data_ref)
+        .flatten()
+        .copied()
+        .collect::<Vec<_>>();
+    match ProblemDetails::<JsonExtensions>::from_json_slice(&data) {
+        Ok(details) => Ok((parts, details)),
+        Err(_) => Err(Response::from_parts(parts, Body::new(replay))),
+    }
+}
+
+/// A body replaying the frames already read, followed by the rest of the original body.
+#[derive(Default)]
+struct Replay {
+    frames: VecDeque<Frame<Bytes>>,
+    error: Option<axum::Error>,
+    rest: Body,
+}
+
+impl HttpBody for Replay {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, axum::Error>>> {
+        if let Some(frame) = self.frames.pop_front() {
+            return Poll::Ready(Some(Ok(frame)));
+        }
+        if let Some(err) = self.error.take() {
+            return Poll::Ready(Some(Err(err)));
+        }
+        Pin::new(&mut self.rest).poll_frame(cx)
+    }
+}
+
+/// Creates the response for problem details read using [`read_problem`].
+///
+/// The status, headers and extensions of the original response are kept.
+/// Without a format, the problem is written as JSON.
+pub(super) fn problem_response(
+    mut parts: Parts,
+    mut details: ProblemDetails<JsonExtensions>,
+    format: Option<ProblemFormat>,
+) -> Response {
+    parts.headers.remove(header::CONTENT_TYPE);
+    parts.headers.remove(header::CONTENT_LENGTH);
+    *details.headers_mut() = parts.headers;
+
+    let mut response = match format {
+        Some(format) => NegotiatedProblemDetails::new(details, format).into_response(),
+        None => JsonProblemDetails(details).into_response(),
+    };
+    *response.status_mut() = parts.status;
+    response.extensions_mut().extend(parts.extensions);
+    response
+}
+
+/// Returns true if the body of the response is known to be larger than [`BODY_LIMIT`].
+fn exceeds_limit(response: &Response) -> bool {
+    let content_length = response
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|length| length.to_str().ok()?.parse::<u64>().ok())
+        .unwrap_or(0);
+    let size = content_length.max(response.body().size_hint().lower());
+    size > BODY_LIMIT as u64
+}
+
+/// Returns true if the content type is `application/problem+json`.
+fn is_problem_json(content_type: &HeaderValue) -> bool {
+    content_type.to_str().is_ok_and(|content_type| {
+        content_type
+            .trim_start()
+            .starts_with(JsonProblemDetails::<()>::CONTENT_TYPE)
+    })
+}
@@ -0,0 +1,160 @@
+use std::fmt;
+use std::future::{poll_fn, Future};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::pin::{pin, Pin};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum::response::{IntoResponse, Response};
+use http::{Request, StatusCode};
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{presets, JsonProblemDetails, ProblemDetails, ProblemType};
+
+type ReferenceId = Arc<dyn Fn() -> String + Send + Sync>;
+
+/// A tower layer converting panics in handlers into `500 Internal Server Error` problems.
+///
+/// Without this layer, a panicking handler results in the connection being closed,
+/// or an empty `500` response. The problem does not reveal anything about the panic.
+/// Optionally, a reference id can be generated for each panic, which is included
+/// like in [`presets::internal`] and can be logged using a panic hook.
+///
+/// ```rust
+/// use std::sync::atomic::{AtomicU64, Ordering};
+///
+/// use axum::{routing::get, Router};
+/// use problem_details::axum::CatchPanicProblemLayer;
+///
+/// static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+///
+/// let app: Router = Router::new()
+///     .route("/", get(|| async { "Hello" }))
+///     .layer(
+///         CatchPanicProblemLayer::new()
+///             .with_type(http::Uri::from_static("https://example.com/probs/internal"))
+///             .with_reference_id(|| format!("ERR-{}", NEXT_ID.fetch_add(1, Ordering::Relaxed))),
+///     );
+/// ```
+///
+/// Requires feature `json`.
+#[derive(Clone, Default)]
+pub struct CatchPanicProblemLayer {
+    r#type: Option<ProblemType>,
+    reference_id: Option<ReferenceId>,
+}
+
+impl CatchPanicProblemLayer {
+    /// Creates a new layer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder-style method that sets the type of the problems for panics.
+    #[must_use]
+    pub fn with_type(mut self, r#type: impl Into<ProblemType>) -> Self {
+        self.r#type = Some(r#type.into());
+        self
+    }
+
+    /// Builder-style method that sets a function generating a reference id for each panic.
+    ///
+    /// The reference id is included in the detail and as `reference_id` extension member.
+    #[must_use]
+    pub fn with_reference_id<F>(mut self, reference_id: F) -> Self
+    where
+        F: Fn() -> String + Send + Sync + 'static,
+    {
+        self.reference_id = Some(Arc::new(reference_id));
+        self
+    }
+}
+
+impl fmt::Debug for CatchPanicProblemLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CatchPanicProblemLayer")
+            .field("type", &self.r#type)
+            .field("reference_id", &self.reference_id.is_some())
+            .finish()
+    }
+}
+
+impl<S> Layer<S> for CatchPanicProblemLayer {
+    type Service = CatchPanicProblemService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CatchPanicProblemService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// The service created by [`CatchPanicProblemLayer`].
+#[derive(Clone, Debug)]
+pub struct CatchPanicProblemService<S> {
+    inner: S,
+    layer: CatchPanicProblemLayer,
+}
+
+impl<S, B> Service<Request<B>> for CatchPanicProblemService<S>
+where
+    S: Service<Request<B>, Response = Response>,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let layer = self.layer.clone();
+        let future = match catch_unwind(AssertUnwindSafe(|| self.inner.call(request))) {
+            Ok(future) => future,
+            Err(_) => return Box::pin(async move { Ok(panic_response(&layer)) }),
+        };
+
+        Box::pin(async move {
+            let mut future = pin!(future);
+            let poll = |cx: &mut Context<'_>| match catch_unwind(AssertUnwindSafe(|| {
+                future.as_mut().poll(cx)
+            })) {
+                Ok(Poll::Ready(result)) => Poll::Ready(Some(result)),
+                Ok(Poll::Pending) => Poll::Pending,
+                Err(_) => Poll::Ready(None),
+            };
+
+            poll_fn(poll)
+                .await
+                .unwrap_or_else(|| Ok(panic_response(&layer)))
+        })
+    }
+}
+
+fn panic_response(layer: &CatchPanicProblemLayer) -> Response {
+    match &layer.reference_id {
+        Some(reference_id) => {
+            let details = presets::internal(reference_id());
+            JsonProblemDetails(with_type(details, layer)).into_response()
+        }
+        None => {
+            let details = ProblemDetails::from_status_code(StatusCode::INTERNAL_SERVER_ERROR);
+            JsonProblemDetails(with_type(details, layer)).into_response()
+        }
+    }
+}
+
+fn with_type<Ext>(
+    details: ProblemDetails<Ext>,
+    layer: &CatchPanicProblemLayer,
+) -> ProblemDetails<Ext> {
+    match &layer.r#type {
+        Some(r#type) => details.with_type(r#type.clone()),
+        None => details,
+    }
+}
@@ -0,0 +1,208 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum::response::Response;
+use http::{Request, StatusCode};
+use tower_layer::Layer;
+use tower_service::Service;
+
+use super::body::{problem_response, read_problem};
+use crate::{ContentNegotiation, Correlation, ProblemFormat, ProblemType, RedactionPolicy};
+
+type Redaction = Arc<dyn RedactionPolicy + Send + Sync>;
+
+/// Configuration of how the problems of a router are rendered.
+///
+/// The configuration is added to a router as layer. It applies to all
+/// `application/problem+json` responses passing through it, including the problems of
+/// the crate's `IntoResponse` impls, rejections and inner layers like
+/// [`CatchPanicProblemLayer`](super::CatchPanicProblemLayer). Unlike the process-wide
+/// settings (e.g. [`set_default_type`](crate::set_default_type)), different routers can use
+/// different configurations. The configuration is also added to the request extensions,
+/// so handlers can extract it using `Extension<ProblemDetailsConfig>`.
+///
+/// ```rust
+/// use axum::{routing::get, Router};
+/// use problem_details::axum::ProblemDetailsConfig;
+/// use problem_details::{ContentNegotiation, ProductionPolicy};
+///
+/// let config = ProblemDetailsConfig::new()
+///     .with_default_type(http::Uri::from_static("https://example.com/probs/generic"))
+///     .with_redaction(ProductionPolicy::default())
+///     .with_negotiation(ContentNegotiation::new())
+///     .with_correlation(true);
+///
+/// let app: Router = Router::new()
+///     .route("/", get(|| async { "Hello" }))
+///     .layer(config);
+/// ```
+///
+/// Requires feature `json`.
+#[derive(Clone, Default)]
+pub struct ProblemDetailsConfig {
+    default_type: Option<ProblemType>,
+    redaction: Option<Redaction>,
+    negotiation: Option<ContentNegotiation>,
+    correlation: bool,
+}
+
+impl ProblemDetailsConfig {
+    /// Creates a new configuration, which writes problems as JSON without changes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder-style method that sets the type of problems without a `type`.
+    #[must_use]
+    pub fn with_default_type(mut self, r#type: impl Into<ProblemType>) -> Self {
+        self.default_type = Some(r#type.into());
+        self
+    }
+
+    /// Builder-style method that sets the policy used to redact problems, see
+    /// [`ProblemDetails::redacted`](crate::ProblemDetails::redacted).
+    #[must_use]
+    pub fn with_redaction<P>(mut self, policy: P) -> Self
+    where
+        P: RedactionPolicy + Send + Sync + 'static,
+    {
+        self.redaction = Some(Arc::new(policy));
+        self
+    }
+
+    /// Builder-style method that sets the content negotiation selecting the format of problems.
+    ///
+    /// Without negotiation, problems are written as JSON.
+    #[must_use]
+    pub fn with_negotiation(mut self, negotiation: ContentNegotiation) -> Self {
+        self.negotiation = Some(negotiation);
+        self
+    }
+
+    /// Builder-style method that sets whether the [`Correlation`] of the request is added
+    /// to problems. Defaults to `false`.
+    #[must_use]
+    pub fn with_correlation(mut self, correlation: bool) -> Self {
+        self.correlation = correlation;
+        self
+    }
+
+    /// Returns the type of problems without a `type`, if any.
+    pub fn default_type(&self) -> Option<&ProblemType> {
+        self.default_type.as_ref()
+    }
+
+    /// Returns the content negotiation selecting the format of problems, if any.
+    pub fn negotiation(&self) -> Option<&ContentNegotiation> {
+        self.negotiation.as_ref()
+    }
+
+    /// Returns whether the correlation of the request is added to problems.
+    pub fn correlation(&self) -> bool {
+        self.correlation
+    }
+}
+
+impl std::fmt::Debug for ProblemDetailsConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProblemDetailsConfig")
+            .field("default_type", &self.default_type)
+            .field("redaction", &self.redaction.is_some())
+            .field("negotiation", &self.negotiation)
+            .field("correlation", &self.correlation)
+            .finish()
+    }
+}
+
+impl<S> Layer<S> for ProblemDetailsConfig {
+    type Service = ProblemDetailsConfigService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ProblemDetailsConfigService {
+            inner,
+            config: self.clone(),
+        }
+    }
+}
+
+/// The service created by [`ProblemDetailsConfig`].
+#[derive(Clone, Debug)]
+pub struct ProblemDetailsConfigService<S> {
+    inner: S,
+    config: ProblemDetailsConfig,
+}
+
+impl<S, B> Service<Request<B>> for ProblemDetailsConfigService<S>
+where
+    S: Service<Request<B>, Response = Response>,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<B>) -> Self::Future {
+        let config = self.config.clone();
+        let format = config
+            .negotiation
+            .as_ref()
+            .map(|negotiation| negotiation.negotiate_headers(request.headers()));
+        let correlation = config
+            .correlation
+            .then(|| Correlation::from_headers(request.headers()));
+
+        request.extensions_mut().insert(config.clone());
+        let response = self.inner.call(request);
+
+        Box::pin(async move { Ok(apply(response.await?, &config, format, correlation).await) })
+    }
+}
+
+/// Applies the configuration to a problem details response.
+async fn apply(
+    response: Response,
+    config: &ProblemDetailsConfig,
+    format: Option<ProblemFormat>,
+    correlation: Option<Correlation>,
+) -> Response {
+    let (parts, mut details) = match read_problem(response).await {
+        Ok(problem) => problem,
+        Err(response) => return response,
+    };
+
+    if let Some(r#type) = &config.default_type {
+        details = details.with_default_type(r#type.clone());
+    }
+    if let Some(redaction) = &config.redaction {
+        details = details.redacted(&DynPolicy(redaction.as_ref()));
+    }
+    if let Some(correlation) = correlation {
+        details.inject_extension(correlation);
+    }
+
+    problem_response(parts, details, format)
+}
+
+/// Forwards to a boxed redaction policy.
+struct DynPolicy<'a>(&'a (dyn RedactionPolicy + Send + Sync));
+
+impl RedactionPolicy for DynPolicy<'_> {
+    fn redact_detail(&self, status: Option<StatusCode>) -> bool {
+        self.0.redact_detail(status)
+    }
+
+    fn redact_instance(&self, status: Option<StatusCode>) -> bool {
+        self.0.redact_instance(status)
+    }
+
+    fn redact_extension(&self, key: &str, value: &serde_json::Value) -> bool {
+        self.0.redact_extension(key, value)
+    }
+}
@@ -0,0 +1,126 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use axum::response::Response;
+use http::{Request, Uri};
+use tower_layer::Layer;
+use tower_service::Service;
+
+use super::body::{problem_response, read_problem};
+use crate::Correlation;
+
+/// A tower layer filling in the `instance` and correlation members of problem responses.
+///
+/// For every `application/problem+json` response, the layer sets the `instance` to the
+/// path of the request, unless the handler already set one. The request id and trace
+/// context of the request (see [`Correlation::from_headers`]) are added as extension
+/// members, keeping any members the handler set itself. Other responses are passed on
+/// unchanged, so handlers do not have to remember these members for each problem.
+///
+/// ```rust
+/// use axum::{routing::get, Router};
+/// use problem_details::axum::ProblemContextLayer;
+///
+/// let app: Router = Router::new()
+///     .route("/orders/{id}", get(|| async { "Hello" }))
+///     .layer(ProblemContextLayer::new().with_instance_template("https://example.com/requests/{request_id}"));
+/// ```
+///
+/// Requires feature `json`.
+#[derive(Clone, Debug, Default)]
+pub struct ProblemContextLayer {
+    instance_template: Option<String>,
+}
+
+impl ProblemContextLayer {
+    /// Creates a new layer using the request path as `instance`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder-style method that sets a template used for the `instance` instead of the path.
+    ///
+    /// The placeholders `{path}` and `{request_id}` are replaced by the path and id of the
+    /// request. If the request has no id or the result is not a valid URI, no `instance` is set.
+    #[must_use]
+    pub fn with_instance_template(mut self, template: impl Into<String>) -> Self {
+        self.instance_template = Some(template.into());
+        self
+    }
+
+    fn instance(&self, uri: &Uri, correlation: &Correlation) -> Option<Uri> {
+        let Some(template) = &self.instance_template else {
+            return Uri::try_from(uri.path()).ok();
+        };
+
+        let mut instance = template.replace("{path}", uri.path());
+        if instance.contains("{request_id}") {
+            instance = instance.replace("{request_id}", correlation.request_id.as_deref()?);
+        }
+        Uri::try_from(instance).ok()
+    }
+}
+
+impl<S> Layer<S> for ProblemContextLayer {
+    type Service = ProblemContextService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ProblemContextService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// The service created by [`ProblemContextLayer`].
+#[derive(Clone, Debug)]
+pub struct ProblemContextService<S> {
+    inner: S,
+    layer: ProblemContextLayer,
+}
+
+impl<S, B> Service<Request<B>> for ProblemContextService<S>
+where
+    S: Service<Request<B>, Response = Response>,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let correlation = Correlation::from_headers(request.headers());
+        let instance = self.layer.instance(request.uri(), &correlation);
+        let response = self.inner.call(request);
+
+        Box::pin(async move { Ok(with_context(response.await?, instance, correlation).await) })
+    }
+}
+
+/// Adds the instance and correlation members to a problem details response.
+async fn with_context(
+    response: Response,
+    instance: Option<Uri>,
+    correlation: Correlation,
+) -> Response {
+    if instance.is_none() && correlation.is_empty() {
+        return response;
+    }
+    let (parts, mut details) = match read_problem(response).await {
+        Ok(problem) => problem,
+        Err(response) => return response,
+    };
+
+    if details.instance.is_none() {
+        details.instance = instance;
+    }
+    details.inject_extension(correlation);
+
+    problem_response(parts, details, None)
+}
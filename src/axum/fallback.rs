@@ -0,0 +1,50 @@
+use http::{Method, StatusCode, Uri};
+
+use crate::ProblemDetails;
+
+/// A fallback handler responding to unmatched routes with a `404 Not Found` problem.
+///
+/// The path of the request is used as `instance`, the query string is omitted.
+///
+/// ```rust
+/// use axum::{routing::get, Router};
+/// use problem_details::axum::{method_not_allowed_fallback, not_found_fallback};
+///
+/// let app: Router = Router::new()
+///     .route("/", get(|| async { "Hello" }))
+///     .fallback(not_found_fallback)
+///     .method_not_allowed_fallback(method_not_allowed_fallback);
+/// ```
+///
+/// Requires feature `json`.
+pub async fn not_found_fallback(uri: Uri) -> ProblemDetails {
+    with_path_instance(
+        ProblemDetails::from_status_code(StatusCode::NOT_FOUND),
+        &uri,
+    )
+    .with_detail(format!("No resource found at `{}`", uri.path()))
+}
+
+/// A fallback handler responding to unsupported methods with a `405 Method Not Allowed` problem.
+///
+/// Register it using `Router::method_not_allowed_fallback`. axum adds the `Allow` header
+/// listing the supported methods of the route to the response.
+///
+/// Requires feature `json`.
+pub async fn method_not_allowed_fallback(method: Method, uri: Uri) -> ProblemDetails {
+    with_path_instance(
+        ProblemDetails::from_status_code(StatusCode::METHOD_NOT_ALLOWED),
+        &uri,
+    )
+    .with_detail(format!(
+        "Method `{method}` is not allowed at `{}`",
+        uri.path()
+    ))
+}
+
+fn with_path_instance(details: ProblemDetails, uri: &Uri) -> ProblemDetails {
+    match Uri::try_from(uri.path()) {
+        Ok(path) => details.with_instance(path),
+        Err(_) => details,
+    }
+}
@@ -0,0 +1,145 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use axum::response::{IntoResponse, Response};
+use http::{header, HeaderValue, Request};
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{JsonProblemDetails, ProblemDetails};
+
+/// A tower layer converting plain error responses into problem details.
+///
+/// Errors like `405 Method Not Allowed`, `415 Unsupported Media Type` or exceeded
+/// body limits are produced by axum itself, so handlers never see them. This layer
+/// inspects all outgoing `4xx` and `5xx` responses with a plain text or empty body,
+/// and replaces the body with a JSON problem using the status, its title and the
+/// original text as detail. Headers like `Allow` are kept.
+///
+/// Responses with any other content type, including problem details, are passed on unchanged.
+///
+/// ```rust
+/// use axum::{routing::get, Router};
+/// use problem_details::axum::ProblemDetailsLayer;
+///
+/// let app: Router = Router::new()
+///     .route("/", get(|| async { "Hello" }))
+///     .layer(ProblemDetailsLayer::new());
+/// ```
+///
+/// Requires feature `json`.
+#[derive(Clone, Copy, Debug)]
+pub struct ProblemDetailsLayer {
+    detail_limit: usize,
+}
+
+impl ProblemDetailsLayer {
+    /// The default number of bytes of plain text bodies used as detail.
+    pub const DEFAULT_DETAIL_LIMIT: usize = 4096;
+
+    /// Creates a new layer.
+    pub fn new() -> Self {
+        Self {
+            detail_limit: Self::DEFAULT_DETAIL_LIMIT,
+        }
+    }
+
+    /// Builder-style method that sets the maximum size of plain text bodies used as detail.
+    ///
+    /// Larger bodies are dropped, and the problem is sent without detail.
+    #[must_use]
+    pub fn with_detail_limit(mut self, limit: usize) -> Self {
+        self.detail_limit = limit;
+        self
+    }
+}
+
+impl Default for ProblemDetailsLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for ProblemDetailsLayer {
+    type Service = ProblemDetailsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ProblemDetailsService {
+            inner,
+            detail_limit: self.detail_limit,
+        }
+    }
+}
+
+/// The service created by [`ProblemDetailsLayer`].
+#[derive(Clone, Debug)]
+pub struct ProblemDetailsService<S> {
+    inner: S,
+    detail_limit: usize,
+}
+
+impl<S, B> Service<Request<B>> for ProblemDetailsService<S>
+where
+    S: Service<Request<B>, Response = Response>,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let response = self.inner.call(request);
+        let detail_limit = self.detail_limit;
+
+        Box::pin(async move { Ok(into_problem_response(response.await?, detail_limit).await) })
+    }
+}
+
+/// Replaces a plain error response with a problem details response.
+async fn into_problem_response(response: Response, detail_limit: usize) -> Response {
+    let status = response.status();
+    if !status.is_client_error() && !status.is_server_error() {
+        return response;
+    }
+
+    let is_plain = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_none_or(is_plain_text);
+    if !is_plain {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let detail = axum::body::to_bytes(body, detail_limit)
+        .await
+        .ok()
+        .and_then(|body| String::from_utf8(body.to_vec()).ok())
+        .map(|detail| detail.trim().to_string())
+        .filter(|detail| !detail.is_empty());
+
+    parts.headers.remove(header::CONTENT_TYPE);
+    parts.headers.remove(header::CONTENT_LENGTH);
+
+    let mut details = ProblemDetails::from_status_code(status);
+    if let Some(detail) = detail {
+        details = details.with_detail(detail);
+    }
+    *details.headers_mut() = parts.headers;
+
+    let mut response = JsonProblemDetails(details).into_response();
+    response.extensions_mut().extend(parts.extensions);
+    response
+}
+
+fn is_plain_text(content_type: &HeaderValue) -> bool {
+    content_type
+        .to_str()
+        .is_ok_and(|content_type| content_type.trim_start().starts_with("text/plain"))
+}
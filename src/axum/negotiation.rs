@@ -0,0 +1,111 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use axum::response::Response;
+use http::Request;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use super::body::{problem_response, read_problem};
+use crate::{content_negotiation, ContentNegotiation, ProblemFormat};
+
+/// A tower layer encoding problem responses in the format accepted by the request.
+///
+/// Handlers return [`ProblemDetails`](crate::ProblemDetails) as usual, which are encoded as JSON. This layer
+/// selects the format by the `Accept` request header, and re-encodes all
+/// `application/problem+json` responses as XML, HTML or any other format enabled by
+/// crate features, like [`NegotiatedProblemDetails`](crate::NegotiatedProblemDetails) does. Status, headers and
+/// extensions of the response are kept, and a `Vary: Accept` header is added.
+///
+/// By default, the process-wide [`content_negotiation`] is used to select the format.
+///
+/// ```rust
+/// use axum::{routing::get, Router};
+/// use problem_details::axum::NegotiationLayer;
+/// use problem_details::{ContentNegotiation, ProblemFormat};
+///
+/// let app: Router = Router::new()
+///     .route("/", get(|| async { "Hello" }))
+///     .layer(NegotiationLayer::new().with_negotiation(
+///         ContentNegotiation::new().with_formats([ProblemFormat::Json, ProblemFormat::Text]),
+///     ));
+/// ```
+///
+/// Add this layer outside of layers producing problems, like [`ProblemDetailsLayer`](super::ProblemDetailsLayer),
+/// so their responses are negotiated too. Requires feature `json`.
+#[derive(Clone, Debug, Default)]
+pub struct NegotiationLayer {
+    negotiation: Option<ContentNegotiation>,
+}
+
+impl NegotiationLayer {
+    /// Creates a new layer using the process-wide content negotiation.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder-style method that sets the content negotiation used instead of the process-wide one.
+    #[must_use]
+    pub fn with_negotiation(mut self, negotiation: ContentNegotiation) -> Self {
+        self.negotiation = Some(negotiation);
+        self
+    }
+
+    fn negotiation(&self) -> &ContentNegotiation {
+        self.negotiation
+            .as_ref()
+            .unwrap_or_else(|| content_negotiation())
+    }
+}
+
+impl<S> Layer<S> for NegotiationLayer {
+    type Service = NegotiationService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        NegotiationService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// The service created by [`NegotiationLayer`].
+#[derive(Clone, Debug)]
+pub struct NegotiationService<S> {
+    inner: S,
+    layer: NegotiationLayer,
+}
+
+impl<S, B> Service<Request<B>> for NegotiationService<S>
+where
+    S: Service<Request<B>, Response = Response>,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let format = self
+            .layer
+            .negotiation()
+            .negotiate_headers(request.headers());
+        let response = self.inner.call(request);
+
+        Box::pin(async move { Ok(into_format(response.await?, format).await) })
+    }
+}
+
+/// Re-encodes a problem details response in the given format.
+async fn into_format(response: Response, format: ProblemFormat) -> Response {
+    match read_problem(response).await {
+        Ok((parts, details)) => problem_response(parts, details, Some(format)),
+        Err(response) => response,
+    }
+}
@@ -0,0 +1,583 @@
+use axum::extract::path::ErrorKind;
+use axum::extract::rejection::{
+    BytesRejection, ExtensionRejection, FailedToBufferBody, FormRejection, JsonRejection,
+    NestedPathRejection, PathRejection, QueryRejection, RawFormRejection, RawPathParamsRejection,
+    StringRejection,
+};
+use http::StatusCode;
+
+use crate::{MergeExtensions, ProblemDetails};
+
+#[cfg(feature = "axum-ws")]
+use axum::extract::ws::{rejection::WebSocketUpgradeRejection, WebSocketUpgrade};
+#[cfg(feature = "axum-ws")]
+use axum::extract::FromRequestParts;
+#[cfg(feature = "axum-ws")]
+use http::{request::Parts, HeaderValue};
+
+#[cfg(feature = "axum-multipart")]
+use axum::extract::multipart::{MultipartError, MultipartRejection};
+
+/// Extension members of problems created from axum rejections.
+///
+/// The built-in rejections of axum convert into a `ProblemDetails<RejectionExtension>`,
+/// using the status of the rejection and its message as detail. For server errors,
+/// like a missing request extension, the message is omitted, as it describes the
+/// setup of the application.
+///
+/// ```rust
+/// use axum::{extract::rejection::JsonRejection, Json};
+/// use problem_details::axum::RejectionExtension;
+/// use problem_details::ProblemDetails;
+///
+/// async fn handler(
+///     payload: Result<Json<serde_json::Value>, JsonRejection>,
+/// ) -> Result<Json<serde_json::Value>, ProblemDetails<RejectionExtension>> {
+///     Ok(payload?)
+/// }
+/// # let _app: axum::Router = axum::Router::new().route("/", axum::routing::post(handler));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RejectionExtension {
+    /// The name of the rejection, e.g. `JsonSyntaxError`.
+    pub rejection: String,
+
+    /// The part of the request which was rejected.
+    pub source: RejectionSource,
+
+    /// The name of the rejected parameter or header, if known.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub parameter: Option<String>,
+
+    /// The size limit in bytes which was exceeded, if known.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub limit: Option<u64>,
+}
+
+/// The part of a request which was rejected by an extractor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum RejectionSource {
+    /// The request body.
+    Body,
+
+    /// The parameters in the path of the request.
+    Path,
+
+    /// The query string of the request.
+    Query,
+
+    /// A request header.
+    Header,
+
+    /// A request extension, added by a layer or the application.
+    Extension,
+
+    /// The request as a whole, e.g. its method or the matched route.
+    Request,
+}
+
+impl RejectionExtension {
+    /// Creates the extension for a rejection with the given name and source.
+    pub fn new(rejection: impl Into<String>, source: RejectionSource) -> Self {
+        Self {
+            rejection: rejection.into(),
+            source,
+            parameter: None,
+            limit: None,
+        }
+    }
+
+    /// Builder-style method that sets the name of the rejected parameter or header.
+    #[must_use]
+    pub fn with_parameter(mut self, parameter: impl Into<String>) -> Self {
+        self.parameter = Some(parameter.into());
+        self
+    }
+
+    /// Builder-style method that sets the size limit in bytes which was exceeded.
+    #[must_use]
+    pub fn with_limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+impl MergeExtensions for RejectionExtension {
+    fn merge_extensions(&mut self, other: Self) {
+        *self = other;
+    }
+}
+
+/// Creates the problem for a rejection with the given status and message.
+pub(crate) fn rejection_problem(
+    status: StatusCode,
+    message: String,
+    extension: RejectionExtension,
+) -> ProblemDetails<RejectionExtension> {
+    let details = ProblemDetails::from_status_code(status).with_extensions(extension);
+
+    // Messages of server errors describe the application, not the request
+    if status.is_server_error() {
+        details
+    } else {
+        details.with_detail(message)
+    }
+}
+
+fn buffer_rejection_name(rejection: &FailedToBufferBody) -> &'static str {
+    match rejection {
+        FailedToBufferBody::LengthLimitError(_) => "LengthLimitError",
+        FailedToBufferBody::UnknownBodyError(_) => "UnknownBodyError",
+        _ => "FailedToBufferBody",
+    }
+}
+
+impl From<JsonRejection> for ProblemDetails<RejectionExtension> {
+    fn from(rejection: JsonRejection) -> Self {
+        let name = match &rejection {
+            JsonRejection::JsonDataError(_) => "JsonDataError",
+            JsonRejection::JsonSyntaxError(_) => "JsonSyntaxError",
+            JsonRejection::MissingJsonContentType(_) => "MissingJsonContentType",
+            JsonRejection::BytesRejection(BytesRejection::FailedToBufferBody(inner)) => {
+                buffer_rejection_name(inner)
+            }
+            _ => "JsonRejection",
+        };
+        let extension = match &rejection {
+            JsonRejection::MissingJsonContentType(_) => {
+                RejectionExtension::new(name, RejectionSource::Header)
+                    .with_parameter("content-type")
+            }
+            _ => RejectionExtension::new(name, RejectionSource::Body),
+        };
+
+        rejection_problem(rejection.status(), rejection.body_text(), extension)
+    }
+}
+
+impl From<FormRejection> for ProblemDetails<RejectionExtension> {
+    fn from(rejection: FormRejection) -> Self {
+        let extension = match &rejection {
+            FormRejection::InvalidFormContentType(_) => {
+                RejectionExtension::new("InvalidFormContentType", RejectionSource::Header)
+                    .with_parameter("content-type")
+            }
+            FormRejection::FailedToDeserializeForm(_) => {
+                RejectionExtension::new("FailedToDeserializeForm", RejectionSource::Query)
+            }
+            FormRejection::FailedToDeserializeFormBody(_) => {
+                RejectionExtension::new("FailedToDeserializeFormBody", RejectionSource::Body)
+            }
+            FormRejection::BytesRejection(BytesRejection::FailedToBufferBody(inner)) => {
+                RejectionExtension::new(buffer_rejection_name(inner), RejectionSource::Body)
+            }
+            _ => RejectionExtension::new("FormRejection", RejectionSource::Body),
+        };
+
+        rejection_problem(rejection.status(), rejection.body_text(), extension)
+    }
+}
+
+impl From<RawFormRejection> for ProblemDetails<RejectionExtension> {
+    fn from(rejection: RawFormRejection) -> Self {
+        let extension = match &rejection {
+            RawFormRejection::InvalidFormContentType(_) => {
+                RejectionExtension::new("InvalidFormContentType", RejectionSource::Header)
+                    .with_parameter("content-type")
+            }
+            RawFormRejection::BytesRejection(BytesRejection::FailedToBufferBody(inner)) => {
+                RejectionExtension::new(buffer_rejection_name(inner), RejectionSource::Body)
+            }
+            _ => RejectionExtension::new("RawFormRejection", RejectionSource::Body),
+        };
+
+        rejection_problem(rejection.status(), rejection.body_text(), extension)
+    }
+}
+
+impl From<QueryRejection> for ProblemDetails<RejectionExtension> {
+    fn from(rejection: QueryRejection) -> Self {
+        let name = match &rejection {
+            QueryRejection::FailedToDeserializeQueryString(_) => "FailedToDeserializeQueryString",
+            _ => "QueryRejection",
+        };
+        let extension = RejectionExtension::new(name, RejectionSource::Query);
+
+        rejection_problem(rejection.status(), rejection.body_text(), extension)
+    }
+}
+
+/// The rejected parameter is included if axum reports it.
+impl From<PathRejection> for ProblemDetails<RejectionExtension> {
+    fn from(rejection: PathRejection) -> Self {
+        let extension = match &rejection {
+            PathRejection::FailedToDeserializePathParams(inner) => {
+                let extension =
+                    RejectionExtension::new("FailedToDeserializePathParams", RejectionSource::Path);
+                match inner.kind() {
+                    ErrorKind::ParseErrorAtKey { key, .. }
+                    | ErrorKind::InvalidUtf8InPathParam { key }
+                    | ErrorKind::DeserializeError { key, .. } => extension.with_parameter(key),
+                    _ => extension,
+                }
+            }
+            PathRejection::MissingPathParams(_) => {
+                RejectionExtension::new("MissingPathParams", RejectionSource::Path)
+            }
+            _ => RejectionExtension::new("PathRejection", RejectionSource::Path),
+        };
+
+        rejection_problem(rejection.status(), rejection.body_text(), extension)
+    }
+}
+
+impl From<RawPathParamsRejection> for ProblemDetails<RejectionExtension> {
+    fn from(rejection: RawPathParamsRejection) -> Self {
+        let name = match &rejection {
+            RawPathParamsRejection::InvalidUtf8InPathParam(_) => "InvalidUtf8InPathParam",
+            RawPathParamsRejection::MissingPathParams(_) => "MissingPathParams",
+            _ => "RawPathParamsRejection",
+        };
+        let extension = RejectionExtension::new(name, RejectionSource::Path);
+
+        rejection_problem(rejection.status(), rejection.body_text(), extension)
+    }
+}
+
+impl From<ExtensionRejection> for ProblemDetails<RejectionExtension> {
+    fn from(rejection: ExtensionRejection) -> Self {
+        let name = match &rejection {
+            ExtensionRejection::MissingExtension(_) => "MissingExtension",
+            _ => "ExtensionRejection",
+        };
+        let extension = RejectionExtension::new(name, RejectionSource::Extension);
+
+        rejection_problem(rejection.status(), rejection.body_text(), extension)
+    }
+}
+
+impl From<BytesRejection> for ProblemDetails<RejectionExtension> {
+    fn from(rejection: BytesRejection) -> Self {
+        let name = match &rejection {
+            BytesRejection::FailedToBufferBody(inner) => buffer_rejection_name(inner),
+            _ => "BytesRejection",
+        };
+        let extension = RejectionExtension::new(name, RejectionSource::Body);
+
+        rejection_problem(rejection.status(), rejection.body_text(), extension)
+    }
+}
+
+impl From<StringRejection> for ProblemDetails<RejectionExtension> {
+    fn from(rejection: StringRejection) -> Self {
+        let name = match &rejection {
+            StringRejection::FailedToBufferBody(inner) => buffer_rejection_name(inner),
+            StringRejection::InvalidUtf8(_) => "InvalidUtf8",
+            _ => "StringRejection",
+        };
+        let extension = RejectionExtension::new(name, RejectionSource::Body);
+
+        rejection_problem(rejection.status(), rejection.body_text(), extension)
+    }
+}
+
+/// The rejected header is included, and the headers required by RFC 6455 and RFC 9110
+/// are added to the response: `Allow` for `405`, `Sec-WebSocket-Version` for an
+/// unsupported version and `Upgrade` for `426 Upgrade Required`.
+///
+/// Requires feature `axum-ws`.
+#[cfg(feature = "axum-ws")]
+impl From<WebSocketUpgradeRejection> for ProblemDetails<RejectionExtension> {
+    fn from(rejection: WebSocketUpgradeRejection) -> Self {
+        let header = |name: &str, header: &str| {
+            RejectionExtension::new(name, RejectionSource::Header).with_parameter(header)
+        };
+        let (extension, response_header) = match &rejection {
+            WebSocketUpgradeRejection::MethodNotGet(_) => (
+                RejectionExtension::new("MethodNotGet", RejectionSource::Request),
+                Some((http::header::ALLOW, "GET")),
+            ),
+            WebSocketUpgradeRejection::MethodNotConnect(_) => (
+                RejectionExtension::new("MethodNotConnect", RejectionSource::Request),
+                Some((http::header::ALLOW, "CONNECT")),
+            ),
+            WebSocketUpgradeRejection::InvalidConnectionHeader(_) => {
+                (header("InvalidConnectionHeader", "connection"), None)
+            }
+            WebSocketUpgradeRejection::InvalidUpgradeHeader(_) => {
+                (header("InvalidUpgradeHeader", "upgrade"), None)
+            }
+            WebSocketUpgradeRejection::InvalidProtocolPseudoheader(_) => {
+                (header("InvalidProtocolPseudoheader", ":protocol"), None)
+            }
+            WebSocketUpgradeRejection::InvalidWebSocketVersionHeader(_) => (
+                header("InvalidWebSocketVersionHeader", "sec-websocket-version"),
+                Some((http::header::SEC_WEBSOCKET_VERSION, "13")),
+            ),
+            WebSocketUpgradeRejection::WebSocketKeyHeaderMissing(_) => (
+                header("WebSocketKeyHeaderMissing", "sec-websocket-key"),
+                None,
+            ),
+            WebSocketUpgradeRejection::ConnectionNotUpgradable(_) => (
+                RejectionExtension::new("ConnectionNotUpgradable", RejectionSource::Request),
+                Some((http::header::UPGRADE, "websocket")),
+            ),
+            _ => (
+                RejectionExtension::new("WebSocketUpgradeRejection", RejectionSource::Request),
+                None,
+            ),
+        };
+
+        let details = rejection_problem(rejection.status(), rejection.body_text(), extension);
+        match response_header {
+            Some((name, value)) => details.with_header(name, HeaderValue::from_static(value)),
+            None => details,
+        }
+    }
+}
+
+/// A [`WebSocketUpgrade`] extractor whose rejections are problems.
+///
+/// ```rust
+/// use axum::{extract::ws::WebSocket, response::Response, routing::any, Router};
+/// use problem_details::axum::ProblemWebSocketUpgrade;
+///
+/// async fn handler(ProblemWebSocketUpgrade(ws): ProblemWebSocketUpgrade) -> Response {
+///     ws.on_upgrade(|_socket: WebSocket| async {})
+/// }
+///
+/// let app: Router = Router::new().route("/ws", any(handler));
+/// ```
+///
+/// Requires feature `axum-ws`.
+#[cfg(feature = "axum-ws")]
+#[derive(Debug)]
+pub struct ProblemWebSocketUpgrade(pub WebSocketUpgrade);
+
+#[cfg(feature = "axum-ws")]
+impl<S> FromRequestParts<S> for ProblemWebSocketUpgrade
+where
+    S: Send + Sync,
+{
+    type Rejection = ProblemDetails<RejectionExtension>;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        WebSocketUpgrade::from_request_parts(parts, state)
+            .await
+            .map(Self)
+            .map_err(Into::into)
+    }
+}
+
+/// The name of the offending part and the exceeded size limit are included if known.
+/// Errors reading the request body are server errors without detail, unless the
+/// body limit was exceeded.
+///
+/// Requires feature `axum-multipart`.
+#[cfg(feature = "axum-multipart")]
+impl From<MultipartError> for ProblemDetails<RejectionExtension> {
+    fn from(error: MultipartError) -> Self {
+        let extension = std::error::Error::source(&error)
+            .and_then(|source| source.downcast_ref::<multer::Error>())
+            .map_or_else(
+                || RejectionExtension::new("MultipartError", RejectionSource::Body),
+                multer_extension,
+            );
+
+        rejection_problem(error.status(), error.body_text(), extension)
+    }
+}
+
+#[cfg(feature = "axum-multipart")]
+fn multer_extension(error: &multer::Error) -> RejectionExtension {
+    let body = |name: &str, field_name: &Option<String>| {
+        let extension = RejectionExtension::new(name, RejectionSource::Body);
+        match field_name {
+            Some(field_name) => extension.with_parameter(field_name),
+            None => extension,
+        }
+    };
+    let content_type = |name: &str| {
+        RejectionExtension::new(name, RejectionSource::Header).with_parameter("content-type")
+    };
+
+    match error {
+        multer::Error::UnknownField { field_name } => body("UnknownField", field_name),
+        multer::Error::IncompleteFieldData { field_name } => {
+            body("IncompleteFieldData", field_name)
+        }
+        multer::Error::FieldSizeExceeded { limit, field_name } => {
+            body("FieldSizeExceeded", field_name).with_limit(*limit)
+        }
+        multer::Error::StreamSizeExceeded { limit } => {
+            body("StreamSizeExceeded", &None).with_limit(*limit)
+        }
+        multer::Error::IncompleteHeaders => body("IncompleteHeaders", &None),
+        multer::Error::ReadHeaderFailed(_) => body("ReadHeaderFailed", &None),
+        multer::Error::DecodeHeaderName { .. } => body("DecodeHeaderName", &None),
+        multer::Error::DecodeHeaderValue { .. } => body("DecodeHeaderValue", &None),
+        multer::Error::IncompleteStream => body("IncompleteStream", &None),
+        multer::Error::NoMultipart => content_type("NoMultipart"),
+        multer::Error::DecodeContentType(_) => content_type("DecodeContentType"),
+        multer::Error::NoBoundary => content_type("NoBoundary"),
+        multer::Error::StreamReadFailed(source) => match source.downcast_ref::<multer::Error>() {
+            Some(source) => multer_extension(source),
+            None => body("StreamReadFailed", &None),
+        },
+        _ => body("MultipartError", &None),
+    }
+}
+
+/// Requires feature `axum-multipart`.
+#[cfg(feature = "axum-multipart")]
+impl From<MultipartRejection> for ProblemDetails<RejectionExtension> {
+    fn from(rejection: MultipartRejection) -> Self {
+        let extension = match &rejection {
+            MultipartRejection::InvalidBoundary(_) => {
+                RejectionExtension::new("InvalidBoundary", RejectionSource::Header)
+                    .with_parameter("content-type")
+            }
+            _ => RejectionExtension::new("MultipartRejection", RejectionSource::Header),
+        };
+
+        rejection_problem(rejection.status(), rejection.body_text(), extension)
+    }
+}
+
+impl From<NestedPathRejection> for ProblemDetails<RejectionExtension> {
+    fn from(rejection: NestedPathRejection) -> Self {
+        let extension = RejectionExtension::new("NestedPathRejection", RejectionSource::Request);
+
+        rejection_problem(rejection.status(), rejection.body_text(), extension)
+    }
+}
+
+/// Declares a wrapper around an extractor, whose rejection is converted into
+/// [`ProblemDetails`](crate::ProblemDetails) with a [`RejectionExtension`](crate::axum::RejectionExtension).
+///
+/// This works with any extractor whose rejection implements `IntoResponse` and
+/// `Display`, like `TypedHeader` or custom extractors. The problem uses the message
+/// of the rejection as detail, and its status unless configured otherwise.
+/// Optionally, the following settings can be given in this order:
+///
+/// - `type`: A URI string literal used as problem type
+/// - `status`: A [`StatusCode`](http::StatusCode) used instead of the status of the rejection
+/// - `source`: The [`RejectionSource`](crate::axum::RejectionSource) variant reported in the
+///   extension, `Request` by default
+///
+/// The wrapper extracts in the request body if the wrapped extractor does, and from
+/// the request parts otherwise. Requires feature `axum`.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{routing::post, Json, Router};
+/// use problem_details::problem_rejection;
+///
+/// problem_rejection! {
+///     /// JSON body whose rejections are problems.
+///     pub struct ProblemJson<T>(pub Json<T>) {
+///         type: "https://example.com/probs/invalid-body",
+///         status: http::StatusCode::BAD_REQUEST,
+///         source: Body,
+///     }
+/// }
+///
+/// async fn handler(ProblemJson(Json(payload)): ProblemJson<serde_json::Value>) -> Json<serde_json::Value> {
+///     Json(payload)
+/// }
+///
+/// let app: Router = Router::new().route("/", post(handler));
+/// ```
+#[macro_export]
+macro_rules! problem_rejection {
+    (@convert $rejection:ident $({
+        $(type: $type:literal,)?
+        $(status: $status:expr,)?
+        $(source: $source:ident,)?
+    })?) => {
+        $crate::__private::convert_rejection(
+            $rejection,
+            $crate::problem_rejection!(@type $($($type)?)?),
+            $crate::problem_rejection!(@status $($($status)?)?),
+            $crate::problem_rejection!(@source $($($source)?)?),
+        )
+    };
+    (@type) => { ::std::option::Option::None };
+    (@type $type:literal) => {
+        ::std::option::Option::Some($crate::ProblemType::from($crate::__private::Uri::from_static($type)))
+    };
+    (@status) => { ::std::option::Option::None };
+    (@status $status:expr) => { ::std::option::Option::Some($status) };
+    (@source) => { $crate::axum::RejectionSource::Request };
+    (@source $source:ident) => { $crate::axum::RejectionSource::$source };
+
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident $(<$($param:ident),+ $(,)?>)? ($inner_vis:vis $inner:ty)
+        $({
+            $(type: $type:literal,)?
+            $(status: $status:expr,)?
+            $(source: $source:ident $(,)?)?
+        })?
+        $(;)?
+    ) => {
+        $(#[$meta])*
+        $vis struct $name $(<$($param),+>)? ($inner_vis $inner);
+
+        impl<__S, $($($param),+)?> $crate::__private::axum::extract::FromRequestParts<__S>
+            for $name $(<$($param),+>)?
+        where
+            __S: ::std::marker::Send + ::std::marker::Sync,
+            $inner: $crate::__private::axum::extract::FromRequestParts<__S>,
+            <$inner as $crate::__private::axum::extract::FromRequestParts<__S>>::Rejection:
+                $crate::__private::axum::response::IntoResponse + ::std::fmt::Display,
+        {
+            type Rejection = $crate::ProblemDetails<$crate::axum::RejectionExtension>;
+
+            async fn from_request_parts(
+                parts: &mut $crate::__private::axum::http::request::Parts,
+                state: &__S,
+            ) -> ::std::result::Result<Self, Self::Rejection> {
+                <$inner as $crate::__private::axum::extract::FromRequestParts<__S>>::from_request_parts(parts, state)
+                    .await
+                    .map($name)
+                    .map_err(|rejection| $crate::problem_rejection!(@convert rejection $({
+                        $(type: $type,)? $(status: $status,)? $(source: $source,)?
+                    })?))
+            }
+        }
+
+        impl<__S, $($($param),+)?> $crate::__private::axum::extract::FromRequest<__S>
+            for $name $(<$($param),+>)?
+        where
+            __S: ::std::marker::Send + ::std::marker::Sync,
+            $inner: $crate::__private::axum::extract::FromRequest<__S>,
+            <$inner as $crate::__private::axum::extract::FromRequest<__S>>::Rejection:
+                $crate::__private::axum::response::IntoResponse + ::std::fmt::Display,
+        {
+            type Rejection = $crate::ProblemDetails<$crate::axum::RejectionExtension>;
+
+            async fn from_request(
+                request: $crate::__private::axum::extract::Request,
+                state: &__S,
+            ) -> ::std::result::Result<Self, Self::Rejection> {
+                <$inner as $crate::__private::axum::extract::FromRequest<__S>>::from_request(request, state)
+                    .await
+                    .map($name)
+                    .map_err(|rejection| $crate::problem_rejection!(@convert rejection $({
+                        $(type: $type,)? $(status: $status,)? $(source: $source,)?
+                    })?))
+            }
+        }
+    };
+}
@@ -0,0 +1,138 @@
+use std::io;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::ProblemDetails;
+
+/// A batch of problems serialized as newline-delimited JSON (NDJSON).
+///
+/// Every problem is written as a single line `application/problem+json` record
+/// terminated by a newline. This suits bulk endpoints reporting one problem per
+/// failed record, since clients can process the records while they are streamed.
+///
+/// ```rust
+/// use http::StatusCode;
+/// use problem_details::{ProblemBatch, ProblemDetails};
+///
+/// let batch = ProblemBatch::new()
+///     .with_problem(ProblemDetails::from_status_code(StatusCode::NOT_FOUND).with_detail("Record 1"))
+///     .with_problem(ProblemDetails::from_status_code(StatusCode::CONFLICT).with_detail("Record 7"));
+///
+/// let body = batch.to_body_string().unwrap();
+/// assert_eq!(
+///     body,
+///     concat!(
+///         r#"{"status":404,"title":"Not Found","detail":"Record 1"}"#, "\n",
+///         r#"{"status":409,"title":"Conflict","detail":"Record 7"}"#, "\n",
+///     )
+/// );
+///
+/// let parsed = ProblemBatch::<()>::from_body_str(&body).unwrap();
+/// assert_eq!(parsed, batch);
+/// ```
+///
+/// Requires feature `json`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProblemBatch<Ext = ()> {
+    /// The problems in this batch.
+    pub problems: Vec<ProblemDetails<Ext>>,
+}
+
+impl<Ext> ProblemBatch<Ext> {
+    /// The HTTP content type for newline-delimited JSON.
+    pub const CONTENT_TYPE: &'static str = "application/x-ndjson";
+
+    /// Creates a new empty batch.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            problems: Vec::new(),
+        }
+    }
+
+    /// Builder-style method that adds a problem to this batch.
+    #[must_use]
+    pub fn with_problem(mut self, problem: ProblemDetails<Ext>) -> Self {
+        self.problems.push(problem);
+        self
+    }
+}
+
+impl<Ext> ProblemBatch<Ext>
+where
+    Ext: Serialize,
+{
+    /// Write this batch to an NDJSON string suitable for a response body.
+    ///
+    /// Extension members are filtered according to the configured [`extension_filter`](crate::extension_filter).
+    pub fn to_body_string(&self) -> serde_json::Result<String> {
+        let mut body = Vec::new();
+        self.to_writer(&mut body)?;
+        Ok(String::from_utf8(body).expect("serde_json writes valid UTF-8"))
+    }
+
+    /// Write this batch as NDJSON to the given writer, one record at a time.
+    pub fn to_writer<W: io::Write>(&self, mut writer: W) -> serde_json::Result<()> {
+        for problem in &self.problems {
+            write_record(&mut writer, problem)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Ext> ProblemBatch<Ext>
+where
+    Ext: DeserializeOwned,
+{
+    /// Read a batch from an NDJSON string, e.g. a response body.
+    ///
+    /// Errors report the line of the invalid record.
+    pub fn from_body_str(ndjson: &str) -> serde_json::Result<Self> {
+        serde_json::Deserializer::from_str(ndjson)
+            .into_iter()
+            .collect()
+    }
+
+    /// Reads the problems of an NDJSON stream one record at a time.
+    pub fn read_records<R: io::Read>(
+        reader: R,
+    ) -> impl Iterator<Item = serde_json::Result<ProblemDetails<Ext>>> {
+        serde_json::Deserializer::from_reader(reader).into_iter()
+    }
+}
+
+/// Writes a single problem as NDJSON record.
+fn write_record<Ext, W>(writer: &mut W, problem: &ProblemDetails<Ext>) -> serde_json::Result<()>
+where
+    Ext: Serialize,
+    W: io::Write,
+{
+    match crate::extension_filter() {
+        Some(filter) => serde_json::to_writer(&mut *writer, &problem.redacted(filter)),
+        None => serde_json::to_writer(&mut *writer, problem),
+    }
+    .and_then(|()| writer.write_all(b"\n").map_err(serde_json::Error::io))
+}
+
+impl<Ext> Default for ProblemBatch<Ext> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Ext> FromIterator<ProblemDetails<Ext>> for ProblemBatch<Ext> {
+    fn from_iter<I: IntoIterator<Item = ProblemDetails<Ext>>>(iter: I) -> Self {
+        Self {
+            problems: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<Ext> IntoIterator for ProblemBatch<Ext> {
+    type Item = ProblemDetails<Ext>;
+    type IntoIter = std::vec::IntoIter<ProblemDetails<Ext>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.problems.into_iter()
+    }
+}
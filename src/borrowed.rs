@@ -0,0 +1,129 @@
+use std::borrow::Cow;
+
+use http::{StatusCode, Uri};
+
+use crate::{ProblemDetails, ProblemType, UriError};
+
+/// A problem details object borrowing its members from the deserialized input.
+///
+/// Parsing a [`ProblemDetails`] allocates for every string member. When parsing
+/// many upstream problems, e.g. in a proxy, this can be avoided by deserializing
+/// a `ProblemDetailsRef` from a buffer that outlives it. Members are only copied
+/// if they contain escape sequences. The `type` and `instance` members are not
+/// parsed as URIs, use [`into_owned`](ProblemDetailsRef::into_owned) for that.
+///
+/// Extensions are deserialized like for [`ProblemDetails`], so they may allocate.
+///
+/// # Example
+///
+/// ```rust
+/// use std::borrow::Cow;
+///
+/// use http::StatusCode;
+/// use problem_details::ProblemDetailsRef;
+///
+/// let body = r#"{"status":404,"title":"Not Found","detail":"No order with id 42"}"#;
+/// let details: ProblemDetailsRef = serde_json::from_str(body).unwrap();
+///
+/// assert_eq!(details.status, Some(StatusCode::NOT_FOUND));
+/// assert!(matches!(details.detail, Some(Cow::Borrowed("No order with id 42"))));
+/// ```
+///
+/// Requires feature `serde`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ProblemDetailsRef<'a, Ext = ()> {
+    /// An optional uri reference describing the problem type.
+    #[serde(
+        default,
+        borrow,
+        deserialize_with = "crate::serde::cow::opt::deserialize"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<Cow<'a, str>>,
+
+    /// An optional status code for this problem.
+    #[serde(default, with = "crate::serde::status::opt")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<StatusCode>,
+
+    /// An optional human-readable title for this problem.
+    #[serde(
+        default,
+        borrow,
+        deserialize_with = "crate::serde::cow::opt::deserialize"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<Cow<'a, str>>,
+
+    /// An optional human-readable description of this problem.
+    #[serde(
+        default,
+        borrow,
+        deserialize_with = "crate::serde::cow::opt::deserialize"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<Cow<'a, str>>,
+
+    /// An optional uri reference identifying the specific instance of this problem.
+    #[serde(
+        default,
+        borrow,
+        deserialize_with = "crate::serde::cow::opt::deserialize"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<Cow<'a, str>>,
+
+    /// An object containing extensions to this problem details object.
+    #[serde(flatten, with = "crate::serde::extensions")]
+    #[serde(bound(
+        serialize = "Ext: serde::Serialize",
+        deserialize = "Ext: serde::Deserialize<'de>"
+    ))]
+    pub extensions: Ext,
+}
+
+impl<Ext> ProblemDetailsRef<'_, Ext> {
+    /// Returns the `type` of this problem, or `about:blank` if none is set.
+    pub fn effective_type(&self) -> &str {
+        self.r#type.as_deref().unwrap_or("about:blank")
+    }
+
+    /// Converts this into an owned [`ProblemDetails`], parsing the `type` and `instance` URIs.
+    ///
+    /// Members which were not borrowed are moved without copying.
+    pub fn into_owned(self) -> Result<ProblemDetails<Ext>, UriError> {
+        let mut details = ProblemDetails::new().with_extensions(self.extensions);
+        details.r#type = self
+            .r#type
+            .map(|r#type| ProblemType::try_from(r#type.as_ref()))
+            .transpose()?;
+        details.status = self.status;
+        details.title = self.title.map(|title| Cow::Owned(title.into_owned()));
+        details.detail = self.detail.map(|detail| Cow::Owned(detail.into_owned()));
+        details.instance = self
+            .instance
+            .map(|instance| Uri::try_from(instance.into_owned()).map_err(UriError::from))
+            .transpose()?;
+        Ok(details)
+    }
+}
+
+#[cfg(feature = "json")]
+impl<'a, Ext> ProblemDetailsRef<'a, Ext>
+where
+    Ext: serde::Deserialize<'a>,
+{
+    /// Parses a problem details from a JSON string, borrowing members from it.
+    ///
+    /// Requires feature `json`.
+    pub fn from_json(json: &'a str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Parses a problem details from a JSON buffer, borrowing members from it.
+    ///
+    /// Requires feature `json`.
+    pub fn from_json_slice(json: &'a [u8]) -> serde_json::Result<Self> {
+        serde_json::from_slice(json)
+    }
+}
@@ -0,0 +1,148 @@
+//! Type-state builder for [`ProblemDetails`].
+//!
+//! The [`ProblemDetailsBuilder`] tracks whether the `type` and `status` fields
+//! have been set in its type, so [`build`](ProblemDetailsBuilder::build) is only
+//! available once both are present. This allows to enforce API guidelines
+//! requiring these fields at compile time.
+//!
+//! ```rust
+//! use http::{StatusCode, Uri};
+//! use problem_details::ProblemDetails;
+//!
+//! let details = ProblemDetails::builder()
+//!     .with_type(Uri::from_static("https://example.com/probs/out-of-credit"))
+//!     .with_status(StatusCode::FORBIDDEN)
+//!     .with_title("You do not have enough credit.")
+//!     .build();
+//!
+//! assert_eq!(details.status, Some(StatusCode::FORBIDDEN));
+//! ```
+//!
+//! Forgetting a required field is a compile error.
+//!
+//! ```rust,compile_fail
+//! use http::StatusCode;
+//! use problem_details::ProblemDetails;
+//!
+//! let details = ProblemDetails::builder()
+//!     .with_status(StatusCode::FORBIDDEN)
+//!     .build();
+//! ```
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use http::{StatusCode, Uri};
+
+use crate::{ProblemDetails, ProblemType};
+
+/// Marker for a required field that has not been set yet.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Unset;
+
+/// Marker for a required field that has been set.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Set;
+
+/// A builder for [`ProblemDetails`] requiring the `type` and `status` fields.
+///
+/// See the [module documentation](self) for more information.
+#[derive(Clone, Debug)]
+pub struct ProblemDetailsBuilder<Type = Unset, Status = Unset, Ext = ()> {
+    details: ProblemDetails<Ext>,
+    state: PhantomData<(Type, Status)>,
+}
+
+impl ProblemDetailsBuilder {
+    /// Creates a new builder without any fields set.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            details: ProblemDetails::new(),
+            state: PhantomData,
+        }
+    }
+}
+
+impl Default for ProblemDetailsBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Type, Status, Ext> ProblemDetailsBuilder<Type, Status, Ext> {
+    /// Sets the `type` field of the problem details.
+    #[must_use]
+    pub fn with_type(
+        self,
+        r#type: impl Into<ProblemType>,
+    ) -> ProblemDetailsBuilder<Set, Status, Ext> {
+        ProblemDetailsBuilder {
+            details: self.details.with_type(r#type),
+            state: PhantomData,
+        }
+    }
+
+    /// Sets the `status` field of the problem details.
+    #[must_use]
+    pub fn with_status(
+        self,
+        status: impl Into<StatusCode>,
+    ) -> ProblemDetailsBuilder<Type, Set, Ext> {
+        ProblemDetailsBuilder {
+            details: self.details.with_status(status),
+            state: PhantomData,
+        }
+    }
+
+    /// Sets the `title` field of the problem details.
+    #[must_use]
+    pub fn with_title(mut self, title: impl Into<Cow<'static, str>>) -> Self {
+        self.details = self.details.with_title(title);
+        self
+    }
+
+    /// Sets the `detail` field of the problem details.
+    #[must_use]
+    pub fn with_detail(mut self, detail: impl Into<Cow<'static, str>>) -> Self {
+        self.details = self.details.with_detail(detail);
+        self
+    }
+
+    /// Sets the `instance` field of the problem details.
+    #[must_use]
+    pub fn with_instance(mut self, instance: impl Into<Uri>) -> Self {
+        self.details = self.details.with_instance(instance);
+        self
+    }
+
+    /// Sets the `extensions` field of the problem details.
+    #[must_use]
+    pub fn with_extensions<NewExt>(
+        self,
+        extensions: NewExt,
+    ) -> ProblemDetailsBuilder<Type, Status, NewExt> {
+        ProblemDetailsBuilder {
+            details: self.details.with_extensions(extensions),
+            state: PhantomData,
+        }
+    }
+}
+
+impl<Ext> ProblemDetailsBuilder<Set, Set, Ext> {
+    /// Builds the problem details.
+    ///
+    /// This is only available once both `type` and `status` have been set.
+    #[must_use]
+    pub fn build(self) -> ProblemDetails<Ext> {
+        self.details
+    }
+}
+
+impl ProblemDetails<()> {
+    /// Creates a new [`ProblemDetailsBuilder`] which requires the `type` and `status`
+    /// fields to be set at compile time.
+    #[must_use]
+    pub fn builder() -> ProblemDetailsBuilder {
+        ProblemDetailsBuilder::new()
+    }
+}
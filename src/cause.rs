@@ -0,0 +1,205 @@
+use serde_json::{Map, Value};
+
+use crate::{MergeExtensions, ProblemDetails, RedactionPolicy};
+
+/// A problem reported by an upstream service, embedded into another problem.
+///
+/// The upstream problem is serialized as nested problem details object, with an
+/// optional `service` member attributing the problem to the service that reported it.
+///
+/// Since the upstream problem may contain internal information, it can be sanitized
+/// using [`redacted`](Cause::redacted) before embedding it, see [`RedactionPolicy`].
+///
+/// ```rust
+/// use http::StatusCode;
+/// use problem_details::{Cause, ProblemDetails, ProductionPolicy};
+///
+/// let upstream = ProblemDetails::from_status_code(StatusCode::INTERNAL_SERVER_ERROR)
+///     .with_detail("Connection to db-01.internal refused");
+///
+/// let cause = Cause::new(upstream)
+///     .with_service("billing")
+///     .redacted(&ProductionPolicy::default());
+///
+/// let details = ProblemDetails::from_status_code(StatusCode::BAD_GATEWAY).caused_by(cause);
+/// let json = serde_json::to_value(&details).unwrap();
+///
+/// assert_eq!(json["cause"]["service"], "billing");
+/// assert_eq!(json["cause"]["status"], 500);
+/// assert!(json["cause"].get("detail").is_none());
+/// ```
+///
+/// Requires feature `json`.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Cause {
+    /// The service which reported the upstream problem.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service: Option<String>,
+
+    /// The upstream problem, with its extensions converted to JSON.
+    #[serde(flatten)]
+    pub problem: ProblemDetails<Map<String, Value>>,
+}
+
+impl Cause {
+    /// Creates a cause for the given upstream problem.
+    ///
+    /// The extensions are converted into a JSON map, extensions which cannot be
+    /// represented as JSON object are dropped. The source and headers of the
+    /// upstream problem are not included.
+    pub fn new<Ext>(upstream: ProblemDetails<Ext>) -> Self
+    where
+        Ext: serde::Serialize,
+    {
+        let mut problem = upstream.to_json_extensions();
+        problem.headers = None;
+
+        Self {
+            service: None,
+            problem,
+        }
+    }
+
+    /// Builder-style method that attributes the cause to the given service.
+    #[must_use]
+    pub fn with_service(mut self, service: impl Into<String>) -> Self {
+        self.service = Some(service.into());
+        self
+    }
+
+    /// Returns a copy of this cause with the upstream problem redacted according to the given policy.
+    #[must_use]
+    pub fn redacted(&self, policy: &impl RedactionPolicy) -> Self {
+        Self {
+            service: self.service.clone(),
+            problem: self.problem.redacted(policy),
+        }
+    }
+}
+
+impl<Ext> From<ProblemDetails<Ext>> for Cause
+where
+    Ext: serde::Serialize,
+{
+    fn from(upstream: ProblemDetails<Ext>) -> Self {
+        Self::new(upstream)
+    }
+}
+
+/// Extension member listing the upstream problems which caused a problem.
+///
+/// A single cause is serialized as `cause` member, multiple causes as `causes`
+/// array. Both forms are accepted when deserializing.
+///
+/// Requires feature `json`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Causes {
+    /// The upstream problems.
+    pub causes: Vec<Cause>,
+}
+
+impl Causes {
+    /// Creates a new empty list of causes.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl From<Cause> for Causes {
+    fn from(cause: Cause) -> Self {
+        Self {
+            causes: vec![cause],
+        }
+    }
+}
+
+impl<Ext> From<ProblemDetails<Ext>> for Causes
+where
+    Ext: serde::Serialize,
+{
+    fn from(upstream: ProblemDetails<Ext>) -> Self {
+        Cause::new(upstream).into()
+    }
+}
+
+impl FromIterator<Cause> for Causes {
+    fn from_iter<I: IntoIterator<Item = Cause>>(iter: I) -> Self {
+        Self {
+            causes: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl MergeExtensions for Causes {
+    fn merge_extensions(&mut self, other: Self) {
+        self.causes.extend(other.causes);
+    }
+}
+
+impl serde::Serialize for Causes {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(1))?;
+        match self.causes.as_slice() {
+            [cause] => map.serialize_entry("cause", cause)?,
+            causes => map.serialize_entry("causes", causes)?,
+        }
+        map.end()
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Causes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        struct Members {
+            #[serde(default)]
+            cause: Option<Cause>,
+
+            #[serde(default)]
+            causes: Vec<Cause>,
+        }
+
+        let members = Members::deserialize(deserializer)?;
+        Ok(Self {
+            causes: members.cause.into_iter().chain(members.causes).collect(),
+        })
+    }
+}
+
+impl<Ext> ProblemDetails<Ext> {
+    /// Builder-style method that embeds the given upstream problems as cause.
+    ///
+    /// Accepts an upstream [`ProblemDetails`], a [`Cause`] (e.g. attributed to a
+    /// service or redacted) or multiple [`Causes`]. They are added next to the
+    /// existing extensions.
+    ///
+    /// ```rust
+    /// use http::StatusCode;
+    /// use problem_details::{Cause, Causes, ProblemDetails};
+    ///
+    /// let causes: Causes = [
+    ///     Cause::new(ProblemDetails::from_status_code(StatusCode::NOT_FOUND)).with_service("users"),
+    ///     Cause::new(ProblemDetails::from_status_code(StatusCode::CONFLICT)).with_service("orders"),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let details = ProblemDetails::from_status_code(StatusCode::BAD_GATEWAY).caused_by(causes);
+    /// let json = serde_json::to_value(&details).unwrap();
+    ///
+    /// assert_eq!(json["causes"][0]["service"], "users");
+    /// assert_eq!(json["causes"][1]["status"], 409);
+    /// ```
+    #[must_use]
+    pub fn caused_by(self, upstream: impl Into<Causes>) -> ProblemDetails<(Ext, Causes)> {
+        self.extend_extensions(upstream.into())
+    }
+}
@@ -0,0 +1,86 @@
+use std::sync::OnceLock;
+
+static COMPATIBILITY_MODE: OnceLock<CompatibilityMode> = OnceLock::new();
+
+/// The specification whose representation is used when serializing responses.
+///
+/// RFC 9457 obsoletes RFC 7807, but some clients still validate problems strictly
+/// against the older RFC. In [`Rfc7807`](CompatibilityMode::Rfc7807) mode,
+/// [`JsonProblemDetails`](crate::JsonProblemDetails) and `XmlProblemDetails`
+/// emit a representation accepted by such clients:
+///
+/// - a missing `type` is written explicitly as `about:blank`,
+/// - extension members with `null` values are omitted (requires feature `json`),
+/// - XML documents always use [`XmlFormat::Rfc7807`](crate::XmlFormat), which declares the
+///   `urn:ietf:rfc:7807` namespace.
+///
+/// ```rust
+/// use problem_details::{compatibility_mode, CompatibilityMode};
+///
+/// assert_eq!(compatibility_mode(), CompatibilityMode::Rfc9457);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CompatibilityMode {
+    /// The idiomatic representation of RFC 9457, omitting members which are not set.
+    #[default]
+    Rfc9457,
+
+    /// A representation compatible with strict RFC 7807 consumers.
+    Rfc7807,
+}
+
+/// Configures the process-wide [`CompatibilityMode`] of serialized responses.
+///
+/// The mode can only be set once. If it was already set, the given mode
+/// is returned as error. Defaults to [`CompatibilityMode::Rfc9457`].
+pub fn set_compatibility_mode(mode: CompatibilityMode) -> Result<(), CompatibilityMode> {
+    COMPATIBILITY_MODE.set(mode)
+}
+
+/// Returns the process-wide mode configured using [`set_compatibility_mode`].
+pub fn compatibility_mode() -> CompatibilityMode {
+    COMPATIBILITY_MODE.get().copied().unwrap_or_default()
+}
+
+#[cfg(feature = "json")]
+impl<Ext> crate::ProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    /// Returns a copy of this problem details object in the representation of the
+    /// [RFC 7807 compatibility mode](CompatibilityMode::Rfc7807).
+    ///
+    /// ```rust
+    /// use http::StatusCode;
+    /// use problem_details::ProblemDetails;
+    ///
+    /// let details = ProblemDetails::from_status_code(StatusCode::NOT_FOUND)
+    ///     .with_extension("resource", serde_json::Value::Null);
+    ///
+    /// assert_eq!(
+    ///     serde_json::to_value(details.rfc7807_compatible()).unwrap(),
+    ///     serde_json::json!({ "type": "about:blank", "status": 404, "title": "Not Found" })
+    /// );
+    /// ```
+    ///
+    /// Requires feature `json`.
+    pub fn rfc7807_compatible(&self) -> crate::ProblemDetails<crate::JsonExtensions> {
+        let mut details = self.to_json_extensions();
+        details.extensions.retain(|_, value| !value.is_null());
+        details.r#type.get_or_insert_with(Default::default);
+        details
+    }
+
+    /// Returns the representation written to responses, applying the configured
+    /// extension filter and compatibility mode, or `None` if this is sent unchanged.
+    pub(crate) fn for_response(&self) -> Option<crate::ProblemDetails<crate::JsonExtensions>> {
+        match (crate::extension_filter(), compatibility_mode()) {
+            (None, CompatibilityMode::Rfc9457) => None,
+            (Some(filter), CompatibilityMode::Rfc9457) => Some(self.redacted(filter)),
+            (None, CompatibilityMode::Rfc7807) => Some(self.rfc7807_compatible()),
+            (Some(filter), CompatibilityMode::Rfc7807) => {
+                Some(self.redacted(filter).rfc7807_compatible())
+            }
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use http::HeaderMap;
+
+use crate::{MergeExtensions, ProblemDetails};
+
+/// Extension members correlating a problem with traces and server-side logs.
+///
+/// All members are optional and omitted from the serialized problem if not set.
+///
+/// ```rust
+/// use http::StatusCode;
+/// use problem_details::ProblemDetails;
+///
+/// let details = ProblemDetails::from_status_code(StatusCode::BAD_GATEWAY)
+///     .with_request_id("req-42")
+///     .with_trace_id("4bf92f3577b34da6a3ce929d0e0e4736");
+///
+/// let json = serde_json::to_value(&details).unwrap();
+/// assert_eq!(json["request_id"], "req-42");
+/// assert_eq!(json["trace_id"], "4bf92f3577b34da6a3ce929d0e0e4736");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Correlation {
+    /// The id of the distributed trace, e.g. from a W3C `traceparent` header.
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub trace_id: Option<String>,
+
+    /// The id of the span within the trace.
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub span_id: Option<String>,
+
+    /// The id of the request, e.g. from a `X-Request-Id` header.
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub request_id: Option<String>,
+}
+
+impl Correlation {
+    /// The request headers checked for a request id, in order of preference.
+    pub const REQUEST_ID_HEADERS: [&'static str; 2] = ["x-request-id", "x-correlation-id"];
+
+    /// Creates a new empty correlation.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Extracts the correlation from the given request headers.
+    ///
+    /// The trace and span id are taken from a W3C
+    /// [`traceparent`](https://www.w3.org/TR/trace-context/#traceparent-header) header,
+    /// the request id from the first of [`REQUEST_ID_HEADERS`](Self::REQUEST_ID_HEADERS)
+    /// present. Malformed headers are ignored.
+    ///
+    /// ```rust
+    /// use http::{HeaderMap, HeaderValue};
+    /// use problem_details::Correlation;
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert(
+    ///     "traceparent",
+    ///     HeaderValue::from_static("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"),
+    /// );
+    /// headers.insert("x-request-id", HeaderValue::from_static("req-42"));
+    ///
+    /// let correlation = Correlation::from_headers(&headers);
+    /// assert_eq!(correlation.trace_id.as_deref(), Some("4bf92f3577b34da6a3ce929d0e0e4736"));
+    /// assert_eq!(correlation.span_id.as_deref(), Some("00f067aa0ba902b7"));
+    /// assert_eq!(correlation.request_id.as_deref(), Some("req-42"));
+    /// ```
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+
+        let (trace_id, span_id) = header("traceparent")
+            .and_then(parse_traceparent)
+            .map_or((None, None), |(trace_id, span_id)| {
+                (Some(trace_id.to_string()), Some(span_id.to_string()))
+            });
+
+        let request_id = Self::REQUEST_ID_HEADERS
+            .iter()
+            .find_map(|name| header(name))
+            .map(str::to_string);
+
+        Self {
+            trace_id,
+            span_id,
+            request_id,
+        }
+    }
+
+    /// Builder-style method that sets the trace id.
+    #[must_use]
+    pub fn with_trace_id(mut self, trace_id: impl Into<String>) -> Self {
+        self.trace_id = Some(trace_id.into());
+        self
+    }
+
+    /// Builder-style method that sets the span id.
+    #[must_use]
+    pub fn with_span_id(mut self, span_id: impl Into<String>) -> Self {
+        self.span_id = Some(span_id.into());
+        self
+    }
+
+    /// Builder-style method that sets the request id.
+    #[must_use]
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    /// Returns true if no member is set.
+    pub fn is_empty(&self) -> bool {
+        self.trace_id.is_none() && self.span_id.is_none() && self.request_id.is_none()
+    }
+}
+
+/// Members of `other` which are set take precedence.
+impl MergeExtensions for Correlation {
+    fn merge_extensions(&mut self, other: Self) {
+        if other.trace_id.is_some() {
+            self.trace_id = other.trace_id;
+        }
+        if other.span_id.is_some() {
+            self.span_id = other.span_id;
+        }
+        if other.request_id.is_some() {
+            self.request_id = other.request_id;
+        }
+    }
+}
+
+/// Parses the trace and span id from a `traceparent` header value.
+fn parse_traceparent(value: &str) -> Option<(&str, &str)> {
+    let mut parts = value.split('-');
+    let version = parts.next()?;
+    let trace_id = parts.next()?;
+    let span_id = parts.next()?;
+    let _flags = parts.next()?;
+
+    let is_hex = |value: &str, len: usize| {
+        value.len() == len
+            && value
+                .bytes()
+                .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+    };
+    let is_zero = |value: &str| value.bytes().all(|b| b == b'0');
+
+    (is_hex(version, 2)
+        && version != "ff"
+        && is_hex(trace_id, 32)
+        && !is_zero(trace_id)
+        && is_hex(span_id, 16)
+        && !is_zero(span_id))
+    .then_some((trace_id, span_id))
+}
+
+impl<Ext> ProblemDetails<Ext> {
+    /// Builder-style method that adds correlation members next to the existing extensions.
+    ///
+    /// The existing extensions are kept (see
+    /// [`extend_extensions`](ProblemDetails::extend_extensions)), so middleware can
+    /// inject the correlation without clobbering extensions set by handlers.
+    ///
+    /// ```rust
+    /// use http::HeaderMap;
+    /// use problem_details::{Correlation, ProblemDetails};
+    ///
+    /// # let request_headers = HeaderMap::new();
+    /// let details = ProblemDetails::new()
+    ///     .with_extension("balance", 30)
+    ///     .with_correlation(Correlation::from_headers(&request_headers).with_request_id("req-42"));
+    ///
+    /// let json = serde_json::to_value(&details).unwrap();
+    /// assert_eq!(json["balance"], 30);
+    /// assert_eq!(json["request_id"], "req-42");
+    /// ```
+    #[must_use]
+    pub fn with_correlation(self, correlation: Correlation) -> ProblemDetails<(Ext, Correlation)> {
+        self.extend_extensions(correlation)
+    }
+}
+
+impl ProblemDetails<()> {
+    /// Builder-style method that sets the trace id, using [`Correlation`] extensions.
+    #[must_use]
+    pub fn with_trace_id(self, trace_id: impl Into<String>) -> ProblemDetails<Correlation> {
+        self.with_extensions(Correlation::new().with_trace_id(trace_id))
+    }
+
+    /// Builder-style method that sets the span id, using [`Correlation`] extensions.
+    #[must_use]
+    pub fn with_span_id(self, span_id: impl Into<String>) -> ProblemDetails<Correlation> {
+        self.with_extensions(Correlation::new().with_span_id(span_id))
+    }
+
+    /// Builder-style method that sets the request id, using [`Correlation`] extensions.
+    #[must_use]
+    pub fn with_request_id(self, request_id: impl Into<String>) -> ProblemDetails<Correlation> {
+        self.with_extensions(Correlation::new().with_request_id(request_id))
+    }
+}
+
+impl ProblemDetails<Correlation> {
+    /// Builder-style method that sets the trace id.
+    #[must_use]
+    pub fn with_trace_id(self, trace_id: impl Into<String>) -> Self {
+        self.map_extensions(|correlation| correlation.with_trace_id(trace_id))
+    }
+
+    /// Builder-style method that sets the span id.
+    #[must_use]
+    pub fn with_span_id(self, span_id: impl Into<String>) -> Self {
+        self.map_extensions(|correlation| correlation.with_span_id(span_id))
+    }
+
+    /// Builder-style method that sets the request id.
+    #[must_use]
+    pub fn with_request_id(self, request_id: impl Into<String>) -> Self {
+        self.map_extensions(|correlation| correlation.with_request_id(request_id))
+    }
+}
@@ -0,0 +1,70 @@
+use std::sync::OnceLock;
+
+use crate::{ProblemDetails, ProblemType};
+
+static DEFAULT_TYPE: OnceLock<ProblemType> = OnceLock::new();
+
+/// Configures a process-wide default problem type.
+///
+/// The web framework integrations apply this type to all problem details
+/// without a `type` before they are serialized to a response, instead of
+/// leaving it out (which is equivalent to `about:blank`).
+///
+/// The default type can only be set once. If it was already set, the given
+/// type is returned as error.
+///
+/// ```rust
+/// use http::Uri;
+/// use problem_details::ProblemType;
+///
+/// problem_details::set_default_type(Uri::from_static("https://example.com/probs/generic"))
+///     .unwrap();
+///
+/// assert_eq!(
+///     problem_details::default_type(),
+///     Some(&ProblemType::from(Uri::from_static("https://example.com/probs/generic")))
+/// );
+/// ```
+pub fn set_default_type(r#type: impl Into<ProblemType>) -> Result<(), ProblemType> {
+    DEFAULT_TYPE.set(r#type.into())
+}
+
+/// Returns the process-wide default problem type configured using [`set_default_type`].
+pub fn default_type() -> Option<&'static ProblemType> {
+    DEFAULT_TYPE.get()
+}
+
+impl<Ext> ProblemDetails<Ext> {
+    /// Builder-style method that sets the `type` field of this problem details object
+    /// if it is not set yet.
+    ///
+    /// ```rust
+    /// use http::Uri;
+    /// use problem_details::ProblemDetails;
+    ///
+    /// let details = ProblemDetails::new()
+    ///     .with_default_type(Uri::from_static("https://example.com/probs/generic"));
+    /// assert_eq!(details.r#type.unwrap().to_string(), "https://example.com/probs/generic");
+    ///
+    /// let details = ProblemDetails::new()
+    ///     .with_type(Uri::from_static("https://example.com/probs/specific"))
+    ///     .with_default_type(Uri::from_static("https://example.com/probs/generic"));
+    /// assert_eq!(details.r#type.unwrap().to_string(), "https://example.com/probs/specific");
+    /// ```
+    #[must_use]
+    pub fn with_default_type(mut self, r#type: impl Into<ProblemType>) -> Self {
+        if self.r#type.is_none() {
+            self.r#type = Some(r#type.into());
+        }
+        self
+    }
+
+    /// Applies the default type configured using [`set_default_type`], if any.
+    #[cfg_attr(not(feature = "json"), allow(dead_code))]
+    pub(crate) fn with_configured_default_type(self) -> Self {
+        match default_type() {
+            Some(r#type) => self.with_default_type(r#type.clone()),
+            None => self,
+        }
+    }
+}
@@ -0,0 +1,368 @@
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{MergeExtensions, ProblemDetails};
+
+/// Member names defined by RFC 9457 which must not be used by extensions.
+pub(crate) const RESERVED_MEMBERS: [&str; 5] = ["type", "status", "title", "detail", "instance"];
+
+/// Dynamic extension members, keyed by name.
+///
+/// This is the recommended extensions type if the extension members are not
+/// known at compile time. It is used by [`ProblemDetails::with_extension`].
+///
+/// Members are iterated in insertion order, and keys reserved by RFC 9457
+/// (`type`, `status`, `title`, `detail` and `instance`) are rejected.
+/// With feature `indexmap`, the members are stored in an
+/// [`IndexMap`](indexmap::IndexMap) for constant time lookups.
+///
+/// ```rust
+/// use problem_details::DynamicExtensions;
+///
+/// let mut extensions = DynamicExtensions::new();
+/// extensions.insert("balance", 30).unwrap();
+/// extensions.insert("accounts", vec!["/account/12345"]).unwrap();
+///
+/// assert!(extensions.insert("status", 400).is_err());
+/// assert_eq!(extensions.get::<u32>("balance").unwrap(), Some(30));
+/// assert_eq!(extensions.keys().collect::<Vec<_>>(), ["balance", "accounts"]);
+/// ```
+///
+/// Requires feature `json`.
+#[derive(Clone, Debug, Default)]
+pub struct DynamicExtensions {
+    members: Members,
+}
+
+#[cfg(not(feature = "indexmap"))]
+type Members = Vec<(String, Value)>;
+
+#[cfg(feature = "indexmap")]
+type Members = indexmap::IndexMap<String, Value>;
+
+impl DynamicExtensions {
+    /// Creates a new empty set of extension members.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts an extension member, returning the previous value with the same key.
+    ///
+    /// Returns an error if the key is reserved by RFC 9457. Replacing an
+    /// existing member keeps its position.
+    pub fn insert(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<Value>,
+    ) -> Result<Option<Value>, ReservedKeyError> {
+        let key = key.into();
+        if RESERVED_MEMBERS.contains(&key.as_str()) {
+            return Err(ReservedKeyError(key));
+        }
+
+        let value = value.into();
+
+        #[cfg(not(feature = "indexmap"))]
+        match self
+            .members
+            .iter_mut()
+            .find(|(existing, _)| *existing == key)
+        {
+            Some((_, existing)) => Ok(Some(std::mem::replace(existing, value))),
+            None => {
+                self.members.push((key, value));
+                Ok(None)
+            }
+        }
+
+        #[cfg(feature = "indexmap")]
+        Ok(self.members.insert(key, value))
+    }
+
+    /// Returns the raw value of the extension member with the given key.
+    pub fn get_value(&self, key: &str) -> Option<&Value> {
+        #[cfg(not(feature = "indexmap"))]
+        return self
+            .members
+            .iter()
+            .find(|(existing, _)| existing == key)
+            .map(|(_, value)| value);
+
+        #[cfg(feature = "indexmap")]
+        return self.members.get(key);
+    }
+
+    /// Deserializes the extension member with the given key into a typed value.
+    ///
+    /// Returns `Ok(None)` if there is no extension member with the given key,
+    /// and an error if the member could not be deserialized into `T`.
+    pub fn get<T>(&self, key: &str) -> Result<Option<T>, serde_json::Error>
+    where
+        T: DeserializeOwned,
+    {
+        self.get_value(key).map(T::deserialize).transpose()
+    }
+
+    /// Removes the extension member with the given key, returning its value.
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        #[cfg(not(feature = "indexmap"))]
+        return self
+            .members
+            .iter()
+            .position(|(existing, _)| existing == key)
+            .map(|index| self.members.remove(index).1);
+
+        #[cfg(feature = "indexmap")]
+        return self.members.shift_remove(key);
+    }
+
+    /// Returns true if there is an extension member with the given key.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get_value(key).is_some()
+    }
+
+    /// Returns the number of extension members.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns true if there are no extension members.
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Iterates over the keys of the extension members in insertion order.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Iterates over the extension members in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.members
+            .iter()
+            .map(|(key, value)| (key.as_str(), value))
+    }
+}
+
+impl std::ops::Index<&str> for DynamicExtensions {
+    type Output = Value;
+
+    /// Returns the value of the extension member with the given key.
+    ///
+    /// Panics if there is no extension member with the given key.
+    fn index(&self, key: &str) -> &Value {
+        self.get_value(key)
+            .unwrap_or_else(|| panic!("no extension member {key}"))
+    }
+}
+
+/// Extension members are compared regardless of their order.
+impl PartialEq for DynamicExtensions {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(key, value)| other.get_value(key) == Some(value))
+    }
+}
+
+impl Eq for DynamicExtensions {}
+
+impl MergeExtensions for DynamicExtensions {
+    fn merge_extensions(&mut self, other: Self) {
+        for (key, value) in other.members {
+            // Keys of `other` were checked when they were inserted.
+            let _ = self.insert(key, value);
+        }
+    }
+}
+
+impl serde::Serialize for DynamicExtensions {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_map(self.iter())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for DynamicExtensions {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct DynamicExtensionsVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for DynamicExtensionsVisitor {
+            type Value = DynamicExtensions;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a map of extension members")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                let mut extensions = DynamicExtensions::new();
+                while let Some((key, value)) = map.next_entry::<String, Value>()? {
+                    extensions
+                        .insert(key, value)
+                        .map_err(serde::de::Error::custom)?;
+                }
+                Ok(extensions)
+            }
+        }
+
+        deserializer.deserialize_map(DynamicExtensionsVisitor)
+    }
+}
+
+/// Error returned when inserting an extension member with a key reserved by RFC 9457.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReservedKeyError(pub(crate) String);
+
+impl ReservedKeyError {
+    /// Returns the rejected key.
+    pub fn key(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for ReservedKeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Extension member {} is reserved", self.0)
+    }
+}
+
+impl std::error::Error for ReservedKeyError {}
+
+impl ProblemDetails<()> {
+    /// Builder-style method that adds a single dynamic extension field.
+    ///
+    /// This turns the problem details object into one with [`DynamicExtensions`],
+    /// so additional fields can be appended by calling this method repeatedly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is reserved by RFC 9457, see [`DynamicExtensions::insert`].
+    ///
+    /// ```rust
+    /// use problem_details::{DynamicExtensions, ProblemDetails};
+    ///
+    /// let details = ProblemDetails::new()
+    ///     .with_extension("traceId", "abc123")
+    ///     .with_extension("balance", 30);
+    ///
+    /// // details is of type ProblemDetails<DynamicExtensions>
+    /// let typecheck: ProblemDetails<DynamicExtensions> = details;
+    /// ```
+    #[must_use]
+    pub fn with_extension(
+        self,
+        key: impl Into<String>,
+        value: impl Into<Value>,
+    ) -> ProblemDetails<DynamicExtensions> {
+        self.with_extensions(DynamicExtensions::new())
+            .with_extension(key, value)
+    }
+
+    /// Builder-style method that adds a single dynamic extension field.
+    ///
+    /// Like [`with_extension`](ProblemDetails::with_extension), but returns an
+    /// error instead of panicking if the key is reserved by RFC 9457. Use this
+    /// when the key is not known at compile time.
+    pub fn try_with_extension(
+        self,
+        key: impl Into<String>,
+        value: impl Into<Value>,
+    ) -> Result<ProblemDetails<DynamicExtensions>, ReservedKeyError> {
+        self.with_extensions(DynamicExtensions::new())
+            .try_with_extension(key, value)
+    }
+}
+
+impl ProblemDetails<DynamicExtensions> {
+    /// Builder-style method that adds a single dynamic extension field.
+    ///
+    /// If an extension with the same key already exists, it is replaced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is reserved by RFC 9457, see [`DynamicExtensions::insert`].
+    /// Use [`try_with_extension`](Self::try_with_extension) if the key is not known
+    /// at compile time.
+    #[must_use]
+    pub fn with_extension(self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        match self.try_with_extension(key, value) {
+            Ok(details) => details,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Builder-style method that adds a single dynamic extension field.
+    ///
+    /// If an extension with the same key already exists, it is replaced.
+    /// Returns an error if the key is reserved by RFC 9457.
+    ///
+    /// ```rust
+    /// use problem_details::ProblemDetails;
+    ///
+    /// let key = "status";
+    /// let result = ProblemDetails::new()
+    ///     .with_extension("balance", 30)
+    ///     .try_with_extension(key, 500);
+    ///
+    /// assert_eq!(result.unwrap_err().key(), "status");
+    /// ```
+    pub fn try_with_extension(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<Value>,
+    ) -> Result<Self, ReservedKeyError> {
+        self.extensions.insert(key, value)?;
+        Ok(self)
+    }
+
+    /// Deserializes the extension member with the given key into a typed value.
+    ///
+    /// Returns `Ok(None)` if there is no extension member with the given key,
+    /// and an error if the member could not be deserialized into `T`.
+    ///
+    /// ```rust
+    /// use problem_details::ProblemDetails;
+    ///
+    /// let details = ProblemDetails::new()
+    ///     .with_extension("balance", 30)
+    ///     .with_extension("accounts", vec!["/account/12345", "/account/67890"]);
+    ///
+    /// let balance: Option<u32> = details.get_extension("balance").unwrap();
+    /// let accounts: Option<Vec<String>> = details.get_extension("accounts").unwrap();
+    ///
+    /// assert_eq!(balance, Some(30));
+    /// assert_eq!(accounts.unwrap().len(), 2);
+    /// assert!(details.get_extension::<String>("balance").is_err());
+    /// ```
+    pub fn get_extension<T>(&self, key: &str) -> Result<Option<T>, serde_json::Error>
+    where
+        T: DeserializeOwned,
+    {
+        self.extensions.get(key)
+    }
+
+    /// Removes the extension member with the given key and deserializes it into a typed value.
+    ///
+    /// Returns `Ok(None)` if there is no extension member with the given key,
+    /// and an error if the member could not be deserialized into `T`. The member
+    /// is removed in either case.
+    pub fn take_extension<T>(&mut self, key: &str) -> Result<Option<T>, serde_json::Error>
+    where
+        T: DeserializeOwned,
+    {
+        self.extensions
+            .remove(key)
+            .map(serde_json::from_value)
+            .transpose()
+    }
+}
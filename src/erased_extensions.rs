@@ -0,0 +1,161 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use serde::ser::Error as _;
+use serde_json::{Map, Value};
+
+use crate::MergeExtensions;
+
+/// Object safe serialization of a single extension object.
+trait ErasedExtension: Any + Send + Sync {
+    fn to_members(&self) -> Result<Map<String, Value>, serde_json::Error>;
+    fn as_any(&self) -> &dyn Any;
+    fn type_name(&self) -> &'static str;
+}
+
+impl<T> ErasedExtension for T
+where
+    T: serde::Serialize + Any + Send + Sync,
+{
+    fn to_members(&self) -> Result<Map<String, Value>, serde_json::Error> {
+        match serde_json::to_value(self)? {
+            Value::Object(members) => Ok(members),
+            Value::Null => Ok(Map::new()),
+            _ => Err(serde_json::Error::custom(format!(
+                "extension {} is not serialized as object",
+                std::any::type_name::<T>()
+            ))),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+}
+
+/// Type-erased storage for any number of extension objects.
+///
+/// Use this if different code paths attach different extension structs, and the
+/// concrete types are only known at runtime. All stored objects are flattened into
+/// the problem details object when serialized, so they must serialize as objects.
+/// If multiple objects contain the same member, the one added last wins.
+///
+/// ```rust
+/// use problem_details::{ErasedExtensions, ProblemDetails};
+///
+/// #[derive(serde::Serialize)]
+/// struct Balance {
+///     balance: u32,
+/// }
+///
+/// #[derive(serde::Serialize)]
+/// struct Accounts {
+///     accounts: Vec<String>,
+/// }
+///
+/// let mut details = ProblemDetails::new().with_extensions(ErasedExtensions::new());
+/// details.extensions.insert(Balance { balance: 30 });
+/// details.extensions.insert(Accounts { accounts: vec!["/account/12345".into()] });
+///
+/// assert_eq!(details.extensions.get::<Balance>().unwrap().balance, 30);
+/// assert_eq!(
+///     serde_json::to_value(&details).unwrap(),
+///     serde_json::json!({ "balance": 30, "accounts": ["/account/12345"] })
+/// );
+/// ```
+///
+/// Requires feature `json`.
+#[derive(Clone, Default)]
+pub struct ErasedExtensions {
+    extensions: Vec<Arc<dyn ErasedExtension>>,
+}
+
+impl ErasedExtensions {
+    /// Creates a new empty storage.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an extension object.
+    pub fn insert<T>(&mut self, extension: T)
+    where
+        T: serde::Serialize + Any + Send + Sync,
+    {
+        self.extensions.push(Arc::new(extension));
+    }
+
+    /// Builder-style method that adds an extension object.
+    #[must_use]
+    pub fn with<T>(mut self, extension: T) -> Self
+    where
+        T: serde::Serialize + Any + Send + Sync,
+    {
+        self.insert(extension);
+        self
+    }
+
+    /// Returns the last added extension object of type `T`, if any.
+    pub fn get<T>(&self) -> Option<&T>
+    where
+        T: Any,
+    {
+        self.extensions
+            .iter()
+            .rev()
+            .find_map(|extension| extension.as_any().downcast_ref())
+    }
+
+    /// Returns true if an extension object of type `T` was added.
+    pub fn contains<T>(&self) -> bool
+    where
+        T: Any,
+    {
+        self.get::<T>().is_some()
+    }
+
+    /// Returns the number of stored extension objects.
+    pub fn len(&self) -> usize {
+        self.extensions.len()
+    }
+
+    /// Returns true if no extension objects are stored.
+    pub fn is_empty(&self) -> bool {
+        self.extensions.is_empty()
+    }
+}
+
+impl std::fmt::Debug for ErasedExtensions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(
+                self.extensions
+                    .iter()
+                    .map(|extension| extension.type_name()),
+            )
+            .finish()
+    }
+}
+
+impl MergeExtensions for ErasedExtensions {
+    fn merge_extensions(&mut self, other: Self) {
+        self.extensions.extend(other.extensions);
+    }
+}
+
+impl serde::Serialize for ErasedExtensions {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut members = Map::new();
+        for extension in &self.extensions {
+            members.extend(extension.to_members().map_err(S::Error::custom)?);
+        }
+        members.serialize(serializer)
+    }
+}
@@ -0,0 +1,127 @@
+use std::sync::{Arc, OnceLock};
+
+use crate::RedactionPolicy;
+
+static EXTENSION_FILTER: OnceLock<ExtensionFilter> = OnceLock::new();
+
+/// A filter deciding which extension members are included in responses.
+///
+/// Once configured using [`set_extension_filter`], the filter is applied by
+/// [`JsonProblemDetails`](crate::JsonProblemDetails) and `XmlProblemDetails`
+/// whenever they are serialized, so sensitive members never leave the process
+/// regardless of the handler code. The standard members are never filtered.
+///
+/// The filter can also be used as [`RedactionPolicy`] with [`ProblemDetails::redacted`](crate::ProblemDetails::redacted).
+///
+/// ```rust
+/// use problem_details::{ExtensionFilter, ProblemDetails};
+///
+/// let details = ProblemDetails::new()
+///     .with_extension("trace_id", "abc123")
+///     .with_extension("debug_sql", "SELECT * FROM users");
+///
+/// let filtered = details.redacted(&ExtensionFilter::deny(["debug_sql"]));
+///
+/// assert!(filtered.extensions.contains_key("trace_id"));
+/// assert!(!filtered.extensions.contains_key("debug_sql"));
+/// ```
+///
+/// Requires feature `json`.
+#[derive(Clone)]
+pub struct ExtensionFilter {
+    kind: FilterKind,
+}
+
+#[derive(Clone)]
+enum FilterKind {
+    Allow(Vec<String>),
+    Deny(Vec<String>),
+    Predicate(Arc<dyn Fn(&str) -> bool + Send + Sync>),
+}
+
+impl ExtensionFilter {
+    /// Creates a filter which only includes the extension members with the given keys.
+    pub fn allow<I>(keys: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self {
+            kind: FilterKind::Allow(keys.into_iter().map(Into::into).collect()),
+        }
+    }
+
+    /// Creates a filter which excludes the extension members with the given keys.
+    pub fn deny<I>(keys: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self {
+            kind: FilterKind::Deny(keys.into_iter().map(Into::into).collect()),
+        }
+    }
+
+    /// Creates a filter which includes the extension members for which the predicate returns true.
+    pub fn new(predicate: impl Fn(&str) -> bool + Send + Sync + 'static) -> Self {
+        Self {
+            kind: FilterKind::Predicate(Arc::new(predicate)),
+        }
+    }
+
+    /// Returns true if the extension member with the given key is included.
+    pub fn includes(&self, key: &str) -> bool {
+        match &self.kind {
+            FilterKind::Allow(keys) => keys.iter().any(|allowed| allowed == key),
+            FilterKind::Deny(keys) => !keys.iter().any(|denied| denied == key),
+            FilterKind::Predicate(predicate) => predicate(key),
+        }
+    }
+}
+
+impl std::fmt::Debug for ExtensionFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            FilterKind::Allow(keys) => f.debug_tuple("Allow").field(keys).finish(),
+            FilterKind::Deny(keys) => f.debug_tuple("Deny").field(keys).finish(),
+            FilterKind::Predicate(_) => f.debug_tuple("Predicate").finish_non_exhaustive(),
+        }
+    }
+}
+
+impl RedactionPolicy for ExtensionFilter {
+    fn redact_extension(&self, key: &str, _value: &serde_json::Value) -> bool {
+        !self.includes(key)
+    }
+}
+
+/// Configures a process-wide filter for extension members in responses.
+///
+/// The filter can only be set once. If it was already set, the given
+/// filter is returned as error.
+///
+/// ```rust
+/// use problem_details::{ExtensionFilter, JsonProblemDetails, ProblemDetails};
+///
+/// problem_details::set_extension_filter(ExtensionFilter::deny(["debug_sql", "stacktrace"]))
+///     .unwrap();
+///
+/// let details = ProblemDetails::new()
+///     .with_extension("trace_id", "abc123")
+///     .with_extension("debug_sql", "SELECT * FROM users");
+///
+/// let body = JsonProblemDetails::from(details).to_body_string().unwrap();
+/// assert_eq!(body, r#"{"trace_id":"abc123"}"#);
+/// ```
+///
+/// Requires feature `json`.
+pub fn set_extension_filter(filter: ExtensionFilter) -> Result<(), ExtensionFilter> {
+    EXTENSION_FILTER.set(filter)
+}
+
+/// Returns the process-wide extension filter configured using [`set_extension_filter`].
+///
+/// Requires feature `json`.
+pub fn extension_filter() -> Option<&'static ExtensionFilter> {
+    EXTENSION_FILTER.get()
+}
@@ -0,0 +1,135 @@
+use std::fmt::Display;
+
+use crate::{MergeExtensions, ProblemDetails};
+
+/// A single invalid part of a request, as shown in the
+/// [RFC 9457 example](https://www.rfc-editor.org/rfc/rfc9457.html#name-extension-members).
+///
+/// ```rust
+/// use problem_details::FieldError;
+///
+/// let error = FieldError::from_path(["items", "0", "a/b"], "must not be empty");
+/// assert_eq!(error.pointer, "/items/0/a~1b");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FieldError {
+    /// A human-readable description of what is wrong.
+    pub detail: String,
+
+    /// A [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901) to the invalid
+    /// part of the request body, e.g. `/user/email`.
+    pub pointer: String,
+}
+
+impl FieldError {
+    /// Creates a new field error for the given JSON Pointer.
+    pub fn new(pointer: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            detail: detail.into(),
+            pointer: pointer.into(),
+        }
+    }
+
+    /// Creates a new field error for the given path segments.
+    ///
+    /// The segments are escaped as required by JSON Pointer, i.e. `~` becomes `~0`
+    /// and `/` becomes `~1`.
+    pub fn from_path<I>(path: I, detail: impl Into<String>) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        let pointer = path
+            .into_iter()
+            .map(|segment| {
+                let segment = segment.to_string().replace('~', "~0").replace('/', "~1");
+                format!("/{segment}")
+            })
+            .collect::<String>();
+
+        Self::new(pointer, detail)
+    }
+
+    /// Returns the unescaped path segments of the JSON Pointer.
+    ///
+    /// ```rust
+    /// use problem_details::FieldError;
+    ///
+    /// let error = FieldError::new("/user/a~1b", "is invalid");
+    /// assert_eq!(error.path().collect::<Vec<_>>(), ["user", "a/b"]);
+    /// ```
+    pub fn path(&self) -> impl Iterator<Item = String> + '_ {
+        self.pointer
+            .split('/')
+            .skip(1)
+            .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+    }
+}
+
+/// Extension member listing the invalid parts of a request as `errors`.
+///
+/// This models the `errors` member of the validation example in
+/// [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html#name-extension-members).
+///
+/// ```rust
+/// use http::StatusCode;
+/// use problem_details::ProblemDetails;
+///
+/// let details = ProblemDetails::from_status_code(StatusCode::UNPROCESSABLE_ENTITY)
+///     .with_field_error("/user/email", "must be a valid address")
+///     .with_field_error("/user/age", "must be a positive integer");
+///
+/// assert_eq!(details.extensions.errors.len(), 2);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidationErrors {
+    /// The invalid parts of the request.
+    pub errors: Vec<FieldError>,
+}
+
+impl MergeExtensions for ValidationErrors {
+    fn merge_extensions(&mut self, other: Self) {
+        self.errors.extend(other.errors);
+    }
+}
+
+impl FromIterator<FieldError> for ValidationErrors {
+    fn from_iter<T: IntoIterator<Item = FieldError>>(iter: T) -> Self {
+        Self {
+            errors: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl ProblemDetails<()> {
+    /// Builder-style method that adds an error for the given JSON Pointer.
+    ///
+    /// This turns the problem details object into one with [`ValidationErrors`],
+    /// so additional errors can be appended by calling this method repeatedly.
+    #[must_use]
+    pub fn with_field_error(
+        self,
+        pointer: impl Into<String>,
+        detail: impl Into<String>,
+    ) -> ProblemDetails<ValidationErrors> {
+        self.with_extensions(ValidationErrors::default())
+            .with_field_error(pointer, detail)
+    }
+}
+
+impl ProblemDetails<ValidationErrors> {
+    /// Builder-style method that adds an error for the given JSON Pointer.
+    #[must_use]
+    pub fn with_field_error(
+        mut self,
+        pointer: impl Into<String>,
+        detail: impl Into<String>,
+    ) -> Self {
+        self.extensions
+            .errors
+            .push(FieldError::new(pointer, detail));
+        self
+    }
+}
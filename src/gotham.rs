@@ -0,0 +1,284 @@
+//! Gotham response types for [`ProblemDetails`]. Requires feature `gotham` (implies `json`).
+//!
+//! With the `gotham` feature enabled, [`ProblemDetails`] implements [`IntoResponse`] using
+//! [`JsonProblemDetails`]. You can also return [`JsonProblemDetails`] to be specific.
+//! If you want to return XML, you can use [`XmlProblemDetails`] (requires feature `xml`).
+//! Concise problem details in CBOR are returned using `CborProblemDetails` (requires feature `cbor`).
+//! YAML is returned using `YamlProblemDetails` (requires feature `yaml`).
+//! Browsers can be served an HTML page using `HtmlProblemDetails` (requires feature `html`).
+//! As last resort, [`TextProblemDetails`] returns a plain text body.
+//!
+//! A [`HandlerError`] results in an empty response with its status. Add the
+//! [`ProblemDetailsMiddleware`] to a pipeline to respond with the problem the error was
+//! created from instead. The router itself responds with empty `404 Not Found` and
+//! `405 Method Not Allowed` responses, which are turned into problems by registering
+//! a [`ProblemResponseExtender`] for these statuses.
+//!
+//! # Example
+//!
+//! ```rust
+//! use gotham::helpers::http::Body;
+//! use gotham::router::builder::{build_simple_router, DefineSingleRoute, DrawRoutes};
+//! use gotham::state::State;
+//! use http::{Response, StatusCode};
+//! use problem_details::gotham::ProblemResponseExtender;
+//! use problem_details::ProblemDetails;
+//!
+//! fn teapot(state: State) -> (State, Result<Response<Body>, ProblemDetails>) {
+//!     // always return a problem description
+//!     let problem = ProblemDetails::from_status_code(StatusCode::IM_A_TEAPOT)
+//!         .with_detail("short and stout");
+//!     (state, Err(problem))
+//! }
+//!
+//! let router = build_simple_router(|route| {
+//!     route.get("/teapot").to(teapot);
+//!     route.add_response_extender(StatusCode::NOT_FOUND, ProblemResponseExtender);
+//!     route.add_response_extender(StatusCode::METHOD_NOT_ALLOWED, ProblemResponseExtender);
+//! });
+//! # let _router = router;
+//! ```
+use std::pin::Pin;
+
+use gotham::anyhow;
+use gotham::handler::{HandlerError, HandlerFuture, IntoResponse};
+use gotham::helpers::http::response::{create_empty_response, create_response};
+use gotham::helpers::http::Body;
+use gotham::http_body::Body as _;
+use gotham::middleware::{Middleware, NewMiddleware};
+use gotham::mime::Mime;
+use gotham::router::response::ResponseExtender;
+use gotham::state::{FromState, State};
+use http::{header, Response, StatusCode, Uri};
+
+use crate::{JsonProblemDetails, ProblemDetails, TextProblemDetails};
+
+#[cfg(feature = "xml")]
+use crate::XmlProblemDetails;
+
+#[cfg(feature = "cbor")]
+use crate::CborProblemDetails;
+
+#[cfg(feature = "yaml")]
+use crate::YamlProblemDetails;
+
+#[cfg(feature = "html")]
+use crate::HtmlProblemDetails;
+
+impl<Ext> IntoResponse for JsonProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn into_response(self, state: &State) -> Response<Body> {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = match Self(details).to_body_bytes() {
+            Ok(json) => json,
+            Err(_) => return create_empty_response(state, StatusCode::INTERNAL_SERVER_ERROR),
+        };
+
+        let mut response = create_response(state, status_code, mime(Self::CONTENT_TYPE), content);
+        response.headers_mut().extend(headers);
+        response
+    }
+}
+
+#[cfg(feature = "xml")]
+impl<Ext> IntoResponse for XmlProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn into_response(self, state: &State) -> Response<Body> {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = match Self(details).to_body_bytes() {
+            Ok(xml) => xml,
+            Err(_) => return create_empty_response(state, StatusCode::INTERNAL_SERVER_ERROR),
+        };
+
+        let mut response = create_response(state, status_code, mime(Self::CONTENT_TYPE), content);
+        response.headers_mut().extend(headers);
+        response
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl<Ext> IntoResponse for CborProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn into_response(self, state: &State) -> Response<Body> {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = match Self(details).to_body_vec() {
+            Ok(cbor) => cbor,
+            Err(_) => return create_empty_response(state, StatusCode::INTERNAL_SERVER_ERROR),
+        };
+
+        let mut response = create_response(state, status_code, mime(Self::CONTENT_TYPE), content);
+        response.headers_mut().extend(headers);
+        response
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl<Ext> IntoResponse for YamlProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn into_response(self, state: &State) -> Response<Body> {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = match Self(details).to_body_string() {
+            Ok(yaml) => yaml,
+            Err(_) => return create_empty_response(state, StatusCode::INTERNAL_SERVER_ERROR),
+        };
+
+        let mut response = create_response(state, status_code, mime(Self::CONTENT_TYPE), content);
+        response.headers_mut().extend(headers);
+        response
+    }
+}
+
+#[cfg(feature = "html")]
+impl<Ext> IntoResponse for HtmlProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn into_response(self, state: &State) -> Response<Body> {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = match Self(details).to_body_string() {
+            Ok(html) => html,
+            Err(_) => return create_empty_response(state, StatusCode::INTERNAL_SERVER_ERROR),
+        };
+
+        let mut response = create_response(state, status_code, mime(Self::CONTENT_TYPE), content);
+        response.headers_mut().extend(headers);
+        response
+    }
+}
+
+impl<Ext> IntoResponse for TextProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn into_response(self, state: &State) -> Response<Body> {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = Self(details).to_body_string();
+
+        let mut response = create_response(state, status_code, mime(Self::CONTENT_TYPE), content);
+        response.headers_mut().extend(headers);
+        response
+    }
+}
+
+impl<Ext> IntoResponse for ProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn into_response(self, state: &State) -> Response<Body> {
+        JsonProblemDetails(self).into_response(state)
+    }
+}
+
+/// A middleware converting handler errors into JSON problems.
+///
+/// If the error was created from a [`ProblemDetails`], e.g. using `?`, this problem
+/// is returned. For all other errors, a problem with the status of the error is
+/// returned, without revealing the cause. The path of the request is used as `instance`.
+///
+/// ```rust
+/// use gotham::pipeline::{new_pipeline, single_pipeline};
+/// use gotham::router::builder::build_router;
+/// use problem_details::gotham::ProblemDetailsMiddleware;
+///
+/// let (chain, pipelines) = single_pipeline(new_pipeline().add(ProblemDetailsMiddleware).build());
+/// let router = build_router(chain, pipelines, |_route| {
+///     // define routes...
+/// });
+/// # let _router = router;
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ProblemDetailsMiddleware;
+
+impl NewMiddleware for ProblemDetailsMiddleware {
+    type Instance = Self;
+
+    fn new_middleware(&self) -> anyhow::Result<Self::Instance> {
+        Ok(*self)
+    }
+}
+
+impl Middleware for ProblemDetailsMiddleware {
+    fn call<Chain>(self, state: State, chain: Chain) -> Pin<Box<HandlerFuture>>
+    where
+        Chain: FnOnce(State) -> Pin<Box<HandlerFuture>> + Send + 'static,
+    {
+        let future = chain(state);
+        Box::pin(async move {
+            match future.await {
+                Ok(result) => Ok(result),
+                Err((state, error)) => {
+                    let response = handler_error_problem(&state, error).into_response(&state);
+                    Ok((state, response))
+                }
+            }
+        })
+    }
+}
+
+/// A response extender replacing empty responses with JSON problems.
+///
+/// Register it for the statuses of the router, like `404 Not Found` and
+/// `405 Method Not Allowed`. The problem has the status of the response and the
+/// path of the request as `instance`. Headers, like `Allow`, are kept. Responses
+/// with a body, including problems returned by handlers, are not changed.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ProblemResponseExtender;
+
+impl ResponseExtender<Body> for ProblemResponseExtender {
+    fn extend(&self, state: &mut State, response: &mut Response<Body>) {
+        if response.body().size_hint().exact() != Some(0) {
+            return;
+        }
+
+        let details = status_problem(state, response.status());
+        let (parts, body) = details.into_response(state).into_parts();
+        if let Some(content_type) = parts.headers.get(header::CONTENT_TYPE) {
+            response
+                .headers_mut()
+                .insert(header::CONTENT_TYPE, content_type.clone());
+        }
+        *response.body_mut() = body;
+    }
+}
+
+fn handler_error_problem(state: &State, error: HandlerError) -> ProblemDetails {
+    let status = error.status();
+    match error.into_cause().downcast::<ProblemDetails>() {
+        Ok(details) => details,
+        Err(_) => status_problem(state, status),
+    }
+}
+
+fn status_problem(state: &State, status: StatusCode) -> ProblemDetails {
+    let details = ProblemDetails::from_status_code(status);
+    match Uri::try_from(Uri::borrow_from(state).path()) {
+        Ok(path) => details.with_instance(path),
+        Err(_) => details,
+    }
+}
+
+fn mime(content_type: &'static str) -> Mime {
+    content_type
+        .parse()
+        .expect("content types of problems are valid")
+}
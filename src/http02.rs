@@ -0,0 +1,132 @@
+//! Conversion between the types of [`http`] 1.x, used by this crate, and http 0.2.
+//! Requires feature `http02`.
+//!
+//! Frameworks like actix-web or tonic 0.11 still use http 0.2. The functions in this
+//! module convert status codes, URIs and header maps between both versions, and
+//! [`ProblemDetails`] provides builder methods and accessors for http 0.2 types.
+//! Both versions validate values identically, so the conversions never fail.
+//!
+//! # Example
+//!
+//! ```rust
+//! use problem_details::ProblemDetails;
+//!
+//! // The types of a framework using http 0.2
+//! let status = http02::StatusCode::NOT_FOUND;
+//! let instance = http02::Uri::from_static("/orders/42");
+//!
+//! let details = ProblemDetails::new()
+//!     .with_http02_status(status)
+//!     .with_http02_instance(instance.clone());
+//!
+//! assert_eq!(details.status, Some(http::StatusCode::NOT_FOUND));
+//! assert_eq!(details.http02_status(), Some(status));
+//! assert_eq!(details.http02_instance(), Some(instance));
+//! ```
+use http::{HeaderMap, HeaderName, HeaderValue, StatusCode, Uri};
+
+use crate::ProblemDetails;
+
+/// Converts an http 0.2 status code.
+pub fn from_http02_status_code(status: http02::StatusCode) -> StatusCode {
+    StatusCode::from_u16(status.as_u16()).expect("status codes of http 0.2 are valid")
+}
+
+/// Converts a status code to http 0.2.
+pub fn to_http02_status_code(status: StatusCode) -> http02::StatusCode {
+    http02::StatusCode::from_u16(status.as_u16()).expect("status codes of http 1 are valid")
+}
+
+/// Converts an http 0.2 URI.
+pub fn from_http02_uri(uri: &http02::Uri) -> Uri {
+    Uri::try_from(uri.to_string()).expect("URIs of http 0.2 are valid")
+}
+
+/// Converts a URI to http 0.2.
+pub fn to_http02_uri(uri: &Uri) -> http02::Uri {
+    http02::Uri::try_from(uri.to_string()).expect("URIs of http 1 are valid")
+}
+
+/// Converts an http 0.2 header map, keeping repeated headers.
+pub fn from_http02_header_map(headers: &http02::HeaderMap) -> HeaderMap {
+    let mut converted = HeaderMap::with_capacity(headers.len());
+    for (name, value) in headers {
+        converted.append(
+            HeaderName::from_bytes(name.as_str().as_bytes())
+                .expect("header names of http 0.2 are valid"),
+            HeaderValue::from_bytes(value.as_bytes()).expect("header values of http 0.2 are valid"),
+        );
+    }
+    converted
+}
+
+/// Converts a header map to http 0.2, keeping repeated headers.
+pub fn to_http02_header_map(headers: &HeaderMap) -> http02::HeaderMap {
+    let mut converted = http02::HeaderMap::with_capacity(headers.len());
+    for (name, value) in headers {
+        converted.append(
+            http02::HeaderName::from_bytes(name.as_str().as_bytes())
+                .expect("header names of http 1 are valid"),
+            http02::HeaderValue::from_bytes(value.as_bytes())
+                .expect("header values of http 1 are valid"),
+        );
+    }
+    converted
+}
+
+impl<Ext> ProblemDetails<Ext> {
+    /// Builder-style method that sets the `status` field from an http 0.2 status code.
+    ///
+    /// Requires feature `http02`.
+    #[must_use]
+    pub fn with_http02_status(self, status: http02::StatusCode) -> Self {
+        self.with_status(from_http02_status_code(status))
+    }
+
+    /// Builder-style method that sets the `instance` field from an http 0.2 URI.
+    ///
+    /// Requires feature `http02`.
+    #[must_use]
+    pub fn with_http02_instance(self, instance: http02::Uri) -> Self {
+        self.with_instance(from_http02_uri(&instance))
+    }
+
+    /// Builder-style method that adds http 0.2 headers to the response for this problem.
+    ///
+    /// Existing headers with the same names are replaced.
+    ///
+    /// Requires feature `http02`.
+    #[must_use]
+    pub fn with_http02_headers(mut self, headers: &http02::HeaderMap) -> Self {
+        let headers = from_http02_header_map(headers);
+        let existing = self.headers_mut();
+        for name in headers.keys() {
+            existing.remove(name);
+        }
+        for (name, value) in &headers {
+            existing.append(name, value.clone());
+        }
+        self
+    }
+
+    /// Returns the `status` field as http 0.2 status code.
+    ///
+    /// Requires feature `http02`.
+    pub fn http02_status(&self) -> Option<http02::StatusCode> {
+        self.status.map(to_http02_status_code)
+    }
+
+    /// Returns the `instance` field as http 0.2 URI.
+    ///
+    /// Requires feature `http02`.
+    pub fn http02_instance(&self) -> Option<http02::Uri> {
+        self.instance.as_ref().map(to_http02_uri)
+    }
+
+    /// Returns the headers to send with the response for this problem as http 0.2 header map.
+    ///
+    /// Requires feature `http02`.
+    pub fn http02_headers(&self) -> http02::HeaderMap {
+        to_http02_header_map(self.headers())
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{Correlation, ProblemDetails};
+
+/// Extensions that can take an additional extension value of type `T`.
+///
+/// This allows framework layers to add well-known members (e.g. a [`Correlation`])
+/// to problem details without knowing the concrete extensions type. Extensions
+/// types opt in by implementing this trait for the values they can carry.
+///
+/// Injected values never replace members the extensions already have, so values set
+/// by a handler take precedence over the ones added by a layer.
+///
+/// The crate implements this trait for
+///
+/// - [`Correlation`], filling in the members which are not set,
+/// - tuples ending with `T`, e.g. as created using
+///   [`extend_extensions`](ProblemDetails::extend_extensions), injecting into the last element,
+/// - [`DynamicExtensions`](crate::DynamicExtensions), `serde_json::Map` and `HashMap<String, Value>`
+///   for all serializable values, adding the members which are not present (requires feature `json`).
+///
+/// # Implementing for custom extensions
+///
+/// ```rust
+/// use problem_details::{Correlation, InjectExtension, ProblemDetails};
+///
+/// struct MyExt {
+///     balance: u32,
+///     correlation: Correlation,
+/// }
+///
+/// impl InjectExtension<Correlation> for MyExt {
+///     fn inject_extension(&mut self, value: Correlation) {
+///         self.correlation.inject_extension(value);
+///     }
+/// }
+///
+/// let mut details = ProblemDetails::new().with_extensions(MyExt {
+///     balance: 30,
+///     correlation: Correlation::new(),
+/// });
+///
+/// details.inject_extension(Correlation::new().with_request_id("req-42"));
+/// assert_eq!(details.extensions.correlation.request_id.as_deref(), Some("req-42"));
+/// ```
+pub trait InjectExtension<T> {
+    /// Adds the given value to these extensions, keeping existing members.
+    fn inject_extension(&mut self, value: T);
+}
+
+impl InjectExtension<Correlation> for Correlation {
+    fn inject_extension(&mut self, value: Correlation) {
+        if self.trace_id.is_none() {
+            self.trace_id = value.trace_id;
+        }
+        if self.span_id.is_none() {
+            self.span_id = value.span_id;
+        }
+        if self.request_id.is_none() {
+            self.request_id = value.request_id;
+        }
+    }
+}
+
+impl<Ext, T> InjectExtension<T> for (Ext, T)
+where
+    T: InjectExtension<T>,
+{
+    fn inject_extension(&mut self, value: T) {
+        self.1.inject_extension(value);
+    }
+}
+
+impl<Ext> ProblemDetails<Ext> {
+    /// Adds the given value to the extensions, keeping existing members, see [`InjectExtension`].
+    pub fn inject_extension<T>(&mut self, value: T)
+    where
+        Ext: InjectExtension<T>,
+    {
+        self.extensions.inject_extension(value);
+    }
+}
+
+#[cfg(feature = "json")]
+mod json {
+    use std::collections::HashMap;
+    use std::hash::BuildHasher;
+
+    use serde_json::{Map, Value};
+
+    use super::InjectExtension;
+    use crate::dynamic_extensions::RESERVED_MEMBERS;
+    use crate::DynamicExtensions;
+
+    /// Returns the members of the given value, skipping reserved keys.
+    ///
+    /// Values which cannot be represented as JSON object have no members.
+    fn members<T>(value: T) -> impl Iterator<Item = (String, Value)>
+    where
+        T: serde::Serialize,
+    {
+        let members = match serde_json::to_value(value) {
+            Ok(Value::Object(members)) => members,
+            _ => Map::new(),
+        };
+
+        members
+            .into_iter()
+            .filter(|(key, _)| !RESERVED_MEMBERS.contains(&key.as_str()))
+    }
+
+    impl<T> InjectExtension<T> for DynamicExtensions
+    where
+        T: serde::Serialize,
+    {
+        fn inject_extension(&mut self, value: T) {
+            for (key, value) in members(value) {
+                if !self.contains_key(&key) {
+                    // Reserved keys were skipped.
+                    let _ = self.insert(key, value);
+                }
+            }
+        }
+    }
+
+    impl<T> InjectExtension<T> for Map<String, Value>
+    where
+        T: serde::Serialize,
+    {
+        fn inject_extension(&mut self, value: T) {
+            for (key, value) in members(value) {
+                self.entry(key).or_insert(value);
+            }
+        }
+    }
+
+    impl<T, S> InjectExtension<T> for HashMap<String, Value, S>
+    where
+        T: serde::Serialize,
+        S: BuildHasher,
+    {
+        fn inject_extension(&mut self, value: T) {
+            for (key, value) in members(value) {
+                self.entry(key).or_insert(value);
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use serde_json::{Map, Value};
+
+use crate::dynamic_extensions::RESERVED_MEMBERS;
+use crate::{DynamicExtensions, ProblemDetails, ReservedKeyError};
+
+/// Extension members as plain JSON object.
+///
+/// See [`ProblemDetails::with_json_extensions`].
+///
+/// Requires feature `json`.
+pub type JsonExtensions = Map<String, Value>;
+
+/// Error returned when a JSON value cannot be used as extension members.
+///
+/// Requires feature `json`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonExtensionsError {
+    /// The value is not a JSON object.
+    NotAnObject,
+
+    /// The object contains a member reserved by RFC 9457.
+    ReservedKey(ReservedKeyError),
+}
+
+impl std::fmt::Display for JsonExtensionsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotAnObject => write!(f, "Extension members must be a JSON object"),
+            Self::ReservedKey(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for JsonExtensionsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::NotAnObject => None,
+            Self::ReservedKey(err) => Some(err),
+        }
+    }
+}
+
+impl From<ReservedKeyError> for JsonExtensionsError {
+    fn from(err: ReservedKeyError) -> Self {
+        Self::ReservedKey(err)
+    }
+}
+
+/// Checks that the given value is an object without reserved members.
+fn json_extensions(value: Value) -> Result<JsonExtensions, JsonExtensionsError> {
+    let Value::Object(members) = value else {
+        return Err(JsonExtensionsError::NotAnObject);
+    };
+
+    if let Some(key) = members
+        .keys()
+        .find(|key| RESERVED_MEMBERS.contains(&key.as_str()))
+    {
+        return Err(ReservedKeyError(key.clone()).into());
+    }
+
+    Ok(members)
+}
+
+impl TryFrom<Value> for DynamicExtensions {
+    type Error = JsonExtensionsError;
+
+    /// Converts a JSON object into dynamic extension members.
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let mut extensions = DynamicExtensions::new();
+        for (key, value) in json_extensions(value)? {
+            extensions.insert(key, value)?;
+        }
+        Ok(extensions)
+    }
+}
+
+impl From<DynamicExtensions> for JsonExtensions {
+    fn from(extensions: DynamicExtensions) -> Self {
+        extensions
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .collect()
+    }
+}
+
+impl<Ext> ProblemDetails<Ext> {
+    /// Builder-style method that replaces the extensions with the members of the given JSON object.
+    ///
+    /// Returns an error if the value is not a JSON object, or if it contains
+    /// a member reserved by RFC 9457.
+    ///
+    /// ```rust
+    /// use problem_details::{JsonExtensions, ProblemDetails};
+    /// use serde_json::json;
+    ///
+    /// let details = ProblemDetails::new()
+    ///     .with_json_extensions(json!({ "balance": 30, "accounts": ["/account/12345"] }))
+    ///     .unwrap();
+    ///
+    /// // details is of type ProblemDetails<JsonExtensions>
+    /// let typecheck: ProblemDetails<JsonExtensions> = details;
+    ///
+    /// assert!(ProblemDetails::new().with_json_extensions(json!([1, 2])).is_err());
+    /// assert!(ProblemDetails::new().with_json_extensions(json!({ "status": 400 })).is_err());
+    /// ```
+    pub fn with_json_extensions(
+        self,
+        value: Value,
+    ) -> Result<ProblemDetails<JsonExtensions>, JsonExtensionsError> {
+        let extensions = json_extensions(value)?;
+        Ok(self.with_extensions(extensions))
+    }
+}
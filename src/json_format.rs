@@ -0,0 +1,48 @@
+use std::sync::OnceLock;
+
+static JSON_FORMAT: OnceLock<JsonFormat> = OnceLock::new();
+
+/// The layout of JSON written by [`JsonProblemDetails`](crate::JsonProblemDetails).
+///
+/// In [`Pretty`](JsonFormat::Pretty) format, responses are indented, so error responses
+/// can be read in curl or a browser without piping them through a formatter. The members
+/// are written in the same order as in [`Compact`](JsonFormat::Compact) format. Intended
+/// for local development, as it increases the size of responses.
+///
+/// ```rust
+/// use problem_details::{json_format, JsonFormat};
+///
+/// assert_eq!(json_format(), JsonFormat::Compact);
+/// ```
+///
+/// Requires feature `json`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum JsonFormat {
+    /// JSON without any whitespace.
+    #[default]
+    Compact,
+
+    /// Indented JSON.
+    Pretty,
+}
+
+/// Configures the process-wide [`JsonFormat`] of serialized responses.
+///
+/// The format can only be set once. If it was already set, the given format
+/// is returned as error. Defaults to [`JsonFormat::Compact`].
+///
+/// ```rust,no_run
+/// use problem_details::{set_json_format, JsonFormat};
+///
+/// if cfg!(debug_assertions) {
+///     set_json_format(JsonFormat::Pretty).unwrap();
+/// }
+/// ```
+pub fn set_json_format(format: JsonFormat) -> Result<(), JsonFormat> {
+    JSON_FORMAT.set(format)
+}
+
+/// Returns the process-wide format configured using [`set_json_format`].
+pub fn json_format() -> JsonFormat {
+    JSON_FORMAT.get().copied().unwrap_or_default()
+}
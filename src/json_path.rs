@@ -0,0 +1,161 @@
+use std::fmt;
+
+use http::StatusCode;
+use serde::de::DeserializeOwned;
+use serde_path_to_error::{Path, Segment};
+
+use crate::{FieldError, ProblemDetails, ValidationErrors};
+
+/// Error returned when JSON cannot be parsed, including the path to the failing value.
+///
+/// The error can be converted into a `400 Bad Request` problem, whose detail names the
+/// path of the invalid value and which lists it as [`FieldError`]:
+///
+/// ```rust
+/// use http::StatusCode;
+/// use problem_details::{from_json_with_path, ProblemDetails, ValidationErrors};
+///
+/// #[derive(Debug, serde::Deserialize)]
+/// struct Order {
+///     items: Vec<Item>,
+/// }
+///
+/// #[derive(Debug, serde::Deserialize)]
+/// struct Item {
+///     quantity: u32,
+/// }
+///
+/// let error = from_json_with_path::<Order>(r#"{"items":[{"quantity":1},{"quantity":-1}]}"#)
+///     .unwrap_err();
+/// assert_eq!(error.path(), "items[1].quantity");
+/// assert_eq!(error.pointer(), "/items/1/quantity");
+///
+/// let details = ProblemDetails::<ValidationErrors>::from(error);
+/// assert_eq!(details.status, Some(StatusCode::BAD_REQUEST));
+/// assert!(details.detail.unwrap().starts_with("Invalid request body at `items[1].quantity`"));
+/// assert_eq!(details.extensions.errors[0].pointer, "/items/1/quantity");
+/// ```
+///
+/// Requires feature `serde_path_to_error`.
+#[derive(Debug)]
+pub struct JsonPathError {
+    path: Option<Path>,
+    error: serde_json::Error,
+}
+
+impl JsonPathError {
+    /// Returns the path to the failing value, e.g. `errors[3].pointer`.
+    ///
+    /// The path is `.` if the error is not related to a specific value.
+    pub fn path(&self) -> String {
+        match &self.path {
+            Some(path) => path.to_string(),
+            None => ".".to_string(),
+        }
+    }
+
+    /// Returns the path to the failing value as JSON Pointer, e.g. `/errors/3/pointer`.
+    pub fn pointer(&self) -> String {
+        let segments = self.segments().filter_map(|segment| match segment {
+            Segment::Seq { index } => Some(index.to_string()),
+            Segment::Map { key } => Some(key.clone()),
+            Segment::Enum { .. } | Segment::Unknown => None,
+        });
+
+        FieldError::from_path(segments, "").pointer
+    }
+
+    fn segments(&self) -> impl Iterator<Item = &Segment> {
+        self.path.iter().flat_map(|path| path.iter())
+    }
+
+    /// Returns the underlying JSON error.
+    pub fn inner(&self) -> &serde_json::Error {
+        &self.error
+    }
+
+    /// Converts this into the underlying JSON error, discarding the path.
+    pub fn into_inner(self) -> serde_json::Error {
+        self.error
+    }
+}
+
+impl fmt::Display for JsonPathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.segments().next() {
+            Some(_) => write!(f, "{}: {}", self.path(), self.error),
+            None => self.error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for JsonPathError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<serde_path_to_error::Error<serde_json::Error>> for JsonPathError {
+    fn from(error: serde_path_to_error::Error<serde_json::Error>) -> Self {
+        Self {
+            path: Some(error.path().clone()),
+            error: error.into_inner(),
+        }
+    }
+}
+
+impl From<JsonPathError> for ProblemDetails<ValidationErrors> {
+    fn from(error: JsonPathError) -> Self {
+        let detail = match error.segments().next() {
+            Some(_) => format!(
+                "Invalid request body at `{}`: {}",
+                error.path(),
+                error.error
+            ),
+            None => format!("Invalid request body: {}", error.error),
+        };
+
+        ProblemDetails::from_status_code(StatusCode::BAD_REQUEST)
+            .with_detail(detail)
+            .with_field_error(error.pointer(), error.error.to_string())
+    }
+}
+
+/// Parses a value from a JSON string, reporting the path to the failing value on error.
+///
+/// Requires feature `serde_path_to_error`.
+pub fn from_json_with_path<T: DeserializeOwned>(json: &str) -> Result<T, JsonPathError> {
+    let deserializer = &mut serde_json::Deserializer::from_str(json);
+    let value = serde_path_to_error::deserialize(&mut *deserializer)?;
+    deserializer
+        .end()
+        .map_err(|error| JsonPathError { path: None, error })?;
+    Ok(value)
+}
+
+/// Parses a value from a JSON buffer, reporting the path to the failing value on error.
+///
+/// Requires feature `serde_path_to_error`.
+pub fn from_json_slice_with_path<T: DeserializeOwned>(json: &[u8]) -> Result<T, JsonPathError> {
+    let deserializer = &mut serde_json::Deserializer::from_slice(json);
+    let value = serde_path_to_error::deserialize(&mut *deserializer)?;
+    deserializer
+        .end()
+        .map_err(|error| JsonPathError { path: None, error })?;
+    Ok(value)
+}
+
+impl<Ext> ProblemDetails<Ext>
+where
+    Ext: DeserializeOwned,
+{
+    /// Parses a problem details object from JSON, reporting the path to the failing value on error.
+    ///
+    /// Members of the extensions are buffered before they are deserialized, so errors
+    /// in extensions are reported without a path.
+    ///
+    /// Requires feature `serde_path_to_error`.
+    pub fn from_json_with_path(json: &str) -> Result<Self, JsonPathError> {
+        from_json_with_path(json)
+    }
+}
@@ -0,0 +1,227 @@
+//! Conversion between [`ProblemDetails`] and [JSON:API](https://jsonapi.org/format/#errors)
+//! error objects. Requires feature `jsonapi`.
+//!
+//! The members of both formats are mapped as follows:
+//!
+//! | Problem details               | JSON:API error object |
+//! |-------------------------------|-----------------------|
+//! | `type`                        | `links.type`          |
+//! | `status`                      | `status`              |
+//! | `title`                       | `title`               |
+//! | `detail`                      | `detail`              |
+//! | `instance`                    | `links.about`         |
+//! | extension `id`                | `id`                  |
+//! | extension `code`              | `code`                |
+//! | extension `pointer`           | `source.pointer`      |
+//! | extension `parameter`         | `source.parameter`    |
+//! | extension `header`            | `source.header`       |
+//! | other extensions              | `meta`                |
+//!
+//! # Example
+//!
+//! ```rust
+//! use http::StatusCode;
+//! use problem_details::{jsonapi, JsonExtensions, ProblemDetails};
+//!
+//! let details = ProblemDetails::from_status_code(StatusCode::UNPROCESSABLE_ENTITY)
+//!     .with_detail("First name must contain at least two characters.")
+//!     .with_extension("pointer", "/data/attributes/firstName")
+//!     .with_extension("minLength", 2);
+//!
+//! let error = jsonapi::ErrorObject::from(&details);
+//! assert_eq!(error.status.as_deref(), Some("422"));
+//! assert_eq!(error.source.as_ref().unwrap().pointer.as_deref(), Some("/data/attributes/firstName"));
+//! assert_eq!(error.meta.as_ref().unwrap()["minLength"], 2);
+//!
+//! let restored = ProblemDetails::<JsonExtensions>::from(error);
+//! assert_eq!(restored.status, Some(StatusCode::UNPROCESSABLE_ENTITY));
+//! assert_eq!(restored.extensions["pointer"], "/data/attributes/firstName");
+//! assert_eq!(restored.extensions["minLength"], 2);
+//! ```
+use http::{StatusCode, Uri};
+use serde_json::{Map, Value};
+
+use crate::dynamic_extensions::RESERVED_MEMBERS;
+use crate::{JsonExtensions, ProblemDetails, ProblemType};
+
+/// The content type of JSON:API documents.
+pub const CONTENT_TYPE: &str = "application/vnd.api+json";
+
+/// A JSON:API document containing error objects.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ErrorDocument {
+    /// The error objects.
+    pub errors: Vec<ErrorObject>,
+}
+
+/// A JSON:API error object.
+///
+/// Members which are not set are `None` and omitted when serialized.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ErrorObject {
+    /// A unique identifier for this occurrence of the problem.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// Links describing the problem.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub links: Option<ErrorLinks>,
+
+    /// The HTTP status code, as string.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+
+    /// An application-specific error code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+
+    /// A short, human-readable summary of the problem.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// A human-readable explanation specific to this occurrence of the problem.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+
+    /// References to the source of the error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<ErrorSource>,
+
+    /// Non-standard meta-information about the error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Map<String, Value>>,
+}
+
+/// The links of a JSON:API error object.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ErrorLinks {
+    /// A link leading to further details about this occurrence of the problem.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub about: Option<String>,
+
+    /// A link identifying the type of the error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+}
+
+/// The source of a JSON:API error.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ErrorSource {
+    /// A JSON Pointer to the value in the request document that caused the error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pointer: Option<String>,
+
+    /// The URI query parameter that caused the error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameter: Option<String>,
+
+    /// The request header that caused the error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header: Option<String>,
+}
+
+impl<Ext> From<&ProblemDetails<Ext>> for ErrorObject
+where
+    Ext: serde::Serialize,
+{
+    fn from(details: &ProblemDetails<Ext>) -> Self {
+        let details = details.to_json_extensions();
+        let mut extensions = details.extensions;
+
+        let links = ErrorLinks {
+            about: details.instance.map(|instance| instance.to_string()),
+            r#type: details.r#type.map(|r#type| r#type.to_string()),
+        };
+        let source = ErrorSource {
+            pointer: take_string(&mut extensions, "pointer"),
+            parameter: take_string(&mut extensions, "parameter"),
+            header: take_string(&mut extensions, "header"),
+        };
+
+        Self {
+            id: take_string(&mut extensions, "id"),
+            links: (links != ErrorLinks::default()).then_some(links),
+            status: details.status.map(|status| status.as_u16().to_string()),
+            code: take_string(&mut extensions, "code"),
+            title: details.title.map(Into::into),
+            detail: details.detail.map(Into::into),
+            source: (source != ErrorSource::default()).then_some(source),
+            meta: (!extensions.is_empty()).then_some(extensions),
+        }
+    }
+}
+
+impl<Ext> From<ProblemDetails<Ext>> for ErrorObject
+where
+    Ext: serde::Serialize,
+{
+    fn from(details: ProblemDetails<Ext>) -> Self {
+        Self::from(&details)
+    }
+}
+
+/// Members with invalid values, like unparseable status codes or URIs, are ignored.
+/// Meta members reserved by RFC 9457 are dropped.
+impl From<ErrorObject> for ProblemDetails<JsonExtensions> {
+    fn from(error: ErrorObject) -> Self {
+        let source = error.source.unwrap_or_default();
+        let mut extensions = JsonExtensions::new();
+        let members = [
+            ("id", error.id),
+            ("code", error.code),
+            ("pointer", source.pointer),
+            ("parameter", source.parameter),
+            ("header", source.header),
+        ];
+        for (key, value) in members {
+            if let Some(value) = value {
+                extensions.insert(key.to_string(), Value::String(value));
+            }
+        }
+        for (key, value) in error.meta.unwrap_or_default() {
+            if !RESERVED_MEMBERS.contains(&key.as_str()) {
+                extensions.entry(key).or_insert(value);
+            }
+        }
+
+        let links = error.links.unwrap_or_default();
+        let mut details = ProblemDetails::new().with_extensions(extensions);
+        details.r#type = links
+            .r#type
+            .and_then(|r#type| ProblemType::try_from(r#type.as_str()).ok());
+        details.status = error
+            .status
+            .and_then(|status| status.parse::<u16>().ok())
+            .and_then(|status| StatusCode::from_u16(status).ok());
+        details.title = error.title.map(Into::into);
+        details.detail = error.detail.map(Into::into);
+        details.instance = links
+            .about
+            .and_then(|instance| Uri::try_from(instance).ok());
+        details
+    }
+}
+
+impl FromIterator<ErrorObject> for ErrorDocument {
+    fn from_iter<I: IntoIterator<Item = ErrorObject>>(iter: I) -> Self {
+        Self {
+            errors: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<Ext> FromIterator<ProblemDetails<Ext>> for ErrorDocument
+where
+    Ext: serde::Serialize,
+{
+    fn from_iter<I: IntoIterator<Item = ProblemDetails<Ext>>>(iter: I) -> Self {
+        iter.into_iter().map(ErrorObject::from).collect()
+    }
+}
+
+/// Removes a string member from the extensions, leaving other values in place.
+fn take_string(extensions: &mut JsonExtensions, key: &str) -> Option<String> {
+    let value = extensions.get(key)?.as_str()?.to_string();
+    extensions.retain(|existing, _| existing != key);
+    Some(value)
+}
@@ -0,0 +1,75 @@
+use http::StatusCode;
+
+use crate::ProblemDetails;
+
+/// The broad class of a problem, derived from its status code.
+///
+/// See [`ProblemDetails::kind`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ProblemKind {
+    /// The problem was caused by the client (4xx status code).
+    ClientError,
+
+    /// The problem was caused by the server (5xx status code).
+    ServerError,
+
+    /// The problem has no status code, or a status code which does not indicate an error.
+    Unknown,
+}
+
+impl<Ext> ProblemDetails<Ext> {
+    /// Returns the [`ProblemKind`] of this problem based on its `status` field.
+    ///
+    /// ```rust
+    /// use http::StatusCode;
+    /// use problem_details::{ProblemDetails, ProblemKind};
+    ///
+    /// let details = ProblemDetails::from_status_code(StatusCode::NOT_FOUND);
+    /// assert_eq!(details.kind(), ProblemKind::ClientError);
+    ///
+    /// let details = ProblemDetails::new();
+    /// assert_eq!(details.kind(), ProblemKind::Unknown);
+    /// ```
+    pub fn kind(&self) -> ProblemKind {
+        match self.status {
+            Some(status) if status.is_client_error() => ProblemKind::ClientError,
+            Some(status) if status.is_server_error() => ProblemKind::ServerError,
+            _ => ProblemKind::Unknown,
+        }
+    }
+
+    /// Returns true if the `status` field of this problem is a client error (4xx).
+    pub fn is_client_error(&self) -> bool {
+        self.kind() == ProblemKind::ClientError
+    }
+
+    /// Returns true if the `status` field of this problem is a server error (5xx).
+    pub fn is_server_error(&self) -> bool {
+        self.kind() == ProblemKind::ServerError
+    }
+
+    /// Returns true if the request causing this problem may succeed when retried.
+    ///
+    /// This is a heuristic based on the `status` field. The following status codes
+    /// are considered retryable:
+    ///
+    /// - `408 Request Timeout`
+    /// - `425 Too Early`
+    /// - `429 Too Many Requests`
+    /// - `502 Bad Gateway`
+    /// - `503 Service Unavailable`
+    /// - `504 Gateway Timeout`
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self.status,
+            Some(
+                StatusCode::REQUEST_TIMEOUT
+                    | StatusCode::TOO_EARLY
+                    | StatusCode::TOO_MANY_REQUESTS
+                    | StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT
+            )
+        )
+    }
+}
@@ -0,0 +1,65 @@
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use crate::dynamic_extensions::RESERVED_MEMBERS;
+use crate::ProblemDetails;
+
+impl<Ext> ProblemDetails<Ext>
+where
+    Ext: DeserializeOwned,
+{
+    /// Parses a problem details object from JSON, ignoring invalid standard members.
+    ///
+    /// [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html#name-members-of-a-problem-detail)
+    /// requires consumers to ignore members with invalid values, e.g. a malformed `type`
+    /// or an out-of-range `status`. Such members are left as `None` instead of failing
+    /// the whole document, which is useful when talking to sloppy servers.
+    ///
+    /// Returns an error if the input is not a JSON object, or if the extensions
+    /// cannot be deserialized.
+    ///
+    /// ```rust
+    /// use problem_details::ProblemDetails;
+    ///
+    /// let details = ProblemDetails::<()>::from_json_lenient(
+    ///     r#"{"type": "not a uri", "status": 1000, "title": "Not Found"}"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(details.r#type, None);
+    /// assert_eq!(details.status, None);
+    /// assert_eq!(details.title.as_deref(), Some("Not Found"));
+    /// ```
+    ///
+    /// Requires feature `json`.
+    pub fn from_json_lenient(json: &str) -> Result<Self, serde_json::Error> {
+        Self::from_value_lenient(serde_json::from_str(json)?)
+    }
+
+    /// Converts a JSON value into a problem details object, ignoring invalid standard members.
+    ///
+    /// See [`from_json_lenient`](ProblemDetails::from_json_lenient) for details.
+    ///
+    /// Requires feature `json`.
+    pub fn from_value_lenient(value: Value) -> Result<Self, serde_json::Error> {
+        let Value::Object(mut members) = value else {
+            return serde_json::from_value(value);
+        };
+
+        for key in RESERVED_MEMBERS {
+            if let Some(member) = members.remove(key) {
+                if is_valid_member(key, &member) {
+                    members.insert(key.to_string(), member);
+                }
+            }
+        }
+
+        serde_json::from_value(Value::Object(members))
+    }
+}
+
+/// Returns true if the given standard member can be deserialized on its own.
+fn is_valid_member(key: &str, value: &Value) -> bool {
+    let members = Map::from_iter([(key.to_string(), value.clone())]);
+    serde_json::from_value::<ProblemDetails>(Value::Object(members)).is_ok()
+}
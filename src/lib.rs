@@ -36,22 +36,48 @@
 //! let typecheck: ProblemDetails<MyExt> = details;
 //! ```
 //!
-//! If you need dynamic extensions, you can use a [`HashMap`](std::collections::HashMap)
-//! as extensions object.
+//! If you need dynamic extensions, you can use [`DynamicExtensions`]
+//! as extensions object, most easily via [`ProblemDetails::with_extension`].
+//!
+//! ```rust
+//! use problem_details::{DynamicExtensions, ProblemDetails};
+//!
+//! let details = ProblemDetails::new()
+//!    .with_extension("foo", "Hello")
+//!    .with_extension("bar", 42);
+//!
+//! // details is of type ProblemDetails<DynamicExtensions>
+//! let typecheck: ProblemDetails<DynamicExtensions> = details;
+//! ```
+//!
+//! Multiple extension structs can be combined using a tuple. Each element
+//! of the tuple is flattened into the problem details object.
 //!
 //! ```rust
-//! use std::collections::HashMap;
 //! use problem_details::ProblemDetails;
 //!
-//! let mut extensions = HashMap::<String, serde_json::Value>::new();
-//! extensions.insert("foo".to_string(), serde_json::json!("Hello"));
-//! extensions.insert("bar".to_string(), serde_json::json!(42));
+//! #[derive(serde::Serialize)]
+//! struct TraceExt {
+//!     trace_id: String,
+//! }
+//!
+//! #[derive(serde::Serialize)]
+//! struct BalanceExt {
+//!     balance: u32,
+//! }
 //!
 //! let details = ProblemDetails::new()
-//!    .with_extensions(extensions);
+//!     .with_extensions((
+//!         TraceExt { trace_id: "abc123".to_string() },
+//!         BalanceExt { balance: 30 },
+//!     ));
 //!
-//! // details is of type ProblemDetails<HashMap<String, serde_json::Value>>
-//! let typecheck: ProblemDetails<HashMap<String, serde_json::Value>> = details;
+//! let json = serde_json::to_value(&details).unwrap();
+//!
+//! assert_eq!(json, serde_json::json!({
+//!   "trace_id": "abc123",
+//!   "balance": 30
+//! }));
 //! ```
 //!
 //! # Example
@@ -102,34 +128,369 @@
 //! - **serde**: Enables serde support for the `ProblemDetails` struct (_enabled by default_)
 //! - **json**:  Enables serialization to JSON when using web framework integrations (_enabled by default, implies `serde`)
 //! - **xml**:   Enables serialization to XML when using web framework integrations (_implies `serde`_)
+//! - **cbor**:  Enables serialization to CBOR as concise problem details
+//!   ([RFC 9290](https://www.rfc-editor.org/rfc/rfc9290.html)) (_implies `serde`_)
+//! - **yaml**:  Enables serialization to YAML when using web framework integrations (_implies `serde`_)
+//! - **html**:  Enables rendering problem details as HTML page for browsers (_implies `json`_)
 //! - **axum**:  Enables integration with the [`axum`](https://crates.io/crates/axum) web framework, allowing to
-//!              return `ProblemDetails` as responses.
+//!   return `ProblemDetails` as responses.
+//! - **axum-ws**: Converts rejections of the axum `WebSocketUpgrade` extractor into problems (_implies `axum`_).
+//! - **axum-multipart**: Converts errors of the axum `Multipart` extractor into problems (_implies `axum`_).
+//! - **axum-macros**: Provides the `#[problem_rejection]` attribute to wrap extractors (_implies `axum`, `json`_).
+//! - **gotham**: Enables integration with the [`gotham`](https://crates.io/crates/gotham) web framework, allowing to
+//!   return `ProblemDetails` as responses and converting handler errors and router responses into problems
+//!   (_implies `json`_).
 //! - **poem**:  Enables integration with the [`poem`](https://crates.io/crates/poem) web framework, allowing to
-//!              return `ProblemDetails` as responses and errors.
+//!   return `ProblemDetails` as responses and errors.
+//! - **poem-openapi**: Describes problem responses of [`poem-openapi`](https://crates.io/crates/poem-openapi)
+//!   operations in the generated OpenAPI document (_implies `poem` and `json`_).
+//! - **rocket**: Enables integration with the [`rocket`](https://crates.io/crates/rocket) web framework, allowing to
+//!   return `ProblemDetails` as responses and registering catchers responding with problems (_implies `json`_).
+//! - **salvo**: Enables integration with the [`salvo`](https://crates.io/crates/salvo) web framework, allowing to
+//!   return `ProblemDetails` as responses and catching errors with problems (_implies `json`_).
+//! - **warp**: Enables integration with the [`warp`](https://crates.io/crates/warp) web framework, allowing to
+//!   return `ProblemDetails` as replies and recovering from rejections with problems (_implies `json` and `http02`_).
+//! - **backtrace**: Captures a backtrace for server error problems, which is available for logging
+//!   but never serialized.
+//! - **indexmap**: Stores [`DynamicExtensions`] in an `IndexMap`, supports `IndexMap` as extensions
+//!   and preserves the insertion order of `serde_json::Map`, so serialized output is deterministic.
+//! - **time**:  Allows creating a [`Timestamp`] from a [`time`](https://crates.io/crates/time) `OffsetDateTime`.
+//! - **chrono**: Allows creating a [`Timestamp`] from a [`chrono`](https://crates.io/crates/chrono) `DateTime`.
+//! - **utoipa**: Implements `ToSchema` from [`utoipa`](https://crates.io/crates/utoipa) for `ProblemDetails`,
+//!   composing the schemas of extension structs.
+//! - **schemars**: Implements `JsonSchema` from [`schemars`](https://crates.io/crates/schemars) for
+//!   `ProblemDetails` and `ProblemType`, flattening the schemas of extensions.
+//! - **prost**: Provides a protobuf [`ProblemDetails`](proto::ProblemDetails) message and conversions
+//!   to and from `google.rpc.Status` in the [`proto`] module (_implies `json`_).
+//! - **tokio**: Allows writing a [`JsonProblemDetails`] to a [`tokio`](https://crates.io/crates/tokio) `AsyncWrite`
+//!   (_implies `json`_).
+//! - **jsonapi**: Converts problem details to and from JSON:API error objects in the [`jsonapi`] module
+//!   (_implies `json`_).
+//! - **odata**: Converts problem details to and from OData v4 error responses in the [`odata`] module
+//!   (_implies `json`_).
+//! - **aws**: Converts problem details to AWS API Gateway proxy responses and parses gateway errors
+//!   in the [`aws`] module (_implies `json`_).
+//! - **serde_path_to_error**: Reports the path of the failing value when JSON cannot be parsed,
+//!   see [`JsonPathError`] (_implies `json`_).
+//! - **http02**: Converts status codes, URIs and headers from and to http 0.2 in the [`http02`] module.
+//! - **url**: Allows setting and reading the `type` and `instance` of problem details as
+//!   [`url`](https://crates.io/crates/url) `Url`.
 //!
 //! # Caveats
 //!
-//! This crate is not fully compliant with the RFC, because it fails to deserialize
+//! By default, this crate is not fully compliant with the RFC, because it fails to deserialize
 //! JSON values containing properties with incorrect types (required by
 //! [Chapter 3.1 of the RFC](https://www.rfc-editor.org/rfc/rfc9457.pdf#name-members-of-a-problem-detail)).
+//! Use [`ProblemDetails::from_json_lenient`] to ignore such properties instead.
+//! Conversely, unknown extension members are ignored by default. Deserialize a
+//! [`StrictProblemDetails`] to reject them, as well as duplicate members.
 
 #![warn(missing_docs)]
 #![forbid(unsafe_code)]
 
+#[cfg(feature = "serde")]
+mod borrowed;
+mod compatibility;
+mod correlation;
+mod date;
+mod default_type;
+mod field_errors;
+mod inject;
+mod kind;
+mod localization;
+mod macros;
+mod merge;
+mod method_not_allowed;
+mod multi_problem;
+mod parts;
 mod problem_details;
 mod problem_type;
+mod rate_limit;
+mod retry_after;
+mod severity;
+#[cfg(feature = "serde")]
+mod strict_details;
+mod template;
+mod timestamp;
 
+#[cfg(feature = "serde")]
+pub use borrowed::*;
+pub use compatibility::*;
+pub use correlation::*;
+pub use default_type::*;
+pub use field_errors::*;
+pub use inject::*;
+pub use kind::*;
+pub use localization::*;
+pub use merge::*;
+pub use method_not_allowed::*;
+pub use multi_problem::*;
+pub use parts::*;
 pub use problem_details::*;
 pub use problem_type::*;
+pub use rate_limit::*;
+pub use retry_after::*;
+pub use severity::*;
+#[cfg(feature = "serde")]
+pub use strict_details::*;
+pub use template::*;
+pub use timestamp::*;
+
+pub mod builder;
+pub mod presets;
+pub mod problems;
+pub mod types;
+
+pub use builder::ProblemDetailsBuilder;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use http::{StatusCode, Uri};
+
+    #[cfg(feature = "axum")]
+    pub use ::axum;
+
+    #[cfg(feature = "utoipa")]
+    pub use ::utoipa;
+
+    #[cfg(feature = "poem-openapi")]
+    pub use ::poem_openapi;
+
+    /// Converts the rejection of any extractor, used by [`problem_rejection!`](crate::problem_rejection).
+    #[cfg(all(feature = "axum", feature = "json"))]
+    pub fn convert_rejection<R>(
+        rejection: R,
+        r#type: Option<crate::ProblemType>,
+        status: Option<StatusCode>,
+        source: crate::axum::RejectionSource,
+    ) -> crate::ProblemDetails<crate::axum::RejectionExtension>
+    where
+        R: ::axum::response::IntoResponse + std::fmt::Display,
+    {
+        let message = rejection.to_string();
+        let status = status.unwrap_or_else(|| rejection.into_response().status());
+
+        let name = std::any::type_name::<R>();
+        let name = name.split('<').next().unwrap_or(name);
+        let name = name.rsplit("::").next().unwrap_or(name);
+
+        let extension = crate::axum::RejectionExtension::new(name, source);
+        let details = crate::axum::rejection_problem(status, message, extension);
+        match r#type {
+            Some(r#type) => details.with_type(r#type),
+            None => details,
+        }
+    }
+
+    /// Checks a URI literal of [`problem!`](crate::problem) at compile time.
+    ///
+    /// This rejects empty literals and whitespace or control characters before the
+    /// fragment, which are invalid in all parts of a URI. Other errors are still
+    /// reported by [`Uri::from_static`].
+    pub const fn is_valid_uri_literal(uri: &str) -> bool {
+        let bytes = uri.as_bytes();
+        if bytes.is_empty() {
+            return false;
+        }
+
+        let mut i = 0;
+        while i < bytes.len() && bytes[i] != b'#' {
+            if bytes[i] <= b' ' || bytes[i] == 0x7f {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Checks an extension key of [`problem!`](crate::problem) at compile time.
+    ///
+    /// This rejects the keys reserved by RFC 9457, which would panic when inserted.
+    #[cfg(feature = "json")]
+    pub const fn is_valid_extension_key(key: &str) -> bool {
+        let mut i = 0;
+        while i < crate::dynamic_extensions::RESERVED_MEMBERS.len() {
+            let reserved = crate::dynamic_extensions::RESERVED_MEMBERS[i].as_bytes();
+            let key = key.as_bytes();
+
+            if reserved.len() == key.len() {
+                let mut j = 0;
+                while j < key.len() && key[j] == reserved[j] {
+                    j += 1;
+                }
+                if j == key.len() {
+                    return false;
+                }
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Checks a status code literal of [`problem!`](crate::problem) at compile time.
+    pub const fn is_valid_status_literal(status: u16) -> bool {
+        100 <= status && status < 1000
+    }
+}
 
 // Axum Support
 #[cfg(feature = "axum")]
 pub mod axum;
 
+// Gotham Support
+#[cfg(feature = "gotham")]
+pub mod gotham;
+
 // Poem Support
 #[cfg(feature = "poem")]
 pub mod poem;
 
+// Poem OpenAPI Support
+#[cfg(feature = "poem-openapi")]
+pub mod poem_openapi;
+
+// Rocket Support
+#[cfg(feature = "rocket")]
+pub mod rocket;
+
+// Salvo Support
+#[cfg(feature = "salvo")]
+pub mod salvo;
+
+// Warp Support
+#[cfg(feature = "warp")]
+pub mod warp;
+
+// Utoipa Support
+#[cfg(feature = "utoipa")]
+pub mod utoipa;
+
+// Protobuf Support
+#[cfg(feature = "prost")]
+pub mod proto;
+
+// Schemars Support
+#[cfg(feature = "schemars")]
+pub mod schemars;
+
+// JSON:API Support
+#[cfg(feature = "jsonapi")]
+pub mod jsonapi;
+
+// OData Support
+#[cfg(feature = "odata")]
+pub mod odata;
+
+// AWS API Gateway Support
+#[cfg(feature = "aws")]
+pub mod aws;
+
+// http 0.2 Compatibility
+#[cfg(feature = "http02")]
+pub mod http02;
+
+// Newline-delimited batches of problems
+#[cfg(feature = "json")]
+mod batch;
+
+#[cfg(feature = "json")]
+pub use batch::*;
+
+// Cause chains of upstream problems
+#[cfg(feature = "json")]
+mod cause;
+
+#[cfg(feature = "json")]
+pub use cause::*;
+
+// Dynamic extension members
+#[cfg(feature = "json")]
+mod dynamic_extensions;
+
+#[cfg(feature = "json")]
+pub use dynamic_extensions::*;
+
+// Type-erased extension objects
+#[cfg(feature = "json")]
+mod erased_extensions;
+
+#[cfg(feature = "json")]
+pub use erased_extensions::*;
+
+// Filtering of extension members in responses
+#[cfg(feature = "json")]
+mod extension_filter;
+
+#[cfg(feature = "json")]
+pub use extension_filter::*;
+
+// Extension members as plain JSON object
+#[cfg(feature = "json")]
+mod json_extensions;
+
+#[cfg(feature = "json")]
+pub use json_extensions::*;
+
+// Layout of JSON responses
+#[cfg(feature = "json")]
+mod json_format;
+
+#[cfg(feature = "json")]
+pub use json_format::*;
+
+// Paths of JSON parse failures
+#[cfg(feature = "serde_path_to_error")]
+mod json_path;
+
+#[cfg(feature = "serde_path_to_error")]
+pub use json_path::*;
+
+// Lenient parsing of problems
+#[cfg(feature = "json")]
+mod lenient;
+
+// 207 Multi-Status responses
+#[cfg(feature = "json")]
+mod multi_status;
+
+#[cfg(feature = "json")]
+pub use multi_status::*;
+
+// Namespaced extension members
+#[cfg(feature = "json")]
+mod namespace;
+
+#[cfg(feature = "json")]
+pub use namespace::*;
+
+// Content negotiation
+#[cfg(feature = "json")]
+mod negotiation;
+
+#[cfg(feature = "json")]
+pub use negotiation::*;
+
+// Redaction of problems sent to clients
+#[cfg(feature = "json")]
+mod redaction;
+
+#[cfg(feature = "json")]
+pub use redaction::*;
+
+// RFC conformance checks
+#[cfg(feature = "json")]
+mod validation;
+
+#[cfg(feature = "json")]
+pub use validation::*;
+
+// Typed extensions keeping unknown members
+#[cfg(feature = "json")]
+mod with_unknown;
+
+#[cfg(feature = "json")]
+pub use with_unknown::*;
+
 // Serde related extensions for http
 #[cfg(feature = "serde")]
 mod serde;
@@ -0,0 +1,141 @@
+use std::borrow::Cow;
+
+use http::{header, HeaderMap};
+
+use crate::{ProblemDetails, ProblemType};
+
+/// A provider for localized problem titles and details.
+///
+/// Translations are looked up using a key derived from the problem:
+/// the `type` URI, or the status code (e.g. `"404"`) if the type
+/// is not set or `about:blank`.
+///
+/// ```rust
+/// use std::borrow::Cow;
+/// use http::StatusCode;
+/// use problem_details::{Localizer, ProblemDetails};
+///
+/// struct German;
+///
+/// impl Localizer for German {
+///     fn title(&self, key: &str, locale: &str) -> Option<Cow<'static, str>> {
+///         match (key, locale) {
+///             ("404", "de") => Some("Nicht gefunden".into()),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let details = ProblemDetails::from_status_code(StatusCode::NOT_FOUND)
+///     .localized(&German, "de");
+///
+/// assert_eq!(details.title.as_deref(), Some("Nicht gefunden"));
+/// ```
+pub trait Localizer {
+    /// Returns the localized title for the given key and locale, if available.
+    fn title(&self, key: &str, locale: &str) -> Option<Cow<'static, str>>;
+
+    /// Returns the localized detail for the given key and locale, if available.
+    ///
+    /// The default implementation does not localize details.
+    fn detail(&self, key: &str, locale: &str) -> Option<Cow<'static, str>> {
+        let _ = (key, locale);
+        None
+    }
+}
+
+impl<Ext> ProblemDetails<Ext> {
+    /// Returns the key used to look up translations for this problem.
+    ///
+    /// This is the `type` URI, or the status code if the type is not set or
+    /// `about:blank`. Returns `None` if neither is available.
+    pub fn translation_key(&self) -> Option<String> {
+        match &self.r#type {
+            Some(r#type) if *r#type != ProblemType::default() => Some(r#type.to_string()),
+            _ => self.status.map(|status| status.as_u16().to_string()),
+        }
+    }
+
+    /// Returns a copy of this problem details object with `title` and `detail`
+    /// localized for the given locale.
+    ///
+    /// Fields without a translation are left unchanged.
+    #[must_use]
+    pub fn localized(&self, localizer: &impl Localizer, locale: &str) -> Self
+    where
+        Ext: Clone,
+    {
+        let mut localized = self.clone();
+
+        if let Some(key) = self.translation_key() {
+            if let Some(title) = localizer.title(&key, locale) {
+                localized.title = Some(title);
+            }
+            if let Some(detail) = localizer.detail(&key, locale) {
+                localized.detail = Some(detail);
+            }
+        }
+
+        localized
+    }
+
+    /// Returns a copy of this problem details object localized for the most
+    /// preferred locale in the `Accept-Language` header of the given request headers.
+    ///
+    /// The locales are tried in order of preference, and the first locale for
+    /// which a title or detail translation is available is used.
+    #[must_use]
+    pub fn localized_for(&self, localizer: &impl Localizer, headers: &HeaderMap) -> Self
+    where
+        Ext: Clone,
+    {
+        let Some(key) = self.translation_key() else {
+            return self.clone();
+        };
+
+        let locale = accept_language(headers).into_iter().find(|locale| {
+            localizer.title(&key, locale).is_some() || localizer.detail(&key, locale).is_some()
+        });
+
+        match locale {
+            Some(locale) => self.localized(localizer, &locale),
+            None => self.clone(),
+        }
+    }
+}
+
+/// Returns the locales of the `Accept-Language` header, ordered by preference.
+///
+/// Wildcards and locales with a quality of zero are skipped.
+///
+/// ```rust
+/// use http::{header, HeaderMap, HeaderValue};
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert(header::ACCEPT_LANGUAGE, HeaderValue::from_static("fr;q=0.5, de-CH, en;q=0.8"));
+///
+/// assert_eq!(problem_details::accept_language(&headers), vec!["de-CH", "en", "fr"]);
+/// ```
+pub fn accept_language(headers: &HeaderMap) -> Vec<String> {
+    let mut locales = headers
+        .get_all(header::ACCEPT_LANGUAGE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|item| {
+            let mut parts = item.split(';').map(str::trim);
+            let locale = parts
+                .next()
+                .filter(|locale| !locale.is_empty() && *locale != "*")?;
+            let quality = parts
+                .find_map(|param| param.strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.parse::<f32>().ok())?;
+
+            (quality > 0.0).then(|| (locale.to_string(), quality))
+        })
+        .collect::<Vec<_>>();
+
+    // stable sort keeps the header order for equal qualities
+    locales.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    locales.into_iter().map(|(locale, _)| locale).collect()
+}
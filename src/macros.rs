@@ -0,0 +1,155 @@
+/// Creates a [`ProblemDetails`](crate::ProblemDetails) object using a concise syntax.
+///
+/// The macro accepts a comma separated list of fields, which are expanded to the
+/// corresponding builder methods. All fields are optional and can be given in any order.
+///
+/// - `type`: A URI string literal or an expression convertible into a [`ProblemType`](crate::ProblemType)
+/// - `status`: A status code given as number literal or as [`StatusCode`](http::StatusCode) expression
+/// - `title`: An expression convertible into the title
+/// - `detail`: A format string literal (with inline arguments) or an expression convertible into the detail
+/// - `instance`: A URI string literal or an expression convertible into an [`Uri`](http::Uri)
+/// - `ext`: A list of dynamic extension fields in struct literal shorthand
+///   (requires feature `json`)
+/// - `extensions`: An extensions object passed to [`with_extensions`](crate::ProblemDetails::with_extensions)
+///
+/// Status code literals outside of `100..=999` fail to compile. URI literals which are
+/// empty or contain whitespace or control characters fail to compile as well. Other
+/// malformed URI literals are detected when the problem details object is created and
+/// panic, like [`Uri::from_static`](http::Uri::from_static). Keys in `ext` which are
+/// reserved by RFC 9457, like `status`, fail to compile, as they would overwrite the
+/// standard members; use the respective field instead.
+///
+/// # Example
+///
+/// ```rust
+/// use problem_details::problem;
+///
+/// let id = 42;
+/// let details = problem! {
+///     status: 404,
+///     type: "https://errors.example/not-found",
+///     detail: "user {id} missing",
+///     ext: { id, retry: false },
+/// };
+///
+/// assert_eq!(details.status, Some(http::StatusCode::NOT_FOUND));
+/// assert_eq!(details.detail.as_deref(), Some("user 42 missing"));
+/// assert_eq!(details.extensions["id"], serde_json::json!(42));
+/// assert_eq!(details.extensions["retry"], serde_json::json!(false));
+/// ```
+///
+/// Invalid literals are rejected by the compiler:
+///
+/// ```rust,compile_fail
+/// let details = problem_details::problem! { status: 42 };
+/// ```
+///
+/// ```rust,compile_fail
+/// let details = problem_details::problem! { type: "https://errors.example/not found" };
+/// ```
+///
+/// ```rust,compile_fail
+/// let status = 404;
+/// let details = problem_details::problem! { ext: { status } };
+/// ```
+#[macro_export]
+macro_rules! problem {
+    (@fields ($details:expr) $(,)?) => {
+        $details
+    };
+
+    (@fields ($details:expr) type: $type:literal $(, $($rest:tt)*)?) => {
+        $crate::problem!(@fields (
+            $details.with_type({
+                const {
+                    ::core::assert!(
+                        $crate::__private::is_valid_uri_literal($type),
+                        "problem! requires a valid type URI"
+                    )
+                };
+                $crate::__private::Uri::from_static($type)
+            })
+        ) $($($rest)*)?)
+    };
+    (@fields ($details:expr) type: $type:expr $(, $($rest:tt)*)?) => {
+        $crate::problem!(@fields ($details.with_type($type)) $($($rest)*)?)
+    };
+
+    (@fields ($details:expr) status: $status:literal $(, $($rest:tt)*)?) => {
+        $crate::problem!(@fields (
+            $details.with_status({
+                const {
+                    ::core::assert!(
+                        $crate::__private::is_valid_status_literal($status),
+                        "problem! requires a valid status code"
+                    )
+                };
+                $crate::__private::StatusCode::from_u16($status)
+                    .expect("problem! requires a valid status code")
+            })
+        ) $($($rest)*)?)
+    };
+    (@fields ($details:expr) status: $status:expr $(, $($rest:tt)*)?) => {
+        $crate::problem!(@fields ($details.with_status($status)) $($($rest)*)?)
+    };
+
+    (@fields ($details:expr) title: $title:expr $(, $($rest:tt)*)?) => {
+        $crate::problem!(@fields ($details.with_title($title)) $($($rest)*)?)
+    };
+
+    (@fields ($details:expr) detail: $detail:literal $(, $($rest:tt)*)?) => {
+        $crate::problem!(@fields ($details.with_detail(::std::format!($detail))) $($($rest)*)?)
+    };
+    (@fields ($details:expr) detail: $detail:expr $(, $($rest:tt)*)?) => {
+        $crate::problem!(@fields ($details.with_detail($detail)) $($($rest)*)?)
+    };
+
+    (@fields ($details:expr) instance: $instance:literal $(, $($rest:tt)*)?) => {
+        $crate::problem!(@fields (
+            $details.with_instance({
+                const {
+                    ::core::assert!(
+                        $crate::__private::is_valid_uri_literal($instance),
+                        "problem! requires a valid instance URI"
+                    )
+                };
+                $crate::__private::Uri::from_static($instance)
+            })
+        ) $($($rest)*)?)
+    };
+    (@fields ($details:expr) instance: $instance:expr $(, $($rest:tt)*)?) => {
+        $crate::problem!(@fields ($details.with_instance($instance)) $($($rest)*)?)
+    };
+
+    (@fields ($details:expr) ext: { $($key:ident $(: $value:expr)?),* $(,)? } $(, $($rest:tt)*)?) => {
+        $crate::problem!(@fields (
+            $details $(.with_extension(
+                {
+                    const {
+                        ::core::assert!(
+                            $crate::__private::is_valid_extension_key(::std::stringify!($key)),
+                            "problem! ext cannot use the standard members as keys"
+                        )
+                    };
+                    ::std::stringify!($key)
+                },
+                $crate::problem!(@ext_value $key $($value)?)
+            ))*
+        ) $($($rest)*)?)
+    };
+
+    (@fields ($details:expr) extensions: $extensions:expr $(, $($rest:tt)*)?) => {
+        $crate::problem!(@fields ($details.with_extensions($extensions)) $($($rest)*)?)
+    };
+
+    (@ext_value $key:ident) => {
+        $key
+    };
+    (@ext_value $key:ident $value:expr) => {
+        $value
+    };
+
+    ($($fields:tt)*) => {
+        $crate::problem!(@fields ($crate::ProblemDetails::new()) $($fields)*)
+    };
+}
@@ -0,0 +1,225 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+
+use crate::ProblemDetails;
+
+/// Extensions that can be merged with another extensions object of the same type.
+///
+/// This is used by [`ProblemDetails::merge`] and [`ProblemDetails::merged_with`]
+/// to combine the extensions of two problem details objects.
+///
+/// Map-based extensions are merged key-by-key, with entries from `other`
+/// replacing entries with the same key in `self`.
+///
+/// # Implementing for custom extensions
+///
+/// ```rust
+/// use problem_details::MergeExtensions;
+///
+/// struct MyExt {
+///     trace_id: Option<String>,
+/// }
+///
+/// impl MergeExtensions for MyExt {
+///     fn merge_extensions(&mut self, other: Self) {
+///         if other.trace_id.is_some() {
+///             self.trace_id = other.trace_id;
+///         }
+///     }
+/// }
+/// ```
+pub trait MergeExtensions {
+    /// Merges `other` into `self`, with values from `other` taking precedence.
+    fn merge_extensions(&mut self, other: Self);
+}
+
+impl MergeExtensions for () {
+    fn merge_extensions(&mut self, _other: Self) {}
+}
+
+impl<K, V, S> MergeExtensions for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    fn merge_extensions(&mut self, other: Self) {
+        self.extend(other);
+    }
+}
+
+impl<K, V> MergeExtensions for BTreeMap<K, V>
+where
+    K: Ord,
+{
+    fn merge_extensions(&mut self, other: Self) {
+        self.extend(other);
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<K, V, S> MergeExtensions for indexmap::IndexMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    fn merge_extensions(&mut self, other: Self) {
+        self.extend(other);
+    }
+}
+
+#[cfg(feature = "json")]
+impl MergeExtensions for serde_json::Map<String, serde_json::Value> {
+    fn merge_extensions(&mut self, other: Self) {
+        self.extend(other);
+    }
+}
+
+macro_rules! impl_merge_extensions_for_tuple {
+    ($($name:ident $index:tt),+) => {
+        impl<$($name),+> MergeExtensions for ($($name,)+)
+        where
+            $($name: MergeExtensions,)+
+        {
+            fn merge_extensions(&mut self, other: Self) {
+                $(self.$index.merge_extensions(other.$index);)+
+            }
+        }
+    };
+}
+
+impl_merge_extensions_for_tuple!(A 0);
+impl_merge_extensions_for_tuple!(A 0, B 1);
+impl_merge_extensions_for_tuple!(A 0, B 1, C 2);
+impl_merge_extensions_for_tuple!(A 0, B 1, C 2, D 3);
+impl_merge_extensions_for_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_merge_extensions_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_merge_extensions_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_merge_extensions_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+impl<Ext> ProblemDetails<Ext> {
+    /// Builder-style method that stacks additional extensions on top of the existing ones.
+    ///
+    /// Unlike [`with_extensions`](ProblemDetails::with_extensions), the existing extensions
+    /// are kept. The result uses tuple-composed extensions, so both are flattened
+    /// into the problem details object when serialized.
+    ///
+    /// ```rust
+    /// use problem_details::ProblemDetails;
+    ///
+    /// #[derive(serde::Serialize)]
+    /// struct BalanceExt {
+    ///     balance: u32,
+    /// }
+    ///
+    /// #[derive(serde::Serialize)]
+    /// struct TraceExt {
+    ///     trace_id: String,
+    /// }
+    ///
+    /// let details = ProblemDetails::new()
+    ///     .with_extensions(BalanceExt { balance: 30 })
+    ///     .extend_extensions(TraceExt { trace_id: "abc123".to_string() });
+    ///
+    /// let json = serde_json::to_value(&details).unwrap();
+    ///
+    /// assert_eq!(json, serde_json::json!({
+    ///   "balance": 30,
+    ///   "trace_id": "abc123"
+    /// }));
+    /// ```
+    #[must_use]
+    pub fn extend_extensions<NewExt>(self, extensions: NewExt) -> ProblemDetails<(Ext, NewExt)> {
+        self.map_extensions(|existing| (existing, extensions))
+    }
+}
+
+impl<Ext> ProblemDetails<Ext>
+where
+    Ext: MergeExtensions,
+{
+    /// Builder-style method that merges the given extensions into the existing ones.
+    ///
+    /// Unlike [`with_extensions`](ProblemDetails::with_extensions), the existing extensions
+    /// are kept and combined using [`MergeExtensions`]. Map-backed extensions are merged
+    /// key-by-key and tuple-composed extensions are merged element by element.
+    ///
+    /// ```rust
+    /// use problem_details::{DynamicExtensions, ProblemDetails};
+    ///
+    /// let mut trace = DynamicExtensions::new();
+    /// trace.insert("traceId", "abc123").unwrap();
+    ///
+    /// let details = ProblemDetails::new()
+    ///     .with_extension("balance", 30)
+    ///     .with_extensions_merged(trace);
+    ///
+    /// assert_eq!(details.extensions.len(), 2);
+    /// ```
+    #[must_use]
+    pub fn with_extensions_merged(mut self, extensions: Ext) -> Self {
+        self.extensions.merge_extensions(extensions);
+        self
+    }
+
+    /// Merges another problem details object into this one.
+    ///
+    /// Fields which are set in `other` take precedence and replace the
+    /// corresponding fields in `self`. Fields which are not set in `other`
+    /// are left untouched. Extensions are merged using [`MergeExtensions`].
+    ///
+    /// The [`source`](std::error::Error::source) of `other` also takes precedence if set,
+    /// and its [`headers`](ProblemDetails::headers) replace headers with the same name.
+    pub fn merge(&mut self, other: Self) {
+        if other.r#type.is_some() {
+            self.r#type = other.r#type;
+        }
+        if other.status.is_some() {
+            self.status = other.status;
+        }
+        if other.title.is_some() {
+            self.title = other.title;
+        }
+        if other.detail.is_some() {
+            self.detail = other.detail;
+        }
+        if other.instance.is_some() {
+            self.instance = other.instance;
+        }
+        if other.source.is_some() {
+            self.source = other.source;
+        }
+        if let Some(headers) = other.headers {
+            self.headers_mut().extend(headers);
+        }
+        self.extensions.merge_extensions(other.extensions);
+    }
+
+    /// Builder-style method that merges another problem details object into this one.
+    ///
+    /// See [`merge`](ProblemDetails::merge) for the precedence rules.
+    ///
+    /// ```rust
+    /// use http::{StatusCode, Uri};
+    /// use problem_details::ProblemDetails;
+    ///
+    /// let upstream = ProblemDetails::from_status_code(StatusCode::BAD_GATEWAY)
+    ///     .with_detail("Upstream failed")
+    ///     .with_extension("upstream", "billing");
+    ///
+    /// let local = ProblemDetails::new()
+    ///     .with_instance(Uri::from_static("/orders/42"))
+    ///     .with_extension("traceId", "abc123");
+    ///
+    /// let merged = upstream.merged_with(local);
+    ///
+    /// assert_eq!(merged.status, Some(StatusCode::BAD_GATEWAY));
+    /// assert_eq!(merged.detail.as_deref(), Some("Upstream failed"));
+    /// assert_eq!(merged.instance, Some(Uri::from_static("/orders/42")));
+    /// assert_eq!(merged.extensions.len(), 2);
+    /// ```
+    #[must_use]
+    pub fn merged_with(mut self, other: Self) -> Self {
+        self.merge(other);
+        self
+    }
+}
@@ -0,0 +1,64 @@
+use http::{header, HeaderValue, Method, StatusCode};
+
+use crate::{MergeExtensions, ProblemDetails};
+
+/// Extension member listing the methods allowed for a resource as `allowed_methods`.
+///
+/// This is added by [`ProblemDetails::method_not_allowed`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AllowedMethodsExtension {
+    /// The methods allowed for the requested resource.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde::methods"))]
+    pub allowed_methods: Vec<Method>,
+}
+
+impl MergeExtensions for AllowedMethodsExtension {
+    fn merge_extensions(&mut self, other: Self) {
+        *self = other;
+    }
+}
+
+impl ProblemDetails<()> {
+    /// Creates a new `405 Method Not Allowed` problem for a resource supporting the given methods.
+    ///
+    /// The allowed methods are listed in the `detail`, added as `allowed_methods`
+    /// extension member and as `Allow` response header, as required by
+    /// [RFC 9110, Section 15.5.6](https://www.rfc-editor.org/rfc/rfc9110.html#section-15.5.6).
+    ///
+    /// ```rust
+    /// use http::{header, Method, StatusCode};
+    /// use problem_details::ProblemDetails;
+    ///
+    /// let details = ProblemDetails::method_not_allowed(&[Method::GET, Method::HEAD]);
+    ///
+    /// assert_eq!(details.status, Some(StatusCode::METHOD_NOT_ALLOWED));
+    /// assert_eq!(details.headers()[header::ALLOW], "GET, HEAD");
+    /// assert_eq!(
+    ///     details.detail.as_deref(),
+    ///     Some("The method is not allowed for this resource. Allowed methods: GET, HEAD.")
+    /// );
+    /// ```
+    #[must_use]
+    pub fn method_not_allowed(allowed: &[Method]) -> ProblemDetails<AllowedMethodsExtension> {
+        let allowed_methods = allowed.to_vec();
+        let allow = allowed_methods
+            .iter()
+            .map(Method::as_str)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let detail = if allowed_methods.is_empty() {
+            "The method is not allowed for this resource.".to_string()
+        } else {
+            format!("The method is not allowed for this resource. Allowed methods: {allow}.")
+        };
+
+        let allow = HeaderValue::try_from(allow).expect("method names are valid header values");
+
+        ProblemDetails::from_status_code(StatusCode::METHOD_NOT_ALLOWED)
+            .with_detail(detail)
+            .with_header(header::ALLOW, allow)
+            .with_extensions(AllowedMethodsExtension { allowed_methods })
+    }
+}
@@ -0,0 +1,82 @@
+use http::StatusCode;
+
+use crate::{MergeExtensions, ProblemDetails};
+
+/// Extension member listing multiple problems as nested problem details in `errors`.
+///
+/// RFC 9457 recommends reporting multiple problems of different types using
+/// the most relevant one or a generic problem that embeds the others. This
+/// models the latter, e.g. for batch endpoints where each item may fail on its own.
+///
+/// ```rust
+/// use http::StatusCode;
+/// use problem_details::ProblemDetails;
+///
+/// let details = ProblemDetails::multiple([
+///     ProblemDetails::from_status_code(StatusCode::NOT_FOUND).with_detail("Item 1 does not exist."),
+///     ProblemDetails::from_status_code(StatusCode::CONFLICT).with_detail("Item 2 was modified."),
+/// ]);
+///
+/// let json = serde_json::to_value(&details).unwrap();
+/// assert_eq!(json["status"], 422);
+/// assert_eq!(json["errors"][0]["status"], 404);
+/// assert_eq!(json["errors"][1]["detail"], "Item 2 was modified.");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultiProblem<Ext = ()> {
+    /// The nested problems.
+    pub errors: Vec<ProblemDetails<Ext>>,
+}
+
+impl<Ext> MultiProblem<Ext> {
+    /// Creates a new empty list of problems.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { errors: Vec::new() }
+    }
+}
+
+impl<Ext> Default for MultiProblem<Ext> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Ext> FromIterator<ProblemDetails<Ext>> for MultiProblem<Ext> {
+    fn from_iter<I: IntoIterator<Item = ProblemDetails<Ext>>>(iter: I) -> Self {
+        Self {
+            errors: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<Ext> MergeExtensions for MultiProblem<Ext> {
+    fn merge_extensions(&mut self, other: Self) {
+        self.errors.extend(other.errors);
+    }
+}
+
+impl ProblemDetails<()> {
+    /// Creates a `422 Unprocessable Entity` problem aggregating the given problems.
+    ///
+    /// The problems are listed in an `errors` extension member, see [`MultiProblem`].
+    /// Status, title and detail can be adjusted using the builder methods.
+    #[must_use]
+    pub fn multiple<Ext>(
+        problems: impl IntoIterator<Item = ProblemDetails<Ext>>,
+    ) -> ProblemDetails<MultiProblem<Ext>> {
+        ProblemDetails::from_status_code(StatusCode::UNPROCESSABLE_ENTITY)
+            .with_detail("Multiple problems occurred.")
+            .with_extensions(problems.into_iter().collect())
+    }
+}
+
+impl<Ext> ProblemDetails<MultiProblem<Ext>> {
+    /// Builder-style method that adds a nested problem.
+    #[must_use]
+    pub fn with_problem(mut self, problem: ProblemDetails<Ext>) -> Self {
+        self.extensions.errors.push(problem);
+        self
+    }
+}
@@ -0,0 +1,188 @@
+use http::StatusCode;
+use serde::{ser::SerializeMap, Serialize, Serializer};
+
+use crate::ProblemDetails;
+
+/// A `207 Multi-Status` response reporting the outcome of every item of a batch request.
+///
+/// Batch endpoints may succeed for some items and fail for others. Instead of failing
+/// the whole request, every item gets its own sub-response: successful items contain
+/// their status and `body`, failed items their status and `problem`.
+///
+/// ```rust
+/// use http::StatusCode;
+/// use problem_details::{MultiStatusProblems, ProblemDetails};
+///
+/// let results = vec![
+///     Ok(serde_json::json!({ "id": 1 })),
+///     Err(ProblemDetails::from_status_code(StatusCode::CONFLICT).with_detail("Item 2 exists.")),
+/// ];
+/// let response: MultiStatusProblems<_> = results.into_iter().collect();
+///
+/// assert_eq!(
+///     serde_json::to_value(&response).unwrap(),
+///     serde_json::json!({
+///         "responses": [
+///             { "status": 200, "body": { "id": 1 } },
+///             { "status": 409, "problem": { "status": 409, "title": "Conflict", "detail": "Item 2 exists." } },
+///         ]
+///     })
+/// );
+/// ```
+///
+/// Problems are serialized like [`JsonProblemDetails`](crate::JsonProblemDetails), i.e. extension
+/// members are filtered according to the configured [`extension_filter`](crate::extension_filter).
+///
+/// Requires feature `json`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultiStatusProblems<T, Ext = ()> {
+    /// The sub-responses, in the order of the items of the request.
+    pub responses: Vec<SubResponse<T, Ext>>,
+}
+
+/// The outcome of a single item in a [`MultiStatusProblems`] response.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubResponse<T, Ext = ()> {
+    /// The item was processed successfully.
+    Success {
+        /// The status of the item.
+        status: StatusCode,
+        /// The body describing the result.
+        body: T,
+    },
+    /// Processing the item failed. The status of the problem is used as status of the item.
+    Problem(Box<ProblemDetails<Ext>>),
+}
+
+impl<T, Ext> SubResponse<T, Ext> {
+    /// Returns the status of this sub-response.
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::Success { status, .. } => *status,
+            Self::Problem(problem) => problem.status_or_default(),
+        }
+    }
+}
+
+impl<T, Ext> MultiStatusProblems<T, Ext> {
+    /// The HTTP status of a multi status response.
+    pub const STATUS: StatusCode = StatusCode::MULTI_STATUS;
+
+    /// The HTTP content type of a multi status response.
+    pub const CONTENT_TYPE: &'static str = "application/json";
+
+    /// Creates a new response without sub-responses.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            responses: Vec::new(),
+        }
+    }
+
+    /// Builder-style method that adds a successful sub-response.
+    #[must_use]
+    pub fn with_success(mut self, status: impl Into<StatusCode>, body: T) -> Self {
+        self.responses.push(SubResponse::Success {
+            status: status.into(),
+            body,
+        });
+        self
+    }
+
+    /// Builder-style method that adds a failed sub-response.
+    #[must_use]
+    pub fn with_problem(mut self, problem: ProblemDetails<Ext>) -> Self {
+        self.responses.push(SubResponse::Problem(Box::new(problem)));
+        self
+    }
+
+    /// Applies the configured default type to all problems.
+    #[cfg_attr(not(any(feature = "axum", feature = "poem")), allow(dead_code))]
+    pub(crate) fn with_configured_default_type(self) -> Self {
+        Self {
+            responses: self
+                .responses
+                .into_iter()
+                .map(|response| match response {
+                    SubResponse::Problem(problem) => {
+                        SubResponse::Problem(Box::new(problem.with_configured_default_type()))
+                    }
+                    success => success,
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns true if any of the sub-responses is a problem.
+    pub fn has_problems(&self) -> bool {
+        self.responses
+            .iter()
+            .any(|response| matches!(response, SubResponse::Problem(_)))
+    }
+}
+
+impl<T, Ext> MultiStatusProblems<T, Ext>
+where
+    T: Serialize,
+    Ext: Serialize,
+{
+    /// Write this response to a JSON string suitable for a response body.
+    pub fn to_body_string(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+}
+
+impl<T, Ext> Default for MultiStatusProblems<T, Ext> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Successful items are reported as `200 OK`.
+impl<T, Ext> FromIterator<Result<T, ProblemDetails<Ext>>> for MultiStatusProblems<T, Ext> {
+    fn from_iter<I: IntoIterator<Item = Result<T, ProblemDetails<Ext>>>>(iter: I) -> Self {
+        Self {
+            responses: iter
+                .into_iter()
+                .map(|result| match result {
+                    Ok(body) => SubResponse::Success {
+                        status: StatusCode::OK,
+                        body,
+                    },
+                    Err(problem) => SubResponse::Problem(Box::new(problem)),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<T, Ext> Serialize for MultiStatusProblems<T, Ext>
+where
+    T: Serialize,
+    Ext: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry("responses", &self.responses)?;
+        map.end()
+    }
+}
+
+impl<T, Ext> Serialize for SubResponse<T, Ext>
+where
+    T: Serialize,
+    Ext: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("status", &self.status().as_u16())?;
+        match self {
+            Self::Success { body, .. } => map.serialize_entry("body", body)?,
+            Self::Problem(problem) => match crate::extension_filter() {
+                Some(filter) => map.serialize_entry("problem", &problem.redacted(filter))?,
+                None => map.serialize_entry("problem", problem)?,
+            },
+        }
+        map.end()
+    }
+}
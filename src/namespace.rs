@@ -0,0 +1,204 @@
+use std::marker::PhantomData;
+
+use serde_json::Value;
+
+use crate::{DynamicExtensions, MergeExtensions, ProblemDetails};
+
+/// A namespace for extension members, identified by a key prefix.
+///
+/// See [`Namespaced`] for details.
+pub trait Namespace {
+    /// The prefix applied to the keys of all extension members, e.g. `acme_`.
+    const PREFIX: &'static str;
+}
+
+/// Dynamic extension members within a [`Namespace`].
+///
+/// The members are accessed using plain keys, while the namespace prefix is
+/// applied on serialization and stripped on deserialization. This avoids
+/// collisions between extension members of different vendors or teams.
+///
+/// When deserializing, members outside of the namespace are ignored.
+///
+/// ```rust
+/// use problem_details::{Namespace, Namespaced, ProblemDetails};
+///
+/// struct Acme;
+///
+/// impl Namespace for Acme {
+///     const PREFIX: &'static str = "acme_";
+/// }
+///
+/// let details = ProblemDetails::new()
+///     .with_extension("trace_id", "abc123")
+///     .with_namespace::<Acme>();
+///
+/// let json = serde_json::to_value(&details).unwrap();
+/// assert_eq!(json, serde_json::json!({ "acme_trace_id": "abc123" }));
+///
+/// let parsed: ProblemDetails<Namespaced<Acme>> = serde_json::from_value(json).unwrap();
+/// assert_eq!(parsed.extensions["trace_id"], "abc123");
+/// ```
+///
+/// Requires feature `json`.
+pub struct Namespaced<N> {
+    extensions: DynamicExtensions,
+    namespace: PhantomData<fn() -> N>,
+}
+
+impl<N> Namespaced<N> {
+    /// Creates a new empty set of namespaced extension members.
+    #[must_use]
+    pub fn new() -> Self {
+        DynamicExtensions::new().into()
+    }
+
+    /// Returns the extension members without namespace.
+    pub fn into_inner(self) -> DynamicExtensions {
+        self.extensions
+    }
+}
+
+impl<N> From<DynamicExtensions> for Namespaced<N> {
+    fn from(extensions: DynamicExtensions) -> Self {
+        Self {
+            extensions,
+            namespace: PhantomData,
+        }
+    }
+}
+
+impl<N> std::ops::Deref for Namespaced<N> {
+    type Target = DynamicExtensions;
+
+    fn deref(&self) -> &DynamicExtensions {
+        &self.extensions
+    }
+}
+
+impl<N> std::ops::DerefMut for Namespaced<N> {
+    fn deref_mut(&mut self) -> &mut DynamicExtensions {
+        &mut self.extensions
+    }
+}
+
+impl<N> Clone for Namespaced<N> {
+    fn clone(&self) -> Self {
+        self.extensions.clone().into()
+    }
+}
+
+impl<N> Default for Namespaced<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N> std::fmt::Debug for Namespaced<N>
+where
+    N: Namespace,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Namespaced")
+            .field("prefix", &N::PREFIX)
+            .field("extensions", &self.extensions)
+            .finish()
+    }
+}
+
+impl<N> PartialEq for Namespaced<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.extensions == other.extensions
+    }
+}
+
+impl<N> Eq for Namespaced<N> {}
+
+impl<N> MergeExtensions for Namespaced<N> {
+    fn merge_extensions(&mut self, other: Self) {
+        self.extensions.merge_extensions(other.extensions);
+    }
+}
+
+impl<N> serde::Serialize for Namespaced<N>
+where
+    N: Namespace,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_map(
+            self.extensions
+                .iter()
+                .map(|(key, value)| (format!("{}{key}", N::PREFIX), value)),
+        )
+    }
+}
+
+impl<'de, N> serde::Deserialize<'de> for Namespaced<N>
+where
+    N: Namespace,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct NamespacedVisitor<N>(PhantomData<fn() -> N>);
+
+        impl<'de, N> serde::de::Visitor<'de> for NamespacedVisitor<N>
+        where
+            N: Namespace,
+        {
+            type Value = Namespaced<N>;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a map of extension members")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                let mut extensions = DynamicExtensions::new();
+                while let Some((key, value)) = map.next_entry::<String, Value>()? {
+                    if let Some(key) = key.strip_prefix(N::PREFIX) {
+                        extensions
+                            .insert(key, value)
+                            .map_err(serde::de::Error::custom)?;
+                    }
+                }
+                Ok(extensions.into())
+            }
+        }
+
+        deserializer.deserialize_map(NamespacedVisitor(PhantomData))
+    }
+}
+
+impl ProblemDetails<DynamicExtensions> {
+    /// Builder-style method that moves the dynamic extension members into the given namespace.
+    ///
+    /// See [`Namespaced`] for details.
+    #[must_use]
+    pub fn with_namespace<N>(self) -> ProblemDetails<Namespaced<N>> {
+        self.map_extensions(Namespaced::from)
+    }
+}
+
+impl<N> ProblemDetails<Namespaced<N>> {
+    /// Builder-style method that adds a single extension field within the namespace.
+    ///
+    /// If an extension with the same key already exists, it is replaced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is reserved by RFC 9457, see [`DynamicExtensions::insert`].
+    #[must_use]
+    pub fn with_extension(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        if let Err(err) = self.extensions.insert(key, value) {
+            panic!("{err}");
+        }
+        self
+    }
+}
@@ -0,0 +1,355 @@
+use std::sync::OnceLock;
+
+use http::{header, HeaderMap};
+
+use crate::ProblemDetails;
+
+static CONTENT_NEGOTIATION: OnceLock<ContentNegotiation> = OnceLock::new();
+
+/// A representation of problem details which can be selected by content negotiation.
+///
+/// Requires feature `json`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ProblemFormat {
+    /// `application/problem+json`, see [`JsonProblemDetails`](crate::JsonProblemDetails).
+    Json,
+    /// `application/problem+xml`, see `XmlProblemDetails` (requires feature `xml`).
+    #[cfg(feature = "xml")]
+    Xml,
+    /// `text/html`, see `HtmlProblemDetails` (requires feature `html`).
+    #[cfg(feature = "html")]
+    Html,
+    /// `application/concise-problem-details+cbor`, see `CborProblemDetails` (requires feature `cbor`).
+    #[cfg(feature = "cbor")]
+    Cbor,
+    /// `application/yaml`, see `YamlProblemDetails` (requires feature `yaml`).
+    #[cfg(feature = "yaml")]
+    Yaml,
+    /// `text/plain`, see [`TextProblemDetails`](crate::TextProblemDetails).
+    Text,
+}
+
+impl ProblemFormat {
+    /// Returns the content type of responses in this format.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Json => crate::JsonProblemDetails::<()>::CONTENT_TYPE,
+            #[cfg(feature = "xml")]
+            Self::Xml => crate::XmlProblemDetails::<()>::CONTENT_TYPE,
+            #[cfg(feature = "html")]
+            Self::Html => crate::HtmlProblemDetails::<()>::CONTENT_TYPE,
+            #[cfg(feature = "cbor")]
+            Self::Cbor => crate::CborProblemDetails::<()>::CONTENT_TYPE,
+            #[cfg(feature = "yaml")]
+            Self::Yaml => crate::YamlProblemDetails::<()>::CONTENT_TYPE,
+            Self::Text => crate::TextProblemDetails::<()>::CONTENT_TYPE,
+        }
+    }
+
+    /// Returns the media types in `Accept` headers which select this format.
+    pub fn media_types(&self) -> &'static [&'static str] {
+        match self {
+            Self::Json => &["application/problem+json", "application/json"],
+            #[cfg(feature = "xml")]
+            Self::Xml => &["application/problem+xml", "application/xml", "text/xml"],
+            #[cfg(feature = "html")]
+            Self::Html => &["text/html", "application/xhtml+xml"],
+            #[cfg(feature = "cbor")]
+            Self::Cbor => &[
+                "application/concise-problem-details+cbor",
+                "application/cbor",
+            ],
+            #[cfg(feature = "yaml")]
+            Self::Yaml => &["application/yaml", "application/x-yaml", "text/yaml"],
+            Self::Text => &["text/plain"],
+        }
+    }
+
+    /// Returns all formats enabled by crate features.
+    fn all() -> Vec<Self> {
+        vec![
+            Self::Json,
+            #[cfg(feature = "xml")]
+            Self::Xml,
+            #[cfg(feature = "html")]
+            Self::Html,
+            #[cfg(feature = "cbor")]
+            Self::Cbor,
+            #[cfg(feature = "yaml")]
+            Self::Yaml,
+            Self::Text,
+        ]
+    }
+}
+
+/// Selects the [`ProblemFormat`] of a response based on the `Accept` request header.
+///
+/// Media ranges are weighted by their quality value (`q`), and formats with a
+/// quality of `0` are never selected. More specific media ranges take precedence,
+/// e.g. `text/html;q=0.5` over `text/*`. If several formats are equally acceptable,
+/// the first of the offered formats is selected.
+///
+/// The default format is used if the request has no `Accept` header or none of
+/// the offered formats is acceptable.
+///
+/// ```rust
+/// use problem_details::{ContentNegotiation, ProblemFormat};
+///
+/// let negotiation = ContentNegotiation::new();
+///
+/// assert_eq!(negotiation.negotiate(None), ProblemFormat::Json);
+/// assert_eq!(negotiation.negotiate(Some("text/plain, */*;q=0.1")), ProblemFormat::Text);
+/// assert_eq!(negotiation.negotiate(Some("image/png")), ProblemFormat::Json);
+///
+/// let negotiation = negotiation.with_default(ProblemFormat::Text);
+/// assert_eq!(negotiation.negotiate(Some("image/png")), ProblemFormat::Text);
+/// ```
+///
+/// Requires feature `json`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContentNegotiation {
+    formats: Vec<ProblemFormat>,
+    default: ProblemFormat,
+}
+
+impl ContentNegotiation {
+    /// Creates a negotiation offering all formats enabled by crate features, defaulting to JSON.
+    pub fn new() -> Self {
+        Self {
+            formats: ProblemFormat::all(),
+            default: ProblemFormat::Json,
+        }
+    }
+
+    /// Builder-style method that sets the offered formats in order of preference.
+    #[must_use]
+    pub fn with_formats(mut self, formats: impl IntoIterator<Item = ProblemFormat>) -> Self {
+        self.formats = formats.into_iter().collect();
+        self
+    }
+
+    /// Builder-style method that sets the format used if no offered format is acceptable.
+    #[must_use]
+    pub fn with_default(mut self, default: ProblemFormat) -> Self {
+        self.default = default;
+        self
+    }
+
+    /// Returns the offered formats in order of preference.
+    pub fn formats(&self) -> &[ProblemFormat] {
+        &self.formats
+    }
+
+    /// Returns the format used if no offered format is acceptable.
+    pub fn default_format(&self) -> ProblemFormat {
+        self.default
+    }
+
+    /// Selects the format for the given `Accept` header value.
+    pub fn negotiate(&self, accept: Option<&str>) -> ProblemFormat {
+        let Some(accept) = accept.filter(|accept| !accept.trim().is_empty()) else {
+            return self.default;
+        };
+
+        let ranges = accept
+            .split(',')
+            .filter_map(MediaRange::parse)
+            .collect::<Vec<_>>();
+
+        let mut selected = None;
+        for format in &self.formats {
+            let quality = quality(*format, &ranges);
+            if quality > selected.map_or(0, |(_, best)| best) {
+                selected = Some((*format, quality));
+            }
+        }
+
+        selected.map_or(self.default, |(format, _)| format)
+    }
+
+    /// Selects the format for the `Accept` header in the given request headers.
+    pub fn negotiate_headers(&self, headers: &HeaderMap) -> ProblemFormat {
+        let accept = headers
+            .get_all(header::ACCEPT)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        self.negotiate(Some(&accept))
+    }
+}
+
+impl Default for ContentNegotiation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Configures the process-wide content negotiation used by [`NegotiatedProblemDetails`].
+///
+/// The negotiation can only be set once. If it was already set, the given
+/// negotiation is returned as error.
+///
+/// Requires feature `json`.
+pub fn set_content_negotiation(negotiation: ContentNegotiation) -> Result<(), ContentNegotiation> {
+    CONTENT_NEGOTIATION.set(negotiation)
+}
+
+/// Returns the process-wide content negotiation configured using [`set_content_negotiation`].
+///
+/// Requires feature `json`.
+pub fn content_negotiation() -> &'static ContentNegotiation {
+    CONTENT_NEGOTIATION.get_or_init(ContentNegotiation::new)
+}
+
+/// ProblemDetails that is encoded in the format selected by content negotiation
+/// when used with web framework integrations.
+///
+/// The format is usually selected by extracting an [`AcceptedFormat`] in the handler,
+/// which uses the configured [`content_negotiation`]. Responses include a
+/// `Vary: Accept` header.
+///
+/// # Example
+///
+/// ```rust
+/// use http::{header, HeaderMap, HeaderValue, StatusCode};
+/// use problem_details::{NegotiatedProblemDetails, ProblemDetails, ProblemFormat};
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert(header::ACCEPT, HeaderValue::from_static("text/plain"));
+///
+/// let details = NegotiatedProblemDetails::from_headers(
+///     ProblemDetails::from_status_code(StatusCode::NOT_FOUND),
+///     &headers,
+/// );
+///
+/// assert_eq!(details.format(), ProblemFormat::Text);
+/// ```
+///
+/// Requires feature `json`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NegotiatedProblemDetails<Ext = ()> {
+    pub(crate) details: ProblemDetails<Ext>,
+    pub(crate) format: ProblemFormat,
+}
+
+impl<Ext> NegotiatedProblemDetails<Ext> {
+    /// Creates a problem details response in the given format.
+    pub fn new(details: ProblemDetails<Ext>, format: ProblemFormat) -> Self {
+        Self { details, format }
+    }
+
+    /// Creates a problem details response in the format negotiated for the given request headers.
+    pub fn from_headers(details: ProblemDetails<Ext>, headers: &HeaderMap) -> Self {
+        Self::new(details, content_negotiation().negotiate_headers(headers))
+    }
+
+    /// Returns the selected format.
+    pub fn format(&self) -> ProblemFormat {
+        self.format
+    }
+
+    /// Returns the problem details.
+    pub fn details(&self) -> &ProblemDetails<Ext> {
+        &self.details
+    }
+}
+
+impl<Ext> From<NegotiatedProblemDetails<Ext>> for ProblemDetails<Ext> {
+    fn from(value: NegotiatedProblemDetails<Ext>) -> Self {
+        value.details
+    }
+}
+
+impl<Ext> std::fmt::Display for NegotiatedProblemDetails<Ext> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.details.fmt(f)
+    }
+}
+
+impl<Ext> std::error::Error for NegotiatedProblemDetails<Ext> where Ext: std::fmt::Debug {}
+
+/// The [`ProblemFormat`] accepted by a request, selected using the configured [`content_negotiation`].
+///
+/// With the `axum` or `poem` feature, this can be extracted in handlers.
+///
+/// Requires feature `json`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AcceptedFormat(pub ProblemFormat);
+
+impl AcceptedFormat {
+    /// Selects the accepted format for the given request headers.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        Self(content_negotiation().negotiate_headers(headers))
+    }
+
+    /// Creates a problem details response in the accepted format.
+    pub fn respond<Ext>(
+        self,
+        details: impl Into<ProblemDetails<Ext>>,
+    ) -> NegotiatedProblemDetails<Ext> {
+        NegotiatedProblemDetails::new(details.into(), self.0)
+    }
+}
+
+struct MediaRange<'a> {
+    r#type: &'a str,
+    subtype: &'a str,
+    quality: u16,
+}
+
+impl<'a> MediaRange<'a> {
+    fn parse(range: &'a str) -> Option<Self> {
+        let mut params = range.split(';');
+        let (r#type, subtype) = params.next()?.trim().split_once('/')?;
+
+        let quality = params
+            .filter_map(|param| param.split_once('='))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("q"))
+            .map_or(Some(1000), |(_, value)| parse_quality(value.trim()))?;
+
+        Some(Self {
+            r#type: r#type.trim(),
+            subtype: subtype.trim(),
+            quality,
+        })
+    }
+
+    /// Returns how specific this range matches the media type, or `None` if it does not match.
+    fn specificity(&self, media_type: &str) -> Option<u8> {
+        let (r#type, subtype) = media_type.split_once('/')?;
+
+        match (self.r#type, self.subtype) {
+            ("*", "*") => Some(0),
+            (t, "*") if t.eq_ignore_ascii_case(r#type) => Some(1),
+            (t, s) if t.eq_ignore_ascii_case(r#type) && s.eq_ignore_ascii_case(subtype) => Some(2),
+            _ => None,
+        }
+    }
+}
+
+/// Parses a quality value into thousandths, e.g. `0.5` into `500`.
+fn parse_quality(value: &str) -> Option<u16> {
+    let value: f32 = value.parse().ok()?;
+    (0.0..=1.0)
+        .contains(&value)
+        .then(|| (value * 1000.0).round() as u16)
+}
+
+/// Returns the quality of the most specific range matching the format.
+fn quality(format: ProblemFormat, ranges: &[MediaRange<'_>]) -> u16 {
+    format
+        .media_types()
+        .iter()
+        .flat_map(|media_type| {
+            ranges.iter().filter_map(move |range| {
+                range
+                    .specificity(media_type)
+                    .map(|specificity| (specificity, range.quality))
+            })
+        })
+        .max()
+        .map_or(0, |(_, quality)| quality)
+}
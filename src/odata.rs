@@ -0,0 +1,186 @@
+//! Conversion between [`ProblemDetails`] and the
+//! [OData v4 error response](https://docs.oasis-open.org/odata/odata-json-format/v4.01/odata-json-format-v4.01.html#sec_ErrorResponse).
+//! Requires feature `odata`.
+//!
+//! The members of both formats are mapped as follows:
+//!
+//! | Problem details                 | OData error           |
+//! |---------------------------------|-----------------------|
+//! | extension `code`, or `status`   | `code`                |
+//! | `detail`, or `title`            | `message`             |
+//! | extension `target`              | `target`              |
+//! | extension `details`             | `details`             |
+//! | `type`, `instance`, other extensions | `innererror`     |
+//!
+//! OData errors carry no status code, it is part of the HTTP response. Set it on the
+//! converted problem using [`with_status`](ProblemDetails::with_status).
+//!
+//! # Example
+//!
+//! ```rust
+//! use http::StatusCode;
+//! use problem_details::{odata, JsonExtensions, ProblemDetails};
+//!
+//! let details = ProblemDetails::from_status_code(StatusCode::BAD_REQUEST)
+//!     .with_detail("The price must be positive.")
+//!     .with_extension("code", "InvalidPrice")
+//!     .with_extension("target", "Price");
+//!
+//! let response = odata::ErrorResponse::from(&details);
+//! assert_eq!(response.error.code, "InvalidPrice");
+//! assert_eq!(response.error.message, "The price must be positive.");
+//! assert_eq!(response.error.target.as_deref(), Some("Price"));
+//!
+//! let restored = ProblemDetails::<JsonExtensions>::from(response)
+//!     .with_status(StatusCode::BAD_REQUEST);
+//! assert_eq!(restored.detail.as_deref(), Some("The price must be positive."));
+//! assert_eq!(restored.extensions["code"], "InvalidPrice");
+//! ```
+use http::Uri;
+use serde_json::{Map, Value};
+
+use crate::dynamic_extensions::RESERVED_MEMBERS;
+use crate::{JsonExtensions, ProblemDetails, ProblemType};
+
+/// An OData error response body.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ErrorResponse {
+    /// The error.
+    pub error: MainError,
+}
+
+/// The error of an OData error response.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MainError {
+    /// A service-defined error code.
+    pub code: String,
+
+    /// A human-readable, language-dependent representation of the error.
+    pub message: String,
+
+    /// The target of the error, e.g. the name of a property in error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+
+    /// Details about specific errors that led to this error.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<ErrorDetail>,
+
+    /// Service-defined debugging information.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub innererror: Option<Map<String, Value>>,
+}
+
+/// A specific error in the `details` of an OData error.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ErrorDetail {
+    /// A service-defined error code.
+    pub code: String,
+
+    /// A human-readable, language-dependent representation of the error.
+    pub message: String,
+
+    /// The target of the error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+}
+
+/// A `details` extension which cannot be read as OData error details is kept in `innererror`.
+impl<Ext> From<&ProblemDetails<Ext>> for ErrorResponse
+where
+    Ext: serde::Serialize,
+{
+    fn from(details: &ProblemDetails<Ext>) -> Self {
+        let message = details
+            .detail
+            .as_deref()
+            .or(details.effective_title())
+            .unwrap_or_default()
+            .to_string();
+
+        let details = details.to_json_extensions();
+        let mut extensions = details.extensions;
+
+        let code = take::<String>(&mut extensions, "code")
+            .or_else(|| details.status.map(|status| status.as_u16().to_string()))
+            .unwrap_or_default();
+        let target = take(&mut extensions, "target");
+        let error_details = take(&mut extensions, "details").unwrap_or_default();
+
+        let mut innererror = Map::new();
+        if let Some(r#type) = details.r#type {
+            innererror.insert("type".to_string(), r#type.to_string().into());
+        }
+        if let Some(instance) = details.instance {
+            innererror.insert("instance".to_string(), instance.to_string().into());
+        }
+        innererror.extend(extensions);
+
+        Self {
+            error: MainError {
+                code,
+                message,
+                target,
+                details: error_details,
+                innererror: (!innererror.is_empty()).then_some(innererror),
+            },
+        }
+    }
+}
+
+/// A `details` extension which cannot be read as OData error details is kept in `innererror`.
+impl<Ext> From<ProblemDetails<Ext>> for ErrorResponse
+where
+    Ext: serde::Serialize,
+{
+    fn from(details: ProblemDetails<Ext>) -> Self {
+        Self::from(&details)
+    }
+}
+
+/// The `type` and `instance` are restored from `innererror`, whose other members
+/// become extensions. Members with invalid values are ignored.
+impl From<ErrorResponse> for ProblemDetails<JsonExtensions> {
+    fn from(response: ErrorResponse) -> Self {
+        let error = response.error;
+        let mut innererror = error.innererror.unwrap_or_default();
+
+        let mut extensions = JsonExtensions::new();
+        if !error.code.is_empty() {
+            extensions.insert("code".to_string(), error.code.into());
+        }
+        if let Some(target) = error.target {
+            extensions.insert("target".to_string(), target.into());
+        }
+        if !error.details.is_empty() {
+            let details = serde_json::to_value(error.details).unwrap_or_default();
+            extensions.insert("details".to_string(), details);
+        }
+
+        let r#type = take::<String>(&mut innererror, "type")
+            .and_then(|r#type| ProblemType::try_from(r#type.as_str()).ok());
+        let instance = take::<String>(&mut innererror, "instance")
+            .and_then(|instance| Uri::try_from(instance).ok());
+        for (key, value) in innererror {
+            if !RESERVED_MEMBERS.contains(&key.as_str()) {
+                extensions.entry(key).or_insert(value);
+            }
+        }
+
+        let mut details = ProblemDetails::new().with_extensions(extensions);
+        details.r#type = r#type;
+        details.detail = (!error.message.is_empty()).then(|| error.message.into());
+        details.instance = instance;
+        details
+    }
+}
+
+/// Removes a member from the extensions if it can be read as `T`, leaving other values in place.
+fn take<T>(extensions: &mut JsonExtensions, key: &str) -> Option<T>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let value = T::deserialize(extensions.get(key)?).ok()?;
+    extensions.retain(|existing, _| existing != key);
+    Some(value)
+}
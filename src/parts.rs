@@ -0,0 +1,121 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use http::{HeaderMap, StatusCode, Uri};
+
+use crate::{ProblemDetails, ProblemType};
+
+/// The decomposed parts of a [`ProblemDetails`] object.
+///
+/// Use [`ProblemDetails::into_parts`] and [`ProblemDetails::from_parts`] to take a
+/// problem apart and reassemble it. This struct is `#[non_exhaustive]`, so code
+/// using it keeps compiling when new fields are added.
+///
+/// ```rust
+/// use http::StatusCode;
+/// use problem_details::{ProblemDetails, ProblemDetailsParts};
+///
+/// let details = ProblemDetails::from_status_code(StatusCode::NOT_FOUND)
+///     .with_detail("No such user");
+///
+/// let mut parts = details.into_parts();
+/// parts.detail = None;
+///
+/// let details = ProblemDetails::from_parts(parts);
+/// assert_eq!(details, ProblemDetails::from_status_code(StatusCode::NOT_FOUND));
+/// ```
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct ProblemDetailsParts<Ext = ()> {
+    /// See [`ProblemDetails::type`].
+    pub r#type: Option<ProblemType>,
+
+    /// See [`ProblemDetails::status`].
+    pub status: Option<StatusCode>,
+
+    /// See [`ProblemDetails::title`].
+    pub title: Option<Cow<'static, str>>,
+
+    /// See [`ProblemDetails::detail`].
+    pub detail: Option<Cow<'static, str>>,
+
+    /// See [`ProblemDetails::instance`].
+    pub instance: Option<Uri>,
+
+    /// See [`ProblemDetails::extensions`].
+    pub extensions: Ext,
+
+    /// The underlying cause of the problem, see [`ProblemDetails::with_source`].
+    pub source: Option<Arc<dyn std::error::Error + Send + Sync + 'static>>,
+
+    /// The captured backtrace of the problem, see [`ProblemDetails::backtrace`].
+    #[cfg(feature = "backtrace")]
+    pub backtrace: Option<Arc<std::backtrace::Backtrace>>,
+
+    /// The response headers of the problem, see [`ProblemDetails::headers`].
+    pub headers: HeaderMap,
+}
+
+impl<Ext> ProblemDetailsParts<Ext> {
+    /// Creates new parts with the given extensions and all other fields empty.
+    pub fn new(extensions: Ext) -> Self {
+        Self {
+            r#type: None,
+            status: None,
+            title: None,
+            detail: None,
+            instance: None,
+            extensions,
+            source: None,
+            #[cfg(feature = "backtrace")]
+            backtrace: None,
+            headers: HeaderMap::new(),
+        }
+    }
+}
+
+impl<Ext> ProblemDetails<Ext> {
+    /// Decomposes this problem details object into its parts.
+    pub fn into_parts(self) -> ProblemDetailsParts<Ext> {
+        ProblemDetailsParts {
+            r#type: self.r#type,
+            status: self.status,
+            title: self.title,
+            detail: self.detail,
+            instance: self.instance,
+            extensions: self.extensions,
+            source: self.source,
+            #[cfg(feature = "backtrace")]
+            backtrace: self.backtrace,
+            headers: self.headers.unwrap_or_default(),
+        }
+    }
+
+    /// Reassembles a problem details object from its parts.
+    pub fn from_parts(parts: ProblemDetailsParts<Ext>) -> Self {
+        Self {
+            r#type: parts.r#type,
+            status: parts.status,
+            title: parts.title,
+            detail: parts.detail,
+            instance: parts.instance,
+            extensions: parts.extensions,
+            source: parts.source,
+            #[cfg(feature = "backtrace")]
+            backtrace: parts.backtrace,
+            headers: (!parts.headers.is_empty()).then_some(parts.headers),
+        }
+    }
+}
+
+impl<Ext> From<ProblemDetails<Ext>> for ProblemDetailsParts<Ext> {
+    fn from(details: ProblemDetails<Ext>) -> Self {
+        details.into_parts()
+    }
+}
+
+impl<Ext> From<ProblemDetailsParts<Ext>> for ProblemDetails<Ext> {
+    fn from(parts: ProblemDetailsParts<Ext>) -> Self {
+        Self::from_parts(parts)
+    }
+}
@@ -3,6 +3,29 @@
 //! With the `poem` feature enabled, [`ProblemDetails`] implements [`IntoResponse`] using
 //! [`JsonProblemDetails`]. You can also return [`JsonProblemDetails`] to be specific.
 //! If you want to return XML, you can use [`XmlProblemDetails`] (requires feature `xml`).
+//! Concise problem details in CBOR are returned using `CborProblemDetails` (requires feature `cbor`).
+//! YAML is returned using `YamlProblemDetails` (requires feature `yaml`).
+//! Browsers can be served an HTML page using `HtmlProblemDetails` (requires feature `html`).
+//! As last resort, [`TextProblemDetails`] returns a plain text body.
+//! Partial failures of batch requests are returned as `207 Multi-Status` using [`MultiStatusProblems`].
+//!
+//! To select the format by the `Accept` request header, extract an [`AcceptedFormat`]
+//! and return a [`NegotiatedProblemDetails`]:
+//!
+//! ```rust
+//! use http::StatusCode;
+//! use problem_details::{AcceptedFormat, NegotiatedProblemDetails, ProblemDetails};
+//!
+//! #[poem::handler]
+//! async fn handler(format: AcceptedFormat) -> Result<&'static str, NegotiatedProblemDetails> {
+//!     Err(format.respond(ProblemDetails::from_status_code(StatusCode::NOT_FOUND)))
+//! }
+//! ```
+//!
+//! Errors of poem itself, like a `NotFoundError` or rejected extractors, are plain text
+//! responses. Add a [`ProblemDetailsMiddleware`] to the route to convert them into problems.
+//! Unmatched paths and methods are answered with problems using
+//! [`with_problem_fallback`](ProblemRouteExt::with_problem_fallback) or [`catch_all`].
 //!
 //! # Example
 //!
@@ -25,22 +48,44 @@
 //! }
 //! ```
 use http::StatusCode;
-use poem::{error::ResponseError, web::Json, IntoResponse, Response};
+use poem::{error::ResponseError, IntoResponse, Response};
 
 use crate::ProblemDetails;
 
 #[cfg(feature = "json")]
-use crate::JsonProblemDetails;
+mod fallback;
+#[cfg(feature = "json")]
+mod middleware;
+
+#[cfg(feature = "json")]
+pub use fallback::*;
+#[cfg(feature = "json")]
+pub use middleware::*;
+
+#[cfg(feature = "json")]
+use crate::{
+    AcceptedFormat, JsonProblemDetails, MultiStatusProblems, NegotiatedProblemDetails,
+    ProblemFormat, TextProblemDetails,
+};
 
 #[cfg(feature = "xml")]
 use crate::XmlProblemDetails;
 
+#[cfg(feature = "cbor")]
+use crate::CborProblemDetails;
+
+#[cfg(feature = "yaml")]
+use crate::YamlProblemDetails;
+
+#[cfg(feature = "html")]
+use crate::HtmlProblemDetails;
+
 impl<Ext> ResponseError for ProblemDetails<Ext>
 where
     Ext: serde::Serialize + Clone + Send,
 {
     fn status(&self) -> StatusCode {
-        self.status.unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+        self.status_or_default()
     }
 
     fn as_response(&self) -> poem::Response {
@@ -54,7 +99,7 @@ where
     Ext: serde::Serialize + Clone + Send,
 {
     fn status(&self) -> StatusCode {
-        self.0.status.unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+        self.0.status_or_default()
     }
 
     fn as_response(&self) -> poem::Response {
@@ -68,7 +113,63 @@ where
     Ext: serde::Serialize + Clone + Send,
 {
     fn status(&self) -> StatusCode {
-        self.0.status.unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+        self.0.status_or_default()
+    }
+
+    fn as_response(&self) -> poem::Response {
+        self.clone().into_response()
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl<Ext> ResponseError for CborProblemDetails<Ext>
+where
+    Ext: serde::Serialize + Clone + Send,
+{
+    fn status(&self) -> StatusCode {
+        self.0.status_or_default()
+    }
+
+    fn as_response(&self) -> poem::Response {
+        self.clone().into_response()
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl<Ext> ResponseError for YamlProblemDetails<Ext>
+where
+    Ext: serde::Serialize + Clone + Send,
+{
+    fn status(&self) -> StatusCode {
+        self.0.status_or_default()
+    }
+
+    fn as_response(&self) -> poem::Response {
+        self.clone().into_response()
+    }
+}
+
+#[cfg(feature = "html")]
+impl<Ext> ResponseError for HtmlProblemDetails<Ext>
+where
+    Ext: serde::Serialize + Clone + Send,
+{
+    fn status(&self) -> StatusCode {
+        self.0.status_or_default()
+    }
+
+    fn as_response(&self) -> poem::Response {
+        self.clone().into_response()
+    }
+}
+
+#[cfg(feature = "json")]
+impl<Ext> ResponseError for TextProblemDetails<Ext>
+where
+    Ext: serde::Serialize + Clone + Send,
+{
+    fn status(&self) -> StatusCode {
+        self.0.status_or_default()
     }
 
     fn as_response(&self) -> poem::Response {
@@ -82,10 +183,16 @@ where
     Ext: serde::Serialize + Send,
 {
     fn into_response(self) -> Response {
-        let status_code = self.0.status.unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
-        let content = Json(self.0).with_content_type(Self::CONTENT_TYPE);
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = match Self(details).to_body_bytes() {
+            Ok(json) => json,
+            Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        };
+        let content = content.with_content_type(Self::CONTENT_TYPE);
 
-        (status_code, content).into_response()
+        (status_code, headers, content).into_response()
     }
 }
 
@@ -95,14 +202,90 @@ where
     Ext: serde::Serialize + Send,
 {
     fn into_response(self) -> Response {
-        let status_code = self.0.status.unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
-        let content = match self.to_body_string() {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = match Self(details).to_body_bytes() {
             Ok(xml) => xml,
             Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
         };
         let content = content.with_content_type(Self::CONTENT_TYPE);
 
-        (status_code, content).into_response()
+        (status_code, headers, content).into_response()
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl<Ext> IntoResponse for CborProblemDetails<Ext>
+where
+    Ext: serde::Serialize + Send,
+{
+    fn into_response(self) -> Response {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = match Self(details).to_body_vec() {
+            Ok(cbor) => cbor,
+            Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        };
+        let content = content.with_content_type(Self::CONTENT_TYPE);
+
+        (status_code, headers, content).into_response()
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl<Ext> IntoResponse for YamlProblemDetails<Ext>
+where
+    Ext: serde::Serialize + Send,
+{
+    fn into_response(self) -> Response {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = match Self(details).to_body_string() {
+            Ok(yaml) => yaml,
+            Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        };
+        let content = content.with_content_type(Self::CONTENT_TYPE);
+
+        (status_code, headers, content).into_response()
+    }
+}
+
+#[cfg(feature = "html")]
+impl<Ext> IntoResponse for HtmlProblemDetails<Ext>
+where
+    Ext: serde::Serialize + Send,
+{
+    fn into_response(self) -> Response {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = match Self(details).to_body_string() {
+            Ok(html) => html,
+            Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        };
+        let content = content.with_content_type(Self::CONTENT_TYPE);
+
+        (status_code, headers, content).into_response()
+    }
+}
+
+#[cfg(feature = "json")]
+impl<Ext> IntoResponse for TextProblemDetails<Ext>
+where
+    Ext: serde::Serialize + Send,
+{
+    fn into_response(self) -> Response {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = Self(details)
+            .to_body_string()
+            .with_content_type(Self::CONTENT_TYPE);
+
+        (status_code, headers, content).into_response()
     }
 }
 
@@ -115,3 +298,70 @@ where
         JsonProblemDetails(self).into_response()
     }
 }
+
+#[cfg(feature = "json")]
+impl<T, Ext> IntoResponse for MultiStatusProblems<T, Ext>
+where
+    T: serde::Serialize + Send,
+    Ext: serde::Serialize + Send,
+{
+    fn into_response(self) -> Response {
+        let content = match self.with_configured_default_type().to_body_string() {
+            Ok(json) => json,
+            Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        };
+        let content = content.with_content_type(Self::CONTENT_TYPE);
+
+        (Self::STATUS, content).into_response()
+    }
+}
+
+#[cfg(feature = "json")]
+impl<Ext> ResponseError for NegotiatedProblemDetails<Ext>
+where
+    Ext: serde::Serialize + Clone + Send,
+{
+    fn status(&self) -> StatusCode {
+        self.details.status_or_default()
+    }
+
+    fn as_response(&self) -> poem::Response {
+        self.clone().into_response()
+    }
+}
+
+#[cfg(feature = "json")]
+impl<Ext> IntoResponse for NegotiatedProblemDetails<Ext>
+where
+    Ext: serde::Serialize + Send,
+{
+    fn into_response(self) -> Response {
+        let mut response = match self.format {
+            ProblemFormat::Json => JsonProblemDetails(self.details).into_response(),
+            #[cfg(feature = "xml")]
+            ProblemFormat::Xml => XmlProblemDetails(self.details).into_response(),
+            #[cfg(feature = "html")]
+            ProblemFormat::Html => HtmlProblemDetails(self.details).into_response(),
+            #[cfg(feature = "cbor")]
+            ProblemFormat::Cbor => CborProblemDetails(self.details).into_response(),
+            #[cfg(feature = "yaml")]
+            ProblemFormat::Yaml => YamlProblemDetails(self.details).into_response(),
+            ProblemFormat::Text => TextProblemDetails(self.details).into_response(),
+        };
+
+        response
+            .headers_mut()
+            .append(http::header::VARY, http::header::ACCEPT.into());
+        response
+    }
+}
+
+#[cfg(feature = "json")]
+impl<'a> poem::FromRequest<'a> for AcceptedFormat {
+    async fn from_request(
+        req: &'a poem::Request,
+        _body: &mut poem::RequestBody,
+    ) -> poem::Result<Self> {
+        Ok(Self::from_headers(req.headers()))
+    }
+}
@@ -0,0 +1,103 @@
+use http::{Method, StatusCode, Uri};
+use poem::error::{MethodNotAllowedError, NotFoundError};
+use poem::{Endpoint, IntoResponse, Request, Response, Result, Route};
+
+use crate::ProblemDetails;
+
+/// Returns an endpoint responding to all requests with a `404 Not Found` problem.
+///
+/// The path of the request is used as `instance`, the query string is omitted.
+/// Mount it at a wildcard path to answer unmatched requests below a prefix.
+///
+/// ```rust
+/// use poem::{get, handler, Route};
+/// use problem_details::poem::catch_all;
+///
+/// #[handler]
+/// fn index() -> &'static str {
+///     "Hello"
+/// }
+///
+/// let app = Route::new()
+///     .at("/", get(index))
+///     .at("/api/*path", catch_all());
+/// ```
+///
+/// Requires feature `json`.
+pub fn catch_all() -> impl Endpoint<Output = Response> {
+    poem::endpoint::make_sync(|request: Request| not_found(request.uri()).into_response())
+}
+
+/// Extension methods for poem routes.
+///
+/// Requires feature `json`.
+pub trait ProblemRouteExt: Sized {
+    /// Answers unmatched paths with `404 Not Found` and unsupported methods with
+    /// `405 Method Not Allowed` problems.
+    ///
+    /// ```rust
+    /// use poem::{get, handler, Route};
+    /// use problem_details::poem::ProblemRouteExt;
+    ///
+    /// #[handler]
+    /// fn index() -> &'static str {
+    ///     "Hello"
+    /// }
+    ///
+    /// let app = Route::new().at("/", get(index)).with_problem_fallback();
+    /// ```
+    fn with_problem_fallback(self) -> ProblemFallback<Self>;
+}
+
+impl ProblemRouteExt for Route {
+    fn with_problem_fallback(self) -> ProblemFallback<Self> {
+        ProblemFallback { inner: self }
+    }
+}
+
+/// The endpoint created by [`ProblemRouteExt::with_problem_fallback`].
+#[derive(Debug)]
+pub struct ProblemFallback<E> {
+    inner: E,
+}
+
+impl<E: Endpoint> Endpoint for ProblemFallback<E> {
+    type Output = Response;
+
+    async fn call(&self, request: Request) -> Result<Self::Output> {
+        let method = request.method().clone();
+        let uri = request.uri().clone();
+
+        match self.inner.call(request).await {
+            Ok(response) => Ok(response.into_response()),
+            Err(error) if error.is::<NotFoundError>() => Ok(not_found(&uri).into_response()),
+            Err(error) if error.is::<MethodNotAllowedError>() => {
+                Ok(method_not_allowed(&method, &uri).into_response())
+            }
+            Err(error) => Err(error),
+        }
+    }
+}
+
+fn not_found(uri: &Uri) -> ProblemDetails {
+    with_path_instance(ProblemDetails::from_status_code(StatusCode::NOT_FOUND), uri)
+        .with_detail(format!("No resource found at `{}`", uri.path()))
+}
+
+fn method_not_allowed(method: &Method, uri: &Uri) -> ProblemDetails {
+    with_path_instance(
+        ProblemDetails::from_status_code(StatusCode::METHOD_NOT_ALLOWED),
+        uri,
+    )
+    .with_detail(format!(
+        "Method `{method}` is not allowed at `{}`",
+        uri.path()
+    ))
+}
+
+fn with_path_instance(details: ProblemDetails, uri: &Uri) -> ProblemDetails {
+    match Uri::try_from(uri.path()) {
+        Ok(path) => details.with_instance(path),
+        Err(_) => details,
+    }
+}
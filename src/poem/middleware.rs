@@ -0,0 +1,167 @@
+use std::error::Error as StdError;
+use std::sync::Arc;
+
+use http::header;
+use poem::{Endpoint, IntoResponse, Middleware, Request, Response, Result};
+
+use crate::{ProblemDetails, ProblemFormat};
+
+type Mapping = Arc<dyn Fn(&poem::Error) -> Option<Response> + Send + Sync>;
+
+/// A poem middleware converting all errors into problem details responses.
+///
+/// Errors of the built-in extractors or a `NotFoundError` are plain text responses.
+/// This middleware converts any [`poem::Error`] into a JSON problem using its status,
+/// and its message as detail for client errors. Errors which already respond with
+/// problem details, e.g. a returned [`ProblemDetails`], are passed on unchanged,
+/// except for [`TextProblemDetails`](crate::TextProblemDetails) which cannot be told
+/// apart from plain text errors.
+///
+/// Custom error types are converted using [`map_error`](ProblemDetailsMiddleware::map_error).
+///
+/// ```rust
+/// use http::StatusCode;
+/// use poem::{get, handler, EndpointExt, Route};
+/// use problem_details::poem::ProblemDetailsMiddleware;
+/// use problem_details::ProblemDetails;
+///
+/// #[derive(Debug)]
+/// struct OrderShipped(u32);
+///
+/// impl std::fmt::Display for OrderShipped {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         write!(f, "order {} is already shipped", self.0)
+///     }
+/// }
+///
+/// impl std::error::Error for OrderShipped {}
+///
+/// #[handler]
+/// fn cancel() -> poem::Result<()> {
+///     Err(poem::Error::new(OrderShipped(42), StatusCode::CONFLICT))
+/// }
+///
+/// let app = Route::new().at("/", get(cancel)).with(
+///     ProblemDetailsMiddleware::new().map_error(|err: &OrderShipped| {
+///         ProblemDetails::from_status_code(StatusCode::CONFLICT)
+///             .with_detail(err.to_string())
+///             .with_extension("order", err.0)
+///     }),
+/// );
+/// ```
+///
+/// Requires feature `json`.
+#[derive(Clone, Default)]
+pub struct ProblemDetailsMiddleware {
+    mappings: Vec<Mapping>,
+}
+
+impl ProblemDetailsMiddleware {
+    /// Creates a new middleware.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder-style method that adds a conversion of errors with source type `T` into problems.
+    ///
+    /// Conversions are tried in the order they are added.
+    #[must_use]
+    pub fn map_error<T, Ext, F>(mut self, mapping: F) -> Self
+    where
+        T: StdError + Send + Sync + 'static,
+        Ext: serde::Serialize + Send,
+        F: Fn(&T) -> ProblemDetails<Ext> + Send + Sync + 'static,
+    {
+        self.mappings.push(Arc::new(move |error: &poem::Error| {
+            error
+                .downcast_ref::<T>()
+                .map(|error| mapping(error).into_response())
+        }));
+        self
+    }
+}
+
+impl std::fmt::Debug for ProblemDetailsMiddleware {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProblemDetailsMiddleware")
+            .field("mappings", &self.mappings.len())
+            .finish()
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for ProblemDetailsMiddleware {
+    type Output = ProblemDetailsEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        ProblemDetailsEndpoint {
+            inner: ep,
+            mappings: self.mappings.clone(),
+        }
+    }
+}
+
+/// The endpoint created by [`ProblemDetailsMiddleware`].
+pub struct ProblemDetailsEndpoint<E> {
+    inner: E,
+    mappings: Vec<Mapping>,
+}
+
+impl<E: Endpoint> Endpoint for ProblemDetailsEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        match self.inner.call(req).await {
+            Ok(response) => Ok(response.into_response()),
+            Err(error) => Ok(self.error_response(error)),
+        }
+    }
+}
+
+impl<E> ProblemDetailsEndpoint<E> {
+    /// Converts an error into a problem details response.
+    fn error_response(&self, error: poem::Error) -> Response {
+        if let Some(response) = self.mappings.iter().find_map(|mapping| mapping(&error)) {
+            return response;
+        }
+
+        let status = error.status();
+        let message = error.to_string();
+        let response = error.into_response();
+        if response.content_type().is_some_and(is_problem_format) {
+            return response;
+        }
+
+        let mut details = ProblemDetails::from_status_code(status);
+        if status.is_client_error() && !message.is_empty() && message != status.to_string() {
+            details = details.with_detail(message);
+        }
+
+        let (mut parts, _) = response.into_parts();
+        parts.headers.remove(header::CONTENT_TYPE);
+        parts.headers.remove(header::CONTENT_LENGTH);
+        *details.headers_mut() = parts.headers;
+
+        let mut response = details.into_response();
+        *response.extensions_mut() = parts.extensions;
+        response
+    }
+}
+
+/// Returns true if the content type is a problem details format other than plain text.
+fn is_problem_format(content_type: &str) -> bool {
+    let formats = [
+        ProblemFormat::Json,
+        #[cfg(feature = "xml")]
+        ProblemFormat::Xml,
+        #[cfg(feature = "html")]
+        ProblemFormat::Html,
+        #[cfg(feature = "cbor")]
+        ProblemFormat::Cbor,
+        #[cfg(feature = "yaml")]
+        ProblemFormat::Yaml,
+    ];
+
+    formats
+        .iter()
+        .any(|format| content_type.starts_with(format.content_type()))
+}
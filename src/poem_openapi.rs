@@ -0,0 +1,353 @@
+//! OpenAPI descriptions of problem responses using [`poem_openapi`]. Requires feature `poem-openapi`.
+//!
+//! With the `poem-openapi` feature enabled, [`ProblemDetails`], [`JsonProblemDetails`] and
+//! `XmlProblemDetails` (requires feature `xml`) implement [`Payload`] and [`ApiResponse`],
+//! so problems returned from API operations appear in the generated OpenAPI document with
+//! their media type and a `ProblemDetails` component schema. Returned directly, they are
+//! documented as `default` response. To document specific statuses, use them as
+//! payload of an `ApiResponse` enum.
+//!
+//! Extension structs implementing [`Type`](poem_openapi::types::Type) can be registered using
+//! the [`openapi_extension_schema!`](crate::openapi_extension_schema) macro. The schema of a
+//! problem with such extensions is then composed via `allOf` of the `ProblemDetails` schema
+//! and the extension schema, e.g. `ProblemDetails_OutOfCredit`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use poem_openapi::{payload::PlainText, ApiResponse, Object, OpenApi};
+//! use problem_details::{JsonProblemDetails, ProblemDetails};
+//!
+//! #[derive(Clone, serde::Serialize, Object)]
+//! struct OutOfCredit {
+//!     balance: u32,
+//! }
+//!
+//! problem_details::openapi_extension_schema!(OutOfCredit);
+//!
+//! #[derive(ApiResponse)]
+//! enum BuyResponse {
+//!     #[oai(status = 200)]
+//!     Ok(PlainText<String>),
+//!     #[oai(status = 403)]
+//!     OutOfCredit(JsonProblemDetails<OutOfCredit>),
+//! }
+//!
+//! struct Api;
+//!
+//! #[OpenApi]
+//! impl Api {
+//!     #[oai(path = "/buy", method = "post")]
+//!     async fn buy(&self) -> BuyResponse {
+//!         BuyResponse::Ok(PlainText("bought".to_string()))
+//!     }
+//!
+//!     #[oai(path = "/orders", method = "get")]
+//!     async fn orders(&self) -> Result<PlainText<String>, ProblemDetails> {
+//!         Ok(PlainText("[]".to_string()))
+//!     }
+//! }
+//! ```
+use poem_openapi::{
+    payload::Payload,
+    registry::{MetaMediaType, MetaResponse, MetaResponses, MetaSchema, MetaSchemaRef, Registry},
+    ApiResponse,
+};
+
+use crate::{JsonProblemDetails, ProblemDetails};
+
+#[cfg(feature = "xml")]
+use crate::XmlProblemDetails;
+
+/// The name of the schema containing the standard members.
+const BASE_SCHEMA_NAME: &str = "ProblemDetails";
+
+/// Extensions which can be described in the schema of a [`ProblemDetails`].
+///
+/// This is implemented for `()` (no extensions) and for tuples of extensions.
+/// Extension structs implementing [`Type`](poem_openapi::types::Type) can implement it
+/// using the [`openapi_extension_schema!`](crate::openapi_extension_schema) macro.
+pub trait ExtensionSchema {
+    /// Returns the schema of the extension members, or `None` if there are none.
+    fn extension_schema() -> Option<MetaSchemaRef>;
+
+    /// Returns the name used to identify problems with these extensions.
+    fn extension_name() -> Option<String>;
+
+    /// Registers the component schemas referenced by the extension schema.
+    fn register_extension(registry: &mut Registry) {
+        let _ = registry;
+    }
+}
+
+impl ExtensionSchema for () {
+    fn extension_schema() -> Option<MetaSchemaRef> {
+        None
+    }
+
+    fn extension_name() -> Option<String> {
+        None
+    }
+}
+
+/// Implements [`ExtensionSchema`](crate::poem_openapi::ExtensionSchema) for the given extension types.
+///
+/// The types need to implement [`Type`](::poem_openapi::types::Type), e.g. by deriving
+/// `Object`. Requires feature `poem-openapi`.
+///
+/// See the [`poem_openapi`](crate::poem_openapi) module for an example.
+#[macro_export]
+macro_rules! openapi_extension_schema {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl $crate::poem_openapi::ExtensionSchema for $ty {
+                fn extension_schema() -> ::std::option::Option<
+                    $crate::__private::poem_openapi::registry::MetaSchemaRef
+                > {
+                    ::std::option::Option::Some(
+                        <$ty as $crate::__private::poem_openapi::types::Type>::schema_ref()
+                    )
+                }
+
+                fn extension_name() -> ::std::option::Option<::std::string::String> {
+                    ::std::option::Option::Some(
+                        <$ty as $crate::__private::poem_openapi::types::Type>::name().into_owned()
+                    )
+                }
+
+                fn register_extension(
+                    registry: &mut $crate::__private::poem_openapi::registry::Registry,
+                ) {
+                    <$ty as $crate::__private::poem_openapi::types::Type>::register(registry);
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_extension_schema_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name),+> ExtensionSchema for ($($name,)+)
+        where
+            $($name: ExtensionSchema,)+
+        {
+            fn extension_schema() -> Option<MetaSchemaRef> {
+                let schemas = [$($name::extension_schema()),+]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>();
+
+                match schemas.len() {
+                    0 => None,
+                    1 => schemas.into_iter().next(),
+                    _ => Some(MetaSchemaRef::Inline(Box::new(MetaSchema {
+                        all_of: schemas,
+                        ..MetaSchema::ANY
+                    }))),
+                }
+            }
+
+            fn extension_name() -> Option<String> {
+                let names = [$($name::extension_name()),+]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>();
+
+                match names.len() {
+                    0 => None,
+                    _ => Some(names.join("_")),
+                }
+            }
+
+            fn register_extension(registry: &mut Registry) {
+                $($name::register_extension(registry);)+
+            }
+        }
+    };
+}
+
+impl_extension_schema_for_tuple!(A);
+impl_extension_schema_for_tuple!(A, B);
+impl_extension_schema_for_tuple!(A, B, C);
+impl_extension_schema_for_tuple!(A, B, C, D);
+
+/// Returns the schema containing the standard members.
+fn base_schema() -> MetaSchema {
+    let uri_reference = |description| MetaSchema {
+        description: Some(description),
+        ..MetaSchema::new_with_format("string", "uri-reference")
+    };
+
+    MetaSchema {
+        description: Some("A problem details object as defined in RFC 9457."),
+        properties: vec![
+            (
+                "type",
+                MetaSchemaRef::Inline(Box::new(MetaSchema {
+                    default: Some("about:blank".into()),
+                    ..uri_reference("A URI reference that identifies the problem type.")
+                })),
+            ),
+            (
+                "status",
+                MetaSchemaRef::Inline(Box::new(MetaSchema {
+                    minimum: Some(100.0),
+                    maximum: Some(599.0),
+                    description: Some("The HTTP status code for this occurrence of the problem."),
+                    ..MetaSchema::new_with_format("integer", "int32")
+                })),
+            ),
+            (
+                "title",
+                MetaSchemaRef::Inline(Box::new(MetaSchema {
+                    description: Some("A short, human-readable summary of the problem type."),
+                    ..MetaSchema::new("string")
+                })),
+            ),
+            (
+                "detail",
+                MetaSchemaRef::Inline(Box::new(MetaSchema {
+                    description: Some(
+                        "A human-readable explanation specific to this occurrence of the problem.",
+                    ),
+                    ..MetaSchema::new("string")
+                })),
+            ),
+            (
+                "instance",
+                MetaSchemaRef::Inline(Box::new(uri_reference(
+                    "A URI reference that identifies the specific occurrence of the problem.",
+                ))),
+            ),
+        ],
+        ..MetaSchema::new("object")
+    }
+}
+
+/// Returns the name of the component schema of problems with the given extensions.
+fn schema_name<Ext: ExtensionSchema>() -> String {
+    match Ext::extension_name() {
+        Some(name) => format!("{BASE_SCHEMA_NAME}_{name}"),
+        None => BASE_SCHEMA_NAME.to_string(),
+    }
+}
+
+/// Registers the component schemas of problems with the given extensions.
+fn register_schema<Ext: ExtensionSchema>(registry: &mut Registry) {
+    registry.create_schema::<ProblemDetails, _>(BASE_SCHEMA_NAME.to_string(), |_| base_schema());
+
+    if let Some(extension_schema) = Ext::extension_schema() {
+        registry.create_schema::<ProblemDetails<Ext>, _>(schema_name::<Ext>(), |registry| {
+            Ext::register_extension(registry);
+            MetaSchema {
+                all_of: vec![
+                    MetaSchemaRef::Reference(BASE_SCHEMA_NAME.to_string()),
+                    extension_schema,
+                ],
+                ..MetaSchema::ANY
+            }
+        });
+    }
+}
+
+/// Returns the responses of a payload documented as `default` response.
+fn default_response<T: Payload>() -> MetaResponses {
+    MetaResponses {
+        responses: vec![MetaResponse {
+            description: "A problem details object as defined in RFC 9457.",
+            status: None,
+            status_range: None,
+            content: vec![MetaMediaType {
+                content_type: T::CONTENT_TYPE,
+                schema: T::schema_ref(),
+            }],
+            headers: vec![],
+        }],
+    }
+}
+
+impl<Ext> Payload for ProblemDetails<Ext>
+where
+    Ext: ExtensionSchema + Send,
+{
+    const CONTENT_TYPE: &'static str = JsonProblemDetails::<()>::CONTENT_TYPE;
+
+    fn schema_ref() -> MetaSchemaRef {
+        MetaSchemaRef::Reference(schema_name::<Ext>())
+    }
+
+    fn register(registry: &mut Registry) {
+        register_schema::<Ext>(registry);
+    }
+}
+
+impl<Ext> ApiResponse for ProblemDetails<Ext>
+where
+    Ext: ExtensionSchema + Send,
+{
+    fn meta() -> MetaResponses {
+        default_response::<Self>()
+    }
+
+    fn register(registry: &mut Registry) {
+        register_schema::<Ext>(registry);
+    }
+}
+
+impl<Ext> Payload for JsonProblemDetails<Ext>
+where
+    Ext: ExtensionSchema + Send,
+{
+    const CONTENT_TYPE: &'static str = JsonProblemDetails::<()>::CONTENT_TYPE;
+
+    fn schema_ref() -> MetaSchemaRef {
+        MetaSchemaRef::Reference(schema_name::<Ext>())
+    }
+
+    fn register(registry: &mut Registry) {
+        register_schema::<Ext>(registry);
+    }
+}
+
+impl<Ext> ApiResponse for JsonProblemDetails<Ext>
+where
+    Ext: ExtensionSchema + Send,
+{
+    fn meta() -> MetaResponses {
+        default_response::<Self>()
+    }
+
+    fn register(registry: &mut Registry) {
+        register_schema::<Ext>(registry);
+    }
+}
+
+#[cfg(feature = "xml")]
+impl<Ext> Payload for XmlProblemDetails<Ext>
+where
+    Ext: ExtensionSchema + Send,
+{
+    const CONTENT_TYPE: &'static str = XmlProblemDetails::<()>::CONTENT_TYPE;
+
+    fn schema_ref() -> MetaSchemaRef {
+        MetaSchemaRef::Reference(schema_name::<Ext>())
+    }
+
+    fn register(registry: &mut Registry) {
+        register_schema::<Ext>(registry);
+    }
+}
+
+#[cfg(feature = "xml")]
+impl<Ext> ApiResponse for XmlProblemDetails<Ext>
+where
+    Ext: ExtensionSchema + Send,
+{
+    fn meta() -> MetaResponses {
+        default_response::<Self>()
+    }
+
+    fn register(registry: &mut Registry) {
+        register_schema::<Ext>(registry);
+    }
+}
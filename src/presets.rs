@@ -0,0 +1,153 @@
+//! Ready-made problems for common scenarios.
+//!
+//! Each preset picks a status, title, detail and a typed extension struct, so
+//! services get consistent problems with a single call. Presets use a registered
+//! problem type where one exists (see [`types`]) and `about:blank`
+//! otherwise. All fields can still be adjusted using the builder methods.
+//!
+//! ```rust
+//! use http::StatusCode;
+//! use problem_details::presets;
+//!
+//! let details = presets::not_found("user", 42)
+//!     .with_instance(http::Uri::from_static("/users/42"));
+//!
+//! assert_eq!(details.status, Some(StatusCode::NOT_FOUND));
+//! assert_eq!(details.detail.as_deref(), Some("The user 42 does not exist."));
+//! assert_eq!(details.extensions.resource, "user");
+//! ```
+use std::time::Duration;
+
+use http::StatusCode;
+
+use crate::{
+    types, FieldError, MergeExtensions, ProblemDetails, RetryAfterExtension, ValidationErrors,
+};
+
+/// Extension members of a [`not_found`] problem.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NotFoundExtension {
+    /// The kind of resource that was not found, e.g. `user`.
+    pub resource: String,
+
+    /// The identifier of the resource that was not found.
+    pub id: String,
+}
+
+/// Extension members of a [`conflict`] problem.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConflictExtension {
+    /// The current version of the resource, e.g. its entity tag.
+    pub current_version: String,
+}
+
+/// Extension members of an [`internal`] problem.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InternalExtension {
+    /// An identifier to correlate the problem with server-side logs.
+    pub reference_id: String,
+}
+
+impl MergeExtensions for NotFoundExtension {
+    fn merge_extensions(&mut self, other: Self) {
+        *self = other;
+    }
+}
+
+impl MergeExtensions for ConflictExtension {
+    fn merge_extensions(&mut self, other: Self) {
+        *self = other;
+    }
+}
+
+impl MergeExtensions for InternalExtension {
+    fn merge_extensions(&mut self, other: Self) {
+        *self = other;
+    }
+}
+
+/// Creates a `404 Not Found` problem for the given resource and id.
+#[must_use]
+pub fn not_found(
+    resource: impl Into<String>,
+    id: impl std::fmt::Display,
+) -> ProblemDetails<NotFoundExtension> {
+    let resource = resource.into();
+    let id = id.to_string();
+
+    ProblemDetails::from_status_code(StatusCode::NOT_FOUND)
+        .with_detail(format!("The {resource} {id} does not exist."))
+        .with_extensions(NotFoundExtension { resource, id })
+}
+
+/// Creates a `422 Unprocessable Entity` problem for the given invalid fields.
+///
+/// The errors are given as pairs of JSON Pointer and detail, and are
+/// listed in an `errors` extension member, see [`ValidationErrors`].
+///
+/// ```rust
+/// use problem_details::presets;
+///
+/// let details = presets::validation([("/user/email", "must be a valid address")]);
+///
+/// assert_eq!(details.extensions.errors[0].pointer, "/user/email");
+/// ```
+#[must_use]
+pub fn validation<P, D>(
+    errors: impl IntoIterator<Item = (P, D)>,
+) -> ProblemDetails<ValidationErrors>
+where
+    P: Into<String>,
+    D: Into<String>,
+{
+    let errors = errors
+        .into_iter()
+        .map(|(pointer, detail)| FieldError::new(pointer, detail))
+        .collect();
+
+    ProblemDetails::from_status_code(StatusCode::UNPROCESSABLE_ENTITY)
+        .with_detail("The request contains invalid fields.")
+        .with_extensions(ValidationErrors { errors })
+}
+
+/// Creates a `409 Conflict` problem for a resource which was modified concurrently.
+#[must_use]
+pub fn conflict(current_version: impl Into<String>) -> ProblemDetails<ConflictExtension> {
+    ProblemDetails::from_status_code(StatusCode::CONFLICT)
+        .with_detail("The resource was modified by another request.")
+        .with_extensions(ConflictExtension {
+            current_version: current_version.into(),
+        })
+}
+
+/// Creates a `429 Too Many Requests` problem asking the client to retry after the given delay.
+///
+/// Uses the [`QUOTA_EXCEEDED`](types::QUOTA_EXCEEDED) problem type and sets the
+/// `Retry-After` header, see [`ProblemDetails::with_retry_after`].
+#[must_use]
+pub fn rate_limited(retry_after: Duration) -> ProblemDetails<RetryAfterExtension> {
+    ProblemDetails::from_status_code(StatusCode::TOO_MANY_REQUESTS)
+        .with_type(types::QUOTA_EXCEEDED.clone())
+        .with_title("Request quota exceeded")
+        .with_detail("Too many requests, please try again later.")
+        .with_retry_after(retry_after)
+        .map_extensions(|((), extension)| extension)
+}
+
+/// Creates a `500 Internal Server Error` problem referencing the given id.
+///
+/// The detail deliberately does not reveal anything about the error, instead
+/// the reference id can be used to look up the error in server-side logs.
+#[must_use]
+pub fn internal(reference_id: impl Into<String>) -> ProblemDetails<InternalExtension> {
+    let reference_id = reference_id.into();
+
+    ProblemDetails::from_status_code(StatusCode::INTERNAL_SERVER_ERROR)
+        .with_detail(format!(
+            "An unexpected error occurred. Please refer to {reference_id} when contacting support."
+        ))
+        .with_extensions(InternalExtension { reference_id })
+}
@@ -1,18 +1,48 @@
-use http::{StatusCode, Uri};
+use std::borrow::Cow;
+use std::sync::{Arc, LazyLock};
 
-use crate::ProblemType;
+use http::{header::IntoHeaderName, HeaderMap, HeaderValue, StatusCode, Uri};
+
+use crate::{ProblemType, UriError};
 
 #[cfg(feature = "json")]
 mod json;
 
 #[cfg(feature = "json")]
-pub use json::JsonProblemDetails;
+pub use json::{JsonError, JsonProblemDetails};
+
+#[cfg(feature = "json")]
+mod text;
+
+#[cfg(feature = "json")]
+pub use text::TextProblemDetails;
+
+#[cfg(feature = "html")]
+mod html;
+
+#[cfg(feature = "html")]
+pub use html::{
+    escape_html, set_html_template, DefaultHtmlTemplate, HtmlError, HtmlPage, HtmlProblemDetails,
+    HtmlTemplate,
+};
+
+#[cfg(feature = "cbor")]
+mod cbor;
+
+#[cfg(feature = "cbor")]
+pub use cbor::{CborError, CborProblemDetails};
 
 #[cfg(feature = "xml")]
 mod xml;
 
 #[cfg(feature = "xml")]
-pub use xml::XmlProblemDetails;
+pub use xml::{set_xml_format, xml_format, XmlFormat, XmlProblemDetails};
+
+#[cfg(feature = "yaml")]
+mod yaml;
+
+#[cfg(feature = "yaml")]
+pub use yaml::{YamlError, YamlProblemDetails};
 
 #[cfg(test)]
 mod tests;
@@ -35,7 +65,7 @@ mod tests;
 /// let details = ProblemDetails::from_status_code(StatusCode::NOT_FOUND);
 ///
 /// assert_eq!(details.status, Some(StatusCode::NOT_FOUND));
-/// assert_eq!(details.title, Some("Not Found".to_string()));
+/// assert_eq!(details.title.as_deref(), Some("Not Found"));
 /// assert_eq!(details.r#type.unwrap_or_default(), problem_details::ProblemType::default());
 /// ```
 ///
@@ -50,7 +80,7 @@ mod tests;
 ///    .with_title("There is something wrong");
 ///
 /// assert_eq!(details.status, Some(StatusCode::NOT_FOUND));
-/// assert_eq!(details.title, Some("There is something wrong".to_string()));
+/// assert_eq!(details.title.as_deref(), Some("There is something wrong"));
 /// assert_eq!(details.r#type.unwrap_or_default(), Uri::from_static("example:type").into());
 /// ```
 ///
@@ -65,7 +95,7 @@ mod tests;
 ///   .with_title("There is something wrong");
 ///
 /// assert_eq!(details.status, None);
-/// assert_eq!(details.title, Some("There is something wrong".to_string()));
+/// assert_eq!(details.title.as_deref(), Some("There is something wrong"));
 /// assert_eq!(details.r#type.unwrap_or_default(), Uri::from_static("example:type").into());
 /// ```
 /// # Extensions
@@ -95,24 +125,47 @@ mod tests;
 /// let typecheck: ProblemDetails<MyExt> = details;
 /// ```
 ///
-/// If you need dynamic extensions, you can use a [`HashMap`](std::collections::HashMap)
-/// as extensions object.
+/// If you need dynamic extensions, you can use [`DynamicExtensions`](crate::DynamicExtensions)
+/// as extensions object, most easily via [`ProblemDetails::with_extension`].
 ///
 /// ```rust
-/// use std::collections::HashMap;
-/// use problem_details::ProblemDetails;
+/// use problem_details::{DynamicExtensions, ProblemDetails};
+///
+/// let details = ProblemDetails::new()
+///    .with_extension("foo", "Hello")
+///    .with_extension("bar", 42);
+///
+/// // details is of type ProblemDetails<DynamicExtensions>
+/// let typecheck: ProblemDetails<DynamicExtensions> = details;
+/// ```
+///
+/// # Member Order
 ///
-/// let mut extensions = HashMap::<String, serde_json::Value>::new();
-/// extensions.insert("foo".to_string(), serde_json::json!("Hello"));
-/// extensions.insert("bar".to_string(), serde_json::json!(42));
+/// When serialized, the standard members are always written first, in the order
+/// `type`, `status`, `title`, `detail` and `instance`, regardless of how the problem
+/// details were created. The extension members follow: struct fields in declaration
+/// order, tuple elements one after another, and map entries in iteration order.
+/// [`DynamicExtensions`](crate::DynamicExtensions) keeps the insertion order and
+/// [`WithUnknown`](crate::WithUnknown) sorts its unknown members by key, so their
+/// output is byte-stable. A `HashMap` iterates in arbitrary order, use a `BTreeMap`
+/// or `DynamicExtensions` instead if you need stable output.
+///
+/// ```rust
+/// use http::StatusCode;
+/// use problem_details::ProblemDetails;
 ///
 /// let details = ProblemDetails::new()
-///    .with_extensions(extensions);
+///     .with_extension("balance", 30)
+///     .with_instance(http::Uri::from_static("/account/12345"))
+///     .with_title("Out of credit")
+///     .with_status(StatusCode::FORBIDDEN);
 ///
-/// // details is of type ProblemDetails<HashMap<String, serde_json::Value>>
-/// let typecheck: ProblemDetails<HashMap<String, serde_json::Value>> = details;
+/// assert_eq!(
+///     serde_json::to_string(&details).unwrap(),
+///     r#"{"status":403,"title":"Out of credit","instance":"/account/12345","balance":30}"#
+/// );
 /// ```
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ProblemDetails<Ext = ()> {
     /// An optional uri describing the problem type.
@@ -132,17 +185,19 @@ pub struct ProblemDetails<Ext = ()> {
 
     /// An optional human-readable title for this problem.
     ///
+    /// Static titles are stored without allocating.
+    ///
     /// See [https://www.rfc-editor.org/rfc/rfc9457.html#name-title]() for more information.
     #[cfg_attr(feature = "serde", serde(default))]
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub title: Option<String>,
+    pub title: Option<Cow<'static, str>>,
 
     /// An optional human-readable description of this problem.
     ///
     /// See [https://www.rfc-editor.org/rfc/rfc9457.html#name-detail]() for more information.
     #[cfg_attr(feature = "serde", serde(default))]
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub detail: Option<String>,
+    pub detail: Option<Cow<'static, str>>,
 
     /// An optional uri identifying the specific instance of this problem.
     ///
@@ -155,13 +210,42 @@ pub struct ProblemDetails<Ext = ()> {
     /// An object containing extensions to this problem details object.
     ///
     /// Note that the extensions will be flattened into the resulting problem details
-    /// representation.
+    /// representation. Tuples of extension structs are flattened element by element.
     ///
     /// See [https://www.rfc-editor.org/rfc/rfc9457.html#name-extension-members]() for more information.
     #[cfg_attr(feature = "serde", serde(flatten))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde::extensions"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(bound(
+            serialize = "Ext: serde::Serialize",
+            deserialize = "Ext: serde::Deserialize<'de>"
+        ))
+    )]
     pub extensions: Ext,
+
+    /// The underlying cause of this problem, if any.
+    ///
+    /// This is never serialized and is only exposed via [`Error::source`](std::error::Error::source).
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) source: Option<Source>,
+
+    /// The backtrace captured for this problem, if any.
+    ///
+    /// This is never serialized and is only exposed via [`ProblemDetails::backtrace`].
+    #[cfg(feature = "backtrace")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) backtrace: Option<Arc<std::backtrace::Backtrace>>,
+
+    /// Additional headers sent with the response for this problem, if any.
+    ///
+    /// This is never serialized and is only exposed via [`ProblemDetails::headers`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) headers: Option<HeaderMap>,
 }
 
+pub(crate) type Source = Arc<dyn std::error::Error + Send + Sync + 'static>;
+
 impl ProblemDetails<()> {
     /// Creates a new empty problem details object.
     #[must_use]
@@ -173,6 +257,10 @@ impl ProblemDetails<()> {
             detail: None,
             instance: None,
             extensions: Default::default(),
+            source: None,
+            #[cfg(feature = "backtrace")]
+            backtrace: None,
+            headers: None,
         }
     }
 
@@ -181,16 +269,51 @@ impl ProblemDetails<()> {
     /// This will set the `status` field to the given status code,
     /// the `title` field to the canonical reason phrase of the status code,
     /// and the `type` field to none, which is equivalent to `about:blank`.
+    ///
+    /// With feature `backtrace` enabled, a backtrace is captured automatically
+    /// for server errors (5xx).
     #[must_use]
     pub fn from_status_code(status: StatusCode) -> Self {
-        Self {
+        let details = Self {
             r#type: None,
             status: Some(status),
-            title: status.canonical_reason().map(ToOwned::to_owned),
+            title: status.canonical_reason().map(Cow::Borrowed),
             detail: None,
             instance: None,
             extensions: Default::default(),
+            source: None,
+            #[cfg(feature = "backtrace")]
+            backtrace: None,
+            headers: None,
+        };
+
+        #[cfg(feature = "backtrace")]
+        if status.is_server_error() {
+            return details.with_backtrace();
         }
+
+        details
+    }
+}
+
+impl From<StatusCode> for ProblemDetails<()> {
+    /// Creates a new problem details object from a given status code.
+    ///
+    /// This is equivalent to [`ProblemDetails::from_status_code`].
+    fn from(value: StatusCode) -> Self {
+        Self::from_status_code(value)
+    }
+}
+
+impl TryFrom<u16> for ProblemDetails<()> {
+    type Error = http::status::InvalidStatusCode;
+
+    /// Creates a new problem details object from a given numeric status code.
+    ///
+    /// This is equivalent to [`ProblemDetails::from_status_code`], but fails
+    /// if the given number is not a valid status code.
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        StatusCode::from_u16(value).map(Self::from_status_code)
     }
 }
 
@@ -202,6 +325,101 @@ impl<Ext> ProblemDetails<Ext> {
         self
     }
 
+    /// Builder-style method that sets the `type` field of this problem details object
+    /// by converting the given value into a URI.
+    ///
+    /// Accepts anything convertible into a [`Uri`], like `&str`, `String` or [`Uri`]
+    /// itself. Returns an [`UriError`] if the given value is not a valid URI.
+    ///
+    /// ```rust
+    /// use problem_details::ProblemDetails;
+    ///
+    /// let details = ProblemDetails::new()
+    ///     .try_with_type("https://example.com/probs/out-of-credit")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     details.r#type.unwrap().to_string(),
+    ///     "https://example.com/probs/out-of-credit"
+    /// );
+    /// ```
+    pub fn try_with_type<T>(self, r#type: T) -> Result<Self, UriError>
+    where
+        T: TryInto<Uri>,
+        T::Error: Into<UriError>,
+    {
+        let r#type = r#type.try_into().map_err(Into::into)?;
+        Ok(self.with_type(r#type))
+    }
+
+    /// Returns true if the `type` of this problem matches the given uri.
+    ///
+    /// A missing `type` is treated as `about:blank`. See [`ProblemType::matches`]
+    /// for details on how the uris are compared.
+    ///
+    /// ```rust
+    /// use http::{StatusCode, Uri};
+    /// use problem_details::ProblemDetails;
+    ///
+    /// let details = ProblemDetails::from_status_code(StatusCode::NOT_FOUND);
+    /// assert!(details.matches_type(&Uri::from_static("about:blank")));
+    /// ```
+    pub fn matches_type(&self, uri: &Uri) -> bool {
+        self.effective_type().matches(uri)
+    }
+
+    /// Returns the `type` of this problem, or `about:blank` if none is set.
+    ///
+    /// RFC 9457 specifies that a missing `type` must be treated as `about:blank`.
+    ///
+    /// ```rust
+    /// use problem_details::{ProblemDetails, ProblemType};
+    ///
+    /// let details = ProblemDetails::new();
+    /// assert_eq!(*details.effective_type(), ProblemType::default());
+    /// ```
+    pub fn effective_type(&self) -> Cow<'_, ProblemType> {
+        match &self.r#type {
+            Some(r#type) => Cow::Borrowed(r#type),
+            None => Cow::Owned(ProblemType::default()),
+        }
+    }
+
+    /// Returns the `status` of this problem, or `500 Internal Server Error`
+    /// if none is set.
+    ///
+    /// This is the status code used by the framework integrations when
+    /// converting a problem into a response.
+    ///
+    /// ```rust
+    /// use http::StatusCode;
+    /// use problem_details::ProblemDetails;
+    ///
+    /// assert_eq!(
+    ///     ProblemDetails::new().status_or_default(),
+    ///     StatusCode::INTERNAL_SERVER_ERROR
+    /// );
+    /// ```
+    pub fn status_or_default(&self) -> StatusCode {
+        self.status.unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    /// Returns the `title` of this problem, or the canonical reason phrase of
+    /// its `status` if no title is set.
+    ///
+    /// ```rust
+    /// use http::StatusCode;
+    /// use problem_details::ProblemDetails;
+    ///
+    /// let details = ProblemDetails::new().with_status(StatusCode::NOT_FOUND);
+    /// assert_eq!(details.effective_title(), Some("Not Found"));
+    /// ```
+    pub fn effective_title(&self) -> Option<&str> {
+        self.title
+            .as_deref()
+            .or(self.status.as_ref().and_then(StatusCode::canonical_reason))
+    }
+
     /// Builder-style method that sets the `status` field of this problem details object.
     #[must_use]
     pub fn with_status(mut self, status: impl Into<StatusCode>) -> Self {
@@ -211,14 +429,14 @@ impl<Ext> ProblemDetails<Ext> {
 
     /// Builder-style method that sets the `title` field of this problem details object.
     #[must_use]
-    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+    pub fn with_title(mut self, title: impl Into<Cow<'static, str>>) -> Self {
         self.title = Some(title.into());
         self
     }
 
     /// Builder-style method that sets the `detail` field of this problem details object.
     #[must_use]
-    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+    pub fn with_detail(mut self, detail: impl Into<Cow<'static, str>>) -> Self {
         self.detail = Some(detail.into());
         self
     }
@@ -230,25 +448,298 @@ impl<Ext> ProblemDetails<Ext> {
         self
     }
 
+    /// Builder-style method that sets the `instance` field of this problem details object
+    /// by converting the given value into a URI.
+    ///
+    /// Accepts anything convertible into a [`Uri`], like `&str`, `String` or [`Uri`]
+    /// itself. Returns an [`UriError`] if the given value is not a valid URI.
+    ///
+    /// ```rust
+    /// use problem_details::ProblemDetails;
+    ///
+    /// let account = 12345;
+    /// let details = ProblemDetails::new()
+    ///     .try_with_instance(&format!("/account/{account}/msgs/abc"))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(details.instance.unwrap().to_string(), "/account/12345/msgs/abc");
+    /// ```
+    pub fn try_with_instance<T>(self, instance: T) -> Result<Self, UriError>
+    where
+        T: TryInto<Uri>,
+        T::Error: Into<UriError>,
+    {
+        let instance = instance.try_into().map_err(Into::into)?;
+        Ok(self.with_instance(instance))
+    }
+
+    /// Builder-style method that sets the `type` field of this problem details object
+    /// from a [`Url`](url::Url).
+    ///
+    /// Fragments are dropped, as they cannot be represented by [`Uri`]. Returns an
+    /// [`UriError`] for URLs which [`Uri`] cannot represent at all, like `urn` URLs
+    /// containing more than one colon.
+    ///
+    /// ```rust
+    /// use problem_details::ProblemDetails;
+    /// use url::Url;
+    ///
+    /// let base = Url::parse("https://example.com/probs/").unwrap();
+    /// let details = ProblemDetails::new()
+    ///     .try_with_type_url(&base.join("out-of-credit").unwrap())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(details.type_url(), base.join("out-of-credit").ok());
+    /// ```
+    ///
+    /// Requires feature `url`.
+    #[cfg(feature = "url")]
+    pub fn try_with_type_url(self, r#type: &url::Url) -> Result<Self, UriError> {
+        Ok(self.with_type(ProblemType::try_from(r#type)?))
+    }
+
+    /// Builder-style method that sets the `instance` field of this problem details object
+    /// from a [`Url`](url::Url).
+    ///
+    /// Fragments are dropped, as they cannot be represented by [`Uri`]. Returns an
+    /// [`UriError`] for URLs which [`Uri`] cannot represent at all.
+    ///
+    /// Requires feature `url`.
+    #[cfg(feature = "url")]
+    pub fn try_with_instance_url(self, instance: &url::Url) -> Result<Self, UriError> {
+        self.try_with_instance(instance.as_str())
+    }
+
+    /// Returns the `type` field as [`Url`](url::Url), or `None` if it is not set or
+    /// is a relative URI.
+    ///
+    /// Requires feature `url`.
+    #[cfg(feature = "url")]
+    pub fn type_url(&self) -> Option<url::Url> {
+        self.r#type.as_ref()?.to_url()
+    }
+
+    /// Returns the `instance` field as [`Url`](url::Url), or `None` if it is not set or
+    /// is a relative URI. Use [`resolve_instance`](ProblemDetails::resolve_instance) for
+    /// relative instances.
+    ///
+    /// Requires feature `url`.
+    #[cfg(feature = "url")]
+    pub fn instance_url(&self) -> Option<url::Url> {
+        url::Url::parse(&self.instance.as_ref()?.to_string()).ok()
+    }
+
+    /// Resolves the `instance` field against the given base URL, e.g. the URL of the request.
+    ///
+    /// ```rust
+    /// use problem_details::ProblemDetails;
+    /// use url::Url;
+    ///
+    /// let details = ProblemDetails::new().try_with_instance("/orders/42").unwrap();
+    /// let base = Url::parse("https://example.com/api/orders").unwrap();
+    ///
+    /// assert_eq!(
+    ///     details.resolve_instance(&base).unwrap().as_str(),
+    ///     "https://example.com/orders/42"
+    /// );
+    /// ```
+    ///
+    /// Requires feature `url`.
+    #[cfg(feature = "url")]
+    pub fn resolve_instance(&self, base: &url::Url) -> Option<url::Url> {
+        base.join(&self.instance.as_ref()?.to_string()).ok()
+    }
+
+    /// Sets the `type` field of this problem details object.
+    pub fn set_type(&mut self, r#type: impl Into<ProblemType>) {
+        self.r#type = Some(r#type.into());
+    }
+
+    /// Sets the `status` field of this problem details object.
+    pub fn set_status(&mut self, status: impl Into<StatusCode>) {
+        self.status = Some(status.into());
+    }
+
+    /// Sets the `title` field of this problem details object.
+    pub fn set_title(&mut self, title: impl Into<Cow<'static, str>>) {
+        self.title = Some(title.into());
+    }
+
+    /// Sets the `detail` field of this problem details object.
+    pub fn set_detail(&mut self, detail: impl Into<Cow<'static, str>>) {
+        self.detail = Some(detail.into());
+    }
+
+    /// Sets the `instance` field of this problem details object.
+    pub fn set_instance(&mut self, instance: impl Into<Uri>) {
+        self.instance = Some(instance.into());
+    }
+
+    /// Clears the `type` field of this problem details object.
+    pub fn clear_type(&mut self) {
+        self.r#type = None;
+    }
+
+    /// Clears the `status` field of this problem details object.
+    pub fn clear_status(&mut self) {
+        self.status = None;
+    }
+
+    /// Clears the `title` field of this problem details object.
+    pub fn clear_title(&mut self) {
+        self.title = None;
+    }
+
+    /// Clears the `detail` field of this problem details object.
+    pub fn clear_detail(&mut self) {
+        self.detail = None;
+    }
+
+    /// Clears the `instance` field of this problem details object.
+    pub fn clear_instance(&mut self) {
+        self.instance = None;
+    }
+
     /// Builder style method that sets the `extensions` field of this probelm details object.
     #[must_use]
     pub fn with_extensions<NewExt>(self, extensions: NewExt) -> ProblemDetails<NewExt> {
+        self.map_extensions(|_| extensions)
+    }
+
+    /// Transforms the `extensions` field of this problem details object using the given function.
+    ///
+    /// All other fields are preserved.
+    ///
+    /// ```rust
+    /// use problem_details::ProblemDetails;
+    ///
+    /// struct DomainExt {
+    ///     balance: u32,
+    /// }
+    ///
+    /// struct TracedExt {
+    ///     domain: DomainExt,
+    ///     trace_id: String,
+    /// }
+    ///
+    /// let details = ProblemDetails::new()
+    ///     .with_title("You do not have enough credit.")
+    ///     .with_extensions(DomainExt { balance: 30 })
+    ///     .map_extensions(|domain| TracedExt {
+    ///         domain,
+    ///         trace_id: "abc123".to_string(),
+    ///     });
+    ///
+    /// assert_eq!(details.title.as_deref(), Some("You do not have enough credit."));
+    /// assert_eq!(details.extensions.domain.balance, 30);
+    /// ```
+    #[must_use]
+    pub fn map_extensions<NewExt>(self, f: impl FnOnce(Ext) -> NewExt) -> ProblemDetails<NewExt> {
         ProblemDetails::<NewExt> {
             r#type: self.r#type,
             status: self.status,
             title: self.title,
             detail: self.detail,
             instance: self.instance,
-            extensions,
+            extensions: f(self.extensions),
+            source: self.source,
+            #[cfg(feature = "backtrace")]
+            backtrace: self.backtrace,
+            headers: self.headers,
         }
     }
+
+    /// Builder-style method that sets the underlying cause of this problem.
+    ///
+    /// The source is not serialized, but is exposed via [`Error::source`](std::error::Error::source)
+    /// and included in the alternate [`Display`](std::fmt::Display) format (`{:#}`).
+    ///
+    /// ```rust
+    /// use std::error::Error;
+    /// use http::StatusCode;
+    /// use problem_details::ProblemDetails;
+    ///
+    /// let io_error = std::io::Error::other("disk full");
+    /// let details = ProblemDetails::from_status_code(StatusCode::INTERNAL_SERVER_ERROR)
+    ///     .with_source(io_error);
+    ///
+    /// assert_eq!(details.source().unwrap().to_string(), "disk full");
+    /// assert_eq!(
+    ///     format!("{details:#}"),
+    ///     "[about:blank 500] Internal Server Error: disk full"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn with_source(mut self, source: impl std::error::Error + Send + Sync + 'static) -> Self {
+        self.source = Some(Arc::new(source));
+        self
+    }
+
+    /// Builder-style method that adds a header to the response for this problem.
+    ///
+    /// Headers are not part of the problem details object itself, but are emitted
+    /// by the framework integrations when the problem is converted into a response.
+    /// Use this for headers like `WWW-Authenticate`, `Allow` or `Retry-After`.
+    /// An existing header with the same name is replaced.
+    ///
+    /// ```rust
+    /// use http::{header, HeaderValue, StatusCode};
+    /// use problem_details::ProblemDetails;
+    ///
+    /// let details = ProblemDetails::from_status_code(StatusCode::UNAUTHORIZED)
+    ///     .with_header(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+    ///
+    /// assert_eq!(details.headers()[header::WWW_AUTHENTICATE], "Bearer");
+    /// ```
+    #[must_use]
+    pub fn with_header(mut self, name: impl IntoHeaderName, value: impl Into<HeaderValue>) -> Self {
+        self.headers_mut().insert(name, value.into());
+        self
+    }
+
+    /// Returns the headers to send with the response for this problem.
+    pub fn headers(&self) -> &HeaderMap {
+        static EMPTY: LazyLock<HeaderMap> = LazyLock::new(HeaderMap::new);
+        self.headers.as_ref().unwrap_or(&EMPTY)
+    }
+
+    /// Returns a mutable reference to the headers to send with the response for this problem.
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        self.headers.get_or_insert_with(HeaderMap::new)
+    }
+
+    /// Builder-style method that captures a backtrace at the current location.
+    ///
+    /// The backtrace is captured using [`Backtrace::capture`](std::backtrace::Backtrace::capture),
+    /// so it is only resolved if backtraces are enabled via the `RUST_BACKTRACE`
+    /// or `RUST_LIB_BACKTRACE` environment variables.
+    ///
+    /// Requires feature `backtrace`.
+    #[cfg(feature = "backtrace")]
+    #[must_use]
+    pub fn with_backtrace(mut self) -> Self {
+        self.backtrace = Some(Arc::new(std::backtrace::Backtrace::capture()));
+        self
+    }
+
+    /// Returns the backtrace captured for this problem, if any.
+    ///
+    /// The backtrace is never serialized.
+    ///
+    /// Requires feature `backtrace`.
+    #[cfg(feature = "backtrace")]
+    pub fn backtrace(&self) -> Option<&std::backtrace::Backtrace> {
+        self.backtrace.as_deref()
+    }
 }
 
-impl<Ext> std::fmt::Display for ProblemDetails<Ext> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let default_type = ProblemType::default();
-        let r#type = self.r#type.as_ref().unwrap_or(&default_type);
-        write!(f, "[{type}")?;
+impl<Ext> ProblemDetails<Ext> {
+    fn fmt_details(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        extensions: &[(String, String)],
+    ) -> std::fmt::Result {
+        write!(f, "[{}", self.effective_type())?;
 
         if let Some(status) = self.status {
             write!(f, " {}]", status.as_u16())?;
@@ -256,10 +747,7 @@ impl<Ext> std::fmt::Display for ProblemDetails<Ext> {
             write!(f, "]")?;
         }
 
-        let title = self
-            .title
-            .as_deref()
-            .or(self.status.as_ref().and_then(StatusCode::canonical_reason));
+        let title = self.effective_title();
 
         if let Some(title) = title {
             write!(f, " {title}")?;
@@ -273,9 +761,134 @@ impl<Ext> std::fmt::Display for ProblemDetails<Ext> {
             write!(f, " {detail}")?;
         }
 
-        // if let Some()
+        if f.alternate() {
+            let instance = self
+                .instance
+                .as_ref()
+                .map(|instance| ("instance".to_string(), instance.to_string()));
+            let mut members = instance.iter().chain(extensions).peekable();
+
+            if members.peek().is_some() {
+                write!(f, " (")?;
+                for (i, (key, value)) in members.enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{key}: {value}")?;
+                }
+                write!(f, ")")?;
+            }
+
+            let mut source = self
+                .source
+                .as_deref()
+                .map(|source| source as &(dyn std::error::Error + 'static));
+            while let Some(err) = source {
+                write!(f, ": {err}")?;
+                source = err.source();
+            }
+        }
+
         Ok(())
     }
 }
 
-impl<Ext> std::error::Error for ProblemDetails<Ext> where Ext: std::fmt::Debug {}
+#[cfg(feature = "json")]
+impl<Ext> ProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    /// Returns a value that displays this problem details object including its extensions.
+    ///
+    /// The compact format (`{}`) is the same as for [`ProblemDetails`] itself.
+    /// The alternate format (`{:#}`) additionally renders the `instance` and all
+    /// extension members as JSON values, as well as the [`source`](std::error::Error::source) chain.
+    ///
+    /// ```rust
+    /// use http::{StatusCode, Uri};
+    /// use problem_details::ProblemDetails;
+    ///
+    /// let details = ProblemDetails::from_status_code(StatusCode::FORBIDDEN)
+    ///     .with_detail("Your current balance is 30, but that costs 50.")
+    ///     .with_instance(Uri::from_static("/account/12345/msgs/abc"))
+    ///     .with_extension("balance", 30);
+    ///
+    /// assert_eq!(
+    ///     format!("{:#}", details.display_extensions()),
+    ///     "[about:blank 403] Forbidden: Your current balance is 30, but that costs 50. \
+    ///      (instance: /account/12345/msgs/abc, balance: 30)"
+    /// );
+    /// ```
+    pub fn display_extensions(&self) -> impl std::fmt::Display + '_ {
+        struct DisplayExtensions<'a, Ext>(&'a ProblemDetails<Ext>);
+
+        impl<Ext> std::fmt::Display for DisplayExtensions<'_, Ext>
+        where
+            Ext: serde::Serialize,
+        {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                if !f.alternate() {
+                    return self.0.fmt_details(f, &[]);
+                }
+
+                let extensions = match serde_json::to_value(&self.0.extensions) {
+                    Ok(serde_json::Value::Object(map)) => map
+                        .into_iter()
+                        .map(|(key, value)| (key, value.to_string()))
+                        .collect(),
+                    _ => Vec::new(),
+                };
+
+                self.0.fmt_details(f, &extensions)
+            }
+        }
+
+        DisplayExtensions(self)
+    }
+}
+
+/// The compact format (`{}`) renders the type, status, title and detail of the problem.
+///
+/// The alternate format (`{:#}`) additionally renders the `instance` and the
+/// [`source`](std::error::Error::source) chain.
+///
+/// Extension members are not rendered by this implementation, not even in the alternate
+/// format: `Display` (and thus [`Error`](std::error::Error)) is implemented for all
+/// extension types, while rendering the members requires them to be serializable.
+/// Use [`display_extensions`](ProblemDetails::display_extensions) (requires feature `json`)
+/// to render them as well.
+impl<Ext> std::fmt::Display for ProblemDetails<Ext> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_details(f, &[])
+    }
+}
+
+impl<Ext> std::error::Error for ProblemDetails<Ext>
+where
+    Ext: std::fmt::Debug,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn std::error::Error + 'static))
+    }
+}
+
+/// Problem details are compared by their fields and extensions.
+/// The [`source`](std::error::Error::source) and [`headers`](ProblemDetails::headers)
+/// are not taken into account.
+impl<Ext> PartialEq for ProblemDetails<Ext>
+where
+    Ext: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.r#type == other.r#type
+            && self.status == other.status
+            && self.title == other.title
+            && self.detail == other.detail
+            && self.instance == other.instance
+            && self.extensions == other.extensions
+    }
+}
+
+impl<Ext> Eq for ProblemDetails<Ext> where Ext: Eq {}
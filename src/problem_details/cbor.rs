@@ -0,0 +1,204 @@
+use ciborium::Value;
+
+use crate::ProblemDetails;
+
+/// The standard keys defined in [RFC 9290](https://www.rfc-editor.org/rfc/rfc9290.html#section-2).
+const TITLE: i64 = -1;
+const DETAIL: i64 = -2;
+const INSTANCE: i64 = -3;
+const RESPONSE_CODE: i64 = -4;
+
+/// The custom problem detail key for members without a standard key, as
+/// defined in [RFC 9290, Section 3.1](https://www.rfc-editor.org/rfc/rfc9290.html#section-3.1).
+const RFC7807: i64 = 7807;
+
+/// The members which have an integer key in concise problem details.
+const STANDARD_MEMBERS: [(&str, i64); 4] = [
+    ("title", TITLE),
+    ("detail", DETAIL),
+    ("instance", INSTANCE),
+    ("status", RESPONSE_CODE),
+];
+
+/// ProblemDetails that is encoded to CBOR as
+/// [Concise Problem Details](https://www.rfc-editor.org/rfc/rfc9290.html).
+///
+/// The `title`, `detail`, `instance` and `status` members are written with the
+/// integer keys defined in RFC 9290. All other members, including `type` and
+/// extension members, are written to the custom problem detail entry `7807`,
+/// which RFC 9290 defines for interworking with RFC 7807 problem details.
+///
+/// When reading concise problem details, custom problem detail entries with a
+/// text key (URI) are provided as extension members. Entries with other keys,
+/// such as `base-uri` or `base-lang`, are ignored.
+///
+/// # Example
+///
+/// ```rust
+/// use http::StatusCode;
+/// use problem_details::{CborProblemDetails, ProblemDetails};
+///
+/// let details = ProblemDetails::from_status_code(StatusCode::NOT_FOUND)
+///     .with_extension("sensor", "temperature");
+///
+/// let body = CborProblemDetails::from(details.clone()).to_body_vec().unwrap();
+/// let parsed = CborProblemDetails::from_body_slice(&body).unwrap();
+///
+/// assert_eq!(ProblemDetails::from(parsed), details);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CborProblemDetails<Ext = ()>(pub(crate) ProblemDetails<Ext>);
+
+impl<Ext> CborProblemDetails<Ext> {
+    /// The HTTP content type for a concise problem details.
+    pub const CONTENT_TYPE: &'static str = "application/concise-problem-details+cbor";
+}
+
+impl<Ext> CborProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    /// Write this problem details to a CBOR buffer suitable for a response body.
+    ///
+    /// With feature `json`, extension members are filtered according to the
+    /// configured `extension_filter`.
+    pub fn to_body_vec(&self) -> Result<Vec<u8>, CborError> {
+        #[cfg(feature = "json")]
+        let value = match crate::extension_filter() {
+            Some(filter) => Value::serialized(&self.0.redacted(filter)),
+            None => Value::serialized(&self.0),
+        };
+
+        #[cfg(not(feature = "json"))]
+        let value = Value::serialized(&self.0);
+
+        let value = to_concise(value.map_err(CborError::Value)?)?;
+
+        let mut body = Vec::new();
+        ciborium::into_writer(&value, &mut body).map_err(CborError::Serialization)?;
+
+        Ok(body)
+    }
+}
+
+impl<Ext> CborProblemDetails<Ext>
+where
+    Ext: serde::de::DeserializeOwned,
+{
+    /// Read a problem details from a CBOR encoded concise problem details body.
+    pub fn from_body_slice(cbor: &[u8]) -> Result<Self, CborError> {
+        let value: Value = ciborium::from_reader(cbor).map_err(CborError::Deserialization)?;
+
+        from_concise(value)?
+            .deserialized()
+            .map(Self)
+            .map_err(CborError::Value)
+    }
+}
+
+/// Converts a problem details serialized with member names to concise problem details.
+fn to_concise(value: Value) -> Result<Value, CborError> {
+    let members = into_map(value)?;
+
+    let mut standard = Vec::new();
+    let mut custom = Vec::new();
+    for (name, value) in members {
+        let key = name
+            .as_text()
+            .and_then(|name| STANDARD_MEMBERS.iter().find(|(member, _)| *member == name));
+
+        match key {
+            Some((_, key)) => standard.push((Value::from(*key), value)),
+            None => custom.push((name, value)),
+        }
+    }
+
+    if !custom.is_empty() {
+        standard.push((Value::from(RFC7807), Value::Map(custom)));
+    }
+
+    Ok(Value::Map(standard))
+}
+
+/// Converts concise problem details to a map with member names.
+fn from_concise(value: Value) -> Result<Value, CborError> {
+    let entries = into_map(value)?;
+
+    let mut members = Vec::new();
+    for (key, value) in entries {
+        match key {
+            Value::Integer(key) => match i64::try_from(key) {
+                Ok(RFC7807) => members.extend(into_map(value)?),
+                Ok(key) => {
+                    if let Some((name, _)) = STANDARD_MEMBERS.iter().find(|(_, k)| *k == key) {
+                        members.push((Value::from(*name), value));
+                    }
+                }
+                Err(_) => {}
+            },
+            Value::Text(_) => members.push((key, value)),
+            _ => {}
+        }
+    }
+
+    Ok(Value::Map(members))
+}
+
+fn into_map(value: Value) -> Result<Vec<(Value, Value)>, CborError> {
+    value.into_map().map_err(|_| {
+        CborError::Value(ciborium::value::Error::Custom(
+            "expected problem details map".to_string(),
+        ))
+    })
+}
+
+impl<Ext> From<ProblemDetails<Ext>> for CborProblemDetails<Ext> {
+    fn from(value: ProblemDetails<Ext>) -> Self {
+        Self(value)
+    }
+}
+
+impl<Ext> From<CborProblemDetails<Ext>> for ProblemDetails<Ext> {
+    fn from(value: CborProblemDetails<Ext>) -> Self {
+        value.0
+    }
+}
+
+impl<Ext> std::fmt::Display for CborProblemDetails<Ext> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<Ext> std::error::Error for CborProblemDetails<Ext> where Ext: std::fmt::Debug {}
+
+/// Error when reading or writing [`CborProblemDetails`].
+#[derive(Debug)]
+pub enum CborError {
+    /// The problem details could not be encoded.
+    Serialization(ciborium::ser::Error<std::io::Error>),
+    /// The body is not valid CBOR.
+    Deserialization(ciborium::de::Error<std::io::Error>),
+    /// The problem details could not be converted from or to concise problem details.
+    Value(ciborium::value::Error),
+}
+
+impl std::fmt::Display for CborError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Serialization(err) => write!(f, "Could not write body: {err}"),
+            Self::Deserialization(err) => write!(f, "Could not read body: {err}"),
+            Self::Value(err) => write!(f, "Invalid problem details: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for CborError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Serialization(err) => Some(err),
+            Self::Deserialization(err) => Some(err),
+            Self::Value(err) => Some(err),
+        }
+    }
+}
@@ -0,0 +1,288 @@
+use std::fmt::Write;
+use std::sync::OnceLock;
+
+use http::StatusCode;
+use serde_json::Value;
+
+use crate::ProblemDetails;
+
+static HTML_TEMPLATE: OnceLock<Box<dyn HtmlTemplate>> = OnceLock::new();
+
+/// ProblemDetails that is rendered as HTML page when
+/// used with web framework integrations.
+///
+/// The page is rendered using the template configured with
+/// [`set_html_template`], or [`DefaultHtmlTemplate`] if none was set.
+///
+/// # Example
+///
+/// ```rust
+/// use http::StatusCode;
+/// use problem_details::{HtmlProblemDetails, ProblemDetails};
+///
+/// async fn handler() -> HtmlProblemDetails {
+///     ProblemDetails::from_status_code(StatusCode::IM_A_TEAPOT)
+///         .with_detail("short and stout")
+///         .into()
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HtmlProblemDetails<Ext = ()>(pub(crate) ProblemDetails<Ext>);
+
+impl<Ext> HtmlProblemDetails<Ext> {
+    /// The HTTP content type for a html problem details page.
+    pub const CONTENT_TYPE: &'static str = "text/html; charset=utf-8";
+}
+
+impl<Ext> HtmlProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    /// Render this problem details to an HTML page suitable for a response body.
+    ///
+    /// Uses the template configured with [`set_html_template`]. Extension members
+    /// are filtered according to the configured [`extension_filter`](crate::extension_filter).
+    pub fn to_body_string(&self) -> Result<String, HtmlError> {
+        match HTML_TEMPLATE.get() {
+            Some(template) => self.to_body_string_with(template.as_ref()),
+            None => self.to_body_string_with(&DefaultHtmlTemplate::default()),
+        }
+    }
+
+    /// Render this problem details to an HTML page using the given template.
+    ///
+    /// ```rust
+    /// use http::StatusCode;
+    /// use problem_details::{escape_html, HtmlPage, HtmlProblemDetails, ProblemDetails};
+    ///
+    /// let template = |page: &HtmlPage| format!("<h1>{}</h1>", escape_html(&page.title));
+    ///
+    /// let html = HtmlProblemDetails::from(ProblemDetails::from_status_code(StatusCode::NOT_FOUND))
+    ///     .to_body_string_with(&template)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(html, "<h1>Not Found</h1>");
+    /// ```
+    pub fn to_body_string_with(&self, template: &dyn HtmlTemplate) -> Result<String, HtmlError> {
+        let value = match crate::extension_filter() {
+            Some(filter) => serde_json::to_value(self.0.redacted(filter)),
+            None => serde_json::to_value(&self.0),
+        }
+        .map_err(HtmlError::Serialization)?;
+
+        let page = HtmlPage::from_value(value, self.0.status_or_default());
+
+        Ok(template.render(&page))
+    }
+}
+
+impl<Ext> From<ProblemDetails<Ext>> for HtmlProblemDetails<Ext> {
+    fn from(value: ProblemDetails<Ext>) -> Self {
+        Self(value)
+    }
+}
+
+impl<Ext> From<HtmlProblemDetails<Ext>> for ProblemDetails<Ext> {
+    fn from(value: HtmlProblemDetails<Ext>) -> Self {
+        value.0
+    }
+}
+
+impl<Ext> std::fmt::Display for HtmlProblemDetails<Ext> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<Ext> std::error::Error for HtmlProblemDetails<Ext> where Ext: std::fmt::Debug {}
+
+/// The contents of a problem details page passed to a [`HtmlTemplate`].
+///
+/// All values are unescaped, templates must escape them, e.g. using [`escape_html`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HtmlPage {
+    /// The status code of the response.
+    pub status: StatusCode,
+    /// The problem type URI, if set.
+    pub r#type: Option<String>,
+    /// The title, or the canonical reason of the status code if not set.
+    pub title: String,
+    /// The detail, if set.
+    pub detail: Option<String>,
+    /// The instance URI, if set.
+    pub instance: Option<String>,
+    /// The extension members, with string values as is and other values as JSON.
+    pub extensions: Vec<(String, String)>,
+}
+
+impl HtmlPage {
+    fn from_value(value: Value, status: StatusCode) -> Self {
+        let Value::Object(members) = value else {
+            unreachable!("problem details are always serialized as object");
+        };
+
+        let mut page = Self {
+            status,
+            r#type: None,
+            title: String::new(),
+            detail: None,
+            instance: None,
+            extensions: Vec::new(),
+        };
+
+        let mut title = None;
+        for (key, value) in members {
+            match (key.as_str(), value) {
+                ("type", Value::String(value)) => page.r#type = Some(value),
+                ("title", Value::String(value)) => title = Some(value),
+                ("detail", Value::String(value)) => page.detail = Some(value),
+                ("instance", Value::String(value)) => page.instance = Some(value),
+                ("type" | "status" | "title" | "detail" | "instance", _) => {}
+                (_, Value::String(value)) => page.extensions.push((key, value)),
+                (_, value) => page.extensions.push((key, value.to_string())),
+            }
+        }
+
+        page.title = title
+            .or_else(|| status.canonical_reason().map(str::to_string))
+            .unwrap_or_else(|| "Error".to_string());
+
+        page
+    }
+}
+
+/// A template rendering an [`HtmlPage`] for [`HtmlProblemDetails`].
+///
+/// Implemented for closures taking an `&HtmlPage` and returning the page as `String`.
+pub trait HtmlTemplate: Send + Sync {
+    /// Renders the page to an HTML document.
+    fn render(&self, page: &HtmlPage) -> String;
+}
+
+impl<F> HtmlTemplate for F
+where
+    F: Fn(&HtmlPage) -> String + Send + Sync,
+{
+    fn render(&self, page: &HtmlPage) -> String {
+        self(page)
+    }
+}
+
+/// The built-in minimal problem details page.
+///
+/// Shows the status, title, detail, a link to the problem type, the instance
+/// and, unless disabled, a table of the extension members.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DefaultHtmlTemplate {
+    extension_table: bool,
+}
+
+impl DefaultHtmlTemplate {
+    /// Builder-style method that sets whether extension members are shown in a table.
+    #[must_use]
+    pub fn with_extension_table(mut self, extension_table: bool) -> Self {
+        self.extension_table = extension_table;
+        self
+    }
+}
+
+impl Default for DefaultHtmlTemplate {
+    fn default() -> Self {
+        Self {
+            extension_table: true,
+        }
+    }
+}
+
+impl HtmlTemplate for DefaultHtmlTemplate {
+    fn render(&self, page: &HtmlPage) -> String {
+        let heading = format!("{} {}", page.status.as_u16(), escape_html(&page.title));
+
+        let mut html = String::new();
+        html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+        let _ = writeln!(html, "<title>{heading}</title>");
+        html.push_str(
+            "<style>body{font-family:system-ui,sans-serif;max-width:40rem;margin:4rem auto;\
+             padding:0 1rem;color:#222}th,td{text-align:left;vertical-align:top;\
+             padding:.25rem 1rem .25rem 0}small{color:#666}</style>\n",
+        );
+        html.push_str("</head>\n<body>\n");
+        let _ = writeln!(html, "<h1>{heading}</h1>");
+
+        if let Some(detail) = &page.detail {
+            let _ = writeln!(html, "<p>{}</p>", escape_html(detail));
+        }
+        if let Some(r#type) = page.r#type.as_deref().filter(|t| *t != "about:blank") {
+            let r#type = escape_html(r#type);
+            let _ = writeln!(html, "<p><a href=\"{type}\">{type}</a></p>");
+        }
+        if let Some(instance) = &page.instance {
+            let _ = writeln!(html, "<p><small>{}</small></p>", escape_html(instance));
+        }
+        if self.extension_table && !page.extensions.is_empty() {
+            html.push_str("<table>\n");
+            for (key, value) in &page.extensions {
+                let _ = writeln!(
+                    html,
+                    "<tr><th>{}</th><td>{}</td></tr>",
+                    escape_html(key),
+                    escape_html(value)
+                );
+            }
+            html.push_str("</table>\n");
+        }
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+/// Configures the process-wide template used by [`HtmlProblemDetails`].
+///
+/// The template can only be set once. If it was already set, the given
+/// template is returned as error.
+pub fn set_html_template(
+    template: impl HtmlTemplate + 'static,
+) -> Result<(), Box<dyn HtmlTemplate>> {
+    HTML_TEMPLATE.set(Box::new(template))
+}
+
+/// Escapes the characters with special meaning in HTML text and attribute values.
+pub fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Error when rendering [`HtmlProblemDetails`].
+#[derive(Debug)]
+pub enum HtmlError {
+    /// The problem details could not be serialized.
+    Serialization(serde_json::Error),
+}
+
+impl std::fmt::Display for HtmlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Serialization(err) => write!(f, "Could not write body: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for HtmlError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Serialization(err) => Some(err),
+        }
+    }
+}
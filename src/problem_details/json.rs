@@ -1,4 +1,6 @@
-use crate::ProblemDetails;
+use bytes::{BufMut, Bytes, BytesMut};
+
+use crate::{JsonFormat, ProblemDetails};
 
 /// ProblemDetails that is encoded to JSON when
 /// used with web framework integrations.
@@ -28,8 +30,133 @@ where
     Ext: serde::Serialize,
 {
     /// Write this problem details to an JSON string suitable for a response body.
+    ///
+    /// Extension members are filtered according to the configured [`extension_filter`](crate::extension_filter),
+    /// and the configured [`compatibility_mode`](crate::compatibility_mode) and
+    /// [`json_format`](crate::json_format) are applied.
     pub fn to_body_string(&self) -> Result<String, JsonError> {
-        serde_json::to_string(&self.0).map_err(JsonError::Serialization)
+        if crate::json_format() == JsonFormat::Pretty {
+            return self.to_body_string_pretty();
+        }
+
+        match self.0.for_response() {
+            Some(details) => serde_json::to_string(&details),
+            None => serde_json::to_string(&self.0),
+        }
+        .map_err(JsonError::Serialization)
+    }
+
+    /// Write this problem details to an indented JSON string, regardless of the configured
+    /// [`json_format`](crate::json_format).
+    ///
+    /// The members are written in the same order as by
+    /// [`to_body_string`](JsonProblemDetails::to_body_string), see
+    /// [Member Order](crate::ProblemDetails#member-order).
+    ///
+    /// ```rust
+    /// use http::StatusCode;
+    /// use problem_details::{JsonProblemDetails, ProblemDetails};
+    ///
+    /// let details = ProblemDetails::from_status_code(StatusCode::NOT_FOUND)
+    ///     .with_extension("resource", "order")
+    ///     .with_extension("id", 42);
+    ///
+    /// assert_eq!(
+    ///     JsonProblemDetails::from(details).to_body_string_pretty().unwrap(),
+    ///     r#"{
+    ///   "status": 404,
+    ///   "title": "Not Found",
+    ///   "resource": "order",
+    ///   "id": 42
+    /// }"#
+    /// );
+    /// ```
+    pub fn to_body_string_pretty(&self) -> Result<String, JsonError> {
+        match self.0.for_response() {
+            Some(details) => serde_json::to_string_pretty(&details),
+            None => serde_json::to_string_pretty(&self.0),
+        }
+        .map_err(JsonError::Serialization)
+    }
+
+    /// Write this problem details to a JSON buffer suitable for a response body.
+    ///
+    /// Unlike [`to_body_string`](JsonProblemDetails::to_body_string), the JSON is
+    /// written directly into the buffer used by web frameworks, which avoids
+    /// copying the body.
+    ///
+    /// ```rust
+    /// use http::StatusCode;
+    /// use problem_details::{JsonProblemDetails, ProblemDetails};
+    ///
+    /// let details = JsonProblemDetails::from(ProblemDetails::from_status_code(StatusCode::NOT_FOUND));
+    ///
+    /// assert_eq!(
+    ///     details.to_body_bytes().unwrap(),
+    ///     r#"{"status":404,"title":"Not Found"}"#
+    /// );
+    /// ```
+    pub fn to_body_bytes(&self) -> Result<Bytes, JsonError> {
+        if crate::json_format() == JsonFormat::Pretty {
+            return Ok(self.to_body_string_pretty()?.into());
+        }
+
+        let mut writer = BytesMut::new().writer();
+        match self.0.for_response() {
+            Some(details) => serde_json::to_writer(&mut writer, &details),
+            None => serde_json::to_writer(&mut writer, &self.0),
+        }
+        .map_err(JsonError::Serialization)?;
+
+        Ok(writer.into_inner().freeze())
+    }
+
+    /// Write this problem details as JSON to the given writer.
+    ///
+    /// The JSON is streamed to the writer, so large problems, e.g. with many
+    /// validation errors, are never held in memory as a whole. Consider wrapping
+    /// unbuffered writers like sockets in a [`BufWriter`](std::io::BufWriter).
+    ///
+    /// ```rust
+    /// use http::StatusCode;
+    /// use problem_details::{JsonProblemDetails, ProblemDetails};
+    ///
+    /// let details = JsonProblemDetails::from(ProblemDetails::from_status_code(StatusCode::NOT_FOUND));
+    ///
+    /// let mut body = Vec::new();
+    /// details.to_writer(&mut body).unwrap();
+    ///
+    /// assert_eq!(body, br#"{"status":404,"title":"Not Found"}"#);
+    /// ```
+    pub fn to_writer<W: std::io::Write>(&self, writer: W) -> Result<(), JsonError> {
+        let pretty = crate::json_format() == JsonFormat::Pretty;
+        match self.0.for_response() {
+            Some(details) if pretty => serde_json::to_writer_pretty(writer, &details),
+            Some(details) => serde_json::to_writer(writer, &details),
+            None if pretty => serde_json::to_writer_pretty(writer, &self.0),
+            None => serde_json::to_writer(writer, &self.0),
+        }
+        .map_err(JsonError::Serialization)
+    }
+
+    /// Write this problem details as JSON to the given asynchronous writer.
+    ///
+    /// Serde serializes synchronously, so the JSON is first written into a buffer
+    /// (see [`to_body_bytes`](JsonProblemDetails::to_body_bytes)), which is then
+    /// written to the writer without further copies.
+    ///
+    /// Requires feature `tokio`.
+    #[cfg(feature = "tokio")]
+    pub async fn to_writer_async<W>(&self, writer: &mut W) -> Result<(), JsonError>
+    where
+        W: tokio::io::AsyncWrite + Unpin + ?Sized,
+    {
+        use tokio::io::AsyncWriteExt;
+
+        let body = self.to_body_bytes()?;
+        let io = |err| JsonError::Serialization(serde_json::Error::io(err));
+        writer.write_all(&body).await.map_err(io)?;
+        writer.flush().await.map_err(io)
     }
 }
 
@@ -52,20 +179,82 @@ impl<Ext> std::fmt::Display for JsonProblemDetails<Ext> {
 
 impl<Ext> std::error::Error for JsonProblemDetails<Ext> where Ext: std::fmt::Debug {}
 
+impl<Ext> ProblemDetails<Ext>
+where
+    Ext: serde::de::DeserializeOwned,
+{
+    /// Parses problem details from a JSON string.
+    ///
+    /// Works with typed extensions as well as dynamic ones like
+    /// [`DynamicExtensions`](crate::DynamicExtensions) or [`JsonExtensions`](crate::JsonExtensions).
+    ///
+    /// ```rust
+    /// use http::StatusCode;
+    /// use problem_details::{JsonExtensions, ProblemDetails};
+    ///
+    /// let details = ProblemDetails::<JsonExtensions>::from_json(
+    ///     r#"{"status":404,"title":"Not Found","resource":"order"}"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(details.status, Some(StatusCode::NOT_FOUND));
+    /// assert_eq!(details.extensions["resource"], "order");
+    /// ```
+    pub fn from_json(json: &str) -> Result<Self, JsonError> {
+        serde_json::from_str(json).map_err(JsonError::Deserialization)
+    }
+
+    /// Parses problem details from a JSON buffer, e.g. a request or response body.
+    pub fn from_json_slice(json: &[u8]) -> Result<Self, JsonError> {
+        serde_json::from_slice(json).map_err(JsonError::Deserialization)
+    }
+
+    /// Converts a JSON value into problem details.
+    pub fn from_json_value(value: serde_json::Value) -> Result<Self, JsonError> {
+        serde_json::from_value(value).map_err(JsonError::Deserialization)
+    }
+}
+
+impl<Ext> ProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    /// Converts this problem details into a JSON value.
+    ///
+    /// Unlike [`JsonProblemDetails`], no response configuration like the
+    /// [`extension_filter`](crate::extension_filter) is applied.
+    ///
+    /// ```rust
+    /// use http::StatusCode;
+    /// use problem_details::ProblemDetails;
+    ///
+    /// let details = ProblemDetails::from_status_code(StatusCode::NOT_FOUND);
+    ///
+    /// assert_eq!(
+    ///     details.to_json_value().unwrap(),
+    ///     serde_json::json!({ "status": 404, "title": "Not Found" })
+    /// );
+    /// ```
+    pub fn to_json_value(&self) -> Result<serde_json::Value, JsonError> {
+        serde_json::to_value(self).map_err(JsonError::Serialization)
+    }
+}
+
+/// Error when reading or writing problem details as JSON.
 #[derive(Debug)]
 pub enum JsonError {
+    /// The problem details could not be written.
     Serialization(serde_json::Error),
+    /// The JSON is not a valid problem details.
+    Deserialization(serde_json::Error),
 }
 
 impl std::fmt::Display for JsonError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "Could not write body: {}",
-            match self {
-                Self::Serialization(err) => err,
-            }
-        )
+        match self {
+            Self::Serialization(err) => write!(f, "Could not write body: {err}"),
+            Self::Deserialization(err) => write!(f, "Could not read body: {err}"),
+        }
     }
 }
 
@@ -73,6 +262,7 @@ impl std::error::Error for JsonError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Self::Serialization(err) => Some(err),
+            Self::Deserialization(err) => Some(err),
         }
     }
 }
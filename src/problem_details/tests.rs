@@ -1,7 +1,12 @@
+use std::borrow::Cow;
+#[cfg(any(feature = "json", feature = "xml", feature = "cbor"))]
+use std::collections::HashMap;
+
 use http::{StatusCode, Uri};
+#[cfg(feature = "serde")]
 use serde_json::json;
 
-use crate::{ProblemDetails, ProblemType};
+use crate::{ProblemDetails, ProblemType, UriError};
 
 #[test]
 #[allow(clippy::unit_cmp)]
@@ -13,12 +18,29 @@ fn from_status() {
         ProblemType::from(Uri::from_static("about:blank"))
     );
     assert_eq!(details.status, Some(StatusCode::NOT_FOUND));
-    assert_eq!(details.title, Some("Not Found".to_string()));
+    assert_eq!(details.title.as_deref(), Some("Not Found"));
+    assert!(matches!(details.title, Some(Cow::Borrowed(_))));
     assert_eq!(details.detail, None);
     assert_eq!(details.instance, None);
     assert_eq!(details.extensions, ());
 }
 
+#[test]
+fn from_status_conversions() {
+    let from_status: ProblemDetails = StatusCode::CONFLICT.into();
+    let from_u16 = ProblemDetails::try_from(409).unwrap();
+
+    assert_eq!(
+        ProblemDetails::from_status_code(StatusCode::CONFLICT),
+        from_status
+    );
+    assert_eq!(
+        ProblemDetails::from_status_code(StatusCode::CONFLICT),
+        from_u16
+    );
+    assert!(ProblemDetails::try_from(1000).is_err());
+}
+
 #[test]
 fn fully_configured() {
     #[derive(Debug, PartialEq, Eq)]
@@ -43,8 +65,8 @@ fn fully_configured() {
         Some(ProblemType::from(Uri::from_static("test:type")))
     );
     assert_eq!(details.status, Some(StatusCode::INTERNAL_SERVER_ERROR));
-    assert_eq!(details.title, Some("Test Title".to_string()));
-    assert_eq!(details.detail, Some("Test Detail".to_string()));
+    assert_eq!(details.title.as_deref(), Some("Test Title"));
+    assert_eq!(details.detail.as_deref(), Some("Test Detail"));
     assert_eq!(details.instance, Some(Uri::from_static("test:instance")));
     assert_eq!(
         details.extensions,
@@ -55,6 +77,358 @@ fn fully_configured() {
     );
 }
 
+#[test]
+fn builder() {
+    let details = ProblemDetails::builder()
+        .with_title("Test Title")
+        .with_status(StatusCode::NOT_FOUND)
+        .with_detail("Test Detail")
+        .with_instance(Uri::from_static("test:instance"))
+        .with_extensions(42u32)
+        .with_type(Uri::from_static("test:type"))
+        .build();
+
+    let expected = ProblemDetails::new()
+        .with_type(Uri::from_static("test:type"))
+        .with_status(StatusCode::NOT_FOUND)
+        .with_title("Test Title")
+        .with_detail("Test Detail")
+        .with_instance(Uri::from_static("test:instance"))
+        .with_extensions(42u32);
+
+    assert_eq!(expected, details);
+}
+
+#[test]
+fn template() {
+    const TEMPLATE: crate::ProblemTemplate =
+        crate::ProblemTemplate::new("test:type", StatusCode::NOT_FOUND, "Test Title")
+            .with_documentation("test:documentation");
+
+    let base = ProblemDetails::new()
+        .with_type(Uri::from_static("test:type"))
+        .with_status(StatusCode::NOT_FOUND)
+        .with_title("Test Title");
+
+    assert_eq!(TEMPLATE.documentation(), Some("test:documentation"));
+    assert_eq!(TEMPLATE.instantiate(), base);
+    assert_eq!(ProblemDetails::from(TEMPLATE), base);
+    assert_eq!(
+        TEMPLATE
+            .with_detail("Test Detail")
+            .with_instance(Uri::from_static("test:instance")),
+        base.clone()
+            .with_detail("Test Detail")
+            .with_instance(Uri::from_static("test:instance"))
+    );
+    assert_eq!(
+        TEMPLATE.with_instance(Uri::from_static("test:instance")),
+        base.with_instance(Uri::from_static("test:instance"))
+    );
+}
+
+#[test]
+fn map_extensions() {
+    let details = ProblemDetails::new()
+        .with_type(Uri::from_static("test:type"))
+        .with_status(StatusCode::NOT_FOUND)
+        .with_title("Test Title")
+        .with_detail("Test Detail")
+        .with_instance(Uri::from_static("test:instance"))
+        .with_extensions(21u32)
+        .map_extensions(|value| value * 2);
+
+    let expected = ProblemDetails::new()
+        .with_type(Uri::from_static("test:type"))
+        .with_status(StatusCode::NOT_FOUND)
+        .with_title("Test Title")
+        .with_detail("Test Detail")
+        .with_instance(Uri::from_static("test:instance"))
+        .with_extensions(42u32);
+
+    assert_eq!(expected, details);
+}
+
+#[test]
+fn merge() {
+    let base = ProblemDetails::new()
+        .with_type(Uri::from_static("test:type"))
+        .with_status(StatusCode::NOT_FOUND)
+        .with_title("Base Title")
+        .with_detail("Base Detail");
+    let overlay = ProblemDetails::new()
+        .with_title("Overlay Title")
+        .with_instance(Uri::from_static("test:instance"));
+
+    let merged = base.merged_with(overlay);
+
+    let expected = ProblemDetails::new()
+        .with_type(Uri::from_static("test:type"))
+        .with_status(StatusCode::NOT_FOUND)
+        .with_title("Overlay Title")
+        .with_detail("Base Detail")
+        .with_instance(Uri::from_static("test:instance"));
+
+    assert_eq!(expected, merged);
+}
+
+#[test]
+fn set_and_clear() {
+    let mut details = ProblemDetails::new();
+
+    details.set_type(Uri::from_static("test:type"));
+    details.set_status(StatusCode::NOT_FOUND);
+    details.set_title("Test Title");
+    details.set_detail("Test Detail");
+    details.set_instance(Uri::from_static("test:instance"));
+
+    let expected = ProblemDetails::new()
+        .with_type(Uri::from_static("test:type"))
+        .with_status(StatusCode::NOT_FOUND)
+        .with_title("Test Title")
+        .with_detail("Test Detail")
+        .with_instance(Uri::from_static("test:instance"));
+
+    assert_eq!(expected, details);
+
+    details.clear_type();
+    details.clear_status();
+    details.clear_title();
+    details.clear_detail();
+    details.clear_instance();
+
+    assert_eq!(ProblemDetails::new(), details);
+}
+
+#[test]
+fn try_with_valid_uris() {
+    let details = ProblemDetails::new()
+        .try_with_type("test:type")
+        .unwrap()
+        .try_with_instance("/test/instance")
+        .unwrap();
+
+    assert_eq!(
+        details.r#type,
+        Some(ProblemType::from(Uri::from_static("test:type")))
+    );
+    assert_eq!(details.instance, Some(Uri::from_static("/test/instance")));
+
+    let owned = ProblemDetails::new()
+        .try_with_type(String::from("test:type"))
+        .unwrap()
+        .try_with_instance(Uri::from_static("/test/instance"))
+        .unwrap();
+
+    assert_eq!(owned, details);
+}
+
+#[test]
+fn try_with_invalid_uris() {
+    assert!(matches!(
+        ProblemDetails::new().try_with_type("not a uri"),
+        Err(UriError::Invalid(_))
+    ));
+    assert!(matches!(
+        ProblemDetails::new().try_with_instance("not a uri"),
+        Err(UriError::Invalid(_))
+    ));
+    assert!(ProblemType::try_from("not a uri").is_err());
+}
+
+#[test]
+fn with_source() {
+    use std::error::Error;
+
+    let inner = ProblemDetails::new().with_detail("Inner Detail");
+    let details = ProblemDetails::from_status_code(StatusCode::BAD_GATEWAY)
+        .with_detail("Outer Detail")
+        .with_source(inner);
+
+    assert_eq!(
+        details.source().unwrap().to_string(),
+        "[about:blank] Inner Detail"
+    );
+    assert_eq!(
+        "[about:blank 502] Bad Gateway: Outer Detail",
+        details.to_string()
+    );
+    assert_eq!(
+        "[about:blank 502] Bad Gateway: Outer Detail: [about:blank] Inner Detail",
+        format!("{details:#}")
+    );
+    assert_eq!(
+        ProblemDetails::from_status_code(StatusCode::BAD_GATEWAY).with_detail("Outer Detail"),
+        details
+    );
+}
+
+#[test]
+fn to_string_alternate() {
+    let details = ProblemDetails::new()
+        .with_status(StatusCode::NOT_FOUND)
+        .with_detail("Test Detail")
+        .with_instance(Uri::from_static("test:instance"));
+
+    assert_eq!(
+        "[about:blank 404] Not Found: Test Detail (instance: test:instance)",
+        format!("{details:#}")
+    );
+    assert_eq!(
+        "[about:blank 404] Not Found: Test Detail",
+        format!("{details}")
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn to_string_alternate_extensions() {
+    let details = ProblemDetails::new()
+        .with_title("Test Title")
+        .with_instance(Uri::from_static("test:instance"))
+        .with_extension("bar", 42)
+        .with_extension("foo", "Foo");
+
+    assert_eq!(
+        r#"[about:blank] Test Title (instance: test:instance, bar: 42, foo: "Foo")"#,
+        format!("{:#}", details.display_extensions())
+    );
+    assert_eq!(
+        "[about:blank] Test Title",
+        details.display_extensions().to_string()
+    );
+    assert_eq!(
+        "[about:blank] Test Title",
+        format!(
+            "{:#}",
+            ProblemDetails::new()
+                .with_title("Test Title")
+                .display_extensions()
+        )
+    );
+}
+
+#[cfg(feature = "backtrace")]
+#[test]
+fn backtrace() {
+    let server_error = ProblemDetails::from_status_code(StatusCode::INTERNAL_SERVER_ERROR);
+    let client_error = ProblemDetails::from_status_code(StatusCode::NOT_FOUND);
+
+    assert!(server_error.backtrace().is_some());
+    assert!(client_error.backtrace().is_none());
+    assert!(client_error.with_backtrace().backtrace().is_some());
+}
+
+#[test]
+fn classification() {
+    use crate::ProblemKind;
+
+    let client_error = ProblemDetails::from_status_code(StatusCode::TOO_MANY_REQUESTS);
+    let server_error = ProblemDetails::from_status_code(StatusCode::INTERNAL_SERVER_ERROR);
+    let unknown = ProblemDetails::new();
+
+    assert_eq!(client_error.kind(), ProblemKind::ClientError);
+    assert!(client_error.is_client_error());
+    assert!(!client_error.is_server_error());
+    assert!(client_error.is_retryable());
+
+    assert_eq!(server_error.kind(), ProblemKind::ServerError);
+    assert!(!server_error.is_client_error());
+    assert!(server_error.is_server_error());
+    assert!(!server_error.is_retryable());
+
+    assert_eq!(unknown.kind(), ProblemKind::Unknown);
+    assert!(!unknown.is_retryable());
+}
+
+#[test]
+fn localized() {
+    use crate::Localizer;
+
+    struct TestLocalizer;
+
+    impl Localizer for TestLocalizer {
+        fn title(&self, key: &str, locale: &str) -> Option<Cow<'static, str>> {
+            match (key, locale) {
+                ("404", "de") => Some("Nicht gefunden".into()),
+                ("test:type", "fr") => Some("Titre".into()),
+                _ => None,
+            }
+        }
+
+        fn detail(&self, key: &str, locale: &str) -> Option<Cow<'static, str>> {
+            match (key, locale) {
+                ("test:type", "fr") => Some("Détail".into()),
+                _ => None,
+            }
+        }
+    }
+
+    let not_found = ProblemDetails::from_status_code(StatusCode::NOT_FOUND);
+    assert_eq!(
+        not_found.localized(&TestLocalizer, "de").title.as_deref(),
+        Some("Nicht gefunden")
+    );
+    assert_eq!(not_found.localized(&TestLocalizer, "fr"), not_found);
+
+    let typed = ProblemDetails::from_status_code(StatusCode::NOT_FOUND)
+        .with_type(Uri::from_static("test:type"))
+        .with_detail("Detail");
+
+    let mut headers = http::HeaderMap::new();
+    headers.insert(
+        http::header::ACCEPT_LANGUAGE,
+        http::HeaderValue::from_static("de, fr;q=0.9"),
+    );
+
+    let localized = typed.localized_for(&TestLocalizer, &headers);
+    assert_eq!(localized.title.as_deref(), Some("Titre"));
+    assert_eq!(localized.detail.as_deref(), Some("Détail"));
+}
+
+#[test]
+fn matches_type() {
+    let details = ProblemDetails::new().with_type(Uri::from_static(
+        "https://example.com/probs/a/../out-of-credit",
+    ));
+
+    assert!(details.matches_type(&Uri::from_static("https://EXAMPLE.com/probs/out-of-credit")));
+    assert!(details.matches_type(&Uri::from_static(
+        "HTTPS://example.com:443/probs/%6Fut-of-credit/"
+    )));
+    assert!(!details.matches_type(&Uri::from_static("http://example.com/probs/out-of-credit")));
+    assert!(!details.matches_type(&Uri::from_static("https://example.com/probs/Out-of-credit")));
+    assert!(!details.matches_type(&Uri::from_static("about:blank")));
+
+    assert!(ProblemDetails::new().matches_type(&Uri::from_static("about:blank")));
+    assert!(ProblemType::from(Uri::from_static("/test/%7ea?q=%2f"))
+        .matches(&Uri::from_static("/test/~a?q=%2F")));
+}
+
+#[test]
+fn parse_strict() {
+    assert_eq!(
+        ProblemType::parse_strict("https://example.com/probs/test").unwrap(),
+        ProblemType::from(Uri::from_static("https://example.com/probs/test"))
+    );
+    assert_eq!(
+        ProblemType::parse_strict("about:blank").unwrap(),
+        ProblemType::default()
+    );
+    assert!(matches!(
+        ProblemType::parse_strict("/probs/test"),
+        Err(UriError::NotAbsolute)
+    ));
+    assert!(matches!(
+        ProblemType::parse_strict("ftp://example.com/probs/test"),
+        Err(UriError::UnsupportedScheme(scheme)) if scheme == "ftp"
+    ));
+    assert!(matches!(
+        ProblemType::parse_strict("not a uri"),
+        Err(UriError::Invalid(_))
+    ));
+}
+
 #[test]
 fn to_string() {
     let empty = ProblemDetails::new();
@@ -236,3 +610,3845 @@ fn deserialize_filled() {
 
     assert_eq!(expected, deserialized);
 }
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialize_status_string() {
+    let deserialized: ProblemDetails = serde_json::from_value(json!({ "status": "404" })).unwrap();
+    assert_eq!(deserialized.status, Some(StatusCode::NOT_FOUND));
+
+    let err = serde_json::from_value::<ProblemDetails>(json!({ "status": "Not Found" }))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("\"Not Found\""), "{err}");
+
+    assert!(serde_json::from_value::<ProblemDetails>(json!({ "status": "1000" })).is_err());
+    assert!(
+        serde_json::from_value::<ProblemDetails>(json!({ "status": { "$text": 404 } })).is_err()
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialize_keeps_xml_like_members() {
+    let deserialized: ProblemDetails<serde_json::Value> =
+        serde_json::from_value(json!({ "@xmlns": "urn:example", "status": 404 })).unwrap();
+    assert_eq!(deserialized.extensions, json!({ "@xmlns": "urn:example" }));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialize_rejects_wrongly_typed_extensions() {
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Extensions {
+        balance: u32,
+        flag: bool,
+    }
+
+    let result = serde_json::from_value::<ProblemDetails<Extensions>>(
+        json!({ "balance": "30", "flag": "true" }),
+    );
+    assert!(result.is_err());
+
+    let result = serde_json::from_value::<ProblemDetails<Extensions>>(
+        json!({ "balance": { "$text": "30" }, "flag": true }),
+    );
+    assert!(result.is_err());
+
+    let parsed: ProblemDetails<Extensions> =
+        serde_json::from_value(json!({ "balance": 30, "flag": true })).unwrap();
+    assert_eq!(
+        parsed.extensions,
+        Extensions {
+            balance: 30,
+            flag: true
+        }
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn with_extension() {
+    let details = ProblemDetails::new()
+        .with_title("Test Title")
+        .with_extension("foo", "Foo")
+        .with_extension("bar", 42)
+        .with_extension("bar", 43);
+
+    assert_eq!(details.title.as_deref(), Some("Test Title"));
+    assert_eq!(details.extensions.len(), 2);
+
+    let serialized = serde_json::to_value(details).unwrap();
+
+    let expected = json!({
+        "title": "Test Title",
+        "foo": "Foo",
+        "bar": 43
+    });
+
+    assert_eq!(expected, serialized);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serialize_tuple_extensions() {
+    #[derive(serde::Serialize)]
+    struct Trace {
+        trace_id: String,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Balance {
+        balance: u32,
+    }
+
+    let details = ProblemDetails::new()
+        .with_title("Test Title")
+        .with_extensions((
+            Trace {
+                trace_id: "abc123".to_string(),
+            },
+            Balance { balance: 30 },
+        ));
+
+    let serialized = serde_json::to_value(details).unwrap();
+
+    let expected = json!({
+        "title": "Test Title",
+        "trace_id": "abc123",
+        "balance": 30
+    });
+
+    assert_eq!(expected, serialized);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialize_tuple_extensions() {
+    #[derive(Debug, PartialEq, Eq, serde::Deserialize)]
+    struct Trace {
+        trace_id: String,
+    }
+
+    #[derive(Debug, PartialEq, Eq, serde::Deserialize)]
+    struct Balance {
+        balance: u32,
+    }
+
+    let filled = json!({
+        "title": "Test Title",
+        "trace_id": "abc123",
+        "balance": 30,
+        "unknown": true
+    });
+
+    let deserialized: ProblemDetails<(Trace, Balance)> = serde_json::from_value(filled).unwrap();
+
+    let expected = ProblemDetails::new()
+        .with_title("Test Title")
+        .with_extensions((
+            Trace {
+                trace_id: "abc123".to_string(),
+            },
+            Balance { balance: 30 },
+        ));
+
+    assert_eq!(expected, deserialized);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialize_ignores_unknown_members_without_extensions() {
+    let filled = json!({
+        "title": "Test Title",
+        "unknown": true
+    });
+
+    let deserialized: ProblemDetails = serde_json::from_value(filled).unwrap();
+
+    assert_eq!(ProblemDetails::new().with_title("Test Title"), deserialized);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn stack_extensions() {
+    #[derive(serde::Serialize)]
+    struct Balance {
+        balance: u32,
+    }
+
+    impl crate::MergeExtensions for Balance {
+        fn merge_extensions(&mut self, _other: Self) {}
+    }
+
+    let details = ProblemDetails::new()
+        .with_extensions((
+            Balance { balance: 30 },
+            HashMap::from([("foo".to_string(), json!("Foo"))]),
+        ))
+        .with_extensions_merged((
+            Balance { balance: 40 },
+            HashMap::from([("bar".to_string(), json!(42))]),
+        ))
+        .extend_extensions(HashMap::from([("baz".to_string(), json!(true))]));
+
+    let serialized = serde_json::to_value(details).unwrap();
+
+    let expected = json!({
+        "balance": 30,
+        "foo": "Foo",
+        "bar": 42,
+        "baz": true
+    });
+
+    assert_eq!(expected, serialized);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn get_and_take_extension() {
+    let mut details: ProblemDetails<crate::DynamicExtensions> = serde_json::from_value(json!({
+        "title": "Test Title",
+        "foo": "Foo",
+        "bar": 42
+    }))
+    .unwrap();
+
+    assert_eq!(
+        details.get_extension::<String>("foo").unwrap(),
+        Some("Foo".to_string())
+    );
+    assert_eq!(details.get_extension::<u32>("bar").unwrap(), Some(42));
+    assert_eq!(details.get_extension::<u32>("baz").unwrap(), None);
+    assert!(details.get_extension::<u32>("foo").is_err());
+
+    assert_eq!(details.take_extension::<u32>("bar").unwrap(), Some(42));
+    assert_eq!(details.take_extension::<u32>("bar").unwrap(), None);
+    assert_eq!(details.extensions.len(), 1);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn merge_map_extensions() {
+    let mut details = ProblemDetails::new()
+        .with_extension("foo", "Foo")
+        .with_extension("bar", 42);
+
+    details.merge(
+        ProblemDetails::new()
+            .with_extension("bar", 43)
+            .with_extension("baz", true),
+    );
+
+    assert_eq!(details.extensions.len(), 3);
+    assert_eq!(details.extensions["foo"], json!("Foo"));
+    assert_eq!(details.extensions["bar"], json!(43));
+    assert_eq!(details.extensions["baz"], json!(true));
+}
+
+#[test]
+fn problem_macro() {
+    let details = crate::problem! {
+        type: "test:type",
+        status: 404,
+        title: "Test Title",
+        detail: "Test Detail",
+        instance: "test:instance",
+    };
+
+    let expected = ProblemDetails::new()
+        .with_type(Uri::from_static("test:type"))
+        .with_status(StatusCode::NOT_FOUND)
+        .with_title("Test Title")
+        .with_detail("Test Detail")
+        .with_instance(Uri::from_static("test:instance"));
+
+    assert_eq!(expected, details);
+    assert_eq!(ProblemDetails::new(), crate::problem! {});
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn problem_macro_extensions() {
+    let id = 42;
+    let details = crate::problem! {
+        status: StatusCode::NOT_FOUND,
+        detail: "user {id} missing",
+        ext: { id, foo: "Foo" }
+    };
+
+    let expected = ProblemDetails::new()
+        .with_status(StatusCode::NOT_FOUND)
+        .with_detail("user 42 missing")
+        .with_extension("id", 42)
+        .with_extension("foo", "Foo");
+
+    assert_eq!(expected, details);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn validate() {
+    use crate::ValidationWarning;
+
+    let valid = ProblemDetails::from_status_code(StatusCode::NOT_FOUND);
+    assert_eq!(valid.validate(), vec![]);
+
+    let invalid = ProblemDetails::new()
+        .with_status(StatusCode::NOT_FOUND)
+        .with_title("Test Title")
+        .with_extensions(HashMap::from([(
+            "detail".to_string(),
+            json!("Test Detail"),
+        )]));
+
+    assert_eq!(
+        invalid.validate(),
+        vec![
+            ValidationWarning::TitleMismatch {
+                title: "Test Title".to_string(),
+                reason: "Not Found"
+            },
+            ValidationWarning::ReservedExtension("detail".to_string()),
+        ]
+    );
+
+    let relative = ProblemDetails::new().with_type(Uri::from_static("/test/type"));
+    assert_eq!(
+        relative.validate(),
+        vec![ValidationWarning::RelativeType(ProblemType::from(
+            Uri::from_static("/test/type")
+        ))]
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn validate_against() {
+    use crate::ValidationWarning;
+
+    let details = ProblemDetails::from_status_code(StatusCode::NOT_FOUND);
+
+    let (parts, _) = http::Response::builder()
+        .status(StatusCode::NOT_FOUND)
+        .header(http::header::CONTENT_TYPE, "application/problem+json")
+        .body(())
+        .unwrap()
+        .into_parts();
+    assert_eq!(details.validate_against(&parts), vec![]);
+
+    for content_type in [
+        "application/concise-problem-details+cbor",
+        "application/yaml",
+        "text/html; charset=utf-8",
+        "text/plain; charset=utf-8",
+    ] {
+        let (parts, _) = http::Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .header(http::header::CONTENT_TYPE, content_type)
+            .body(())
+            .unwrap()
+            .into_parts();
+        assert_eq!(details.validate_against(&parts), vec![]);
+    }
+
+    let (parts, _) = http::Response::builder()
+        .status(StatusCode::BAD_REQUEST)
+        .header(http::header::CONTENT_TYPE, "application/json")
+        .body(())
+        .unwrap()
+        .into_parts();
+    assert_eq!(
+        details.validate_against(&parts),
+        vec![
+            ValidationWarning::StatusMismatch {
+                problem: StatusCode::NOT_FOUND,
+                response: StatusCode::BAD_REQUEST
+            },
+            ValidationWarning::ContentTypeMismatch(Some("application/json".to_string())),
+        ]
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn redacted() {
+    use crate::{ProductionPolicy, RedactionPolicy};
+
+    struct RedactAll;
+
+    impl RedactionPolicy for RedactAll {
+        fn redact_detail(&self, _status: Option<StatusCode>) -> bool {
+            true
+        }
+
+        fn redact_instance(&self, _status: Option<StatusCode>) -> bool {
+            true
+        }
+
+        fn redact_extension(&self, _key: &str, _value: &serde_json::Value) -> bool {
+            true
+        }
+    }
+
+    let details = ProblemDetails::from_status_code(StatusCode::BAD_REQUEST)
+        .with_detail("Test Detail")
+        .with_instance(Uri::from_static("test:instance"))
+        .with_extension("foo", "Foo")
+        .with_extension("_internal", "Internal")
+        .with_extension("secret", "Secret")
+        .with_extension("stack_trace", "Stack");
+
+    let policy = ProductionPolicy::default().with_redacted_extension("secret");
+    let expected = ProblemDetails::from_status_code(StatusCode::BAD_REQUEST)
+        .with_detail("Test Detail")
+        .with_instance(Uri::from_static("test:instance"))
+        .with_extensions(serde_json::Map::from_iter([(
+            "foo".to_string(),
+            json!("Foo"),
+        )]));
+    assert_eq!(expected, details.redacted(&policy));
+
+    let expected = ProblemDetails::from_status_code(StatusCode::BAD_REQUEST)
+        .with_extensions(serde_json::Map::new());
+    assert_eq!(expected, details.redacted(&RedactAll));
+}
+
+#[test]
+fn effective_values() {
+    let empty = ProblemDetails::new();
+    assert_eq!(*empty.effective_type(), ProblemType::default());
+    assert_eq!(empty.status_or_default(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(empty.effective_title(), None);
+
+    let details = ProblemDetails::new()
+        .with_type(Uri::from_static("https://example.com/problem"))
+        .with_status(StatusCode::NOT_FOUND);
+    assert_eq!(
+        *details.effective_type(),
+        ProblemType::from(Uri::from_static("https://example.com/problem"))
+    );
+    assert_eq!(details.status_or_default(), StatusCode::NOT_FOUND);
+    assert_eq!(details.effective_title(), Some("Not Found"));
+    assert_eq!(
+        details.with_title("Custom").effective_title(),
+        Some("Custom")
+    );
+}
+
+#[test]
+fn parts_roundtrip() {
+    let details = ProblemDetails::from_status_code(StatusCode::BAD_REQUEST)
+        .with_type(Uri::from_static("https://example.com/problem"))
+        .with_detail("Test Detail")
+        .with_instance(Uri::from_static("test:instance"))
+        .with_extensions(42)
+        .with_source(std::fmt::Error);
+
+    let parts = details.clone().into_parts();
+    assert_eq!(parts.status, Some(StatusCode::BAD_REQUEST));
+    assert_eq!(parts.extensions, 42);
+    assert!(parts.source.is_some());
+
+    let reassembled = ProblemDetails::from_parts(parts);
+    assert_eq!(reassembled, details);
+    assert!(std::error::Error::source(&reassembled).is_some());
+
+    let empty = ProblemDetails::from_parts(crate::ProblemDetailsParts::new(()));
+    assert_eq!(empty, ProblemDetails::new());
+}
+
+#[test]
+fn headers() {
+    use http::{header, HeaderValue};
+
+    let details = ProblemDetails::from_status_code(StatusCode::TOO_MANY_REQUESTS)
+        .with_header(header::RETRY_AFTER, HeaderValue::from_static("120"));
+
+    assert_eq!(details.headers()[header::RETRY_AFTER], "120");
+    assert!(ProblemDetails::new().headers().is_empty());
+    assert_eq!(
+        details,
+        ProblemDetails::from_status_code(StatusCode::TOO_MANY_REQUESTS)
+    );
+
+    let merged = ProblemDetails::new()
+        .with_header(header::RETRY_AFTER, HeaderValue::from_static("60"))
+        .with_header(header::ALLOW, HeaderValue::from_static("GET"))
+        .merged_with(details.clone());
+    assert_eq!(merged.headers()[header::RETRY_AFTER], "120");
+    assert_eq!(merged.headers()[header::ALLOW], "GET");
+
+    let reassembled = ProblemDetails::from_parts(details.into_parts());
+    assert_eq!(reassembled.headers()[header::RETRY_AFTER], "120");
+}
+
+#[cfg(all(feature = "axum", feature = "json"))]
+#[test]
+fn axum_response_headers() {
+    use axum::response::IntoResponse;
+    use http::{header, HeaderValue};
+
+    let response = ProblemDetails::from_status_code(StatusCode::UNAUTHORIZED)
+        .with_header(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"))
+        .into_response();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(response.headers()[header::WWW_AUTHENTICATE], "Bearer");
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "application/problem+json"
+    );
+}
+
+#[cfg(all(feature = "poem", feature = "json"))]
+#[test]
+fn poem_response_headers() {
+    use http::{header, HeaderValue};
+    use poem::IntoResponse;
+
+    let response = ProblemDetails::from_status_code(StatusCode::UNAUTHORIZED)
+        .with_header(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"))
+        .into_response();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(response.headers()[header::WWW_AUTHENTICATE], "Bearer");
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "application/problem+json"
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn retry_after() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use http::header;
+
+    use crate::RetryAfter;
+
+    let details = ProblemDetails::from_status_code(StatusCode::TOO_MANY_REQUESTS)
+        .with_retry_after(Duration::from_millis(1500));
+    assert_eq!(details.headers()[header::RETRY_AFTER], "2");
+    assert_eq!(
+        serde_json::to_value(&details).unwrap(),
+        json!({ "status": 429, "title": "Too Many Requests", "retry_after": 2 })
+    );
+
+    let at = UNIX_EPOCH + Duration::from_secs(1_709_251_200);
+    let details =
+        ProblemDetails::from_status_code(StatusCode::SERVICE_UNAVAILABLE).with_retry_at(at);
+    assert_eq!(
+        details.headers()[header::RETRY_AFTER],
+        "Fri, 01 Mar 2024 00:00:00 GMT"
+    );
+
+    let json = serde_json::to_string(&details).unwrap();
+    let parsed: ProblemDetails<crate::RetryAfterExtension> = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.extensions.retry_after, RetryAfter::At(at));
+
+    let invalid = json!({ "retry_after": "Sat, 01 Mar 2024 00:00:00 GMT" });
+    assert!(serde_json::from_value::<ProblemDetails<crate::RetryAfterExtension>>(invalid).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn method_not_allowed() {
+    use http::{header, Method};
+
+    let details = ProblemDetails::method_not_allowed(&[Method::GET, Method::POST]);
+
+    assert_eq!(details.headers()[header::ALLOW], "GET, POST");
+    assert_eq!(
+        serde_json::to_value(&details).unwrap(),
+        json!({
+            "status": 405,
+            "title": "Method Not Allowed",
+            "detail": "The method is not allowed for this resource. Allowed methods: GET, POST.",
+            "allowed_methods": ["GET", "POST"]
+        })
+    );
+
+    let json = serde_json::to_string(&details).unwrap();
+    let parsed: ProblemDetails<crate::AllowedMethodsExtension> =
+        serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, details);
+
+    let none = ProblemDetails::method_not_allowed(&[]);
+    assert_eq!(none.headers()[header::ALLOW], "");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn presets() {
+    use std::time::Duration;
+
+    use http::header;
+
+    use crate::{presets, types};
+
+    assert_eq!(
+        serde_json::to_value(presets::not_found("user", 42)).unwrap(),
+        json!({
+            "status": 404,
+            "title": "Not Found",
+            "detail": "The user 42 does not exist.",
+            "resource": "user",
+            "id": "42"
+        })
+    );
+
+    assert_eq!(
+        serde_json::to_value(presets::validation([("/age", "must be positive")])).unwrap(),
+        json!({
+            "status": 422,
+            "title": "Unprocessable Entity",
+            "detail": "The request contains invalid fields.",
+            "errors": [{ "pointer": "/age", "detail": "must be positive" }]
+        })
+    );
+
+    let conflict = presets::conflict("\"v2\"");
+    assert_eq!(conflict.status, Some(StatusCode::CONFLICT));
+    assert_eq!(conflict.extensions.current_version, "\"v2\"");
+
+    let rate_limited = presets::rate_limited(Duration::from_secs(30));
+    assert!(rate_limited.matches_type(&types::QUOTA_EXCEEDED));
+    assert_eq!(rate_limited.headers()[header::RETRY_AFTER], "30");
+
+    let internal = presets::internal("ref-123");
+    assert_eq!(internal.status, Some(StatusCode::INTERNAL_SERVER_ERROR));
+    assert_eq!(internal.extensions.reference_id, "ref-123");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn dynamic_extensions() {
+    use crate::DynamicExtensions;
+
+    let details = ProblemDetails::new()
+        .with_extension("zeta", 1)
+        .with_extension("alpha", 2)
+        .with_extension("zeta", 3);
+
+    assert_eq!(
+        details.extensions.keys().collect::<Vec<_>>(),
+        ["zeta", "alpha"]
+    );
+    assert_eq!(details.get_extension::<u32>("zeta").unwrap(), Some(3));
+    assert_eq!(
+        serde_json::to_string(&details).unwrap(),
+        r#"{"zeta":3,"alpha":2}"#
+    );
+
+    let mut extensions = DynamicExtensions::new();
+    let err = extensions.insert("instance", "/test").unwrap_err();
+    assert_eq!(err.key(), "instance");
+    assert!(extensions.is_empty());
+
+    let parsed: ProblemDetails<DynamicExtensions> =
+        serde_json::from_value(json!({ "status": 400, "foo": "bar" })).unwrap();
+    assert_eq!(parsed.status, Some(StatusCode::BAD_REQUEST));
+    assert_eq!(parsed.extensions["foo"], json!("bar"));
+    assert!(serde_json::from_value::<DynamicExtensions>(json!({ "type": "x" })).is_err());
+}
+
+#[cfg(feature = "json")]
+#[test]
+#[should_panic(expected = "Extension member status is reserved")]
+fn dynamic_extensions_reserved_key() {
+    let _ = ProblemDetails::new().with_extension("status", 500);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn dynamic_extensions_try_with_extension() {
+    let details = ProblemDetails::new()
+        .try_with_extension("foo", "bar")
+        .unwrap();
+    assert_eq!(details.extensions["foo"], json!("bar"));
+
+    let err = details.try_with_extension("title", "Test").unwrap_err();
+    assert_eq!(err.key(), "title");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn field_errors() {
+    use crate::{FieldError, ValidationErrors};
+
+    let details = ProblemDetails::from_status_code(StatusCode::UNPROCESSABLE_ENTITY)
+        .with_field_error("/age", "must be a positive integer")
+        .with_field_error("/color", "must be 'green', 'red' or 'blue'");
+
+    assert_eq!(
+        serde_json::to_string(&details).unwrap(),
+        r#"{"status":422,"title":"Unprocessable Entity","errors":[{"detail":"must be a positive integer","pointer":"/age"},{"detail":"must be 'green', 'red' or 'blue'","pointer":"/color"}]}"#
+    );
+
+    let parsed: ProblemDetails<ValidationErrors> =
+        serde_json::from_str(&serde_json::to_string(&details).unwrap()).unwrap();
+    assert_eq!(parsed, details);
+
+    let error = FieldError::from_path(["a~b", "c/d"], "invalid");
+    assert_eq!(error.pointer, "/a~0b/c~1d");
+    assert_eq!(error.path().collect::<Vec<_>>(), ["a~b", "c/d"]);
+    assert_eq!(FieldError::new("", "root").path().count(), 0);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn correlation() {
+    use http::{HeaderMap, HeaderValue};
+
+    use crate::Correlation;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        "traceparent",
+        HeaderValue::from_static("00-00000000000000000000000000000000-00f067aa0ba902b7-01"),
+    );
+    headers.insert("x-correlation-id", HeaderValue::from_static("corr-1"));
+
+    let correlation = Correlation::from_headers(&headers);
+    assert_eq!(correlation, Correlation::new().with_request_id("corr-1"));
+    assert!(Correlation::from_headers(&HeaderMap::new()).is_empty());
+
+    let details = ProblemDetails::new()
+        .with_extension("foo", "bar")
+        .with_correlation(correlation)
+        .with_correlation(Correlation::new().with_span_id("span"));
+    assert_eq!(
+        serde_json::to_value(&details).unwrap(),
+        json!({ "foo": "bar", "request_id": "corr-1", "span_id": "span" })
+    );
+
+    let mut merged = Correlation::new().with_trace_id("a").with_request_id("b");
+    crate::MergeExtensions::merge_extensions(&mut merged, Correlation::new().with_trace_id("c"));
+    assert_eq!(
+        merged,
+        Correlation::new().with_trace_id("c").with_request_id("b")
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn erased_extensions() {
+    use crate::{Correlation, ErasedExtensions, MergeExtensions};
+
+    #[derive(serde::Serialize)]
+    struct Balance {
+        balance: u32,
+    }
+
+    let mut extensions = ErasedExtensions::new()
+        .with(Balance { balance: 30 })
+        .with(Correlation::new().with_request_id("req-1"));
+    extensions.merge_extensions(ErasedExtensions::new().with(Balance { balance: 40 }));
+
+    assert_eq!(extensions.len(), 3);
+    assert_eq!(extensions.get::<Balance>().unwrap().balance, 40);
+    assert!(!extensions.contains::<String>());
+
+    let details =
+        ProblemDetails::from_status_code(StatusCode::FORBIDDEN).with_extensions(extensions);
+    assert_eq!(
+        serde_json::to_value(&details).unwrap(),
+        json!({ "status": 403, "title": "Forbidden", "balance": 40, "request_id": "req-1" })
+    );
+
+    let invalid = ProblemDetails::new().with_extensions(ErasedExtensions::new().with(42));
+    assert!(serde_json::to_value(&invalid).is_err());
+}
+
+#[cfg(all(feature = "indexmap", feature = "json"))]
+#[test]
+fn indexmap_ordering() {
+    use indexmap::IndexMap;
+
+    let details = ProblemDetails::new()
+        .with_extensions(IndexMap::from([
+            ("zeta".to_string(), json!(1)),
+            ("alpha".to_string(), json!(2)),
+        ]))
+        .with_extensions_merged(IndexMap::from([("beta".to_string(), json!(3))]));
+
+    assert_eq!(
+        serde_json::to_string(&details).unwrap(),
+        r#"{"zeta":1,"alpha":2,"beta":3}"#
+    );
+
+    let redacted = details.redacted(&crate::ProductionPolicy::default());
+    assert_eq!(
+        serde_json::to_string(&redacted).unwrap(),
+        r#"{"zeta":1,"alpha":2,"beta":3}"#
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn timestamp() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crate::Timestamp;
+
+    let time = UNIX_EPOCH + Duration::from_millis(1_709_251_200_042);
+    let details = ProblemDetails::from_status_code(StatusCode::BAD_REQUEST)
+        .with_extension("foo", "bar")
+        .with_occurred_at(time);
+
+    assert_eq!(
+        serde_json::to_value(&details).unwrap(),
+        json!({
+            "status": 400,
+            "title": "Bad Request",
+            "foo": "bar",
+            "timestamp": "2024-03-01T00:00:00.042Z"
+        })
+    );
+
+    let custom = Timestamp::new(UNIX_EPOCH).with_key("occurred_at");
+    assert_eq!(custom.key(), "occurred_at");
+    assert_eq!(custom.to_string(), "1970-01-01T00:00:00Z");
+
+    let now = ProblemDetails::new().with_timestamp();
+    assert!(now.extensions.1.time() > time);
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn timestamp_from_time() {
+    let time = time::OffsetDateTime::from_unix_timestamp(784111777)
+        .unwrap()
+        .to_offset(time::UtcOffset::from_hms(2, 0, 0).unwrap());
+    assert_eq!(
+        crate::Timestamp::from(time).to_string(),
+        "1994-11-06T08:49:37Z"
+    );
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn timestamp_from_chrono() {
+    let time = chrono::DateTime::from_timestamp(784111777, 0).unwrap();
+    assert_eq!(
+        crate::Timestamp::from(time).to_string(),
+        "1994-11-06T08:49:37Z"
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn caused_by() {
+    use crate::{Cause, Causes, ProductionPolicy};
+
+    let upstream = ProblemDetails::from_status_code(StatusCode::INTERNAL_SERVER_ERROR)
+        .with_detail("Connection refused")
+        .with_extension("retryable", true);
+
+    let details = ProblemDetails::from_status_code(StatusCode::BAD_GATEWAY)
+        .with_extension("foo", "bar")
+        .caused_by(upstream.clone());
+
+    let json = serde_json::to_value(&details).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "status": 502,
+            "title": "Bad Gateway",
+            "foo": "bar",
+            "cause": {
+                "status": 500,
+                "title": "Internal Server Error",
+                "detail": "Connection refused",
+                "retryable": true
+            }
+        })
+    );
+
+    let parsed: ProblemDetails<Causes> = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed.extensions.causes, [Cause::new(upstream.clone())]);
+
+    let redacted = Cause::new(upstream)
+        .with_service("billing")
+        .redacted(&ProductionPolicy::default());
+    let details =
+        ProblemDetails::new().caused_by(Causes::from_iter([redacted.clone(), redacted.clone()]));
+
+    let json = serde_json::to_value(&details).unwrap();
+    assert_eq!(json["causes"][1]["service"], "billing");
+    assert_eq!(json["causes"][1].get("detail"), None);
+
+    let parsed: ProblemDetails<Causes> = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed.extensions.causes, [redacted.clone(), redacted]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn multi_problem() {
+    use crate::MultiProblem;
+
+    let details =
+        ProblemDetails::multiple([ProblemDetails::from_status_code(StatusCode::NOT_FOUND)
+            .with_instance(Uri::from_static("/items/1"))])
+        .with_problem(ProblemDetails::from_status_code(StatusCode::CONFLICT));
+
+    let json = serde_json::to_value(&details).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "status": 422,
+            "title": "Unprocessable Entity",
+            "detail": "Multiple problems occurred.",
+            "errors": [
+                { "status": 404, "title": "Not Found", "instance": "/items/1" },
+                { "status": 409, "title": "Conflict" }
+            ]
+        })
+    );
+
+    let parsed: ProblemDetails<MultiProblem> = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, details);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn rate_limit() {
+    use std::time::Duration;
+
+    use crate::RateLimit;
+
+    let details = ProblemDetails::from_status_code(StatusCode::TOO_MANY_REQUESTS)
+        .with_rate_limit(RateLimit::new(100, 3, Duration::from_millis(1500)));
+
+    assert_eq!(details.headers()["ratelimit-limit"], "100");
+    assert_eq!(details.headers()["ratelimit-remaining"], "3");
+    assert_eq!(details.headers()["ratelimit-reset"], "2");
+    assert!(!details.headers().contains_key("ratelimit-policy"));
+    assert!(!details.headers().contains_key(http::header::RETRY_AFTER));
+
+    let json = serde_json::to_value(&details).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "status": 429,
+            "title": "Too Many Requests",
+            "limit": 100,
+            "remaining": 3,
+            "reset": 2
+        })
+    );
+
+    let exhausted =
+        ProblemDetails::new().with_rate_limit(RateLimit::new(100, 0, Duration::from_secs(10)));
+    assert_eq!(exhausted.headers()[http::header::RETRY_AFTER], "10");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn extension_filter() {
+    use crate::ExtensionFilter;
+
+    let details = ProblemDetails::from_status_code(StatusCode::BAD_REQUEST)
+        .with_extension("trace_id", "abc123")
+        .with_extension("debug_sql", "SELECT 1")
+        .with_extension("_internal", true);
+
+    let allowed = details.redacted(&ExtensionFilter::allow(["trace_id"]));
+    assert_eq!(
+        serde_json::to_value(&allowed).unwrap(),
+        json!({ "status": 400, "title": "Bad Request", "trace_id": "abc123" })
+    );
+
+    let denied = details.redacted(&ExtensionFilter::deny(["debug_sql"]));
+    assert_eq!(denied.extensions.len(), 2);
+    assert!(!denied.extensions.contains_key("debug_sql"));
+
+    let filter = ExtensionFilter::new(|key| !key.starts_with('_'));
+    assert!(filter.includes("trace_id"));
+    assert!(!filter.includes("_internal"));
+    assert_eq!(details.redacted(&filter).extensions.len(), 2);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn namespaced_extensions() {
+    use crate::{Namespace, Namespaced};
+
+    struct Acme;
+
+    impl Namespace for Acme {
+        const PREFIX: &'static str = "acme_";
+    }
+
+    let details = ProblemDetails::from_status_code(StatusCode::BAD_REQUEST)
+        .with_extension("trace_id", "abc123")
+        .with_namespace::<Acme>()
+        .with_extension("balance", 30);
+
+    let json = serde_json::to_value(&details).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "status": 400,
+            "title": "Bad Request",
+            "acme_trace_id": "abc123",
+            "acme_balance": 30
+        })
+    );
+
+    let json = json!({
+        "status": 400,
+        "acme_trace_id": "abc123",
+        "other_trace_id": "def456"
+    });
+    let parsed: ProblemDetails<Namespaced<Acme>> = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed.extensions.keys().collect::<Vec<_>>(), ["trace_id"]);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn inject_extension() {
+    use crate::Correlation;
+
+    let correlation = Correlation::new().with_trace_id("abc123");
+
+    let mut details = ProblemDetails::new().with_extension("foo", "bar");
+    details.inject_extension(correlation.clone());
+    assert_eq!(details.extensions["trace_id"], "abc123");
+    assert!(!details.extensions.contains_key("span_id"));
+
+    let mut details = ProblemDetails::new().with_extension("trace_id", "handler");
+    details.inject_extension(correlation.clone().with_request_id("req-42"));
+    assert_eq!(details.extensions["trace_id"], "handler");
+    assert_eq!(details.extensions["request_id"], "req-42");
+
+    let mut details = ProblemDetails::new()
+        .with_json_extensions(json!({ "trace_id": "handler" }))
+        .unwrap();
+    details.inject_extension(correlation.clone());
+    assert_eq!(details.extensions["trace_id"], "handler");
+
+    let mut details = ProblemDetails::new().with_request_id("req-42");
+    details.inject_extension(correlation.clone());
+    assert_eq!(details.extensions.request_id.as_deref(), Some("req-42"));
+    assert_eq!(details.extensions.trace_id.as_deref(), Some("abc123"));
+
+    let mut details = ProblemDetails::new()
+        .with_extensions(HashMap::<String, serde_json::Value>::new())
+        .with_correlation(Correlation::new());
+    details.inject_extension(correlation);
+    assert_eq!(details.extensions.1.trace_id.as_deref(), Some("abc123"));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_extensions() {
+    use crate::{DynamicExtensions, JsonExtensions, JsonExtensionsError};
+
+    let details = ProblemDetails::from_status_code(StatusCode::BAD_REQUEST)
+        .with_json_extensions(json!({ "balance": 30 }))
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(&details).unwrap(),
+        json!({ "status": 400, "title": "Bad Request", "balance": 30 })
+    );
+
+    assert_eq!(
+        ProblemDetails::new().with_json_extensions(json!("foo")),
+        Err(JsonExtensionsError::NotAnObject)
+    );
+    match ProblemDetails::new().with_json_extensions(json!({ "title": "foo" })) {
+        Err(JsonExtensionsError::ReservedKey(err)) => assert_eq!(err.key(), "title"),
+        other => panic!("unexpected result {other:?}"),
+    }
+
+    let dynamic = DynamicExtensions::try_from(json!({ "foo": "bar" })).unwrap();
+    assert_eq!(dynamic["foo"], "bar");
+    assert_eq!(
+        JsonExtensions::from(dynamic),
+        json!({ "foo": "bar" }).as_object().unwrap().clone()
+    );
+}
+
+#[cfg(all(feature = "utoipa", feature = "serde"))]
+#[test]
+fn utoipa_schema() {
+    use utoipa::{PartialSchema, ToSchema};
+
+    #[derive(serde::Serialize, ToSchema)]
+    struct Balance {
+        balance: u32,
+    }
+
+    #[derive(serde::Serialize, ToSchema)]
+    struct Trace {
+        trace_id: String,
+    }
+
+    crate::extension_schema!(Balance, Trace);
+
+    assert_eq!(ProblemDetails::<()>::name(), "ProblemDetails");
+    assert_eq!(
+        ProblemDetails::<(Balance, Trace)>::name(),
+        "ProblemDetails_Balance_Trace"
+    );
+
+    let schema = serde_json::to_value(ProblemDetails::<()>::schema()).unwrap();
+    assert_eq!(schema["properties"]["status"]["type"], "integer");
+    assert_eq!(schema["properties"]["type"]["default"], "about:blank");
+
+    let schema = serde_json::to_value(ProblemDetails::<((), Balance)>::schema()).unwrap();
+    assert_eq!(
+        schema["allOf"],
+        json!([
+            { "$ref": "#/components/schemas/ProblemDetails" },
+            { "$ref": "#/components/schemas/Balance" }
+        ])
+    );
+
+    let mut schemas = Vec::new();
+    ProblemDetails::<(Balance, Trace)>::schemas(&mut schemas);
+    let names = schemas
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["ProblemDetails", "Balance", "Trace"]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn severity() {
+    use crate::{DefaultSeverityPolicy, Severity, SeverityExtension};
+
+    assert!(Severity::Info < Severity::Critical);
+    assert_eq!("warning".parse::<Severity>(), Ok(Severity::Warning));
+    assert!("fatal".parse::<Severity>().is_err());
+
+    let details = ProblemDetails::from_status_code(StatusCode::BAD_GATEWAY)
+        .with_severity_from(&DefaultSeverityPolicy);
+    assert_eq!(
+        serde_json::to_value(&details).unwrap(),
+        json!({ "status": 502, "title": "Bad Gateway", "severity": "error" })
+    );
+
+    let details = ProblemDetails::from_status_code(StatusCode::NOT_FOUND)
+        .with_severity_from(&DefaultSeverityPolicy);
+    assert_eq!(details.extensions.1.severity, Severity::Warning);
+
+    let parsed: ProblemDetails<SeverityExtension> =
+        serde_json::from_value(json!({ "severity": "critical" })).unwrap();
+    assert_eq!(parsed.extensions.severity, Severity::Critical);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn lenient_deserialization() {
+    use crate::DynamicExtensions;
+
+    let json = r#"{
+        "type": 42,
+        "status": "oops",
+        "title": ["not", "a", "string"],
+        "detail": "Still valid",
+        "instance": "/foo",
+        "balance": 30
+    }"#;
+
+    assert!(serde_json::from_str::<ProblemDetails<DynamicExtensions>>(json).is_err());
+
+    let details = ProblemDetails::<DynamicExtensions>::from_json_lenient(json).unwrap();
+    assert_eq!(details.r#type, None);
+    assert_eq!(details.status, None);
+    assert_eq!(details.title, None);
+    assert_eq!(details.detail.as_deref(), Some("Still valid"));
+    assert_eq!(details.instance, Some(Uri::from_static("/foo")));
+    assert_eq!(details.extensions["balance"], 30);
+
+    assert!(ProblemDetails::<()>::from_json_lenient("[]").is_err());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn with_unknown_extensions() {
+    use crate::WithUnknown;
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Balance {
+        balance: u32,
+    }
+
+    let json = json!({
+        "type": "https://example.com/probs/out-of-credit",
+        "status": 403,
+        "title": "You do not have enough credit.",
+        "balance": 30,
+        "accounts": ["/account/12345"],
+        "traceId": "abc123"
+    });
+
+    let details: ProblemDetails<WithUnknown<Balance>> =
+        serde_json::from_value(json.clone()).unwrap();
+
+    assert_eq!(details.extensions.known, Balance { balance: 30 });
+    assert_eq!(details.extensions.balance, 30);
+    assert_eq!(details.extensions.unknown.len(), 2);
+    assert_eq!(details.extensions.unknown["traceId"], "abc123");
+    assert_eq!(serde_json::to_value(&details).unwrap(), json);
+}
+
+#[cfg(feature = "xml")]
+#[test]
+fn xml_deserialization() {
+    use crate::XmlProblemDetails;
+
+    #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct OutOfCredit {
+        balance: u32,
+        currency: String,
+    }
+
+    let details = ProblemDetails::from_status_code(StatusCode::FORBIDDEN)
+        .with_type(Uri::from_static("https://example.com/probs/out-of-credit"))
+        .with_detail("Your current balance is 30, but that costs 50.")
+        .with_instance(Uri::from_static("/account/12345/msgs/abc"))
+        .with_extensions(OutOfCredit {
+            balance: 30,
+            currency: "EUR".to_string(),
+        });
+
+    let xml = XmlProblemDetails::from(details.clone())
+        .to_body_string()
+        .unwrap();
+    let parsed: ProblemDetails<OutOfCredit> =
+        XmlProblemDetails::from_body_str(&xml).unwrap().into();
+    assert_eq!(parsed, details);
+
+    let parsed: ProblemDetails<HashMap<String, String>> =
+        XmlProblemDetails::from_body_str("<problem><status>500</status><code>E42</code></problem>")
+            .unwrap()
+            .into();
+    assert_eq!(parsed.status, Some(StatusCode::INTERNAL_SERVER_ERROR));
+    assert_eq!(parsed.extensions["code"], "E42");
+
+    assert!(XmlProblemDetails::<()>::from_body_str("<problem>").is_err());
+}
+
+#[cfg(feature = "xml")]
+#[test]
+fn xml_sequence_roundtrip() {
+    use crate::{XmlFormat, XmlProblemDetails};
+
+    #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Extensions {
+        accounts: Vec<String>,
+        single: Vec<u32>,
+        empty: Vec<String>,
+        owner: Owner,
+    }
+
+    #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Owner {
+        name: String,
+        roles: Vec<String>,
+    }
+
+    let details =
+        ProblemDetails::from_status_code(StatusCode::FORBIDDEN).with_extensions(Extensions {
+            accounts: vec!["/account/12345".to_string(), "/account/67890".to_string()],
+            single: vec![30],
+            empty: vec![],
+            owner: Owner {
+                name: "Jane".to_string(),
+                roles: vec!["admin".to_string(), "billing".to_string()],
+            },
+        });
+
+    let xml = XmlProblemDetails::from(details.clone())
+        .to_body_string_with_format(XmlFormat::Rfc7807)
+        .unwrap();
+    let parsed: ProblemDetails<Extensions> = XmlProblemDetails::from_body_str(&xml).unwrap().into();
+    assert_eq!(parsed, details);
+
+    #[cfg(feature = "json")]
+    {
+        let parsed: ProblemDetails<HashMap<String, serde_json::Value>> =
+            XmlProblemDetails::from_body_str(&xml).unwrap().into();
+        assert_eq!(
+            parsed.extensions["accounts"],
+            json!(["/account/12345", "/account/67890"])
+        );
+    }
+}
+
+#[cfg(feature = "xml")]
+#[test]
+fn xml_formats() {
+    use crate::{XmlFormat, XmlProblemDetails};
+
+    #[derive(serde::Serialize)]
+    struct Extensions {
+        note: &'static str,
+        accounts: Vec<&'static str>,
+        owner: Owner,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Owner {
+        name: &'static str,
+    }
+
+    let details = XmlProblemDetails::from(
+        ProblemDetails::new()
+            .with_type(Uri::from_static("https://example.com/probs/out-of-credit"))
+            .with_title("You do not have enough credit.")
+            .with_extensions(Extensions {
+                note: "a < b & c",
+                accounts: vec!["/account/12345", "/account/67890"],
+                owner: Owner { name: "Jane" },
+            }),
+    );
+
+    assert_eq!(
+        details
+            .to_body_string_with_format(XmlFormat::Rfc7807)
+            .unwrap(),
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            r#"<problem xmlns="urn:ietf:rfc:7807">"#,
+            "<type>https://example.com/probs/out-of-credit</type>",
+            "<title>You do not have enough credit.</title>",
+            "<note>a &lt; b &amp; c</note>",
+            "<accounts><i>/account/12345</i><i>/account/67890</i></accounts>",
+            "<owner><name>Jane</name></owner>",
+            "</problem>"
+        )
+    );
+
+    assert_eq!(
+        details
+            .to_body_string_with_format(XmlFormat::Legacy)
+            .unwrap(),
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            "<problem>",
+            "<type>https://example.com/probs/out-of-credit</type>",
+            "<title>You do not have enough credit.</title>",
+            "<note>a &lt; b &amp; c</note>",
+            "<accounts>/account/12345</accounts><accounts>/account/67890</accounts>",
+            "<owner><name>Jane</name></owner>",
+            "</problem>"
+        )
+    );
+
+    let invalid = XmlProblemDetails::from(
+        ProblemDetails::new().with_extensions(HashMap::from([("not valid", 1)])),
+    );
+    assert!(invalid.to_body_string().is_err());
+
+    let parsed: ProblemDetails<HashMap<String, String>> = XmlProblemDetails::from_body_str(
+        r#"<problem xmlns="urn:ietf:rfc:7807"><status>404</status><code>E42</code></problem>"#,
+    )
+    .unwrap()
+    .into();
+    assert_eq!(parsed.status, Some(StatusCode::NOT_FOUND));
+    assert_eq!(parsed.extensions.len(), 1);
+}
+
+#[cfg(feature = "cbor")]
+#[test]
+fn cbor_concise_problem_details() {
+    use ciborium::Value;
+
+    use crate::CborProblemDetails;
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Sensor {
+        sensor: String,
+        reading: u32,
+    }
+
+    let details = ProblemDetails::from_status_code(StatusCode::SERVICE_UNAVAILABLE)
+        .with_type(Uri::from_static("https://example.com/probs/offline"))
+        .with_detail("Sensor is offline")
+        .with_instance(Uri::from_static("/sensors/7"))
+        .with_extensions(Sensor {
+            sensor: "temperature".to_string(),
+            reading: 21,
+        });
+
+    let body = CborProblemDetails::from(details).to_body_vec().unwrap();
+    let value: Value = ciborium::from_reader(body.as_slice()).unwrap();
+
+    assert_eq!(
+        value,
+        Value::Map(vec![
+            (Value::from(-4), Value::from(503)),
+            (Value::from(-1), Value::from("Service Unavailable")),
+            (Value::from(-2), Value::from("Sensor is offline")),
+            (Value::from(-3), Value::from("/sensors/7")),
+            (
+                Value::from(7807),
+                Value::Map(vec![
+                    (
+                        Value::from("type"),
+                        Value::from("https://example.com/probs/offline")
+                    ),
+                    (Value::from("sensor"), Value::from("temperature")),
+                    (Value::from("reading"), Value::from(21)),
+                ])
+            ),
+        ])
+    );
+
+    let parsed: ProblemDetails<Sensor> = CborProblemDetails::from_body_slice(&body).unwrap().into();
+    assert_eq!(parsed.status, Some(StatusCode::SERVICE_UNAVAILABLE));
+    assert_eq!(
+        parsed.r#type,
+        Some(ProblemType::from(Uri::from_static(
+            "https://example.com/probs/offline"
+        )))
+    );
+    assert_eq!(parsed.extensions.reading, 21);
+
+    // Custom entries with URI keys are extensions, other unknown entries are ignored
+    let concise = Value::Map(vec![
+        (Value::from(-1), Value::from("Out of power")),
+        (Value::from(-5), Value::from("coap://example.com")),
+        (Value::from(4711), Value::from(true)),
+        (Value::from("tag:example.com,2023:battery"), Value::from(3)),
+    ]);
+    let mut body = Vec::new();
+    ciborium::into_writer(&concise, &mut body).unwrap();
+
+    let parsed: ProblemDetails<HashMap<String, u32>> =
+        CborProblemDetails::from_body_slice(&body).unwrap().into();
+    assert_eq!(parsed.title.as_deref(), Some("Out of power"));
+    assert_eq!(
+        parsed.extensions,
+        HashMap::from([("tag:example.com,2023:battery".to_string(), 3)])
+    );
+
+    assert!(CborProblemDetails::<()>::from_body_slice(&[0x01]).is_err());
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn yaml_roundtrip() {
+    use crate::YamlProblemDetails;
+
+    #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct ConfigError {
+        key: String,
+        line: u32,
+    }
+
+    let details = ProblemDetails::from_status_code(StatusCode::UNPROCESSABLE_ENTITY)
+        .with_type(Uri::from_static("https://example.com/probs/invalid-config"))
+        .with_extensions(ConfigError {
+            key: "server.port".to_string(),
+            line: 12,
+        });
+
+    let yaml = YamlProblemDetails::from(details.clone())
+        .to_body_string()
+        .unwrap();
+
+    assert_eq!(
+        yaml,
+        concat!(
+            "type: https://example.com/probs/invalid-config\n",
+            "status: 422\n",
+            "title: Unprocessable Entity\n",
+            "key: server.port\n",
+            "line: 12\n",
+        )
+    );
+
+    let parsed: ProblemDetails<ConfigError> =
+        YamlProblemDetails::from_body_str(&yaml).unwrap().into();
+    assert_eq!(parsed, details);
+
+    assert!(YamlProblemDetails::<()>::from_body_str("status: [").is_err());
+}
+
+#[cfg(feature = "html")]
+#[test]
+fn html_page() {
+    use crate::{DefaultHtmlTemplate, HtmlPage, HtmlProblemDetails};
+
+    let details = HtmlProblemDetails::from(
+        ProblemDetails::from_status_code(StatusCode::FORBIDDEN)
+            .with_type(Uri::from_static("https://example.com/probs/out-of-credit"))
+            .with_detail("Balance <30> is too low")
+            .with_instance(Uri::from_static("/account/12345/msgs/abc"))
+            .with_extension("balance", 30)
+            .with_extension("owner", "O'Brien"),
+    );
+
+    let html = details.to_body_string().unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>\n"));
+    assert!(html.contains("<title>403 Forbidden</title>"));
+    assert!(html.contains("<h1>403 Forbidden</h1>"));
+    assert!(html.contains("<p>Balance &lt;30&gt; is too low</p>"));
+    assert!(html.contains(
+        r#"<a href="https://example.com/probs/out-of-credit">https://example.com/probs/out-of-credit</a>"#
+    ));
+    assert!(html.contains("<small>/account/12345/msgs/abc</small>"));
+    assert!(html.contains("<tr><th>balance</th><td>30</td></tr>"));
+    assert!(html.contains("<tr><th>owner</th><td>O&#39;Brien</td></tr>"));
+
+    let html = details
+        .to_body_string_with(&DefaultHtmlTemplate::default().with_extension_table(false))
+        .unwrap();
+    assert!(!html.contains("<table>"));
+
+    let page = std::sync::Mutex::new(None);
+    details
+        .to_body_string_with(&|p: &HtmlPage| {
+            *page.lock().unwrap() = Some(p.clone());
+            String::new()
+        })
+        .unwrap();
+    let page = page.into_inner().unwrap().unwrap();
+    assert_eq!(page.status, StatusCode::FORBIDDEN);
+    assert_eq!(page.title, "Forbidden");
+    assert_eq!(
+        page.extensions,
+        vec![
+            ("balance".to_string(), "30".to_string()),
+            ("owner".to_string(), "O'Brien".to_string())
+        ]
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn text_body() {
+    use crate::TextProblemDetails;
+
+    #[derive(serde::Serialize)]
+    struct Ext {
+        zone: &'static str,
+        attempts: Vec<u32>,
+    }
+
+    let details = ProblemDetails::from_status_code(StatusCode::SERVICE_UNAVAILABLE)
+        .with_type(Uri::from_static("https://example.com/probs/maintenance"))
+        .with_extensions(Ext {
+            zone: "eu-1",
+            attempts: vec![1, 2],
+        })
+        .with_source(std::io::Error::other("connection refused"));
+
+    let text = TextProblemDetails::from(details).to_body_string();
+    let expected = concat!(
+        "[https://example.com/probs/maintenance 503] Service Unavailable\n",
+        "attempts: [1,2]\n",
+        "zone: \"eu-1\"\n",
+    );
+    let expected_ordered = concat!(
+        "[https://example.com/probs/maintenance 503] Service Unavailable\n",
+        "zone: \"eu-1\"\n",
+        "attempts: [1,2]\n",
+    );
+
+    assert!(text == expected || text == expected_ordered, "{text}");
+    assert!(!text.contains("connection refused"));
+
+    let text = TextProblemDetails::from(ProblemDetails::new()).to_body_string();
+    assert_eq!(text, "[about:blank]\n");
+}
+
+#[cfg(feature = "prost")]
+#[test]
+fn proto_conversion() {
+    use prost::Message;
+
+    use crate::{proto, JsonExtensions};
+
+    let details = ProblemDetails::from_status_code(StatusCode::TOO_MANY_REQUESTS)
+        .with_type(Uri::from_static("https://example.com/probs/rate-limit"))
+        .with_instance(Uri::from_static("/quota/7"))
+        .with_extension("limit", 100)
+        .with_extension("ratio", 0.5)
+        .with_extension("tags", vec!["a", "b"]);
+
+    let message = proto::ProblemDetails::from(&details);
+    assert_eq!(
+        message.r#type.as_deref(),
+        Some("https://example.com/probs/rate-limit")
+    );
+    assert_eq!(message.status, Some(429));
+    assert_eq!(message.title.as_deref(), Some("Too Many Requests"));
+    assert_eq!(message.detail, None);
+
+    let decoded = proto::ProblemDetails::decode(message.encode_to_vec().as_slice()).unwrap();
+    let restored = ProblemDetails::<JsonExtensions>::from(decoded);
+    assert_eq!(restored.r#type, details.r#type);
+    assert_eq!(restored.status, details.status);
+    assert_eq!(restored.instance, details.instance);
+    assert_eq!(restored.extensions["limit"], 100);
+    assert_eq!(restored.extensions["ratio"], 0.5);
+    assert_eq!(restored.extensions["tags"], serde_json::json!(["a", "b"]));
+
+    let status = proto::Status::from(&details);
+    assert_eq!(status.code, 8);
+    assert_eq!(status.message, "Too Many Requests");
+    assert_eq!(
+        status.details[0].type_url,
+        "type.googleapis.com/problem_details.v1.ProblemDetails"
+    );
+    let restored = ProblemDetails::<JsonExtensions>::from(status);
+    assert_eq!(restored.extensions["limit"], 100);
+
+    // Plain gRPC errors without attached problem details
+    let status = proto::Status {
+        code: 14,
+        message: "backend unavailable".to_string(),
+        details: Vec::new(),
+    };
+    let restored = ProblemDetails::<JsonExtensions>::from(status);
+    assert_eq!(restored.status, Some(StatusCode::SERVICE_UNAVAILABLE));
+    assert_eq!(restored.detail.as_deref(), Some("backend unavailable"));
+    assert!(restored.extensions.is_empty());
+
+    assert_eq!(proto::grpc_code(StatusCode::IM_A_TEAPOT), 9);
+    assert_eq!(proto::http_status(42), StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+#[cfg(all(feature = "schemars", feature = "serde"))]
+#[test]
+fn schemars_schema() {
+    use schemars::JsonSchema;
+
+    #[derive(serde::Serialize, JsonSchema)]
+    struct Balance {
+        balance: u32,
+    }
+
+    #[derive(serde::Serialize, JsonSchema)]
+    struct Trace {
+        trace_id: Option<String>,
+    }
+
+    let schema = schemars::schema_for!(ProblemDetails);
+    assert_eq!(schema.get("title").unwrap(), "ProblemDetails");
+    assert_eq!(
+        schema.get("properties").unwrap()["status"],
+        serde_json::json!({ "type": "integer", "format": "uint16", "minimum": 100, "maximum": 999 })
+    );
+    assert_eq!(
+        schema.get("properties").unwrap()["type"]["format"],
+        "uri-reference"
+    );
+    assert!(schema.get("required").is_none());
+
+    let schema = schemars::schema_for!(ProblemDetails<(Balance, Trace)>);
+    let properties = schema.get("properties").unwrap().as_object().unwrap();
+    let names = properties.keys().map(String::as_str).collect::<Vec<_>>();
+    for name in [
+        "type", "status", "title", "detail", "instance", "balance", "trace_id",
+    ] {
+        assert!(names.contains(&name), "{name} missing in {names:?}");
+    }
+    assert_eq!(
+        schema.get("required").unwrap(),
+        &serde_json::json!(["balance"])
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn content_negotiation() {
+    use crate::{ContentNegotiation, ProblemFormat};
+
+    let negotiation = ContentNegotiation::new();
+
+    assert_eq!(negotiation.negotiate(None), ProblemFormat::Json);
+    assert_eq!(negotiation.negotiate(Some("")), ProblemFormat::Json);
+    assert_eq!(negotiation.negotiate(Some("*/*")), ProblemFormat::Json);
+    assert_eq!(
+        negotiation.negotiate(Some("application/json")),
+        ProblemFormat::Json
+    );
+    assert_eq!(
+        negotiation.negotiate(Some("text/plain;q=0.5, application/json;q=0.4")),
+        ProblemFormat::Text
+    );
+    assert_eq!(
+        negotiation.negotiate(Some("text/plain;q=0, */*;q=0.1")),
+        ProblemFormat::Json
+    );
+    assert_eq!(
+        negotiation.negotiate(Some("application/*;q=0, text/plain;q=0.2")),
+        ProblemFormat::Text
+    );
+    assert_eq!(
+        negotiation.negotiate(Some("text/plain;q=invalid")),
+        ProblemFormat::Json
+    );
+
+    let negotiation = ContentNegotiation::new()
+        .with_formats([ProblemFormat::Text, ProblemFormat::Json])
+        .with_default(ProblemFormat::Text);
+    assert_eq!(negotiation.negotiate(Some("*/*")), ProblemFormat::Text);
+    assert_eq!(
+        negotiation.negotiate(Some("image/png")),
+        ProblemFormat::Text
+    );
+
+    let mut headers = http::HeaderMap::new();
+    headers.append(http::header::ACCEPT, "image/png".parse().unwrap());
+    headers.append(http::header::ACCEPT, "text/plain;q=0.9".parse().unwrap());
+    assert_eq!(negotiation.negotiate_headers(&headers), ProblemFormat::Text);
+}
+
+#[cfg(all(feature = "xml", feature = "html"))]
+#[test]
+fn content_negotiation_browser() {
+    use crate::{ContentNegotiation, ProblemFormat};
+
+    let negotiation = ContentNegotiation::new();
+    assert_eq!(
+        negotiation.negotiate(Some(
+            "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8"
+        )),
+        ProblemFormat::Html
+    );
+    assert_eq!(
+        negotiation.negotiate(Some("application/problem+xml, application/json;q=0.5")),
+        ProblemFormat::Xml
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn borrowed_deserialization() {
+    use std::borrow::Cow;
+
+    use crate::ProblemDetailsRef;
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Ext {
+        balance: u32,
+    }
+
+    let json = r#"{
+        "type": "https://example.com/probs/out-of-credit",
+        "status": 403,
+        "title": "You do not have \"enough\" credit.",
+        "detail": "Your current balance is 30, but that costs 50.",
+        "instance": "/account/12345/msgs/abc",
+        "balance": 30
+    }"#;
+
+    let details = ProblemDetailsRef::<Ext>::from_json(json).unwrap();
+    assert!(matches!(
+        details.r#type,
+        Some(Cow::Borrowed("https://example.com/probs/out-of-credit"))
+    ));
+    assert_eq!(details.status, Some(StatusCode::FORBIDDEN));
+    // Escaped strings cannot be borrowed
+    assert!(matches!(details.title, Some(Cow::Owned(_))));
+    assert_eq!(
+        details.title.as_deref(),
+        Some(r#"You do not have "enough" credit."#)
+    );
+    assert!(matches!(details.detail, Some(Cow::Borrowed(_))));
+    assert_eq!(details.extensions, Ext { balance: 30 });
+
+    let owned = details.into_owned().unwrap();
+    assert_eq!(
+        owned,
+        serde_json::from_str::<ProblemDetails<Ext>>(json).unwrap()
+    );
+
+    let details = ProblemDetailsRef::<()>::from_json_slice(b"{\"title\":null}").unwrap();
+    assert_eq!(details, ProblemDetailsRef::default());
+    assert_eq!(details.effective_type(), "about:blank");
+
+    let invalid = ProblemDetailsRef::<()>::from_json(r#"{"instance":"not a uri"}"#).unwrap();
+    assert!(invalid.into_owned().is_err());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn strict_deserialization() {
+    use crate::{DynamicExtensions, StrictProblemDetails};
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Balance {
+        balance: u32,
+    }
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Accounts {
+        accounts: Vec<String>,
+    }
+
+    let json = r#"{"status":403,"balance":30,"accounts":["/account/12345"]}"#;
+
+    let details = StrictProblemDetails::<(Balance, Accounts)>::from_json(json).unwrap();
+    assert_eq!(details.0.status, Some(StatusCode::FORBIDDEN));
+    assert_eq!(details.0.extensions.0, Balance { balance: 30 });
+
+    // The tolerant default ignores the unknown member
+    assert!(serde_json::from_str::<ProblemDetails<Balance>>(json).is_ok());
+    let error = StrictProblemDetails::<Balance>::from_json(json).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("unknown extension member `accounts`"));
+
+    let error = StrictProblemDetails::<()>::from_json(r#"{"title":"A","foo":1}"#).unwrap_err();
+    assert!(error.to_string().contains("unknown extension member `foo`"));
+
+    let error =
+        StrictProblemDetails::<DynamicExtensions>::from_json(r#"{"a":1,"a":2}"#).unwrap_err();
+    assert!(error.to_string().contains("duplicate member `a`"));
+
+    let error = StrictProblemDetails::<()>::from_json(r#"{"title":"A","title":"B"}"#).unwrap_err();
+    assert!(error.to_string().contains("duplicate field `title`"));
+
+    let details =
+        StrictProblemDetails::<DynamicExtensions>::from_json(r#"{"status":404,"a":1}"#).unwrap();
+    let details = ProblemDetails::from(details);
+    assert_eq!(details.extensions["a"], 1);
+
+    let error = StrictProblemDetails::<()>::from_json(r#"{"@xmlns":"urn:example"}"#).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("unknown extension member `@xmlns`"));
+    let details =
+        StrictProblemDetails::<DynamicExtensions>::from_json(r#"{"@xmlns":"urn:example"}"#)
+            .unwrap();
+    assert_eq!(
+        ProblemDetails::from(details).extensions["@xmlns"],
+        "urn:example"
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn canonical_member_order() {
+    use std::collections::HashMap;
+
+    use crate::{ProblemDetailsBuilder, WithUnknown};
+
+    #[derive(Clone, serde::Serialize, serde::Deserialize)]
+    struct Ext {
+        balance: u32,
+        accounts: Vec<String>,
+    }
+
+    let ext = Ext {
+        balance: 30,
+        accounts: vec!["/account/12345".to_string()],
+    };
+
+    let expected = concat!(
+        r#"{"type":"https://example.com/probs/out-of-credit","status":403,"#,
+        r#""title":"Out of credit","detail":"Balance too low","instance":"/account/12345","#,
+        r#""balance":30,"accounts":["/account/12345"]}"#
+    );
+
+    let from_status = ProblemDetails::from_status_code(StatusCode::FORBIDDEN)
+        .with_extensions(ext.clone())
+        .with_instance(Uri::from_static("/account/12345"))
+        .with_detail("Balance too low")
+        .with_title("Out of credit")
+        .with_type(Uri::from_static("https://example.com/probs/out-of-credit"));
+    let from_new = ProblemDetails::new()
+        .with_title("Out of credit")
+        .with_detail("Balance too low")
+        .with_extensions(ext.clone())
+        .with_type(Uri::from_static("https://example.com/probs/out-of-credit"))
+        .with_status(StatusCode::FORBIDDEN)
+        .with_instance(Uri::from_static("/account/12345"));
+    let from_builder = ProblemDetailsBuilder::new()
+        .with_extensions(ext)
+        .with_instance(Uri::from_static("/account/12345"))
+        .with_status(StatusCode::FORBIDDEN)
+        .with_detail("Balance too low")
+        .with_type(Uri::from_static("https://example.com/probs/out-of-credit"))
+        .with_title("Out of credit")
+        .build();
+    let from_json: ProblemDetails<Ext> = serde_json::from_str(
+        r#"{"accounts":["/account/12345"],"instance":"/account/12345","balance":30,
+            "detail":"Balance too low","title":"Out of credit","status":403,
+            "type":"https://example.com/probs/out-of-credit"}"#,
+    )
+    .unwrap();
+
+    for details in [from_status, from_new, from_builder, from_json] {
+        assert_eq!(serde_json::to_string(&details).unwrap(), expected);
+    }
+
+    let unknown: HashMap<_, _> = ["d", "b", "a", "c"]
+        .into_iter()
+        .map(|key| (key.to_string(), json!(1)))
+        .collect();
+    let details = ProblemDetails::new().with_extensions(WithUnknown { known: (), unknown });
+    assert_eq!(
+        serde_json::to_string(&details).unwrap(),
+        r#"{"a":1,"b":1,"c":1,"d":1}"#
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_to_writer() {
+    use crate::JsonProblemDetails;
+
+    let errors = (0..1000)
+        .map(|i| json!({ "pointer": format!("/items/{i}"), "detail": "invalid" }))
+        .collect::<Vec<_>>();
+    let details: JsonProblemDetails<_> = ProblemDetails::from_status_code(StatusCode::BAD_REQUEST)
+        .with_extension("errors", errors)
+        .into();
+
+    let mut body = Vec::new();
+    details.to_writer(&mut body).unwrap();
+
+    assert_eq!(body, details.to_body_string().unwrap().into_bytes());
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn json_to_writer_async() {
+    use crate::JsonProblemDetails;
+
+    let details: JsonProblemDetails =
+        ProblemDetails::from_status_code(StatusCode::NOT_FOUND).into();
+
+    let mut body = Vec::new();
+    details.to_writer_async(&mut body).await.unwrap();
+
+    assert_eq!(body, br#"{"status":404,"title":"Not Found"}"#);
+}
+
+#[cfg(all(feature = "xml", feature = "json"))]
+#[test]
+fn xml_writers() {
+    use std::io;
+
+    use crate::{XmlFormat, XmlProblemDetails};
+
+    let details: XmlProblemDetails<_> = ProblemDetails::from_status_code(StatusCode::FORBIDDEN)
+        .with_extension("accounts", vec!["/account/12345", "/account/67890"])
+        .into();
+
+    let mut body = Vec::new();
+    details.to_writer(&mut body).unwrap();
+    assert_eq!(body, details.to_body_string().unwrap().into_bytes());
+    assert_eq!(details.to_body_bytes().unwrap(), body);
+
+    for format in [XmlFormat::Rfc7807, XmlFormat::Legacy] {
+        let mut xml = String::new();
+        details.to_fmt_writer_with_format(&mut xml, format).unwrap();
+        assert_eq!(xml, details.to_body_string_with_format(format).unwrap());
+    }
+
+    struct Failing;
+
+    impl io::Write for Failing {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let error = details.to_writer(Failing).unwrap_err();
+    assert!(error.to_string().contains("closed"));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn ndjson_batch() {
+    use crate::ProblemBatch;
+
+    let batch: ProblemBatch<_> = (1..=3)
+        .map(|record| {
+            ProblemDetails::from_status_code(StatusCode::BAD_REQUEST)
+                .with_detail(format!("Record {record} is invalid"))
+                .with_extension("record", record)
+        })
+        .collect();
+
+    let body = batch.to_body_string().unwrap();
+    assert_eq!(body.lines().count(), 3);
+    assert!(body.ends_with('\n'));
+    assert_eq!(
+        body.lines().next().unwrap(),
+        r#"{"status":400,"title":"Bad Request","detail":"Record 1 is invalid","record":1}"#
+    );
+
+    let parsed = ProblemBatch::<crate::JsonExtensions>::from_body_str(&body).unwrap();
+    assert_eq!(parsed.problems.len(), 3);
+    assert_eq!(parsed.problems[2].extensions["record"], 3);
+
+    let records = ProblemBatch::<()>::read_records(body.as_bytes())
+        .map(|record| record.unwrap().detail.unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(records[1], "Record 2 is invalid");
+
+    let error =
+        ProblemBatch::<()>::from_body_str("{\"status\":404}\n{\"status\":1}\n").unwrap_err();
+    assert_eq!(error.line(), 2);
+
+    assert_eq!(ProblemBatch::<()>::new().to_body_string().unwrap(), "");
+    assert!(ProblemBatch::<()>::from_body_str("")
+        .unwrap()
+        .problems
+        .is_empty());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn multi_status() {
+    use crate::{MultiStatusProblems, SubResponse};
+
+    let response = MultiStatusProblems::new()
+        .with_success(StatusCode::CREATED, json!({ "id": 1 }))
+        .with_problem(
+            ProblemDetails::from_status_code(StatusCode::CONFLICT).with_extension("id", 2),
+        );
+
+    assert!(response.has_problems());
+    assert_eq!(
+        response
+            .responses
+            .iter()
+            .map(SubResponse::status)
+            .collect::<Vec<_>>(),
+        [StatusCode::CREATED, StatusCode::CONFLICT]
+    );
+    assert_eq!(
+        response.to_body_string().unwrap(),
+        concat!(
+            r#"{"responses":[{"status":201,"body":{"id":1}},"#,
+            r#"{"status":409,"problem":{"status":409,"title":"Conflict","id":2}}]}"#
+        )
+    );
+
+    let all_ok: MultiStatusProblems<u32> = [Ok(1), Ok(2)].into_iter().collect();
+    assert!(!all_ok.has_problems());
+
+    #[cfg(feature = "axum")]
+    {
+        use axum::response::IntoResponse;
+
+        let response = response.clone().into_response();
+        assert_eq!(response.status(), StatusCode::MULTI_STATUS);
+        assert_eq!(
+            response.headers()[http::header::CONTENT_TYPE],
+            "application/json"
+        );
+    }
+
+    #[cfg(feature = "poem")]
+    {
+        use poem::IntoResponse;
+
+        let response = response.into_response();
+        assert_eq!(response.status(), StatusCode::MULTI_STATUS);
+        assert_eq!(
+            response.headers()[http::header::CONTENT_TYPE],
+            "application/json"
+        );
+    }
+}
+
+#[cfg(feature = "jsonapi")]
+#[test]
+fn jsonapi_conversion() {
+    use crate::{jsonapi, JsonExtensions};
+
+    let details = ProblemDetails::from_status_code(StatusCode::FORBIDDEN)
+        .with_type(Uri::from_static("https://example.com/probs/out-of-credit"))
+        .with_instance(Uri::from_static("/account/12345/msgs/abc"))
+        .with_extension("code", "OUT_OF_CREDIT")
+        .with_extension("balance", 30);
+
+    let document: jsonapi::ErrorDocument = [details.clone()].into_iter().collect();
+    assert_eq!(
+        serde_json::to_value(&document).unwrap(),
+        json!({
+            "errors": [{
+                "links": {
+                    "about": "/account/12345/msgs/abc",
+                    "type": "https://example.com/probs/out-of-credit",
+                },
+                "status": "403",
+                "code": "OUT_OF_CREDIT",
+                "title": "Forbidden",
+                "meta": { "balance": 30 },
+            }]
+        })
+    );
+
+    let restored = ProblemDetails::<JsonExtensions>::from(document.errors[0].clone());
+    assert_eq!(restored.r#type, details.r#type);
+    assert_eq!(restored.instance, details.instance);
+    assert_eq!(restored.status, details.status);
+    assert_eq!(restored.extensions["code"], "OUT_OF_CREDIT");
+    assert_eq!(restored.extensions["balance"], 30);
+
+    let error: jsonapi::ErrorObject = serde_json::from_value(json!({
+        "status": "not a status",
+        "source": { "parameter": "page" },
+        "meta": { "status": 500, "trace": "abc" },
+    }))
+    .unwrap();
+    let restored = ProblemDetails::<JsonExtensions>::from(error);
+    assert_eq!(restored.status, None);
+    assert_eq!(restored.extensions["parameter"], "page");
+    assert_eq!(restored.extensions["trace"], "abc");
+    assert!(!restored.extensions.contains_key("status"));
+}
+
+#[cfg(feature = "odata")]
+#[test]
+fn odata_conversion() {
+    use crate::{odata, JsonExtensions};
+
+    let details = ProblemDetails::from_status_code(StatusCode::BAD_REQUEST)
+        .with_type(Uri::from_static("https://example.com/probs/validation"))
+        .with_instance(Uri::from_static("/orders/42"))
+        .with_extension(
+            "details",
+            json!([{ "code": "NullValue", "message": "Name is required.", "target": "Name" }]),
+        )
+        .with_extension("traceId", "abc");
+
+    let response = odata::ErrorResponse::from(&details);
+    assert_eq!(
+        serde_json::to_value(&response).unwrap(),
+        json!({
+            "error": {
+                "code": "400",
+                "message": "Bad Request",
+                "details": [{ "code": "NullValue", "message": "Name is required.", "target": "Name" }],
+                "innererror": {
+                    "type": "https://example.com/probs/validation",
+                    "instance": "/orders/42",
+                    "traceId": "abc",
+                },
+            }
+        })
+    );
+
+    let restored = ProblemDetails::<JsonExtensions>::from(response);
+    assert_eq!(restored.r#type, details.r#type);
+    assert_eq!(restored.instance, details.instance);
+    assert_eq!(restored.detail.as_deref(), Some("Bad Request"));
+    assert_eq!(restored.extensions["details"][0]["target"], "Name");
+    assert_eq!(restored.extensions["traceId"], "abc");
+
+    let response: odata::ErrorResponse = serde_json::from_value(json!({
+        "error": { "code": "Forbidden", "message": "No access", "innererror": { "status": 1 } }
+    }))
+    .unwrap();
+    let restored = ProblemDetails::<JsonExtensions>::from(response);
+    assert_eq!(restored.extensions["code"], "Forbidden");
+    assert!(!restored.extensions.contains_key("status"));
+}
+
+#[cfg(feature = "aws")]
+#[test]
+fn aws_proxy_response() {
+    use http::{header, HeaderValue};
+
+    use crate::aws;
+
+    let mut details = ProblemDetails::from_status_code(StatusCode::METHOD_NOT_ALLOWED)
+        .with_header(header::ALLOW, HeaderValue::from_static("GET"));
+    details
+        .headers_mut()
+        .append(header::ALLOW, HeaderValue::from_static("POST"));
+    let response = aws::ProxyResponse::from(details);
+    assert_eq!(response.status_code, 405);
+    assert_eq!(response.headers["allow"], "GET,POST");
+    assert_eq!(response.headers["content-type"], "application/problem+json");
+    assert_eq!(
+        response.body,
+        r#"{"status":405,"title":"Method Not Allowed"}"#
+    );
+
+    let details = aws::parse_gateway_error(
+        StatusCode::TOO_MANY_REQUESTS,
+        r#"{"Message":"Rate exceeded","requestId":"abc"}"#,
+    )
+    .unwrap();
+    assert_eq!(details.status, Some(StatusCode::TOO_MANY_REQUESTS));
+    assert_eq!(details.detail.as_deref(), Some("Rate exceeded"));
+    assert_eq!(details.extensions["requestId"], "abc");
+    assert!(!details.extensions.contains_key("Message"));
+
+    let details = aws::parse_gateway_error(
+        StatusCode::BAD_GATEWAY,
+        r#"{"title":"Upstream failed","detail":"Timeout"}"#,
+    )
+    .unwrap();
+    assert_eq!(details.status, Some(StatusCode::BAD_GATEWAY));
+    assert_eq!(details.title.as_deref(), Some("Upstream failed"));
+
+    assert!(aws::parse_gateway_error(StatusCode::BAD_GATEWAY, "Internal error").is_err());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn rfc7807_compatibility() {
+    let details = ProblemDetails::from_status_code(StatusCode::CONFLICT)
+        .with_extension("resource", "order")
+        .with_extension("previous", serde_json::Value::Null);
+
+    assert_eq!(
+        serde_json::to_value(&details).unwrap(),
+        json!({ "status": 409, "title": "Conflict", "resource": "order", "previous": null })
+    );
+    assert_eq!(
+        serde_json::to_value(details.rfc7807_compatible()).unwrap(),
+        json!({ "type": "about:blank", "status": 409, "title": "Conflict", "resource": "order" })
+    );
+
+    let typed = ProblemDetails::new().with_type(Uri::from_static("https://example.com/probs/x"));
+    assert_eq!(
+        typed.rfc7807_compatible().r#type,
+        Some(ProblemType::from(Uri::from_static(
+            "https://example.com/probs/x"
+        )))
+    );
+}
+
+#[cfg(feature = "serde_path_to_error")]
+#[test]
+fn json_path_errors() {
+    use crate::{from_json_slice_with_path, from_json_with_path, ValidationErrors};
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Body {
+        #[allow(dead_code)]
+        errors: Vec<crate::FieldError>,
+    }
+
+    let error = from_json_with_path::<Body>(
+        r#"{"errors":[{"detail":"a","pointer":"/a"},{"detail":"b","pointer":3}]}"#,
+    )
+    .unwrap_err();
+    assert_eq!(error.path(), "errors[1].pointer");
+    assert_eq!(error.pointer(), "/errors/1/pointer");
+    assert!(error.to_string().starts_with("errors[1].pointer: "));
+
+    // Extension members are buffered, so their errors have no path
+    let error =
+        ProblemDetails::<ValidationErrors>::from_json_with_path(r#"{"errors":3}"#).unwrap_err();
+    assert_eq!(error.path(), ".");
+
+    let error = ProblemDetails::<()>::from_json_with_path(r#"{"status":"x"}"#).unwrap_err();
+    assert_eq!(error.path(), "status");
+
+    let details = ProblemDetails::<ValidationErrors>::from(error);
+    assert_eq!(details.status, Some(StatusCode::BAD_REQUEST));
+    assert!(details
+        .detail
+        .as_deref()
+        .unwrap()
+        .starts_with("Invalid request body at `status`: "));
+    assert_eq!(details.extensions.errors[0].pointer, "/status");
+
+    let error = from_json_slice_with_path::<serde_json::Value>(b"{} {}").unwrap_err();
+    assert_eq!(error.path(), ".");
+    assert_eq!(error.pointer(), "");
+    let details = ProblemDetails::<ValidationErrors>::from(error);
+    assert!(details
+        .detail
+        .as_deref()
+        .unwrap()
+        .starts_with("Invalid request body: "));
+
+    let value: ProblemDetails = from_json_with_path(r#"{"status":404}"#).unwrap();
+    assert_eq!(value.status, Some(StatusCode::NOT_FOUND));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn pretty_json() {
+    use crate::JsonProblemDetails;
+
+    let details = ProblemDetails::from_status_code(StatusCode::BAD_REQUEST)
+        .with_type(Uri::from_static("https://example.com/probs/invalid"))
+        .with_detail("Invalid order")
+        .with_extension("zone", "eu")
+        .with_extension("amount", json!({ "value": 3 }))
+        .with_extension("code", "E1");
+
+    let body = JsonProblemDetails::from(details)
+        .to_body_string_pretty()
+        .unwrap();
+    assert_eq!(
+        body,
+        r#"{
+  "type": "https://example.com/probs/invalid",
+  "status": 400,
+  "title": "Bad Request",
+  "detail": "Invalid order",
+  "zone": "eu",
+  "amount": {
+    "value": 3
+  },
+  "code": "E1"
+}"#
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_conversions() {
+    use crate::{DynamicExtensions, JsonError};
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Balance {
+        balance: i64,
+    }
+
+    let json = json!({ "status": 403, "title": "Forbidden", "balance": 30 });
+
+    let typed = ProblemDetails::<Balance>::from_json_value(json.clone()).unwrap();
+    assert_eq!(typed.extensions, Balance { balance: 30 });
+    assert_eq!(typed.to_json_value().unwrap(), json);
+
+    let dynamic = ProblemDetails::<DynamicExtensions>::from_json_slice(
+        serde_json::to_vec(&json).unwrap().as_slice(),
+    )
+    .unwrap();
+    assert_eq!(dynamic.status, Some(StatusCode::FORBIDDEN));
+    assert_eq!(dynamic.to_json_value().unwrap(), json);
+
+    let parsed = ProblemDetails::<Balance>::from_json(&json.to_string()).unwrap();
+    assert_eq!(parsed, typed);
+
+    let error = ProblemDetails::<Balance>::from_json(r#"{"status":403}"#).unwrap_err();
+    assert!(matches!(error, JsonError::Deserialization(_)));
+    assert!(error.to_string().starts_with("Could not read body: "));
+}
+
+#[cfg(feature = "http02")]
+#[test]
+fn http02_conversions() {
+    use crate::http02::{from_http02_header_map, to_http02_header_map};
+
+    let mut headers = http02::HeaderMap::new();
+    headers.append(http02::header::VARY, "accept".parse().unwrap());
+    headers.append(http02::header::VARY, "origin".parse().unwrap());
+
+    let details = ProblemDetails::new()
+        .with_header(http::header::VARY, http::HeaderValue::from_static("cookie"))
+        .with_header(http::header::ALLOW, http::HeaderValue::from_static("GET"))
+        .with_http02_status(http02::StatusCode::METHOD_NOT_ALLOWED)
+        .with_http02_instance(http02::Uri::from_static("https://example.com/orders?id=1"))
+        .with_http02_headers(&headers);
+
+    assert_eq!(details.status, Some(StatusCode::METHOD_NOT_ALLOWED));
+    assert_eq!(
+        details.instance,
+        Some(Uri::from_static("https://example.com/orders?id=1"))
+    );
+    assert_eq!(
+        details
+            .headers()
+            .get_all(http::header::VARY)
+            .iter()
+            .collect::<Vec<_>>(),
+        ["accept", "origin"]
+    );
+    assert_eq!(details.headers()[http::header::ALLOW], "GET");
+
+    let converted = details.http02_headers();
+    assert_eq!(converted.get_all(http02::header::VARY).iter().count(), 2);
+    assert_eq!(converted[http02::header::ALLOW], "GET");
+    assert_eq!(from_http02_header_map(&converted), *details.headers());
+    assert_eq!(to_http02_header_map(details.headers()), converted);
+    assert_eq!(
+        details.http02_status(),
+        Some(http02::StatusCode::METHOD_NOT_ALLOWED)
+    );
+}
+
+#[cfg(all(feature = "url", feature = "serde"))]
+#[test]
+fn url_support() {
+    use url::Url;
+
+    let r#type = Url::parse("https://example.com/probs/out-of-credit#balance").unwrap();
+    let instance = Url::parse("https://example.com/account/12345?page=1").unwrap();
+
+    let details = ProblemDetails::new()
+        .try_with_type_url(&r#type)
+        .unwrap()
+        .try_with_instance_url(&instance)
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&details).unwrap(),
+        json!({
+            "type": "https://example.com/probs/out-of-credit",
+            "instance": "https://example.com/account/12345?page=1",
+        })
+    );
+    assert_eq!(
+        details.type_url().unwrap().as_str(),
+        "https://example.com/probs/out-of-credit"
+    );
+    assert_eq!(details.instance_url(), Some(instance));
+
+    let urn = Url::parse("urn:example:problem").unwrap();
+    assert!(matches!(
+        ProblemType::try_from(urn),
+        Err(UriError::Invalid(_))
+    ));
+
+    let relative = ProblemDetails::new()
+        .try_with_instance("/account/12345/msgs/abc?page=2")
+        .unwrap();
+    let base = Url::parse("https://example.com/api/").unwrap();
+    assert_eq!(relative.instance_url(), None);
+    assert_eq!(
+        relative.resolve_instance(&base).unwrap().as_str(),
+        "https://example.com/account/12345/msgs/abc?page=2"
+    );
+}
+
+#[cfg(all(feature = "axum", feature = "json"))]
+#[tokio::test]
+async fn axum_rejections() {
+    use axum::extract::{Extension, FromRequest, FromRequestParts, Json, Query};
+    use http::{header, Request};
+
+    use crate::axum::{RejectionExtension, RejectionSource};
+
+    let request = Request::post("/")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(axum::body::Body::from("{"))
+        .unwrap();
+    let rejection = Json::<serde_json::Value>::from_request(request, &())
+        .await
+        .unwrap_err();
+    let details = ProblemDetails::<RejectionExtension>::from(rejection);
+    assert_eq!(details.status, Some(StatusCode::BAD_REQUEST));
+    assert!(details
+        .detail
+        .as_deref()
+        .unwrap()
+        .starts_with("Failed to parse the request body as JSON"));
+    assert_eq!(
+        serde_json::to_value(&details.extensions).unwrap(),
+        json!({ "rejection": "JsonSyntaxError", "source": "body" })
+    );
+
+    let request = Request::post("/")
+        .body(axum::body::Body::from("{}"))
+        .unwrap();
+    let rejection = Json::<serde_json::Value>::from_request(request, &())
+        .await
+        .unwrap_err();
+    let details = ProblemDetails::<RejectionExtension>::from(rejection);
+    assert_eq!(details.status, Some(StatusCode::UNSUPPORTED_MEDIA_TYPE));
+    assert_eq!(details.extensions.source, RejectionSource::Header);
+    assert_eq!(
+        details.extensions.parameter.as_deref(),
+        Some("content-type")
+    );
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Page {
+        #[allow(dead_code)]
+        page: u32,
+    }
+
+    let (mut parts, ()) = Request::get("/?page=x").body(()).unwrap().into_parts();
+    let rejection = Query::<Page>::from_request_parts(&mut parts, &())
+        .await
+        .unwrap_err();
+    let details = ProblemDetails::<RejectionExtension>::from(rejection);
+    assert_eq!(details.status, Some(StatusCode::BAD_REQUEST));
+    assert_eq!(
+        details.extensions.rejection,
+        "FailedToDeserializeQueryString"
+    );
+    assert_eq!(details.extensions.source, RejectionSource::Query);
+
+    let rejection = Extension::<String>::from_request_parts(&mut parts, &())
+        .await
+        .unwrap_err();
+    let details = ProblemDetails::<RejectionExtension>::from(rejection);
+    assert_eq!(details.status, Some(StatusCode::INTERNAL_SERVER_ERROR));
+    assert_eq!(details.detail, None);
+    assert_eq!(details.extensions.rejection, "MissingExtension");
+}
+
+#[cfg(all(feature = "axum", feature = "json"))]
+#[tokio::test]
+async fn axum_problem_rejection() {
+    use axum::extract::{FromRequest, FromRequestParts, Json, Query};
+    use http::Request;
+
+    use crate::axum::RejectionSource;
+    use crate::problem_rejection;
+
+    problem_rejection! {
+        struct ProblemJson<T>(Json<T>) {
+            type: "https://example.com/probs/invalid-body",
+            status: StatusCode::UNPROCESSABLE_ENTITY,
+            source: Body,
+        }
+    }
+
+    problem_rejection! {
+        struct ProblemQuery<T>(Query<T>);
+    }
+
+    let request = Request::post("/")
+        .body(axum::body::Body::from("{}"))
+        .unwrap();
+    let details = ProblemJson::<serde_json::Value>::from_request(request, &())
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(details.status, Some(StatusCode::UNPROCESSABLE_ENTITY));
+    assert_eq!(details.title.as_deref(), Some("Unprocessable Entity"));
+    assert_eq!(
+        details.r#type,
+        Some(Uri::from_static("https://example.com/probs/invalid-body").into())
+    );
+    assert_eq!(details.extensions.rejection, "JsonRejection");
+    assert_eq!(details.extensions.source, RejectionSource::Body);
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Page {
+        page: u32,
+    }
+
+    let (mut parts, ()) = Request::get("/?page=x").body(()).unwrap().into_parts();
+    let details = ProblemQuery::<Page>::from_request_parts(&mut parts, &())
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(details.status, Some(StatusCode::BAD_REQUEST));
+    assert_eq!(details.r#type, None);
+    assert!(details
+        .detail
+        .as_deref()
+        .unwrap()
+        .starts_with("Failed to deserialize query string"));
+    assert_eq!(details.extensions.rejection, "QueryRejection");
+    assert_eq!(details.extensions.source, RejectionSource::Request);
+
+    let (mut parts, ()) = Request::get("/?page=2").body(()).unwrap().into_parts();
+    let ProblemQuery(Query(page)) = ProblemQuery::<Page>::from_request_parts(&mut parts, &())
+        .await
+        .unwrap();
+    assert_eq!(page.page, 2);
+}
+
+#[cfg(all(feature = "axum", feature = "json"))]
+#[tokio::test]
+async fn axum_problem_details_layer() {
+    use axum::body::Body;
+    use axum::routing::get;
+    use http::{header, Request};
+    use tower_service::Service;
+
+    use crate::axum::ProblemDetailsLayer;
+
+    async fn call(
+        app: &mut axum::Router,
+        request: Request<Body>,
+    ) -> (http::response::Parts, String) {
+        std::future::poll_fn(|cx| Service::<Request<Body>>::poll_ready(app, cx))
+            .await
+            .unwrap();
+        let (parts, body) = app.call(request).await.unwrap().into_parts();
+        let body = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        (parts, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    let mut app = axum::Router::new()
+        .route("/", get(|| async { "Hello" }))
+        .route(
+            "/plain",
+            get(|| async { (StatusCode::BAD_REQUEST, "Missing name\n") }),
+        )
+        .route(
+            "/problem",
+            get(|| async { ProblemDetails::from_status_code(StatusCode::CONFLICT) }),
+        )
+        .layer(ProblemDetailsLayer::new());
+
+    let (parts, body) = call(&mut app, Request::get("/").body(Body::empty()).unwrap()).await;
+    assert_eq!(parts.status, StatusCode::OK);
+    assert_eq!(body, "Hello");
+
+    let (parts, body) = call(
+        &mut app,
+        Request::get("/plain").body(Body::empty()).unwrap(),
+    )
+    .await;
+    assert_eq!(parts.status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        parts.headers[header::CONTENT_TYPE],
+        "application/problem+json"
+    );
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&body).unwrap(),
+        json!({ "status": 400, "title": "Bad Request", "detail": "Missing name" })
+    );
+
+    let (parts, body) = call(&mut app, Request::post("/").body(Body::empty()).unwrap()).await;
+    assert_eq!(parts.status, StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(parts.headers[header::ALLOW], "GET,HEAD");
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&body).unwrap(),
+        json!({ "status": 405, "title": "Method Not Allowed" })
+    );
+
+    let (parts, body) = call(
+        &mut app,
+        Request::get("/problem").body(Body::empty()).unwrap(),
+    )
+    .await;
+    assert_eq!(parts.status, StatusCode::CONFLICT);
+    assert_eq!(body, r#"{"status":409,"title":"Conflict"}"#);
+}
+
+#[cfg(all(feature = "axum", feature = "json"))]
+#[tokio::test]
+async fn axum_catch_panic_layer() {
+    use axum::body::Body;
+    use axum::routing::get;
+    use http::Request;
+    use tower_service::Service;
+
+    use crate::axum::CatchPanicProblemLayer;
+
+    async fn call(app: &mut axum::Router, uri: &str) -> (StatusCode, serde_json::Value) {
+        let request = Request::get(uri).body(Body::empty()).unwrap();
+        std::future::poll_fn(|cx| Service::<Request<Body>>::poll_ready(app, cx))
+            .await
+            .unwrap();
+        let (parts, body) = app.call(request).await.unwrap().into_parts();
+        let body = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        (
+            parts.status,
+            serde_json::from_slice(&body).unwrap_or_default(),
+        )
+    }
+
+    async fn panicking() -> &'static str {
+        panic!("database password is hunter2")
+    }
+
+    let routes = axum::Router::new()
+        .route("/", get(|| async { "{}" }))
+        .route("/panic", get(panicking));
+
+    let mut app = routes.clone().layer(CatchPanicProblemLayer::new());
+    assert_eq!(call(&mut app, "/").await.0, StatusCode::OK);
+    assert_eq!(
+        call(&mut app, "/panic").await,
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            json!({ "status": 500, "title": "Internal Server Error" })
+        )
+    );
+
+    let mut app = routes.layer(
+        CatchPanicProblemLayer::new()
+            .with_type(Uri::from_static("https://example.com/probs/internal"))
+            .with_reference_id(|| "ERR-1".to_string()),
+    );
+    let (status, body) = call(&mut app, "/panic").await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(body["type"], "https://example.com/probs/internal");
+    assert_eq!(body["reference_id"], "ERR-1");
+    assert!(!body.to_string().contains("hunter2"));
+}
+
+#[cfg(all(feature = "axum", feature = "json"))]
+#[tokio::test]
+async fn axum_fallbacks() {
+    use axum::body::Body;
+    use axum::routing::get;
+    use http::{header, Request};
+    use tower_service::Service;
+
+    use crate::axum::{method_not_allowed_fallback, not_found_fallback};
+
+    let mut app = axum::Router::new()
+        .route("/orders", get(|| async { "[]" }).put(|| async { "[]" }))
+        .fallback(not_found_fallback)
+        .method_not_allowed_fallback(method_not_allowed_fallback);
+
+    async fn call(
+        app: &mut axum::Router,
+        request: Request<Body>,
+    ) -> (http::response::Parts, serde_json::Value) {
+        std::future::poll_fn(|cx| Service::<Request<Body>>::poll_ready(app, cx))
+            .await
+            .unwrap();
+        let (parts, body) = app.call(request).await.unwrap().into_parts();
+        let body = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        (parts, serde_json::from_slice(&body).unwrap())
+    }
+
+    let request = Request::get("/missing?token=secret")
+        .body(Body::empty())
+        .unwrap();
+    let (parts, body) = call(&mut app, request).await;
+    assert_eq!(parts.status, StatusCode::NOT_FOUND);
+    assert_eq!(
+        parts.headers[header::CONTENT_TYPE],
+        "application/problem+json"
+    );
+    assert_eq!(
+        body,
+        json!({
+            "status": 404,
+            "title": "Not Found",
+            "detail": "No resource found at `/missing`",
+            "instance": "/missing",
+        })
+    );
+
+    let request = Request::delete("/orders").body(Body::empty()).unwrap();
+    let (parts, body) = call(&mut app, request).await;
+    assert_eq!(parts.status, StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(parts.headers[header::ALLOW], "GET,HEAD,PUT");
+    assert_eq!(
+        body,
+        json!({
+            "status": 405,
+            "title": "Method Not Allowed",
+            "detail": "Method `DELETE` is not allowed at `/orders`",
+            "instance": "/orders",
+        })
+    );
+}
+
+#[cfg(all(feature = "axum", feature = "json"))]
+#[tokio::test]
+async fn axum_problem_context_layer() {
+    use axum::body::Body;
+    use axum::routing::get;
+    use http::Request;
+    use tower_service::Service;
+
+    use crate::axum::ProblemContextLayer;
+
+    async fn call(app: &mut axum::Router, request: Request<Body>) -> serde_json::Value {
+        std::future::poll_fn(|cx| Service::<Request<Body>>::poll_ready(app, cx))
+            .await
+            .unwrap();
+        let body = app.call(request).await.unwrap().into_body();
+        let body = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    let routes = axum::Router::new()
+        .route(
+            "/orders/{id}",
+            get(|| async { ProblemDetails::from_status_code(StatusCode::NOT_FOUND) }),
+        )
+        .route(
+            "/custom",
+            get(|| async {
+                ProblemDetails::from_status_code(StatusCode::CONFLICT)
+                    .with_instance(Uri::from_static("/orders/1"))
+                    .with_request_id("handler")
+            }),
+        )
+        .route(
+            "/large",
+            get(|| async {
+                ProblemDetails::from_status_code(StatusCode::BAD_REQUEST)
+                    .with_detail("x".repeat(100_000))
+            }),
+        )
+        .route("/ok", get(|| async { axum::Json(json!({ "ok": true })) }));
+
+    let mut app = routes.clone().layer(ProblemContextLayer::new());
+    let request = Request::get("/orders/42?expand=items")
+        .header("x-request-id", "req-42")
+        .header(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        )
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(
+        call(&mut app, request).await,
+        json!({
+            "status": 404,
+            "title": "Not Found",
+            "instance": "/orders/42",
+            "trace_id": "4bf92f3577b34da6a3ce929d0e0e4736",
+            "span_id": "00f067aa0ba902b7",
+            "request_id": "req-42",
+        })
+    );
+
+    let request = Request::get("/custom")
+        .header("x-request-id", "req-42")
+        .body(Body::empty())
+        .unwrap();
+    let body = call(&mut app, request).await;
+    assert_eq!(body["instance"], "/orders/1");
+    assert_eq!(body["request_id"], "handler");
+
+    let request = Request::get("/ok")
+        .header("x-request-id", "req-42")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(call(&mut app, request).await, json!({ "ok": true }));
+
+    let request = Request::get("/large")
+        .header("x-request-id", "req-42")
+        .body(Body::empty())
+        .unwrap();
+    let body = call(&mut app, request).await;
+    assert_eq!(body["detail"].as_str().map(str::len), Some(100_000));
+    assert!(body.get("request_id").is_none());
+
+    let mut app = routes.layer(
+        ProblemContextLayer::new()
+            .with_instance_template("https://example.com/requests/{request_id}"),
+    );
+    let request = Request::get("/orders/42")
+        .header("x-request-id", "req-42")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(
+        call(&mut app, request).await["instance"],
+        "https://example.com/requests/req-42"
+    );
+    let request = Request::get("/orders/42").body(Body::empty()).unwrap();
+    assert_eq!(
+        call(&mut app, request).await,
+        json!({ "status": 404, "title": "Not Found" })
+    );
+}
+
+#[cfg(all(feature = "axum", feature = "json"))]
+#[tokio::test]
+async fn axum_problem_context_layer_streamed_bodies() {
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use axum::body::{Body, Bytes};
+    use axum::response::Response;
+    use axum::routing::get;
+    use http::{header, Request};
+    use http_body::Frame;
+    use tower_service::Service;
+
+    use crate::axum::ProblemContextLayer;
+
+    /// A problem padded with whitespace, streamed in chunks without known length.
+    struct Chunked {
+        sent: usize,
+        chunks: usize,
+        fail: bool,
+    }
+
+    impl http_body::Body for Chunked {
+        type Data = Bytes;
+        type Error = std::io::Error;
+
+        fn poll_frame(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Frame<Bytes>, Self::Error>>> {
+            if self.sent == self.chunks {
+                return Poll::Ready(None);
+            }
+            self.sent += 1;
+            if self.sent == self.chunks && self.fail {
+                return Poll::Ready(Some(Err(std::io::Error::other("connection reset"))));
+            }
+            let chunk = match self.sent {
+                1 => Bytes::from_static(br#"{"status":400}"#),
+                _ => Bytes::from(vec![b' '; 16 * 1024]),
+            };
+            Poll::Ready(Some(Ok(Frame::data(chunk))))
+        }
+    }
+
+    fn problem(chunks: usize, fail: bool) -> Response {
+        Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .header(header::CONTENT_TYPE, "application/problem+json")
+            .body(Body::new(Chunked {
+                sent: 0,
+                chunks,
+                fail,
+            }))
+            .unwrap()
+    }
+
+    async fn call(app: &mut axum::Router, path: &str) -> Result<Bytes, axum::Error> {
+        std::future::poll_fn(|cx| Service::<Request<Body>>::poll_ready(app, cx))
+            .await
+            .unwrap();
+        let request = Request::get(path)
+            .header("x-request-id", "req-42")
+            .body(Body::empty())
+            .unwrap();
+        let body = app.call(request).await.unwrap().into_body();
+        axum::body::to_bytes(body, usize::MAX).await
+    }
+
+    let mut app = axum::Router::new()
+        .route("/small", get(|| async { problem(2, false) }))
+        .route("/large", get(|| async { problem(6, false) }))
+        .route("/failing", get(|| async { problem(6, true) }))
+        .layer(ProblemContextLayer::new());
+
+    let body = call(&mut app, "/small").await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["request_id"], "req-42");
+
+    // Bodies over the limit are passed on unread
+    let body = call(&mut app, "/large").await.unwrap();
+    assert_eq!(body.len(), r#"{"status":400}"#.len() + 5 * 16 * 1024);
+    assert!(body.starts_with(br#"{"status":400}"#));
+
+    // Failing bodies pass the error on instead of an empty problem
+    assert!(call(&mut app, "/failing").await.is_err());
+}
+
+#[cfg(all(feature = "axum", feature = "json"))]
+#[tokio::test]
+async fn axum_negotiation_layer() {
+    use axum::body::Body;
+    use axum::routing::get;
+    use http::{header, Request};
+    use tower_service::Service;
+
+    use crate::axum::NegotiationLayer;
+
+    async fn call(app: &mut axum::Router, accept: &str) -> (http::response::Parts, String) {
+        let request = Request::get("/")
+            .header(header::ACCEPT, accept)
+            .body(Body::empty())
+            .unwrap();
+        std::future::poll_fn(|cx| Service::<Request<Body>>::poll_ready(app, cx))
+            .await
+            .unwrap();
+        let (parts, body) = app.call(request).await.unwrap().into_parts();
+        let body = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        (parts, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    let mut app = axum::Router::new()
+        .route(
+            "/",
+            get(|| async {
+                ProblemDetails::from_status_code(StatusCode::TOO_MANY_REQUESTS)
+                    .with_detail("slow down")
+                    .with_header(header::RETRY_AFTER, http::HeaderValue::from_static("5"))
+            }),
+        )
+        .layer(NegotiationLayer::new());
+
+    let (parts, body) = call(&mut app, "text/plain").await;
+    assert_eq!(parts.status, StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(
+        parts.headers[header::CONTENT_TYPE],
+        crate::TextProblemDetails::<()>::CONTENT_TYPE
+    );
+    assert_eq!(parts.headers[header::RETRY_AFTER], "5");
+    assert_eq!(parts.headers[header::VARY], "accept");
+    assert!(body.contains("slow down"));
+
+    let (parts, body) = call(&mut app, "application/json").await;
+    assert_eq!(
+        parts.headers[header::CONTENT_TYPE],
+        "application/problem+json"
+    );
+    assert_eq!(parts.headers[header::VARY], "accept");
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&body).unwrap(),
+        json!({ "status": 429, "title": "Too Many Requests", "detail": "slow down" })
+    );
+}
+
+#[cfg(all(feature = "axum-ws", feature = "json"))]
+#[tokio::test]
+async fn axum_websocket_rejections() {
+    use axum::body::Body;
+    use axum::response::Response;
+    use axum::routing::any;
+    use http::{header, Method, Request};
+    use tower_service::Service;
+
+    use crate::axum::ProblemWebSocketUpgrade;
+
+    async fn handler(ProblemWebSocketUpgrade(ws): ProblemWebSocketUpgrade) -> Response {
+        ws.on_upgrade(|_| async {})
+    }
+
+    let mut app = axum::Router::new().route("/ws", any(handler));
+
+    async fn call(
+        app: &mut axum::Router,
+        method: Method,
+        headers: &[(&str, &str)],
+    ) -> (http::response::Parts, serde_json::Value) {
+        let mut request = Request::builder().method(method).uri("/ws");
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        let request = request.body(Body::empty()).unwrap();
+        std::future::poll_fn(|cx| Service::<Request<Body>>::poll_ready(app, cx))
+            .await
+            .unwrap();
+        let (parts, body) = app.call(request).await.unwrap().into_parts();
+        let body = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        (parts, serde_json::from_slice(&body).unwrap())
+    }
+
+    let (parts, body) = call(&mut app, Method::POST, &[]).await;
+    assert_eq!(parts.status, StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(parts.headers[header::ALLOW], "GET");
+    assert_eq!(body["rejection"], "MethodNotGet");
+    assert_eq!(body["source"], "request");
+
+    let (parts, body) = call(&mut app, Method::GET, &[]).await;
+    assert_eq!(parts.status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body,
+        json!({
+            "status": 400,
+            "title": "Bad Request",
+            "detail": "Connection header did not include 'upgrade'",
+            "rejection": "InvalidConnectionHeader",
+            "source": "header",
+            "parameter": "connection",
+        })
+    );
+
+    let upgrade = [
+        ("connection", "upgrade"),
+        ("upgrade", "websocket"),
+        ("sec-websocket-version", "12"),
+        ("sec-websocket-key", "dGhlIHNhbXBsZSBub25jZQ=="),
+    ];
+    let (parts, body) = call(&mut app, Method::GET, &upgrade).await;
+    assert_eq!(parts.status, StatusCode::BAD_REQUEST);
+    assert_eq!(parts.headers[header::SEC_WEBSOCKET_VERSION], "13");
+    assert_eq!(body["parameter"], "sec-websocket-version");
+
+    let mut upgrade = upgrade;
+    upgrade[2].1 = "13";
+    let (parts, body) = call(&mut app, Method::GET, &upgrade).await;
+    assert_eq!(parts.status, StatusCode::UPGRADE_REQUIRED);
+    assert_eq!(parts.headers[header::UPGRADE], "websocket");
+    assert_eq!(body["rejection"], "ConnectionNotUpgradable");
+    assert!(body.get("parameter").is_none());
+}
+
+#[cfg(all(feature = "axum-multipart", feature = "json"))]
+#[tokio::test]
+async fn axum_multipart_rejections() {
+    use axum::body::Body;
+    use axum::extract::multipart::{Multipart, MultipartRejection};
+    use axum::extract::DefaultBodyLimit;
+    use axum::routing::post;
+    use http::{header, Request};
+    use tower_service::Service;
+
+    use crate::axum::RejectionExtension;
+
+    async fn upload(
+        multipart: Result<Multipart, MultipartRejection>,
+    ) -> Result<&'static str, ProblemDetails<RejectionExtension>> {
+        let mut multipart = multipart?;
+        while let Some(field) = multipart.next_field().await? {
+            field.bytes().await?;
+        }
+        Ok("uploaded")
+    }
+
+    let mut app = axum::Router::new()
+        .route("/", post(upload))
+        .layer(DefaultBodyLimit::max(64));
+
+    async fn call(
+        app: &mut axum::Router,
+        content_type: Option<&str>,
+        body: &'static str,
+    ) -> (StatusCode, serde_json::Value) {
+        let mut request = Request::post("/");
+        if let Some(content_type) = content_type {
+            request = request.header(header::CONTENT_TYPE, content_type);
+        }
+        let request = request.body(Body::from(body)).unwrap();
+        std::future::poll_fn(|cx| Service::<Request<Body>>::poll_ready(app, cx))
+            .await
+            .unwrap();
+        let (parts, body) = app.call(request).await.unwrap().into_parts();
+        let body = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        (
+            parts.status,
+            serde_json::from_slice(&body).unwrap_or_default(),
+        )
+    }
+
+    assert_eq!(
+        call(&mut app, None, "").await,
+        (
+            StatusCode::BAD_REQUEST,
+            json!({
+                "status": 400,
+                "title": "Bad Request",
+                "detail": "Invalid `boundary` for `multipart/form-data` request",
+                "rejection": "InvalidBoundary",
+                "source": "header",
+                "parameter": "content-type",
+            })
+        )
+    );
+
+    let content_type = Some("multipart/form-data; boundary=X");
+    let body = "--X\r\nContent-Disposition: form-data; name=\"file\"\r\n\r\nabc\r\n--X--\r\n";
+    assert_eq!(
+        call(&mut app, content_type, body).await,
+        (StatusCode::OK, serde_json::Value::Null)
+    );
+
+    let body = "--X\r\nContent-Disposition: form-data; name=\"file\"\r\n\r\nabc";
+    let (status, body) = call(&mut app, content_type, body).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["rejection"], "IncompleteFieldData");
+    assert_eq!(body["parameter"], "file");
+
+    let body = "--X\r\nContent-Disposition: form-data; name=\"file\"\r\n\r\n\
+        0123456789012345678901234567890123456789012345678901234567890123456789\r\n--X--\r\n";
+    let (status, body) = call(&mut app, content_type, body).await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(body["detail"], "Request payload is too large");
+}
+
+#[cfg(all(feature = "axum", feature = "json"))]
+#[tokio::test]
+async fn axum_problem_details_config() {
+    use axum::body::Body;
+    use axum::routing::get;
+    use axum::Extension;
+    use http::{header, Request};
+    use tower_service::Service;
+
+    use crate::axum::ProblemDetailsConfig;
+    use crate::{ContentNegotiation, ProblemFormat, ProductionPolicy};
+
+    async fn call(
+        app: &mut axum::Router,
+        request: Request<Body>,
+    ) -> (http::response::Parts, String) {
+        std::future::poll_fn(|cx| Service::<Request<Body>>::poll_ready(app, cx))
+            .await
+            .unwrap();
+        let (parts, body) = app.call(request).await.unwrap().into_parts();
+        let body = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        (parts, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    let routes = axum::Router::new()
+        .route(
+            "/",
+            get(|| async {
+                ProblemDetails::from_status_code(StatusCode::INTERNAL_SERVER_ERROR)
+                    .with_detail("db-01.internal refused the connection")
+                    .with_extension("stackTrace", "at main.rs:42")
+            }),
+        )
+        .route(
+            "/config",
+            get(
+                |Extension(config): Extension<ProblemDetailsConfig>| async move {
+                    config.correlation().to_string()
+                },
+            ),
+        );
+
+    let mut app = routes.clone().layer(
+        ProblemDetailsConfig::new()
+            .with_default_type(Uri::from_static("https://example.com/probs/generic"))
+            .with_redaction(ProductionPolicy::default())
+            .with_correlation(true),
+    );
+    let request = Request::get("/")
+        .header("x-request-id", "req-42")
+        .body(Body::empty())
+        .unwrap();
+    let (parts, body) = call(&mut app, request).await;
+    assert_eq!(parts.status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&body).unwrap(),
+        json!({
+            "type": "https://example.com/probs/generic",
+            "status": 500,
+            "title": "Internal Server Error",
+            "request_id": "req-42",
+        })
+    );
+
+    let request = Request::get("/config").body(Body::empty()).unwrap();
+    assert_eq!(call(&mut app, request).await.1, "true");
+
+    let mut app = routes.layer(
+        ProblemDetailsConfig::new()
+            .with_negotiation(ContentNegotiation::new().with_default(ProblemFormat::Text)),
+    );
+    let request = Request::get("/")
+        .header(header::ACCEPT, "image/png")
+        .body(Body::empty())
+        .unwrap();
+    let (parts, body) = call(&mut app, request).await;
+    assert_eq!(
+        parts.headers[header::CONTENT_TYPE],
+        crate::TextProblemDetails::<()>::CONTENT_TYPE
+    );
+    assert!(body.contains("db-01.internal"));
+    assert!(!body.contains("request_id"));
+}
+
+#[cfg(all(feature = "poem", feature = "json"))]
+#[tokio::test]
+async fn poem_problem_details_middleware() {
+    use poem::web::Json;
+    use poem::{get, handler, post, Endpoint, EndpointExt, Request, Route};
+
+    use crate::poem::ProblemDetailsMiddleware;
+
+    #[derive(Debug)]
+    struct OrderShipped(u32);
+
+    impl std::fmt::Display for OrderShipped {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "order {} is already shipped", self.0)
+        }
+    }
+
+    impl std::error::Error for OrderShipped {}
+
+    #[handler]
+    fn create(Json(body): Json<serde_json::Value>) -> Json<serde_json::Value> {
+        Json(body)
+    }
+
+    #[handler]
+    fn cancel() -> poem::Result<()> {
+        Err(poem::Error::new(OrderShipped(42), StatusCode::CONFLICT))
+    }
+
+    #[handler]
+    async fn problem() -> Result<(), ProblemDetails> {
+        Err(ProblemDetails::from_status_code(StatusCode::IM_A_TEAPOT)
+            .with_detail("short and stout"))
+    }
+
+    let app = Route::new()
+        .at("/orders", post(create))
+        .at("/cancel", get(cancel))
+        .at("/problem", get(problem))
+        .with(
+            ProblemDetailsMiddleware::new().map_error(|err: &OrderShipped| {
+                ProblemDetails::from_status_code(StatusCode::CONFLICT)
+                    .with_detail(err.to_string())
+                    .with_extension("order", err.0)
+            }),
+        );
+
+    let call = |request: Request| async {
+        let response = app.call(request).await.unwrap();
+        let status = response.status();
+        let content_type = response.content_type().map(str::to_string);
+        let body = response
+            .into_body()
+            .into_json::<serde_json::Value>()
+            .await
+            .unwrap();
+        (status, content_type.unwrap(), body)
+    };
+
+    assert_eq!(
+        call(Request::builder().uri_str("/missing").finish()).await,
+        (
+            StatusCode::NOT_FOUND,
+            "application/problem+json".to_string(),
+            json!({ "status": 404, "title": "Not Found", "detail": "not found" })
+        )
+    );
+
+    let request = Request::builder()
+        .method(http::Method::POST)
+        .uri_str("/orders")
+        .content_type("application/json")
+        .body("{");
+    let (status, content_type, body) = call(request).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(content_type, "application/problem+json");
+    assert!(body["detail"].is_string());
+
+    assert_eq!(
+        call(Request::builder().uri_str("/cancel").finish()).await.2,
+        json!({
+            "status": 409,
+            "title": "Conflict",
+            "detail": "order 42 is already shipped",
+            "order": 42,
+        })
+    );
+
+    assert_eq!(
+        call(Request::builder().uri_str("/problem").finish())
+            .await
+            .2,
+        json!({ "status": 418, "title": "I'm a teapot", "detail": "short and stout" })
+    );
+}
+
+#[cfg(feature = "poem-openapi")]
+#[test]
+fn poem_openapi_responses() {
+    use poem_openapi::payload::PlainText;
+    use poem_openapi::{ApiResponse, Object, OpenApi, OpenApiService};
+
+    use crate::JsonProblemDetails;
+
+    #[derive(Clone, serde::Serialize, Object)]
+    struct OutOfCredit {
+        balance: u32,
+    }
+
+    crate::openapi_extension_schema!(OutOfCredit);
+
+    #[derive(ApiResponse)]
+    #[allow(dead_code, clippy::large_enum_variant)]
+    enum BuyResponse {
+        #[oai(status = 200)]
+        Ok(PlainText<String>),
+        #[oai(status = 403)]
+        OutOfCredit(JsonProblemDetails<OutOfCredit>),
+    }
+
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/buy", method = "post")]
+        async fn buy(&self) -> BuyResponse {
+            let details = ProblemDetails::from_status_code(StatusCode::FORBIDDEN)
+                .with_extensions(OutOfCredit { balance: 30 });
+            BuyResponse::OutOfCredit(details.into())
+        }
+
+        #[oai(path = "/orders", method = "get")]
+        async fn orders(&self) -> Result<PlainText<String>, ProblemDetails> {
+            Ok(PlainText("[]".to_string()))
+        }
+    }
+
+    let spec = OpenApiService::new(Api, "Shop", "1.0").spec();
+    let spec: serde_json::Value = serde_json::from_str(&spec).unwrap();
+
+    assert_eq!(
+        spec["paths"]["/buy"]["post"]["responses"]["403"]["content"],
+        json!({
+            "application/problem+json": {
+                "schema": { "$ref": "#/components/schemas/ProblemDetails_OutOfCredit" }
+            }
+        })
+    );
+    assert_eq!(
+        spec["paths"]["/orders"]["get"]["responses"]["default"]["content"],
+        json!({
+            "application/problem+json": {
+                "schema": { "$ref": "#/components/schemas/ProblemDetails" }
+            }
+        })
+    );
+
+    let schemas = &spec["components"]["schemas"];
+    assert_eq!(
+        schemas["ProblemDetails_OutOfCredit"]["allOf"],
+        json!([
+            { "$ref": "#/components/schemas/ProblemDetails" },
+            { "$ref": "#/components/schemas/OutOfCredit" }
+        ])
+    );
+    assert_eq!(schemas["ProblemDetails"]["type"], "object");
+    assert_eq!(
+        schemas["ProblemDetails"]["properties"]["type"]["format"],
+        "uri-reference"
+    );
+    assert!(schemas["OutOfCredit"]["properties"]["balance"].is_object());
+}
+
+#[cfg(all(feature = "poem", feature = "json"))]
+#[tokio::test]
+async fn poem_fallbacks() {
+    use poem::{get, handler, Endpoint, Request, Route};
+
+    use crate::poem::{catch_all, ProblemRouteExt};
+
+    #[handler]
+    fn orders() -> &'static str {
+        "[]"
+    }
+
+    let app = Route::new()
+        .at("/orders", get(orders))
+        .at("/api/*path", catch_all())
+        .with_problem_fallback();
+
+    let call = |request: Request| async {
+        let response = app.call(request).await.unwrap();
+        let status = response.status();
+        let body = response
+            .into_body()
+            .into_json::<serde_json::Value>()
+            .await
+            .unwrap();
+        (status, body)
+    };
+
+    assert_eq!(
+        call(Request::builder().uri_str("/missing?token=secret").finish()).await,
+        (
+            StatusCode::NOT_FOUND,
+            json!({
+                "status": 404,
+                "title": "Not Found",
+                "detail": "No resource found at `/missing`",
+                "instance": "/missing",
+            })
+        )
+    );
+
+    assert_eq!(
+        call(Request::builder().uri_str("/api/v2/users").finish())
+            .await
+            .1["instance"],
+        "/api/v2/users"
+    );
+
+    let request = Request::builder()
+        .method(http::Method::DELETE)
+        .uri_str("/orders")
+        .finish();
+    assert_eq!(
+        call(request).await,
+        (
+            StatusCode::METHOD_NOT_ALLOWED,
+            json!({
+                "status": 405,
+                "title": "Method Not Allowed",
+                "detail": "Method `DELETE` is not allowed at `/orders`",
+                "instance": "/orders",
+            })
+        )
+    );
+}
+
+#[cfg(feature = "rocket")]
+mod rocket_handlers {
+    use http::StatusCode;
+    use rocket::{get, post, serde::json::Json};
+
+    use crate::ProblemDetails;
+
+    #[get("/teapot")]
+    pub async fn teapot() -> Result<&'static str, ProblemDetails> {
+        Err(ProblemDetails::from_status_code(StatusCode::IM_A_TEAPOT)
+            .with_detail("short and stout")
+            .with_header(
+                http::header::RETRY_AFTER,
+                http::HeaderValue::from_static("60"),
+            ))
+    }
+
+    #[post("/orders", data = "<order>")]
+    pub fn create_order(order: Json<u32>) -> String {
+        order.to_string()
+    }
+}
+
+#[cfg(feature = "rocket")]
+#[tokio::test]
+async fn rocket_responders_and_catchers() {
+    use rocket::http::{ContentType, Status};
+    use rocket::local::asynchronous::Client;
+    use rocket::routes;
+
+    use rocket_handlers::{create_order, teapot};
+
+    let rocket = rocket::build()
+        .mount("/", routes![teapot, create_order])
+        .register("/", crate::rocket::catchers());
+    let client = Client::tracked(rocket).await.unwrap();
+
+    let response = client.get("/teapot").dispatch().await;
+    assert_eq!(response.status(), Status::ImATeapot);
+    assert_eq!(
+        response.headers().get_one("Content-Type"),
+        Some("application/problem+json")
+    );
+    assert_eq!(response.headers().get_one("Retry-After"), Some("60"));
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&response.into_string().await.unwrap()).unwrap(),
+        json!({
+            "status": 418,
+            "title": "I'm a teapot",
+            "detail": "short and stout",
+        })
+    );
+
+    let response = client.get("/missing?token=secret").dispatch().await;
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&response.into_string().await.unwrap()).unwrap(),
+        json!({
+            "status": 404,
+            "title": "Not Found",
+            "detail": "No resource found at `/missing`",
+            "instance": "/missing",
+        })
+    );
+
+    let response = client
+        .post("/orders")
+        .header(ContentType::JSON)
+        .body("\"forty-two\"")
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&response.into_string().await.unwrap()).unwrap(),
+        json!({
+            "status": 422,
+            "title": "Unprocessable Entity",
+            "instance": "/orders",
+        })
+    );
+}
+
+#[cfg(feature = "warp")]
+#[tokio::test]
+async fn warp_replies_and_recover() {
+    use warp::Filter;
+
+    #[derive(serde::Deserialize)]
+    struct Page {
+        #[allow(dead_code)]
+        page: u32,
+    }
+
+    let teapot = warp::path("teapot").and(warp::get()).map(|| {
+        ProblemDetails::from_status_code(StatusCode::IM_A_TEAPOT)
+            .with_detail("short and stout")
+            .with_header(
+                http::header::RETRY_AFTER,
+                http::HeaderValue::from_static("60"),
+            )
+    });
+    let orders = warp::path("orders").and(warp::path::end());
+    let list = orders
+        .and(warp::get())
+        .and(warp::query::<Page>())
+        .map(|_| "[]");
+    let create = orders
+        .and(warp::post())
+        .and(warp::body::json::<u32>())
+        .map(|id: u32| id.to_string());
+    let routes = teapot.or(list).or(create).recover(crate::warp::recover);
+
+    let response = warp::test::request().path("/teapot").reply(&routes).await;
+    assert_eq!(response.status().as_u16(), 418);
+    assert_eq!(
+        response.headers()["content-type"],
+        "application/problem+json"
+    );
+    assert_eq!(response.headers()["retry-after"], "60");
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(response.body()).unwrap(),
+        json!({
+            "status": 418,
+            "title": "I'm a teapot",
+            "detail": "short and stout",
+        })
+    );
+
+    let response = warp::test::request().path("/missing").reply(&routes).await;
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(response.body()).unwrap(),
+        json!({ "status": 404, "title": "Not Found" })
+    );
+
+    let response = warp::test::request()
+        .path("/orders?page=first")
+        .reply(&routes)
+        .await;
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(response.body()).unwrap(),
+        json!({
+            "status": 400,
+            "title": "Bad Request",
+            "detail": "Invalid query string",
+        })
+    );
+
+    let response = warp::test::request()
+        .method("POST")
+        .path("/orders")
+        .header("content-type", "application/json")
+        .body("\"forty-two\"")
+        .reply(&routes)
+        .await;
+    let body = serde_json::from_slice::<serde_json::Value>(response.body()).unwrap();
+    assert_eq!(body["status"], 400);
+    assert!(body["detail"]
+        .as_str()
+        .unwrap()
+        .starts_with("invalid type: string \"forty-two\""));
+
+    let response = warp::test::request()
+        .method("DELETE")
+        .path("/orders")
+        .reply(&routes)
+        .await;
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(response.body()).unwrap(),
+        json!({ "status": 405, "title": "Method Not Allowed" })
+    );
+}
+
+#[cfg(feature = "salvo")]
+mod salvo_handlers {
+    use http::StatusCode;
+    use salvo::handler;
+    use salvo::http::StatusError;
+
+    use crate::ProblemDetails;
+
+    #[handler]
+    pub async fn teapot() -> Result<&'static str, ProblemDetails> {
+        Err(ProblemDetails::from_status_code(StatusCode::IM_A_TEAPOT)
+            .with_detail("short and stout")
+            .with_header(
+                http::header::RETRY_AFTER,
+                http::HeaderValue::from_static("60"),
+            ))
+    }
+
+    #[handler]
+    pub async fn orders() -> Result<&'static str, StatusError> {
+        Err(StatusError::bad_request().detail("Missing parameter `page`"))
+    }
+
+    #[handler]
+    pub async fn crash() -> Result<&'static str, StatusError> {
+        Err(StatusError::internal_server_error().detail("database is down"))
+    }
+}
+
+#[cfg(feature = "salvo")]
+#[tokio::test]
+async fn salvo_scribes_and_catcher() {
+    use salvo::test::{ResponseExt, TestClient};
+    use salvo::{Router, Service};
+
+    use salvo_handlers::{crash, orders, teapot};
+
+    let router = Router::new()
+        .push(Router::with_path("teapot").get(teapot))
+        .push(Router::with_path("orders").get(orders))
+        .push(Router::with_path("crash").get(crash));
+    let service = Service::new(router).catcher(crate::salvo::catcher());
+
+    let mut response = TestClient::get("http://localhost/teapot")
+        .send(&service)
+        .await;
+    assert_eq!(response.status_code, Some(StatusCode::IM_A_TEAPOT));
+    assert_eq!(
+        response.headers()[http::header::CONTENT_TYPE],
+        "application/problem+json"
+    );
+    assert_eq!(response.headers()[http::header::RETRY_AFTER], "60");
+    assert_eq!(
+        response.take_json::<serde_json::Value>().await.unwrap(),
+        json!({
+            "status": 418,
+            "title": "I'm a teapot",
+            "detail": "short and stout",
+        })
+    );
+
+    let mut response = TestClient::get("http://localhost/missing?token=secret")
+        .send(&service)
+        .await;
+    assert_eq!(
+        response.take_json::<serde_json::Value>().await.unwrap(),
+        json!({
+            "status": 404,
+            "title": "Not Found",
+            "instance": "/missing",
+        })
+    );
+
+    let mut response = TestClient::get("http://localhost/orders")
+        .send(&service)
+        .await;
+    assert_eq!(
+        response.take_json::<serde_json::Value>().await.unwrap(),
+        json!({
+            "status": 400,
+            "title": "Bad Request",
+            "detail": "Missing parameter `page`",
+            "instance": "/orders",
+        })
+    );
+
+    let mut response = TestClient::get("http://localhost/crash")
+        .send(&service)
+        .await;
+    assert_eq!(
+        response.take_json::<serde_json::Value>().await.unwrap(),
+        json!({
+            "status": 500,
+            "title": "Internal Server Error",
+            "instance": "/crash",
+        })
+    );
+}
+
+#[cfg(feature = "gotham")]
+#[test]
+fn gotham_responses_and_handler_errors() {
+    use std::pin::Pin;
+
+    use gotham::handler::{HandlerError, HandlerFuture};
+    use gotham::helpers::http::Body;
+    use gotham::pipeline::{new_pipeline, single_pipeline};
+    use gotham::router::builder::{build_router, DefineSingleRoute, DrawRoutes};
+    use gotham::state::State;
+    use gotham::test::TestServer;
+    use http::Response;
+
+    use crate::gotham::{ProblemDetailsMiddleware, ProblemResponseExtender};
+
+    fn teapot(state: State) -> (State, Result<Response<Body>, ProblemDetails>) {
+        let problem = ProblemDetails::from_status_code(StatusCode::IM_A_TEAPOT)
+            .with_detail("short and stout")
+            .with_header(
+                http::header::RETRY_AFTER,
+                http::HeaderValue::from_static("60"),
+            );
+        (state, Err(problem))
+    }
+
+    fn ship(state: State) -> Pin<Box<HandlerFuture>> {
+        let error = HandlerError::from(
+            ProblemDetails::from_status_code(StatusCode::CONFLICT)
+                .with_detail("Order is already shipped"),
+        );
+        Box::pin(async move { Err((state, error)) })
+    }
+
+    fn crash(state: State) -> Pin<Box<HandlerFuture>> {
+        let error = HandlerError::from(std::io::Error::other("database is down"));
+        Box::pin(async move { Err((state, error)) })
+    }
+
+    let (chain, pipelines) = single_pipeline(new_pipeline().add(ProblemDetailsMiddleware).build());
+    let router = build_router(chain, pipelines, |route| {
+        route.get("/teapot").to(teapot);
+        route.post("/ship").to(ship);
+        route.get("/crash").to(crash);
+        route.add_response_extender(StatusCode::NOT_FOUND, ProblemResponseExtender);
+        route.add_response_extender(StatusCode::METHOD_NOT_ALLOWED, ProblemResponseExtender);
+    });
+    let server = TestServer::new(router).unwrap();
+    let client = server.client();
+
+    let response = client.get("http://localhost/teapot").perform().unwrap();
+    assert_eq!(response.status(), StatusCode::IM_A_TEAPOT);
+    assert_eq!(
+        response.headers()[http::header::CONTENT_TYPE],
+        "application/problem+json"
+    );
+    assert_eq!(response.headers()[http::header::RETRY_AFTER], "60");
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&response.read_body().unwrap()).unwrap(),
+        json!({
+            "status": 418,
+            "title": "I'm a teapot",
+            "detail": "short and stout",
+        })
+    );
+
+    let response = client
+        .post("http://localhost/ship", "", gotham::mime::TEXT_PLAIN)
+        .perform()
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&response.read_body().unwrap()).unwrap(),
+        json!({
+            "status": 409,
+            "title": "Conflict",
+            "detail": "Order is already shipped",
+        })
+    );
+
+    let response = client.get("http://localhost/crash").perform().unwrap();
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&response.read_body().unwrap()).unwrap(),
+        json!({
+            "status": 500,
+            "title": "Internal Server Error",
+            "instance": "/crash",
+        })
+    );
+
+    let response = client
+        .get("http://localhost/missing?token=secret")
+        .perform()
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&response.read_body().unwrap()).unwrap(),
+        json!({
+            "status": 404,
+            "title": "Not Found",
+            "instance": "/missing",
+        })
+    );
+
+    let response = client.delete("http://localhost/teapot").perform().unwrap();
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(response.headers()[http::header::ALLOW], "GET");
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&response.read_body().unwrap()).unwrap(),
+        json!({
+            "status": 405,
+            "title": "Method Not Allowed",
+            "instance": "/teapot",
+        })
+    );
+}
@@ -0,0 +1,88 @@
+use std::fmt::Write;
+
+use crate::ProblemDetails;
+
+/// ProblemDetails that is encoded as plain text when
+/// used with web framework integrations.
+///
+/// This is useful as last-resort representation if a client accepts none of
+/// the structured formats. The first line is the [`Display`](std::fmt::Display)
+/// format of the problem details, followed by one `key: value` line for the
+/// `instance` and each extension member. Extension values are written as JSON.
+///
+/// Extension members are filtered according to the configured
+/// [`extension_filter`](crate::extension_filter). The
+/// [`source`](std::error::Error::source) is never included.
+///
+/// # Example
+///
+/// ```rust
+/// use http::{StatusCode, Uri};
+/// use problem_details::{ProblemDetails, TextProblemDetails};
+///
+/// let details = ProblemDetails::from_status_code(StatusCode::FORBIDDEN)
+///     .with_detail("Your current balance is 30, but that costs 50.")
+///     .with_instance(Uri::from_static("/account/12345/msgs/abc"))
+///     .with_extension("balance", 30);
+///
+/// assert_eq!(
+///     TextProblemDetails::from(details).to_body_string(),
+///     "[about:blank 403] Forbidden: Your current balance is 30, but that costs 50.\n\
+///      instance: /account/12345/msgs/abc\n\
+///      balance: 30\n"
+/// );
+/// ```
+///
+/// Requires feature `json`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TextProblemDetails<Ext = ()>(pub(crate) ProblemDetails<Ext>);
+
+impl<Ext> TextProblemDetails<Ext> {
+    /// The HTTP content type for a plain text problem details.
+    pub const CONTENT_TYPE: &'static str = "text/plain; charset=utf-8";
+}
+
+impl<Ext> TextProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    /// Write this problem details to a plain text string suitable for a response body.
+    ///
+    /// Extensions which cannot be represented as JSON object are omitted.
+    pub fn to_body_string(&self) -> String {
+        let details = match crate::extension_filter() {
+            Some(filter) => self.0.redacted(filter),
+            None => self.0.to_json_extensions(),
+        };
+
+        let mut text = format!("{details}\n");
+        if let Some(instance) = &details.instance {
+            let _ = writeln!(text, "instance: {instance}");
+        }
+        for (key, value) in &details.extensions {
+            let _ = writeln!(text, "{key}: {value}");
+        }
+
+        text
+    }
+}
+
+impl<Ext> From<ProblemDetails<Ext>> for TextProblemDetails<Ext> {
+    fn from(value: ProblemDetails<Ext>) -> Self {
+        Self(value)
+    }
+}
+
+impl<Ext> From<TextProblemDetails<Ext>> for ProblemDetails<Ext> {
+    fn from(value: TextProblemDetails<Ext>) -> Self {
+        value.0
+    }
+}
+
+impl<Ext> std::fmt::Display for TextProblemDetails<Ext> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<Ext> std::error::Error for TextProblemDetails<Ext> where Ext: std::fmt::Debug {}
@@ -1,5 +1,46 @@
+use std::sync::{Arc, OnceLock};
+use std::{fmt, io};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use quick_xml::SeError;
+
 use crate::ProblemDetails;
 
+mod rfc7807;
+
+static XML_FORMAT: OnceLock<XmlFormat> = OnceLock::new();
+
+/// The layout of XML problem details documents.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum XmlFormat {
+    /// The format defined in [RFC 7807, Appendix A](https://www.rfc-editor.org/rfc/rfc7807.html#appendix-A).
+    ///
+    /// The `problem` root element declares the `urn:ietf:rfc:7807` namespace,
+    /// extension objects are written as nested elements and arrays as
+    /// sequence of `i` elements.
+    #[default]
+    Rfc7807,
+
+    /// The format used by previous versions of this crate.
+    ///
+    /// The `problem` root element has no namespace, and arrays are written
+    /// as repeated elements.
+    Legacy,
+}
+
+/// Configures the process-wide format of XML problem details documents.
+///
+/// The format can only be set once. If it was already set, the given format
+/// is returned as error. Defaults to [`XmlFormat::Rfc7807`].
+pub fn set_xml_format(format: XmlFormat) -> Result<(), XmlFormat> {
+    XML_FORMAT.set(format)
+}
+
+/// Returns the process-wide format configured using [`set_xml_format`].
+pub fn xml_format() -> XmlFormat {
+    XML_FORMAT.get().copied().unwrap_or_default()
+}
+
 /// ProblemDetails that is encoded to XML when
 /// used with web framework integrations.
 ///
@@ -28,13 +69,187 @@ where
     Ext: serde::Serialize,
 {
     /// Write this problem details to an XML string suitable for a response body.
+    ///
+    /// The document uses the configured [`xml_format`], or [`XmlFormat::Rfc7807`] in
+    /// the RFC 7807 [`compatibility_mode`](crate::compatibility_mode). With feature `json`,
+    /// extension members are filtered according to the configured `extension_filter`.
+    ///
+    /// ```rust
+    /// use http::StatusCode;
+    /// use problem_details::{ProblemDetails, XmlProblemDetails};
+    ///
+    /// #[derive(serde::Serialize)]
+    /// struct OutOfCredit {
+    ///     balance: u32,
+    ///     accounts: Vec<String>,
+    /// }
+    ///
+    /// let details = ProblemDetails::from_status_code(StatusCode::FORBIDDEN)
+    ///     .with_extensions(OutOfCredit {
+    ///         balance: 30,
+    ///         accounts: vec!["/account/12345".to_string(), "/account/67890".to_string()],
+    ///     });
+    ///
+    /// let xml = XmlProblemDetails::from(details).to_body_string().unwrap();
+    ///
+    /// assert_eq!(
+    ///     xml,
+    ///     concat!(
+    ///         r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+    ///         r#"<problem xmlns="urn:ietf:rfc:7807">"#,
+    ///         "<status>403</status><title>Forbidden</title><balance>30</balance>",
+    ///         "<accounts><i>/account/12345</i><i>/account/67890</i></accounts>",
+    ///         "</problem>",
+    ///     )
+    /// );
+    /// ```
     pub fn to_body_string(&self) -> Result<String, XmlError> {
-        let xml = quick_xml::se::to_string_with_root("problem", &self.0)
-            .map_err(|e| XmlError::Serialization(e))?;
-        let xml = format!(r#"<?xml version="1.0" encoding="UTF-8"?>{}"#, xml);
+        self.to_body_string_with_format(configured_format())
+    }
 
+    /// Write this problem details to an XML string in the given format.
+    ///
+    /// See [`to_body_string`](XmlProblemDetails::to_body_string).
+    pub fn to_body_string_with_format(&self, format: XmlFormat) -> Result<String, XmlError> {
+        let mut xml = String::new();
+        self.to_fmt_writer_with_format(&mut xml, format)?;
         Ok(xml)
     }
+
+    /// Write this problem details to an XML buffer suitable for a response body.
+    ///
+    /// The document is written directly into the buffer used by web frameworks,
+    /// see [`to_body_string`](XmlProblemDetails::to_body_string).
+    pub fn to_body_bytes(&self) -> Result<Bytes, XmlError> {
+        let mut writer = BytesMut::new().writer();
+        self.to_writer(&mut writer)?;
+        Ok(writer.into_inner().freeze())
+    }
+
+    /// Write this problem details as XML document to the given writer.
+    ///
+    /// The XML declaration and the document are streamed to the writer without
+    /// building the document in memory. Consider wrapping unbuffered writers like
+    /// sockets in a [`BufWriter`](std::io::BufWriter).
+    ///
+    /// ```rust
+    /// use http::StatusCode;
+    /// use problem_details::{ProblemDetails, XmlProblemDetails};
+    ///
+    /// let details = XmlProblemDetails::from(ProblemDetails::from_status_code(StatusCode::NOT_FOUND));
+    ///
+    /// let mut body = Vec::new();
+    /// details.to_writer(&mut body).unwrap();
+    ///
+    /// assert_eq!(body, details.to_body_string().unwrap().as_bytes());
+    /// ```
+    pub fn to_writer<W: io::Write>(&self, writer: W) -> Result<(), XmlError> {
+        let mut writer = IoWriter {
+            inner: writer,
+            error: None,
+        };
+
+        self.to_fmt_writer(&mut writer)
+            .map_err(|err| match writer.error {
+                Some(io) => XmlError::Serialization(SeError::Io(Arc::new(io))),
+                None => err,
+            })
+    }
+
+    /// Write this problem details as XML document to the given formatter or string.
+    ///
+    /// The document uses the configured format, see [`to_body_string`](XmlProblemDetails::to_body_string).
+    pub fn to_fmt_writer<W: fmt::Write>(&self, writer: W) -> Result<(), XmlError> {
+        self.to_fmt_writer_with_format(writer, configured_format())
+    }
+
+    /// Write this problem details as XML document in the given format to the given formatter or string.
+    pub fn to_fmt_writer_with_format<W: fmt::Write>(
+        &self,
+        mut writer: W,
+        format: XmlFormat,
+    ) -> Result<(), XmlError> {
+        #[cfg(feature = "json")]
+        if let Some(details) = self.0.for_response() {
+            return write_body(&mut writer, &details, format);
+        }
+
+        write_body(&mut writer, &self.0, format)
+    }
+}
+
+/// Returns the format used for responses.
+fn configured_format() -> XmlFormat {
+    match crate::compatibility_mode() {
+        crate::CompatibilityMode::Rfc7807 => XmlFormat::Rfc7807,
+        crate::CompatibilityMode::Rfc9457 => xml_format(),
+    }
+}
+
+fn write_body<T>(out: &mut dyn fmt::Write, details: &T, format: XmlFormat) -> Result<(), XmlError>
+where
+    T: serde::Serialize,
+{
+    out.write_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#)
+        .map_err(|err| XmlError::Serialization(SeError::Fmt(err)))?;
+
+    match format {
+        XmlFormat::Rfc7807 => rfc7807::to_writer(out, details),
+        XmlFormat::Legacy => quick_xml::se::to_writer_with_root(out, "problem", details).map(drop),
+    }
+    .map_err(XmlError::Serialization)
+}
+
+/// Adapter writing formatted text into an [`io::Write`], keeping the I/O error.
+struct IoWriter<W> {
+    inner: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> fmt::Write for IoWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
+
+impl<Ext> XmlProblemDetails<Ext>
+where
+    Ext: serde::de::DeserializeOwned,
+{
+    /// Read a problem details from an XML string, e.g. a response body.
+    ///
+    /// Both the RFC 7807 and the legacy [`XmlFormat`] are accepted.
+    /// Extensions can be typed structs or maps. Since XML has no typed values,
+    /// scalar members of typed extensions are parsed from their text content.
+    /// Arrays are read from the sequence of `i` elements used by [`XmlFormat::Rfc7807`],
+    /// the repeated elements of the legacy format are not supported.
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use http::StatusCode;
+    /// use problem_details::{ProblemDetails, XmlProblemDetails};
+    ///
+    /// let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+    /// <problem>
+    ///   <status>404</status>
+    ///   <title>Not Found</title>
+    ///   <resource>user</resource>
+    /// </problem>"#;
+    ///
+    /// let details: ProblemDetails<HashMap<String, String>> =
+    ///     XmlProblemDetails::from_body_str(xml).unwrap().into();
+    ///
+    /// assert_eq!(details.status, Some(StatusCode::NOT_FOUND));
+    /// assert_eq!(details.extensions["resource"], "user");
+    /// ```
+    pub fn from_body_str(xml: &str) -> Result<Self, XmlError> {
+        crate::serde::extensions::read_xml(|| quick_xml::de::from_str(xml))
+            .map(Self)
+            .map_err(XmlError::Deserialization)
+    }
 }
 
 impl<Ext> From<ProblemDetails<Ext>> for XmlProblemDetails<Ext> {
@@ -58,18 +273,16 @@ impl<Ext> std::error::Error for XmlProblemDetails<Ext> where Ext: std::fmt::Debu
 
 #[derive(Clone, Debug)]
 pub enum XmlError {
-    Serialization(quick_xml::SeError),
+    Serialization(SeError),
+    Deserialization(quick_xml::DeError),
 }
 
 impl std::fmt::Display for XmlError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "Could not write body: {}",
-            match self {
-                Self::Serialization(err) => err,
-            }
-        )
+        match self {
+            Self::Serialization(err) => write!(f, "Could not write body: {err}"),
+            Self::Deserialization(err) => write!(f, "Could not read body: {err}"),
+        }
     }
 }
 
@@ -77,6 +290,7 @@ impl std::error::Error for XmlError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Self::Serialization(err) => Some(err),
+            Self::Deserialization(err) => Some(err),
         }
     }
 }
@@ -0,0 +1,523 @@
+//! Writer for the XML format defined in RFC 7807, Appendix A.
+//!
+//! Members are written as elements named after their key, objects as nested
+//! elements and arrays as sequence of `i` elements.
+use std::fmt::Write;
+
+use quick_xml::SeError;
+use serde::ser::{self, Impossible, Serialize};
+
+/// The namespace of problem details XML documents.
+pub(crate) const NAMESPACE: &str = "urn:ietf:rfc:7807";
+
+/// Writes the given value as `problem` root element in the RFC 7807 namespace.
+pub(crate) fn to_writer<T>(out: &mut dyn Write, value: &T) -> Result<(), SeError>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(ElementSerializer {
+        out,
+        name: "problem",
+        namespace: Some(NAMESPACE),
+    })
+}
+
+fn unsupported(kind: &'static str) -> SeError {
+    SeError::Unsupported(kind.into())
+}
+
+/// Returns true if the given member name can be used as XML element name.
+fn is_element_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_alphabetic() || first == '_')
+        && chars.all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+fn write_escaped(out: &mut dyn Write, text: &str) -> Result<(), SeError> {
+    for c in text.chars() {
+        match c {
+            '&' => out.write_str("&amp;")?,
+            '<' => out.write_str("&lt;")?,
+            '>' => out.write_str("&gt;")?,
+            c => out.write_char(c)?,
+        }
+    }
+    Ok(())
+}
+
+/// Serializer writing a value as element with the given name.
+struct ElementSerializer<'a> {
+    out: &'a mut dyn Write,
+    name: &'a str,
+    namespace: Option<&'static str>,
+}
+
+impl<'a> ElementSerializer<'a> {
+    fn open(&mut self) -> Result<(), SeError> {
+        write!(self.out, "<{}", self.name)?;
+        if let Some(namespace) = self.namespace {
+            write!(self.out, " xmlns=\"{namespace}\"")?;
+        }
+        self.out.write_char('>')?;
+        Ok(())
+    }
+
+    fn text(mut self, text: &str) -> Result<(), SeError> {
+        self.open()?;
+        write_escaped(self.out, text)?;
+        close(self.out, self.name)
+    }
+
+    fn element(mut self) -> Result<Element<'a>, SeError> {
+        self.open()?;
+        Ok(Element {
+            out: self.out,
+            name: self.name,
+            key: None,
+        })
+    }
+}
+
+fn close(out: &mut dyn Write, name: &str) -> Result<(), SeError> {
+    write!(out, "</{name}>")?;
+    Ok(())
+}
+
+impl<'a> ser::Serializer for ElementSerializer<'a> {
+    type Ok = ();
+    type Error = SeError;
+
+    type SerializeSeq = Element<'a>;
+    type SerializeTuple = Element<'a>;
+    type SerializeTupleStruct = Element<'a>;
+    type SerializeTupleVariant = Impossible<(), SeError>;
+    type SerializeMap = Element<'a>;
+    type SerializeStruct = Element<'a>;
+    type SerializeStructVariant = Impossible<(), SeError>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), SeError> {
+        self.text(if v { "true" } else { "false" })
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), SeError> {
+        self.text(&v.to_string())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), SeError> {
+        self.text(&v.to_string())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), SeError> {
+        self.text(&v.to_string())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), SeError> {
+        self.text(&v.to_string())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), SeError> {
+        self.text(&v.to_string())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), SeError> {
+        self.text(&v.to_string())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), SeError> {
+        self.text(&v.to_string())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), SeError> {
+        self.text(&v.to_string())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), SeError> {
+        self.text(&v.to_string())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), SeError> {
+        self.text(&v.to_string())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), SeError> {
+        self.text(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), SeError> {
+        self.text(v)
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<(), SeError> {
+        Err(unsupported(
+            "bytes cannot be represented in problem details XML",
+        ))
+    }
+
+    fn serialize_none(self) -> Result<(), SeError> {
+        // Absent members are omitted
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), SeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), SeError> {
+        self.text("")
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), SeError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<(), SeError> {
+        self.text(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), SeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), SeError> {
+        let mut element = self.element()?;
+        ser::SerializeMap::serialize_entry(&mut element, variant, value)?;
+        ser::SerializeMap::end(element)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Element<'a>, SeError> {
+        self.element()
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Element<'a>, SeError> {
+        self.element()
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Element<'a>, SeError> {
+        self.element()
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, SeError> {
+        Err(unsupported(
+            "tuple variants cannot be represented in problem details XML",
+        ))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Element<'a>, SeError> {
+        self.element()
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Element<'a>, SeError> {
+        self.element()
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, SeError> {
+        Err(unsupported(
+            "struct variants cannot be represented in problem details XML",
+        ))
+    }
+}
+
+/// An open element, writing sequence items or object members as child elements.
+struct Element<'a> {
+    out: &'a mut dyn Write,
+    name: &'a str,
+    key: Option<String>,
+}
+
+impl Element<'_> {
+    fn child<T: Serialize + ?Sized>(&mut self, name: &str, value: &T) -> Result<(), SeError> {
+        if !is_element_name(name) {
+            return Err(SeError::Unsupported(
+                format!("member name `{name}` is not a valid XML element name").into(),
+            ));
+        }
+
+        value.serialize(ElementSerializer {
+            out: self.out,
+            name,
+            namespace: None,
+        })
+    }
+
+    fn close(self) -> Result<(), SeError> {
+        close(self.out, self.name)
+    }
+}
+
+impl ser::SerializeSeq for Element<'_> {
+    type Ok = ();
+    type Error = SeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SeError> {
+        self.child("i", value)
+    }
+
+    fn end(self) -> Result<(), SeError> {
+        self.close()
+    }
+}
+
+impl ser::SerializeTuple for Element<'_> {
+    type Ok = ();
+    type Error = SeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SeError> {
+        self.child("i", value)
+    }
+
+    fn end(self) -> Result<(), SeError> {
+        self.close()
+    }
+}
+
+impl ser::SerializeTupleStruct for Element<'_> {
+    type Ok = ();
+    type Error = SeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SeError> {
+        self.child("i", value)
+    }
+
+    fn end(self) -> Result<(), SeError> {
+        self.close()
+    }
+}
+
+impl ser::SerializeMap for Element<'_> {
+    type Ok = ();
+    type Error = SeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SeError> {
+        self.key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SeError> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| <SeError as ser::Error>::custom("value without key"))?;
+        self.child(&key, value)
+    }
+
+    fn end(self) -> Result<(), SeError> {
+        self.close()
+    }
+}
+
+impl ser::SerializeStruct for Element<'_> {
+    type Ok = ();
+    type Error = SeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SeError> {
+        self.child(key, value)
+    }
+
+    fn end(self) -> Result<(), SeError> {
+        self.close()
+    }
+}
+
+/// Serializer converting map keys into member names.
+struct KeySerializer;
+
+impl ser::Serializer for KeySerializer {
+    type Ok = String;
+    type Error = SeError;
+
+    type SerializeSeq = Impossible<String, SeError>;
+    type SerializeTuple = Impossible<String, SeError>;
+    type SerializeTupleStruct = Impossible<String, SeError>;
+    type SerializeTupleVariant = Impossible<String, SeError>;
+    type SerializeMap = Impossible<String, SeError>;
+    type SerializeStruct = Impossible<String, SeError>;
+    type SerializeStructVariant = Impossible<String, SeError>;
+
+    fn serialize_bool(self, _v: bool) -> Result<String, SeError> {
+        Err(key_unsupported())
+    }
+
+    fn serialize_i8(self, _v: i8) -> Result<String, SeError> {
+        Err(key_unsupported())
+    }
+
+    fn serialize_i16(self, _v: i16) -> Result<String, SeError> {
+        Err(key_unsupported())
+    }
+
+    fn serialize_i32(self, _v: i32) -> Result<String, SeError> {
+        Err(key_unsupported())
+    }
+
+    fn serialize_i64(self, _v: i64) -> Result<String, SeError> {
+        Err(key_unsupported())
+    }
+
+    fn serialize_u8(self, _v: u8) -> Result<String, SeError> {
+        Err(key_unsupported())
+    }
+
+    fn serialize_u16(self, _v: u16) -> Result<String, SeError> {
+        Err(key_unsupported())
+    }
+
+    fn serialize_u32(self, _v: u32) -> Result<String, SeError> {
+        Err(key_unsupported())
+    }
+
+    fn serialize_u64(self, _v: u64) -> Result<String, SeError> {
+        Err(key_unsupported())
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<String, SeError> {
+        Err(key_unsupported())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<String, SeError> {
+        Err(key_unsupported())
+    }
+
+    fn serialize_char(self, v: char) -> Result<String, SeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<String, SeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<String, SeError> {
+        Err(key_unsupported())
+    }
+
+    fn serialize_none(self) -> Result<String, SeError> {
+        Err(key_unsupported())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<String, SeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<String, SeError> {
+        Err(key_unsupported())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String, SeError> {
+        Err(key_unsupported())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<String, SeError> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, SeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, SeError> {
+        Err(key_unsupported())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, SeError> {
+        Err(key_unsupported())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, SeError> {
+        Err(key_unsupported())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, SeError> {
+        Err(key_unsupported())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, SeError> {
+        Err(key_unsupported())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, SeError> {
+        Err(key_unsupported())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, SeError> {
+        Err(key_unsupported())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, SeError> {
+        Err(key_unsupported())
+    }
+}
+
+fn key_unsupported() -> SeError {
+    unsupported("member names must be strings")
+}
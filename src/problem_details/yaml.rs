@@ -0,0 +1,112 @@
+use crate::ProblemDetails;
+
+/// ProblemDetails that is encoded to YAML when
+/// used with web framework integrations.
+///
+/// YAML has no registered problem details media type, so the body is
+/// returned as `application/yaml`.
+///
+/// # Example
+///
+/// ```rust
+/// use http::StatusCode;
+/// use problem_details::{ProblemDetails, YamlProblemDetails};
+///
+/// let details = ProblemDetails::from_status_code(StatusCode::BAD_REQUEST)
+///     .with_detail("Key `port` must be a number");
+///
+/// let yaml = YamlProblemDetails::from(details).to_body_string().unwrap();
+///
+/// assert_eq!(
+///     yaml,
+///     "status: 400\ntitle: Bad Request\ndetail: Key `port` must be a number\n"
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct YamlProblemDetails<Ext = ()>(pub(crate) ProblemDetails<Ext>);
+
+impl<Ext> YamlProblemDetails<Ext> {
+    /// The HTTP content type for a yaml problem details.
+    pub const CONTENT_TYPE: &'static str = "application/yaml";
+}
+
+impl<Ext> YamlProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    /// Write this problem details to a YAML string suitable for a response body.
+    ///
+    /// With feature `json`, extension members are filtered according to the
+    /// configured `extension_filter`.
+    pub fn to_body_string(&self) -> Result<String, YamlError> {
+        #[cfg(feature = "json")]
+        let yaml = match crate::extension_filter() {
+            Some(filter) => serde_yaml::to_string(&self.0.redacted(filter)),
+            None => serde_yaml::to_string(&self.0),
+        };
+
+        #[cfg(not(feature = "json"))]
+        let yaml = serde_yaml::to_string(&self.0);
+
+        yaml.map_err(YamlError::Serialization)
+    }
+}
+
+impl<Ext> YamlProblemDetails<Ext>
+where
+    Ext: serde::de::DeserializeOwned,
+{
+    /// Read a problem details from a YAML body.
+    pub fn from_body_str(yaml: &str) -> Result<Self, YamlError> {
+        serde_yaml::from_str(yaml)
+            .map(Self)
+            .map_err(YamlError::Deserialization)
+    }
+}
+
+impl<Ext> From<ProblemDetails<Ext>> for YamlProblemDetails<Ext> {
+    fn from(value: ProblemDetails<Ext>) -> Self {
+        Self(value)
+    }
+}
+
+impl<Ext> From<YamlProblemDetails<Ext>> for ProblemDetails<Ext> {
+    fn from(value: YamlProblemDetails<Ext>) -> Self {
+        value.0
+    }
+}
+
+impl<Ext> std::fmt::Display for YamlProblemDetails<Ext> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<Ext> std::error::Error for YamlProblemDetails<Ext> where Ext: std::fmt::Debug {}
+
+/// Error when reading or writing [`YamlProblemDetails`].
+#[derive(Debug)]
+pub enum YamlError {
+    /// The problem details could not be written.
+    Serialization(serde_yaml::Error),
+    /// The body is not a valid problem details.
+    Deserialization(serde_yaml::Error),
+}
+
+impl std::fmt::Display for YamlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Serialization(err) => write!(f, "Could not write body: {err}"),
+            Self::Deserialization(err) => write!(f, "Could not read body: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for YamlError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Serialization(err) => Some(err),
+            Self::Deserialization(err) => Some(err),
+        }
+    }
+}
@@ -14,6 +14,9 @@ use http::Uri;
 /// the [`From`](std::convert::From) trait to convert a given [`Uri`](http::Uri),
 /// or [`Default::default()`] to create a default URI.
 ///
+/// If the URI is only known at runtime, you can use [`TryFrom<&str>`](std::convert::TryFrom)
+/// to parse it, which returns an [`UriError`] for invalid input.
+///
 /// ```rust
 /// use http::Uri;
 /// use problem_details::ProblemType;
@@ -26,11 +29,234 @@ use http::Uri;
 /// // Create a default problem type
 /// let default_type = ProblemType::default();
 /// assert_eq!(default_type.to_string(), "about:blank");
+///
+/// // Parse a problem type from a string
+/// let parsed = ProblemType::try_from("https://example.com/problem").unwrap();
+/// assert_eq!(parsed.to_string(), "https://example.com/problem");
 /// ```
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ProblemType(#[cfg_attr(feature = "serde", serde(with = "crate::serde::uri"))] Uri);
 
+impl ProblemType {
+    /// The schemes accepted by [`ProblemType::parse_strict`].
+    pub const STRICT_SCHEMES: [&'static str; 4] = ["http", "https", "urn", "tag"];
+
+    /// Parses a problem type, requiring it to be an absolute uri with a
+    /// scheme from [`STRICT_SCHEMES`](ProblemType::STRICT_SCHEMES), or `about:blank`.
+    ///
+    /// This ensures problem types remain resolvable identifiers instead of
+    /// arbitrary strings, which [`TryFrom<&str>`](std::convert::TryFrom) would accept.
+    ///
+    /// Note that [`Uri`] cannot represent `urn` and `tag` uris containing more than
+    /// one colon, so these are rejected as invalid uris.
+    ///
+    /// ```rust
+    /// use problem_details::{ProblemType, UriError};
+    ///
+    /// assert!(ProblemType::parse_strict("https://example.com/probs/out-of-credit").is_ok());
+    /// assert!(ProblemType::parse_strict("about:blank").is_ok());
+    ///
+    /// assert!(matches!(
+    ///     ProblemType::parse_strict("out-of-credit"),
+    ///     Err(UriError::NotAbsolute)
+    /// ));
+    /// assert!(matches!(
+    ///     ProblemType::parse_strict("ftp://example.com/probs"),
+    ///     Err(UriError::UnsupportedScheme(_))
+    /// ));
+    /// ```
+    pub fn parse_strict(value: &str) -> Result<Self, UriError> {
+        let problem_type = Self::try_from(value)?;
+
+        if problem_type == Self::default() {
+            return Ok(problem_type);
+        }
+
+        let Some((scheme, _)) = split_scheme(value) else {
+            return Err(UriError::NotAbsolute);
+        };
+
+        if !Self::STRICT_SCHEMES
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(scheme))
+        {
+            return Err(UriError::UnsupportedScheme(scheme.to_string()));
+        }
+
+        Ok(problem_type)
+    }
+
+    /// Returns true if this problem type identifies the same resource as the given uri.
+    ///
+    /// Both uris are normalized before comparing as described in
+    /// [RFC 3986, Section 6.2.2](https://www.rfc-editor.org/rfc/rfc3986.html#section-6.2.2):
+    /// the scheme and host are compared case-insensitively, percent-encodings
+    /// are normalized, default ports are removed and dot-segments are resolved.
+    /// Additionally, a trailing slash in the path is ignored.
+    ///
+    /// ```rust
+    /// use http::Uri;
+    /// use problem_details::ProblemType;
+    ///
+    /// let problem_type = ProblemType::from(Uri::from_static("https://example.com/probs/out-of-credit"));
+    ///
+    /// assert!(problem_type.matches(&Uri::from_static("HTTPS://Example.COM:443/probs/out-of-credit/")));
+    /// assert!(!problem_type.matches(&Uri::from_static("https://example.com/probs/other")));
+    /// ```
+    pub fn matches(&self, uri: &Uri) -> bool {
+        normalize(&self.0.to_string()) == normalize(&uri.to_string())
+    }
+
+    /// Returns this problem type as [`Url`](url::Url), or `None` if it is a relative URI.
+    ///
+    /// Requires feature `url`.
+    #[cfg(feature = "url")]
+    pub fn to_url(&self) -> Option<url::Url> {
+        url::Url::parse(&self.0.to_string()).ok()
+    }
+}
+
+/// Splits the scheme as defined by RFC 3986 from the given uri.
+///
+/// This does not use [`Uri::scheme`], because `http` only recognizes
+/// schemes of uris with an authority (e.g. not `about:blank`).
+pub(crate) fn split_scheme(uri: &str) -> Option<(&str, &str)> {
+    let (scheme, rest) = uri.split_once(':')?;
+    let mut chars = scheme.chars();
+
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+
+    valid.then_some((scheme, rest))
+}
+
+/// Normalizes a uri for comparison.
+fn normalize(uri: &str) -> String {
+    let (uri, fragment) = match uri.split_once('#') {
+        Some((uri, fragment)) => (uri, Some(fragment)),
+        None => (uri, None),
+    };
+    let (uri, query) = match uri.split_once('?') {
+        Some((uri, query)) => (uri, Some(query)),
+        None => (uri, None),
+    };
+
+    let (scheme, rest) = match split_scheme(uri) {
+        Some((scheme, rest)) => (Some(scheme.to_ascii_lowercase()), rest),
+        None => (None, uri),
+    };
+
+    let (authority, path) = match rest.strip_prefix("//") {
+        Some(rest) => {
+            let end = rest.find('/').unwrap_or(rest.len());
+            (Some(&rest[..end]), &rest[end..])
+        }
+        None => (None, rest),
+    };
+
+    let mut normalized = String::with_capacity(uri.len());
+
+    if let Some(scheme) = &scheme {
+        normalized.push_str(scheme);
+        normalized.push(':');
+    }
+
+    if let Some(authority) = authority {
+        let (userinfo, host) = match authority.rsplit_once('@') {
+            Some((userinfo, host)) => (Some(userinfo), host),
+            None => (None, authority),
+        };
+        let host = host.to_ascii_lowercase();
+        let default_port = match scheme.as_deref() {
+            Some("http") => Some(":80"),
+            Some("https") => Some(":443"),
+            _ => None,
+        };
+        let host = match default_port {
+            Some(port) => host.strip_suffix(port).unwrap_or(&host),
+            None => &host,
+        };
+        let host = host.strip_suffix(':').unwrap_or(host);
+
+        normalized.push_str("//");
+        if let Some(userinfo) = userinfo {
+            normalized.push_str(&normalize_percent_encoding(userinfo));
+            normalized.push('@');
+        }
+        normalized.push_str(&normalize_percent_encoding(host));
+    }
+
+    let path = normalize_percent_encoding(&remove_dot_segments(path));
+    normalized.push_str(path.strip_suffix('/').unwrap_or(&path));
+
+    if let Some(query) = query {
+        normalized.push('?');
+        normalized.push_str(&normalize_percent_encoding(query));
+    }
+
+    if let Some(fragment) = fragment {
+        normalized.push('#');
+        normalized.push_str(&normalize_percent_encoding(fragment));
+    }
+
+    normalized
+}
+
+/// Uppercases percent-encodings and decodes percent-encoded unreserved characters.
+fn normalize_percent_encoding(value: &str) -> String {
+    let mut normalized = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(index) = rest.find('%') {
+        normalized.push_str(&rest[..index]);
+        let encoded = rest.get(index + 1..index + 3);
+
+        match encoded.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+            Some(byte)
+                if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') =>
+            {
+                normalized.push(byte as char);
+                rest = &rest[index + 3..];
+            }
+            Some(byte) => {
+                normalized.push_str(&format!("%{byte:02X}"));
+                rest = &rest[index + 3..];
+            }
+            None => {
+                normalized.push('%');
+                rest = &rest[index + 1..];
+            }
+        }
+    }
+
+    normalized.push_str(rest);
+    normalized
+}
+
+/// Removes `.` and `..` segments from a path as described in RFC 3986, Section 5.2.4.
+fn remove_dot_segments(path: &str) -> String {
+    let mut segments: Vec<&str> = Vec::new();
+
+    for segment in path.split('/') {
+        match segment {
+            "." => {}
+            ".." => {
+                if segments.len() > 1 {
+                    segments.pop();
+                }
+            }
+            segment => segments.push(segment),
+        }
+    }
+
+    let mut normalized = segments.join("/");
+    if path.ends_with("/.") || path.ends_with("/..") {
+        normalized.push('/');
+    }
+    normalized
+}
+
 impl std::default::Default for ProblemType {
     fn default() -> Self {
         Self(Uri::from_static("about:blank"))
@@ -49,6 +275,35 @@ impl std::convert::From<Uri> for ProblemType {
     }
 }
 
+impl std::convert::TryFrom<&str> for ProblemType {
+    type Error = UriError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let uri = value.parse::<Uri>().map_err(UriError::Invalid)?;
+        Ok(ProblemType(uri))
+    }
+}
+
+/// Fragments are dropped, as they cannot be represented by [`Uri`].
+#[cfg(feature = "url")]
+impl std::convert::TryFrom<&url::Url> for ProblemType {
+    type Error = UriError;
+
+    fn try_from(value: &url::Url) -> Result<Self, Self::Error> {
+        Self::try_from(value.as_str())
+    }
+}
+
+/// Fragments are dropped, as they cannot be represented by [`Uri`].
+#[cfg(feature = "url")]
+impl std::convert::TryFrom<url::Url> for ProblemType {
+    type Error = UriError;
+
+    fn try_from(value: url::Url) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
 impl std::convert::From<ProblemType> for Uri {
     fn from(value: ProblemType) -> Self {
         value.0
@@ -92,3 +347,116 @@ impl std::convert::AsMut<Uri> for ProblemType {
         &mut self.0
     }
 }
+
+/// Error returned when a string could not be parsed into a URI.
+///
+/// This is returned by fallible builders like
+/// [`ProblemDetails::try_with_type`](crate::ProblemDetails::try_with_type)
+/// and [`ProblemDetails::try_with_instance`](crate::ProblemDetails::try_with_instance).
+#[derive(Debug)]
+pub enum UriError {
+    /// The given string is not a valid URI.
+    Invalid(http::uri::InvalidUri),
+
+    /// The given URI is not absolute (see [`ProblemType::parse_strict`]).
+    NotAbsolute,
+
+    /// The given URI has a scheme which is not allowed (see [`ProblemType::parse_strict`]).
+    UnsupportedScheme(String),
+}
+
+impl std::fmt::Display for UriError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid(err) => write!(f, "Could not parse uri: {err}"),
+            Self::NotAbsolute => write!(f, "Uri is not absolute"),
+            Self::UnsupportedScheme(scheme) => write!(f, "Uri scheme {scheme} is not supported"),
+        }
+    }
+}
+
+impl From<http::uri::InvalidUri> for UriError {
+    fn from(err: http::uri::InvalidUri) -> Self {
+        Self::Invalid(err)
+    }
+}
+
+impl From<std::convert::Infallible> for UriError {
+    fn from(err: std::convert::Infallible) -> Self {
+        match err {}
+    }
+}
+
+impl std::error::Error for UriError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Invalid(err) => Some(err),
+            Self::NotAbsolute | Self::UnsupportedScheme(_) => None,
+        }
+    }
+}
+
+/// Strict serde support for [`ProblemType`] fields.
+///
+/// Use this module with `#[serde(with = "problem_details::strict")]` to reject
+/// problem types not accepted by [`ProblemType::parse_strict`] during deserialization.
+/// Use [`strict::opt`](crate::strict::opt) for `Option<ProblemType>` fields.
+///
+/// ```rust
+/// use problem_details::ProblemType;
+///
+/// #[derive(serde::Deserialize)]
+/// struct Config {
+///     #[serde(with = "problem_details::strict")]
+///     fallback_type: ProblemType,
+/// }
+///
+/// let valid = serde_json::json!({ "fallback_type": "https://example.com/probs/generic" });
+/// assert!(serde_json::from_value::<Config>(valid).is_ok());
+///
+/// let invalid = serde_json::json!({ "fallback_type": "generic" });
+/// assert!(serde_json::from_value::<Config>(invalid).is_err());
+/// ```
+#[cfg(feature = "serde")]
+pub mod strict {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::ProblemType;
+
+    /// Serializes a problem type.
+    pub fn serialize<S: Serializer>(value: &ProblemType, serializer: S) -> Result<S::Ok, S::Error> {
+        value.serialize(serializer)
+    }
+
+    /// Deserializes a problem type using [`ProblemType::parse_strict`].
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<ProblemType, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        ProblemType::parse_strict(&value).map_err(serde::de::Error::custom)
+    }
+
+    /// Strict serde support for optional [`ProblemType`] fields.
+    pub mod opt {
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        use super::ProblemType;
+
+        /// Serializes an optional problem type.
+        pub fn serialize<S: Serializer>(
+            value: &Option<ProblemType>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            value.serialize(serializer)
+        }
+
+        /// Deserializes an optional problem type using [`ProblemType::parse_strict`].
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<ProblemType>, D::Error> {
+            Option::<String>::deserialize(deserializer)?
+                .map(|value| ProblemType::parse_strict(&value).map_err(serde::de::Error::custom))
+                .transpose()
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! Predefined problem details for the canonical error status codes.
+//!
+//! There is a constant for every client error (`4xx`) and server error (`5xx`)
+//! status code with a canonical reason in [`StatusCode`]. Informational, success
+//! and redirection status codes do not describe problems and have no constants.
+//!
+//! Each constant is equivalent to calling [`ProblemDetails::from_status_code`]
+//! with the corresponding status code, but can be used in `const` and `static`
+//! contexts without any runtime construction.
+//!
+//! Unlike [`ProblemDetails::from_status_code`], these constants never carry
+//! a captured backtrace (feature `backtrace`).
+//!
+//! ```rust
+//! use http::StatusCode;
+//! use problem_details::{problems, ProblemDetails};
+//!
+//! static NOT_FOUND: ProblemDetails = problems::NOT_FOUND;
+//!
+//! assert_eq!(NOT_FOUND, ProblemDetails::from_status_code(StatusCode::NOT_FOUND));
+//!
+//! let details = problems::NOT_FOUND.with_detail("User 42 does not exist");
+//! assert_eq!(details.title.as_deref(), Some("Not Found"));
+//! ```
+use std::borrow::Cow;
+
+use http::StatusCode;
+
+use crate::ProblemDetails;
+
+const fn problem(status: StatusCode, title: &'static str) -> ProblemDetails {
+    ProblemDetails {
+        r#type: None,
+        status: Some(status),
+        title: Some(Cow::Borrowed(title)),
+        detail: None,
+        instance: None,
+        extensions: (),
+        source: None,
+        #[cfg(feature = "backtrace")]
+        backtrace: None,
+        headers: None,
+    }
+}
+
+macro_rules! problems {
+    ($($(#[$docs:meta])* $name:ident => $title:literal;)+) => {
+        $(
+            $(#[$docs])*
+            pub const $name: ProblemDetails = problem(StatusCode::$name, $title);
+        )+
+
+        #[cfg(test)]
+        fn all() -> Vec<ProblemDetails> {
+            vec![$($name),+]
+        }
+    };
+}
+
+problems! {
+    /// `400 Bad Request`
+    BAD_REQUEST => "Bad Request";
+    /// `401 Unauthorized`
+    UNAUTHORIZED => "Unauthorized";
+    /// `402 Payment Required`
+    PAYMENT_REQUIRED => "Payment Required";
+    /// `403 Forbidden`
+    FORBIDDEN => "Forbidden";
+    /// `404 Not Found`
+    NOT_FOUND => "Not Found";
+    /// `405 Method Not Allowed`
+    METHOD_NOT_ALLOWED => "Method Not Allowed";
+    /// `406 Not Acceptable`
+    NOT_ACCEPTABLE => "Not Acceptable";
+    /// `407 Proxy Authentication Required`
+    PROXY_AUTHENTICATION_REQUIRED => "Proxy Authentication Required";
+    /// `408 Request Timeout`
+    REQUEST_TIMEOUT => "Request Timeout";
+    /// `409 Conflict`
+    CONFLICT => "Conflict";
+    /// `410 Gone`
+    GONE => "Gone";
+    /// `411 Length Required`
+    LENGTH_REQUIRED => "Length Required";
+    /// `412 Precondition Failed`
+    PRECONDITION_FAILED => "Precondition Failed";
+    /// `413 Payload Too Large`
+    PAYLOAD_TOO_LARGE => "Payload Too Large";
+    /// `414 URI Too Long`
+    URI_TOO_LONG => "URI Too Long";
+    /// `415 Unsupported Media Type`
+    UNSUPPORTED_MEDIA_TYPE => "Unsupported Media Type";
+    /// `416 Range Not Satisfiable`
+    RANGE_NOT_SATISFIABLE => "Range Not Satisfiable";
+    /// `417 Expectation Failed`
+    EXPECTATION_FAILED => "Expectation Failed";
+    /// `418 I'm a teapot`
+    IM_A_TEAPOT => "I'm a teapot";
+    /// `421 Misdirected Request`
+    MISDIRECTED_REQUEST => "Misdirected Request";
+    /// `422 Unprocessable Entity`
+    UNPROCESSABLE_ENTITY => "Unprocessable Entity";
+    /// `423 Locked`
+    LOCKED => "Locked";
+    /// `424 Failed Dependency`
+    FAILED_DEPENDENCY => "Failed Dependency";
+    /// `425 Too Early`
+    TOO_EARLY => "Too Early";
+    /// `426 Upgrade Required`
+    UPGRADE_REQUIRED => "Upgrade Required";
+    /// `428 Precondition Required`
+    PRECONDITION_REQUIRED => "Precondition Required";
+    /// `429 Too Many Requests`
+    TOO_MANY_REQUESTS => "Too Many Requests";
+    /// `431 Request Header Fields Too Large`
+    REQUEST_HEADER_FIELDS_TOO_LARGE => "Request Header Fields Too Large";
+    /// `451 Unavailable For Legal Reasons`
+    UNAVAILABLE_FOR_LEGAL_REASONS => "Unavailable For Legal Reasons";
+    /// `500 Internal Server Error`
+    INTERNAL_SERVER_ERROR => "Internal Server Error";
+    /// `501 Not Implemented`
+    NOT_IMPLEMENTED => "Not Implemented";
+    /// `502 Bad Gateway`
+    BAD_GATEWAY => "Bad Gateway";
+    /// `503 Service Unavailable`
+    SERVICE_UNAVAILABLE => "Service Unavailable";
+    /// `504 Gateway Timeout`
+    GATEWAY_TIMEOUT => "Gateway Timeout";
+    /// `505 HTTP Version Not Supported`
+    HTTP_VERSION_NOT_SUPPORTED => "HTTP Version Not Supported";
+    /// `506 Variant Also Negotiates`
+    VARIANT_ALSO_NEGOTIATES => "Variant Also Negotiates";
+    /// `507 Insufficient Storage`
+    INSUFFICIENT_STORAGE => "Insufficient Storage";
+    /// `508 Loop Detected`
+    LOOP_DETECTED => "Loop Detected";
+    /// `510 Not Extended`
+    NOT_EXTENDED => "Not Extended";
+    /// `511 Network Authentication Required`
+    NETWORK_AUTHENTICATION_REQUIRED => "Network Authentication Required";
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+
+    use super::all;
+    use crate::ProblemDetails;
+
+    #[test]
+    fn matches_from_status_code() {
+        for problem in all() {
+            let status = problem.status.unwrap();
+            assert_eq!(problem, ProblemDetails::from_status_code(status));
+        }
+    }
+
+    #[test]
+    fn covers_canonical_error_status_codes() {
+        let statuses = all()
+            .into_iter()
+            .map(|problem| problem.status.unwrap())
+            .collect::<Vec<_>>();
+
+        // Only client and server errors (4xx and 5xx) are covered.
+        for code in 400..600 {
+            let status = StatusCode::from_u16(code).unwrap();
+            assert_eq!(
+                statuses.contains(&status),
+                status.canonical_reason().is_some(),
+                "{status}"
+            );
+        }
+    }
+}
@@ -0,0 +1,308 @@
+//! Protobuf messages for [`ProblemDetails`](crate::ProblemDetails) using [`prost`]. Requires feature `prost`.
+//!
+//! The [`ProblemDetails`] message corresponds to the following definition:
+//!
+//! ```protobuf
+//! syntax = "proto3";
+//!
+//! package problem_details.v1;
+//!
+//! import "google/protobuf/struct.proto";
+//!
+//! message ProblemDetails {
+//!   optional string type = 1;
+//!   optional uint32 status = 2;
+//!   optional string title = 3;
+//!   optional string detail = 4;
+//!   optional string instance = 5;
+//!   google.protobuf.Struct extensions = 6;
+//! }
+//! ```
+//!
+//! To bridge HTTP and gRPC services, problem details can be converted to and from
+//! a [`google.rpc.Status`](Status). The HTTP status is mapped to the corresponding
+//! gRPC status code, the detail (or title) is used as message, and the complete
+//! problem details are attached as [`Any`] detail.
+//!
+//! # Example
+//!
+//! ```rust
+//! use http::StatusCode;
+//! use problem_details::{proto, JsonExtensions, ProblemDetails};
+//!
+//! let details = ProblemDetails::from_status_code(StatusCode::NOT_FOUND)
+//!     .with_detail("No order with id 42")
+//!     .with_extension("orderId", 42);
+//!
+//! let status = proto::Status::from(&details);
+//! assert_eq!(status.code, 5); // NOT_FOUND
+//! assert_eq!(status.message, "No order with id 42");
+//!
+//! let restored = ProblemDetails::<JsonExtensions>::from(status);
+//! assert_eq!(restored.status, Some(StatusCode::NOT_FOUND));
+//! assert_eq!(restored.extensions["orderId"], 42);
+//! ```
+use http::{StatusCode, Uri};
+use prost_types::{value::Kind, ListValue, Struct};
+use serde_json::{Map, Number, Value};
+
+use crate::{JsonExtensions, ProblemType};
+
+pub use prost_types::Any;
+
+/// The protobuf message of a problem details object.
+///
+/// Members which are not set in the problem details are `None`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ProblemDetails {
+    /// A URI reference that identifies the problem type.
+    #[prost(string, optional, tag = "1")]
+    pub r#type: Option<String>,
+    /// The HTTP status code.
+    #[prost(uint32, optional, tag = "2")]
+    pub status: Option<u32>,
+    /// A short, human-readable summary of the problem type.
+    #[prost(string, optional, tag = "3")]
+    pub title: Option<String>,
+    /// A human-readable explanation specific to this occurrence of the problem.
+    #[prost(string, optional, tag = "4")]
+    pub detail: Option<String>,
+    /// A URI reference that identifies the specific occurrence of the problem.
+    #[prost(string, optional, tag = "5")]
+    pub instance: Option<String>,
+    /// The extension members.
+    #[prost(message, optional, tag = "6")]
+    pub extensions: Option<Struct>,
+}
+
+impl prost::Name for ProblemDetails {
+    const NAME: &'static str = "ProblemDetails";
+    const PACKAGE: &'static str = "problem_details.v1";
+
+    fn type_url() -> String {
+        format!("type.googleapis.com/{}", Self::full_name())
+    }
+}
+
+/// The `google.rpc.Status` message used by gRPC to describe errors.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Status {
+    /// The status code, see [`grpc_code`].
+    #[prost(int32, tag = "1")]
+    pub code: i32,
+    /// A developer-facing error message.
+    #[prost(string, tag = "2")]
+    pub message: String,
+    /// Messages carrying the error details.
+    #[prost(message, repeated, tag = "3")]
+    pub details: Vec<Any>,
+}
+
+impl prost::Name for Status {
+    const NAME: &'static str = "Status";
+    const PACKAGE: &'static str = "google.rpc";
+
+    fn type_url() -> String {
+        format!("type.googleapis.com/{}", Self::full_name())
+    }
+}
+
+/// Returns the gRPC status code corresponding to the given HTTP status.
+///
+/// Uses the mapping documented in `google/rpc/code.proto`. Other client errors
+/// map to `FAILED_PRECONDITION`, other server errors to `INTERNAL`, and all
+/// remaining status codes to `UNKNOWN`.
+pub fn grpc_code(status: StatusCode) -> i32 {
+    match status.as_u16() {
+        400 => 3,
+        401 => 16,
+        403 => 7,
+        404 => 5,
+        409 => 10,
+        429 => 8,
+        499 => 1,
+        501 => 12,
+        503 => 14,
+        504 => 4,
+        _ if status.is_success() => 0,
+        _ if status.is_client_error() => 9,
+        _ if status.is_server_error() => 13,
+        _ => 2,
+    }
+}
+
+/// Returns the HTTP status corresponding to the given gRPC status code.
+///
+/// Uses the mapping documented in `google/rpc/code.proto`. Unknown codes
+/// map to `500 Internal Server Error`.
+pub fn http_status(code: i32) -> StatusCode {
+    match code {
+        0 => StatusCode::OK,
+        1 => StatusCode::from_u16(499).expect("499 is a valid status code"),
+        3 | 9 | 11 => StatusCode::BAD_REQUEST,
+        4 => StatusCode::GATEWAY_TIMEOUT,
+        5 => StatusCode::NOT_FOUND,
+        6 | 10 => StatusCode::CONFLICT,
+        7 => StatusCode::FORBIDDEN,
+        8 => StatusCode::TOO_MANY_REQUESTS,
+        12 => StatusCode::NOT_IMPLEMENTED,
+        14 => StatusCode::SERVICE_UNAVAILABLE,
+        16 => StatusCode::UNAUTHORIZED,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// Extensions which cannot be represented as JSON object are dropped.
+impl<Ext> From<&crate::ProblemDetails<Ext>> for ProblemDetails
+where
+    Ext: serde::Serialize,
+{
+    fn from(details: &crate::ProblemDetails<Ext>) -> Self {
+        let details = details.to_json_extensions();
+
+        Self {
+            r#type: details.r#type.map(|r#type| r#type.to_string()),
+            status: details.status.map(|status| status.as_u16().into()),
+            title: details.title.map(Into::into),
+            detail: details.detail.map(Into::into),
+            instance: details.instance.map(|instance| instance.to_string()),
+            extensions: (!details.extensions.is_empty()).then(|| to_struct(details.extensions)),
+        }
+    }
+}
+
+/// Extensions which cannot be represented as JSON object are dropped.
+impl<Ext> From<crate::ProblemDetails<Ext>> for ProblemDetails
+where
+    Ext: serde::Serialize,
+{
+    fn from(details: crate::ProblemDetails<Ext>) -> Self {
+        Self::from(&details)
+    }
+}
+
+/// Members with invalid values, like unparseable URIs, are ignored.
+impl From<ProblemDetails> for crate::ProblemDetails<JsonExtensions> {
+    fn from(message: ProblemDetails) -> Self {
+        let mut details = crate::ProblemDetails::new()
+            .with_extensions(message.extensions.map(from_struct).unwrap_or_default());
+        details.r#type = message
+            .r#type
+            .and_then(|r#type| ProblemType::try_from(r#type.as_str()).ok());
+        details.status = message
+            .status
+            .and_then(|status| u16::try_from(status).ok())
+            .and_then(|status| StatusCode::from_u16(status).ok());
+        details.title = message.title.map(Into::into);
+        details.detail = message.detail.map(Into::into);
+        details.instance = message
+            .instance
+            .and_then(|instance| Uri::try_from(instance).ok());
+        details
+    }
+}
+
+impl<Ext> From<&crate::ProblemDetails<Ext>> for Status
+where
+    Ext: serde::Serialize,
+{
+    fn from(details: &crate::ProblemDetails<Ext>) -> Self {
+        let message = details
+            .detail
+            .as_deref()
+            .or(details.effective_title())
+            .unwrap_or_default()
+            .to_string();
+
+        Self {
+            code: grpc_code(details.status_or_default()),
+            message,
+            details: Any::from_msg(&ProblemDetails::from(details))
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+impl<Ext> From<crate::ProblemDetails<Ext>> for Status
+where
+    Ext: serde::Serialize,
+{
+    fn from(details: crate::ProblemDetails<Ext>) -> Self {
+        Self::from(&details)
+    }
+}
+
+/// Uses the [`ProblemDetails`] message attached as detail if present.
+/// Otherwise, the problem is created from the status code and message.
+impl From<Status> for crate::ProblemDetails<JsonExtensions> {
+    fn from(status: Status) -> Self {
+        let attached = status
+            .details
+            .iter()
+            .find_map(|any| any.to_msg::<ProblemDetails>().ok());
+
+        match attached {
+            Some(message) => message.into(),
+            None => {
+                let details = crate::ProblemDetails::from_status_code(http_status(status.code))
+                    .with_extensions(JsonExtensions::new());
+                match status.message.is_empty() {
+                    true => details,
+                    false => details.with_detail(status.message),
+                }
+            }
+        }
+    }
+}
+
+fn to_struct(map: Map<String, Value>) -> Struct {
+    Struct {
+        fields: map
+            .into_iter()
+            .map(|(key, value)| (key, to_value(value)))
+            .collect(),
+    }
+}
+
+fn to_value(value: Value) -> prost_types::Value {
+    let kind = match value {
+        Value::Null => Kind::NullValue(0),
+        Value::Bool(value) => Kind::BoolValue(value),
+        Value::Number(value) => Kind::NumberValue(value.as_f64().unwrap_or_default()),
+        Value::String(value) => Kind::StringValue(value),
+        Value::Array(values) => Kind::ListValue(ListValue {
+            values: values.into_iter().map(to_value).collect(),
+        }),
+        Value::Object(map) => Kind::StructValue(to_struct(map)),
+    };
+
+    prost_types::Value { kind: Some(kind) }
+}
+
+fn from_struct(message: Struct) -> Map<String, Value> {
+    message
+        .fields
+        .into_iter()
+        .map(|(key, value)| (key, from_value(value)))
+        .collect()
+}
+
+fn from_value(value: prost_types::Value) -> Value {
+    match value.kind {
+        None | Some(Kind::NullValue(_)) => Value::Null,
+        Some(Kind::BoolValue(value)) => Value::Bool(value),
+        // Protobuf only has doubles, restore integers where possible
+        Some(Kind::NumberValue(value)) if value.fract() == 0.0 && value.abs() < 2f64.powi(53) => {
+            Value::Number((value as i64).into())
+        }
+        Some(Kind::NumberValue(value)) => Number::from_f64(value)
+            .map(Value::Number)
+            .unwrap_or(Value::Null),
+        Some(Kind::StringValue(value)) => Value::String(value),
+        Some(Kind::ListValue(list)) => {
+            Value::Array(list.values.into_iter().map(from_value).collect())
+        }
+        Some(Kind::StructValue(message)) => Value::Object(from_struct(message)),
+    }
+}
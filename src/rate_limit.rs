@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use http::{header, HeaderName, HeaderValue};
+
+use crate::{retry_after::delay_seconds, MergeExtensions, ProblemDetails};
+
+/// Extension members describing the rate limit a client has hit.
+///
+/// The members mirror the `RateLimit-*` header fields of the
+/// [IETF RateLimit header fields draft](https://datatracker.ietf.org/doc/draft-ietf-httpapi-ratelimit-headers/),
+/// which are set as well by [`ProblemDetails::with_rate_limit`].
+///
+/// ```rust
+/// use std::time::Duration;
+/// use http::{header, StatusCode};
+/// use problem_details::{ProblemDetails, RateLimit};
+///
+/// let details = ProblemDetails::from_status_code(StatusCode::TOO_MANY_REQUESTS)
+///     .with_rate_limit(RateLimit::new(100, 0, Duration::from_secs(30)).with_policy("100;w=60"));
+///
+/// assert_eq!(details.headers()["ratelimit-limit"], "100");
+/// assert_eq!(details.headers()["ratelimit-policy"], "100;w=60");
+/// assert_eq!(details.headers()[header::RETRY_AFTER], "30");
+///
+/// let json = serde_json::to_value(&details).unwrap();
+/// assert_eq!(json["remaining"], 0);
+/// assert_eq!(json["reset"], 30);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RateLimit {
+    /// The maximum number of requests allowed in the current window.
+    pub limit: u64,
+
+    /// The number of requests remaining in the current window.
+    pub remaining: u64,
+
+    /// The number of seconds until the current window resets.
+    pub reset: u64,
+
+    /// The quota policy, e.g. `100;w=60` for 100 requests per 60 seconds.
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub policy: Option<String>,
+}
+
+impl RateLimit {
+    /// The `RateLimit-Limit` header name.
+    pub const LIMIT_HEADER: HeaderName = HeaderName::from_static("ratelimit-limit");
+
+    /// The `RateLimit-Remaining` header name.
+    pub const REMAINING_HEADER: HeaderName = HeaderName::from_static("ratelimit-remaining");
+
+    /// The `RateLimit-Reset` header name.
+    pub const RESET_HEADER: HeaderName = HeaderName::from_static("ratelimit-reset");
+
+    /// The `RateLimit-Policy` header name.
+    pub const POLICY_HEADER: HeaderName = HeaderName::from_static("ratelimit-policy");
+
+    /// Creates a new rate limit which resets after the given delay.
+    ///
+    /// Fractional seconds are rounded up.
+    #[must_use]
+    pub fn new(limit: u64, remaining: u64, reset: Duration) -> Self {
+        Self {
+            limit,
+            remaining,
+            reset: delay_seconds(reset),
+            policy: None,
+        }
+    }
+
+    /// Builder-style method that sets the quota policy.
+    #[must_use]
+    pub fn with_policy(mut self, policy: impl Into<String>) -> Self {
+        self.policy = Some(policy.into());
+        self
+    }
+}
+
+impl MergeExtensions for RateLimit {
+    fn merge_extensions(&mut self, other: Self) {
+        *self = other;
+    }
+}
+
+impl<Ext> ProblemDetails<Ext> {
+    /// Builder-style method that adds the given rate limit.
+    ///
+    /// The rate limit is added as extension members and as `RateLimit-Limit`,
+    /// `RateLimit-Remaining`, `RateLimit-Reset` and (if set) `RateLimit-Policy`
+    /// response headers. If no requests are remaining, the `Retry-After` header
+    /// is set to the reset delay as well.
+    ///
+    /// The policy is omitted from the headers if it is not a valid header value.
+    #[must_use]
+    pub fn with_rate_limit(self, rate_limit: RateLimit) -> ProblemDetails<(Ext, RateLimit)> {
+        let mut details = self
+            .with_header(RateLimit::LIMIT_HEADER, rate_limit.limit)
+            .with_header(RateLimit::REMAINING_HEADER, rate_limit.remaining)
+            .with_header(RateLimit::RESET_HEADER, rate_limit.reset);
+
+        if let Some(policy) = rate_limit
+            .policy
+            .as_deref()
+            .and_then(|policy| HeaderValue::from_str(policy).ok())
+        {
+            details = details.with_header(RateLimit::POLICY_HEADER, policy);
+        }
+
+        if rate_limit.remaining == 0 {
+            details = details.with_header(header::RETRY_AFTER, rate_limit.reset);
+        }
+
+        details.extend_extensions(rate_limit)
+    }
+}
@@ -0,0 +1,134 @@
+use http::StatusCode;
+
+use crate::ProblemDetails;
+
+/// A policy deciding which parts of a problem are removed by [`ProblemDetails::redacted`].
+///
+/// All methods default to keeping the respective data, so implementations only
+/// need to override the parts they want to redact.
+pub trait RedactionPolicy {
+    /// Returns true if the `detail` field should be removed.
+    fn redact_detail(&self, status: Option<StatusCode>) -> bool {
+        let _ = status;
+        false
+    }
+
+    /// Returns true if the `instance` field should be removed.
+    fn redact_instance(&self, status: Option<StatusCode>) -> bool {
+        let _ = status;
+        false
+    }
+
+    /// Returns true if the extension member with the given key and value should be removed.
+    fn redact_extension(&self, key: &str, value: &serde_json::Value) -> bool {
+        let _ = (key, value);
+        false
+    }
+}
+
+/// A redaction policy suitable for responses sent to external clients.
+///
+/// This policy removes
+///
+/// - the `detail` of server errors (5xx), which often contains internal information,
+/// - extension members starting with `_` or `internal`,
+/// - extension members with stack-like data (`stack`, `stackTrace`, `backtrace`,
+///   `exception`, `debug`),
+/// - extension members explicitly added using [`with_redacted_extension`](ProductionPolicy::with_redacted_extension).
+///
+/// ```rust
+/// use http::StatusCode;
+/// use problem_details::{ProblemDetails, ProductionPolicy};
+///
+/// let details = ProblemDetails::from_status_code(StatusCode::INTERNAL_SERVER_ERROR)
+///     .with_detail("Connection to db-01.internal refused")
+///     .with_extension("traceId", "abc123")
+///     .with_extension("stackTrace", "at main.rs:42");
+///
+/// let redacted = details.redacted(&ProductionPolicy::default());
+///
+/// assert_eq!(redacted.detail, None);
+/// assert!(redacted.extensions.contains_key("traceId"));
+/// assert!(!redacted.extensions.contains_key("stackTrace"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ProductionPolicy {
+    redacted_extensions: Vec<String>,
+}
+
+impl ProductionPolicy {
+    /// Builder-style method that additionally redacts extension members with the given key.
+    #[must_use]
+    pub fn with_redacted_extension(mut self, key: impl Into<String>) -> Self {
+        self.redacted_extensions.push(key.into());
+        self
+    }
+}
+
+impl RedactionPolicy for ProductionPolicy {
+    fn redact_detail(&self, status: Option<StatusCode>) -> bool {
+        status.is_some_and(|status| status.is_server_error())
+    }
+
+    fn redact_extension(&self, key: &str, _value: &serde_json::Value) -> bool {
+        const STACK_LIKE: [&str; 5] = ["stack", "stacktrace", "backtrace", "exception", "debug"];
+
+        let normalized = key.replace(['_', '-'], "").to_ascii_lowercase();
+
+        key.starts_with('_')
+            || normalized.starts_with("internal")
+            || STACK_LIKE.contains(&normalized.as_str())
+            || self
+                .redacted_extensions
+                .iter()
+                .any(|redacted| redacted == key)
+    }
+}
+
+impl<Ext> ProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    /// Returns a sanitized copy of this problem details object according to the given policy.
+    ///
+    /// The extensions are converted into a JSON map, so individual members can be removed.
+    /// The [`source`](std::error::Error::source) (and backtrace) are never included in the
+    /// redacted copy, while this object is left untouched so it can still be logged.
+    /// The [`headers`](ProblemDetails::headers) are kept.
+    ///
+    /// Extensions which cannot be represented as JSON object are dropped.
+    pub fn redacted(
+        &self,
+        policy: &impl RedactionPolicy,
+    ) -> ProblemDetails<serde_json::Map<String, serde_json::Value>> {
+        let mut extensions = match serde_json::to_value(&self.extensions) {
+            Ok(serde_json::Value::Object(map)) => map,
+            _ => serde_json::Map::new(),
+        };
+        extensions.retain(|key, value| !policy.redact_extension(key, value));
+
+        let mut redacted = ProblemDetails::new().with_extensions(extensions);
+        redacted.r#type = self.r#type.clone();
+        redacted.status = self.status;
+        redacted.title = self.title.clone();
+        redacted.headers = self.headers.clone();
+
+        if !policy.redact_detail(self.status) {
+            redacted.detail = self.detail.clone();
+        }
+
+        if !policy.redact_instance(self.status) {
+            redacted.instance = self.instance.clone();
+        }
+
+        redacted
+    }
+    /// Returns a copy of this problem details object with the extensions converted
+    /// into a JSON map, as [`redacted`](ProblemDetails::redacted) without removing anything.
+    pub(crate) fn to_json_extensions(&self) -> ProblemDetails<crate::JsonExtensions> {
+        struct KeepAll;
+        impl RedactionPolicy for KeepAll {}
+
+        self.redacted(&KeepAll)
+    }
+}
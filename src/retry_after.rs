@@ -0,0 +1,179 @@
+use std::time::{Duration, SystemTime};
+
+use http::{header, HeaderValue};
+
+use crate::{date, MergeExtensions, ProblemDetails};
+
+/// The value of a `Retry-After` header.
+///
+/// See [RFC 9110, Section 10.2.3](https://www.rfc-editor.org/rfc/rfc9110.html#section-10.2.3)
+/// for more information.
+///
+/// A delay is represented as a number of seconds, a point in time as HTTP-date,
+/// both in the header and when serialized as extension member.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RetryAfter {
+    /// The client should retry after the given delay.
+    ///
+    /// Fractional seconds are rounded up.
+    Delay(Duration),
+
+    /// The client should retry at the given point in time.
+    ///
+    /// Fractional seconds are truncated.
+    At(SystemTime),
+}
+
+impl std::fmt::Display for RetryAfter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Delay(delay) => write!(f, "{}", delay_seconds(*delay)),
+            Self::At(at) => date::fmt_http_date(f, *at),
+        }
+    }
+}
+
+impl From<RetryAfter> for HeaderValue {
+    fn from(value: RetryAfter) -> Self {
+        match value {
+            RetryAfter::Delay(delay) => HeaderValue::from(delay_seconds(delay)),
+            RetryAfter::At(_) => {
+                HeaderValue::try_from(value.to_string()).expect("HTTP-date is a valid header value")
+            }
+        }
+    }
+}
+
+/// Extension member carrying a [`RetryAfter`] value as `retry_after`.
+///
+/// This is added by [`ProblemDetails::with_retry_after`] and [`ProblemDetails::with_retry_at`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RetryAfterExtension {
+    /// When the client should retry the request.
+    pub retry_after: RetryAfter,
+}
+
+impl MergeExtensions for RetryAfterExtension {
+    fn merge_extensions(&mut self, other: Self) {
+        *self = other;
+    }
+}
+
+impl<Ext> ProblemDetails<Ext> {
+    /// Builder-style method that asks the client to retry after the given delay.
+    ///
+    /// The delay is added as `retry_after` extension member (in seconds)
+    /// and as `Retry-After` response header. Use this for `429 Too Many Requests`
+    /// and `503 Service Unavailable` problems.
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use http::{header, StatusCode};
+    /// use problem_details::ProblemDetails;
+    ///
+    /// let details = ProblemDetails::from_status_code(StatusCode::TOO_MANY_REQUESTS)
+    ///     .with_retry_after(Duration::from_secs(120));
+    ///
+    /// assert_eq!(details.headers()[header::RETRY_AFTER], "120");
+    ///
+    /// let json = serde_json::to_value(&details).unwrap();
+    /// assert_eq!(json["retry_after"], 120);
+    /// ```
+    #[must_use]
+    pub fn with_retry_after(self, delay: Duration) -> ProblemDetails<(Ext, RetryAfterExtension)> {
+        self.with_retry(RetryAfter::Delay(delay))
+    }
+
+    /// Builder-style method that asks the client to retry at the given point in time.
+    ///
+    /// The point in time is added as `retry_after` extension member (as HTTP-date)
+    /// and as `Retry-After` response header.
+    ///
+    /// ```rust
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use http::{header, StatusCode};
+    /// use problem_details::ProblemDetails;
+    ///
+    /// let maintenance_end = UNIX_EPOCH + Duration::from_secs(784111777);
+    /// let details = ProblemDetails::from_status_code(StatusCode::SERVICE_UNAVAILABLE)
+    ///     .with_retry_at(maintenance_end);
+    ///
+    /// assert_eq!(details.headers()[header::RETRY_AFTER], "Sun, 06 Nov 1994 08:49:37 GMT");
+    /// ```
+    #[must_use]
+    pub fn with_retry_at(self, at: SystemTime) -> ProblemDetails<(Ext, RetryAfterExtension)> {
+        self.with_retry(RetryAfter::At(at))
+    }
+
+    fn with_retry(self, retry_after: RetryAfter) -> ProblemDetails<(Ext, RetryAfterExtension)> {
+        self.with_header(header::RETRY_AFTER, retry_after)
+            .extend_extensions(RetryAfterExtension { retry_after })
+    }
+}
+
+pub(crate) fn delay_seconds(delay: Duration) -> u64 {
+    delay.as_secs() + u64::from(delay.subsec_nanos() > 0)
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for RetryAfter {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Self::Delay(delay) => serializer.serialize_u64(delay_seconds(*delay)),
+            Self::At(_) => serializer.collect_str(self),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for RetryAfter {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct RetryAfterVisitor;
+
+        impl serde::de::Visitor<'_> for RetryAfterVisitor {
+            type Value = RetryAfter;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a number of seconds or an HTTP-date")
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(RetryAfter::Delay(Duration::from_secs(value)))
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                u64::try_from(value)
+                    .map_err(|_| E::invalid_value(serde::de::Unexpected::Signed(value), &self))
+                    .and_then(|value| self.visit_u64(value))
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                if let Ok(seconds) = value.parse::<u64>() {
+                    return self.visit_u64(seconds);
+                }
+
+                date::parse_http_date(value)
+                    .map(RetryAfter::At)
+                    .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Str(value), &self))
+            }
+        }
+
+        deserializer.deserialize_any(RetryAfterVisitor)
+    }
+}
@@ -0,0 +1,260 @@
+//! Rocket response types for [`ProblemDetails`]. Requires feature `rocket` (implies `json`).
+//!
+//! With the `rocket` feature enabled, [`ProblemDetails`] implements [`Responder`] using
+//! [`JsonProblemDetails`]. You can also return [`JsonProblemDetails`] to be specific.
+//! If you want to return XML, you can use [`XmlProblemDetails`] (requires feature `xml`).
+//! Concise problem details in CBOR are returned using `CborProblemDetails` (requires feature `cbor`).
+//! YAML is returned using `YamlProblemDetails` (requires feature `yaml`).
+//! Browsers can be served an HTML page using `HtmlProblemDetails` (requires feature `html`).
+//! As last resort, [`TextProblemDetails`] returns a plain text body.
+//!
+//! Errors of rocket itself, like unmatched routes or failing data guards, are handled
+//! by catchers, which respond with an HTML page by default. Register [`catchers()`] to
+//! respond with problems instead.
+//!
+//! # Example
+//!
+//! ```rust
+//! use http::StatusCode;
+//! use problem_details::ProblemDetails;
+//! use rocket::{get, routes};
+//!
+//! #[get("/")]
+//! fn handler() -> Result<&'static str, ProblemDetails> {
+//!     // always return a problem description
+//!     Err(ProblemDetails::from_status_code(StatusCode::IM_A_TEAPOT)
+//!         .with_detail("short and stout"))
+//! }
+//!
+//! let rocket = rocket::build()
+//!     .mount("/", routes![handler])
+//!     .register("/", problem_details::rocket::catchers());
+//! # let _rocket = rocket;
+//! ```
+use std::io::Cursor;
+
+use http::{HeaderMap, StatusCode};
+use rocket::http::{Header, Status};
+use rocket::response::{self, Responder, Response};
+use rocket::Request;
+
+use rocket::{catch, catchers, Catcher};
+
+use crate::{JsonProblemDetails, ProblemDetails, TextProblemDetails};
+
+#[cfg(feature = "xml")]
+use crate::XmlProblemDetails;
+
+#[cfg(feature = "cbor")]
+use crate::CborProblemDetails;
+
+#[cfg(feature = "yaml")]
+use crate::YamlProblemDetails;
+
+#[cfg(feature = "html")]
+use crate::HtmlProblemDetails;
+
+impl<'r, 'o: 'r, Ext> Responder<'r, 'o> for JsonProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn respond_to(self, _request: &'r Request<'_>) -> response::Result<'o> {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = Self(details)
+            .to_body_bytes()
+            .map_err(|_| Status::InternalServerError)?;
+
+        problem_response(status_code, headers, Self::CONTENT_TYPE, content)
+    }
+}
+
+#[cfg(feature = "xml")]
+impl<'r, 'o: 'r, Ext> Responder<'r, 'o> for XmlProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn respond_to(self, _request: &'r Request<'_>) -> response::Result<'o> {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = Self(details)
+            .to_body_bytes()
+            .map_err(|_| Status::InternalServerError)?;
+
+        problem_response(status_code, headers, Self::CONTENT_TYPE, content)
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl<'r, 'o: 'r, Ext> Responder<'r, 'o> for CborProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn respond_to(self, _request: &'r Request<'_>) -> response::Result<'o> {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = Self(details)
+            .to_body_vec()
+            .map_err(|_| Status::InternalServerError)?;
+
+        problem_response(status_code, headers, Self::CONTENT_TYPE, content)
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl<'r, 'o: 'r, Ext> Responder<'r, 'o> for YamlProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn respond_to(self, _request: &'r Request<'_>) -> response::Result<'o> {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = Self(details)
+            .to_body_string()
+            .map_err(|_| Status::InternalServerError)?;
+
+        problem_response(status_code, headers, Self::CONTENT_TYPE, content)
+    }
+}
+
+#[cfg(feature = "html")]
+impl<'r, 'o: 'r, Ext> Responder<'r, 'o> for HtmlProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn respond_to(self, _request: &'r Request<'_>) -> response::Result<'o> {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = Self(details)
+            .to_body_string()
+            .map_err(|_| Status::InternalServerError)?;
+
+        problem_response(status_code, headers, Self::CONTENT_TYPE, content)
+    }
+}
+
+impl<'r, 'o: 'r, Ext> Responder<'r, 'o> for TextProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn respond_to(self, _request: &'r Request<'_>) -> response::Result<'o> {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = Self(details).to_body_string();
+
+        problem_response(status_code, headers, Self::CONTENT_TYPE, content)
+    }
+}
+
+impl<'r, 'o: 'r, Ext> Responder<'r, 'o> for ProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'o> {
+        JsonProblemDetails(self).respond_to(request)
+    }
+}
+
+/// Returns catchers responding with JSON problems instead of rocket's HTML pages.
+///
+/// Contains [`not_found`], [`unprocessable_entity`], [`internal_server_error`] and
+/// [`default_catcher`] for all other statuses.
+///
+/// ```rust
+/// let rocket = rocket::build().register("/", problem_details::rocket::catchers());
+/// # let _rocket = rocket;
+/// ```
+pub fn catchers() -> Vec<Catcher> {
+    catchers![
+        not_found,
+        unprocessable_entity,
+        internal_server_error,
+        default_catcher
+    ]
+}
+
+/// Catcher responding with a `404 Not Found` problem.
+///
+/// The path of the request is used as `instance`, the query string is omitted.
+#[catch(404)]
+pub fn not_found(request: &Request<'_>) -> JsonProblemDetails {
+    let path = request.uri().path();
+    JsonProblemDetails(
+        with_path_instance(
+            ProblemDetails::from_status_code(StatusCode::NOT_FOUND),
+            request,
+        )
+        .with_detail(format!("No resource found at `{path}`")),
+    )
+}
+
+/// Catcher responding with a `422 Unprocessable Entity` problem.
+///
+/// Rocket uses this status for data guards rejecting well-formed bodies, like JSON of
+/// the wrong shape. Syntax errors result in `400 Bad Request`, handled by [`default_catcher`].
+/// The reason is not available to catchers, so the problem has no detail.
+#[catch(422)]
+pub fn unprocessable_entity(request: &Request<'_>) -> JsonProblemDetails {
+    JsonProblemDetails(with_path_instance(
+        ProblemDetails::from_status_code(StatusCode::UNPROCESSABLE_ENTITY),
+        request,
+    ))
+}
+
+/// Catcher responding with a `500 Internal Server Error` problem.
+///
+/// The problem does not reveal anything about the error.
+#[catch(500)]
+pub fn internal_server_error() -> JsonProblemDetails {
+    JsonProblemDetails(ProblemDetails::from_status_code(
+        StatusCode::INTERNAL_SERVER_ERROR,
+    ))
+}
+
+/// Catcher responding with a problem for any status without a more specific catcher.
+#[catch(default)]
+pub fn default_catcher(status: Status, request: &Request<'_>) -> JsonProblemDetails {
+    let status_code =
+        StatusCode::from_u16(status.code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    JsonProblemDetails(with_path_instance(
+        ProblemDetails::from_status_code(status_code),
+        request,
+    ))
+}
+
+fn with_path_instance(details: ProblemDetails, request: &Request<'_>) -> ProblemDetails {
+    match http::Uri::try_from(request.uri().path().as_str()) {
+        Ok(path) => details.with_instance(path),
+        Err(_) => details,
+    }
+}
+
+fn problem_response<'o, B>(
+    status_code: StatusCode,
+    headers: HeaderMap,
+    content_type: &'static str,
+    content: B,
+) -> response::Result<'o>
+where
+    B: AsRef<[u8]> + Send + Unpin + 'o,
+{
+    let mut response = Response::build();
+    response.status(Status::new(status_code.as_u16()));
+    for (name, value) in &headers {
+        if let Ok(value) = value.to_str() {
+            response.header_adjoin(Header::new(name.as_str().to_owned(), value.to_owned()));
+        }
+    }
+
+    let size = content.as_ref().len();
+    response
+        .raw_header("Content-Type", content_type)
+        .sized_body(size, Cursor::new(content))
+        .ok()
+}
@@ -0,0 +1,240 @@
+//! Salvo writer types for [`ProblemDetails`]. Requires feature `salvo` (implies `json`).
+//!
+//! With the `salvo` feature enabled, [`ProblemDetails`] implements [`Scribe`], and thereby
+//! [`Writer`](salvo::Writer), using [`JsonProblemDetails`]. You can also return
+//! [`JsonProblemDetails`] to be specific.
+//! If you want to return XML, you can use [`XmlProblemDetails`] (requires feature `xml`).
+//! Concise problem details in CBOR are returned using `CborProblemDetails` (requires feature `cbor`).
+//! YAML is returned using `YamlProblemDetails` (requires feature `yaml`).
+//! Browsers can be served an HTML page using `HtmlProblemDetails` (requires feature `html`).
+//! As last resort, [`TextProblemDetails`] returns a plain text body.
+//!
+//! Errors of salvo itself, like unmatched routes or a [`StatusError`] returned by a handler,
+//! are rendered by the catcher of the service as HTML, JSON, XML or text page. Use
+//! [`catcher()`] to respond with problems instead.
+//!
+//! # Example
+//!
+//! ```rust
+//! use http::StatusCode;
+//! use problem_details::ProblemDetails;
+//! use salvo::{handler, Router, Service};
+//!
+//! #[handler]
+//! async fn teapot() -> Result<&'static str, ProblemDetails> {
+//!     // always return a problem description
+//!     Err(ProblemDetails::from_status_code(StatusCode::IM_A_TEAPOT)
+//!         .with_detail("short and stout"))
+//! }
+//!
+//! let router = Router::new().push(Router::with_path("teapot").get(teapot));
+//! let service = Service::new(router).catcher(problem_details::salvo::catcher());
+//! # let _service = service;
+//! ```
+use bytes::Bytes;
+use http::{header, HeaderMap, HeaderValue, StatusCode, Uri};
+use salvo::catcher::Catcher;
+use salvo::http::{ResBody, StatusError};
+use salvo::{async_trait, Depot, FlowCtrl, Handler, Request, Response, Scribe};
+
+use crate::{JsonProblemDetails, ProblemDetails, TextProblemDetails};
+
+#[cfg(feature = "xml")]
+use crate::XmlProblemDetails;
+
+#[cfg(feature = "cbor")]
+use crate::CborProblemDetails;
+
+#[cfg(feature = "yaml")]
+use crate::YamlProblemDetails;
+
+#[cfg(feature = "html")]
+use crate::HtmlProblemDetails;
+
+impl<Ext> Scribe for JsonProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn render(self, res: &mut Response) {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        match Self(details).to_body_bytes() {
+            Ok(json) => render_problem(res, status_code, headers, Self::CONTENT_TYPE, json),
+            Err(_) => render_serialization_error(res),
+        }
+    }
+}
+
+#[cfg(feature = "xml")]
+impl<Ext> Scribe for XmlProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn render(self, res: &mut Response) {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        match Self(details).to_body_bytes() {
+            Ok(xml) => render_problem(res, status_code, headers, Self::CONTENT_TYPE, xml),
+            Err(_) => render_serialization_error(res),
+        }
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl<Ext> Scribe for CborProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn render(self, res: &mut Response) {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        match Self(details).to_body_vec() {
+            Ok(cbor) => render_problem(res, status_code, headers, Self::CONTENT_TYPE, cbor),
+            Err(_) => render_serialization_error(res),
+        }
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl<Ext> Scribe for YamlProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn render(self, res: &mut Response) {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        match Self(details).to_body_string() {
+            Ok(yaml) => render_problem(res, status_code, headers, Self::CONTENT_TYPE, yaml),
+            Err(_) => render_serialization_error(res),
+        }
+    }
+}
+
+#[cfg(feature = "html")]
+impl<Ext> Scribe for HtmlProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn render(self, res: &mut Response) {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        match Self(details).to_body_string() {
+            Ok(html) => render_problem(res, status_code, headers, Self::CONTENT_TYPE, html),
+            Err(_) => render_serialization_error(res),
+        }
+    }
+}
+
+impl<Ext> Scribe for TextProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn render(self, res: &mut Response) {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let text = Self(details).to_body_string();
+        render_problem(res, status_code, headers, Self::CONTENT_TYPE, text);
+    }
+}
+
+impl<Ext> Scribe for ProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    fn render(self, res: &mut Response) {
+        JsonProblemDetails(self).render(res);
+    }
+}
+
+impl From<&StatusError> for ProblemDetails {
+    /// Creates a problem with the status of the error.
+    ///
+    /// The detail of client errors is used as detail. The messages of server errors
+    /// are omitted, as they may describe the application.
+    fn from(error: &StatusError) -> Self {
+        let details = ProblemDetails::from_status_code(error.code);
+        match &error.detail {
+            Some(detail) if error.code.is_client_error() => details.with_detail(detail.clone()),
+            _ => details,
+        }
+    }
+}
+
+impl From<StatusError> for ProblemDetails {
+    fn from(error: StatusError) -> Self {
+        Self::from(&error)
+    }
+}
+
+/// Returns a catcher responding to errors with JSON problems.
+///
+/// ```rust
+/// use salvo::{Router, Service};
+///
+/// let service = Service::new(Router::new()).catcher(problem_details::salvo::catcher());
+/// # let _service = service;
+/// ```
+pub fn catcher() -> Catcher {
+    Catcher::new(ProblemCatcher)
+}
+
+/// The handler of the catcher created by [`catcher()`].
+///
+/// Client and server errors without a body, or with a [`StatusError`] as body, are
+/// rendered as JSON problem with the path of the request as `instance`. Other
+/// responses, including problems written by handlers, are not changed.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ProblemCatcher;
+
+#[async_trait]
+impl Handler for ProblemCatcher {
+    async fn handle(
+        &self,
+        req: &mut Request,
+        _depot: &mut Depot,
+        res: &mut Response,
+        _ctrl: &mut FlowCtrl,
+    ) {
+        let status = res.status_code.unwrap_or(StatusCode::NOT_FOUND);
+        if !status.is_client_error() && !status.is_server_error() {
+            return;
+        }
+
+        let details = match &res.body {
+            ResBody::None => ProblemDetails::from_status_code(status),
+            ResBody::Error(error) => ProblemDetails::from(error),
+            _ => return,
+        };
+        let details = match Uri::try_from(req.uri().path()) {
+            Ok(path) => details.with_instance(path),
+            Err(_) => details,
+        };
+
+        res.render(JsonProblemDetails(details));
+    }
+}
+
+fn render_problem(
+    res: &mut Response,
+    status_code: StatusCode,
+    headers: HeaderMap,
+    content_type: &'static str,
+    content: impl Into<Bytes>,
+) {
+    res.status_code(status_code);
+    res.headers_mut().extend(headers);
+    res.headers_mut()
+        .insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    res.body(ResBody::Once(content.into()));
+}
+
+fn render_serialization_error(res: &mut Response) {
+    res.status_code(StatusCode::INTERNAL_SERVER_ERROR);
+    res.body(ResBody::None);
+}
@@ -0,0 +1,178 @@
+//! JSON schemas for [`ProblemDetails`] using [`schemars`]. Requires feature `schemars`.
+//!
+//! With the `schemars` feature enabled, [`ProblemDetails`] and [`ProblemType`]
+//! implement [`JsonSchema`]. The properties of the extensions are flattened into
+//! the problem details schema, just like they are flattened when serialized.
+//! This includes extensions composed using tuples.
+//!
+//! Problems without extensions are described by a `ProblemDetails` schema, problems
+//! with extensions by a schema named after the extensions, e.g. `ProblemDetails_OutOfCredit`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use problem_details::ProblemDetails;
+//! use schemars::JsonSchema;
+//!
+//! #[derive(serde::Serialize, JsonSchema)]
+//! struct OutOfCredit {
+//!     balance: u32,
+//! }
+//!
+//! let schema = schemars::schema_for!(ProblemDetails<OutOfCredit>);
+//!
+//! assert_eq!(schema.get("title").unwrap(), "ProblemDetails_OutOfCredit");
+//! assert!(schema.get("properties").unwrap().get("balance").is_some());
+//! assert_eq!(schema.get("required").unwrap(), &serde_json::json!(["balance"]));
+//! ```
+use std::borrow::Cow;
+
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use serde_json::Value;
+
+use crate::{ProblemDetails, ProblemType};
+
+impl JsonSchema for ProblemType {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        "ProblemType".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        "problem_details::ProblemType".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "format": "uri-reference",
+            "default": "about:blank",
+        })
+    }
+}
+
+impl<Ext> JsonSchema for ProblemDetails<Ext>
+where
+    Ext: JsonSchema,
+{
+    fn schema_name() -> Cow<'static, str> {
+        match has_extensions::<Ext>() {
+            true => format!("ProblemDetails_{}", Ext::schema_name()).into(),
+            false => "ProblemDetails".into(),
+        }
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        format!("problem_details::ProblemDetails<{}>", Ext::schema_id()).into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        let mut schema = json_schema!({
+            "type": "object",
+            "description": "A problem details object as defined in RFC 9457.",
+            "properties": {
+                "type": generator.subschema_for::<ProblemType>(),
+                "status": {
+                    "type": "integer",
+                    "format": "uint16",
+                    "minimum": 100,
+                    "maximum": 999,
+                },
+                "title": { "type": "string" },
+                "detail": { "type": "string" },
+                "instance": { "type": "string", "format": "uri-reference" },
+            },
+        });
+
+        if has_extensions::<Ext>() {
+            let extensions = Ext::json_schema(generator);
+            flatten(&mut schema, extensions, generator);
+        }
+
+        schema
+    }
+}
+
+#[cfg(feature = "json")]
+impl JsonSchema for crate::DynamicExtensions {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        "DynamicExtensions".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "object",
+            "additionalProperties": true,
+        })
+    }
+}
+
+/// Returns false if `Ext` is `()`, which schemars describes as `null`.
+fn has_extensions<Ext: JsonSchema>() -> bool {
+    Ext::schema_id() != <()>::schema_id()
+}
+
+/// Merges the properties of the extension schema into the problem details schema.
+fn flatten(schema: &mut Schema, extensions: Schema, generator: &SchemaGenerator) {
+    // Referenced schemas are merged using their definition
+    if let Some(Value::String(reference)) = extensions.get("$ref") {
+        let name = reference.rsplit('/').next().unwrap_or_default();
+        if let Some(Ok(definition)) = generator
+            .definitions()
+            .get(name)
+            .cloned()
+            .map(Schema::try_from)
+        {
+            flatten(schema, definition, generator);
+        }
+        return;
+    }
+
+    // Tuples are described as arrays, flatten each element
+    if let Some(Value::Array(items)) = extensions.get("prefixItems") {
+        for item in items.clone() {
+            if let Ok(item) = Schema::try_from(item) {
+                flatten(schema, item, generator);
+            }
+        }
+        return;
+    }
+
+    if extensions.get("type").and_then(Value::as_str) == Some("null") {
+        return;
+    }
+
+    let Some(extensions) = extensions.as_object() else {
+        return;
+    };
+    let target = schema.ensure_object();
+    for (key, value) in extensions {
+        match (key.as_str(), target.get_mut(key), value) {
+            ("properties", Some(Value::Object(existing)), Value::Object(properties)) => {
+                existing.extend(properties.clone());
+            }
+            ("required", Some(Value::Array(existing)), Value::Array(required)) => {
+                existing.extend(required.iter().cloned());
+            }
+            ("required" | "additionalProperties" | "patternProperties", None, value) => {
+                target.insert(key.clone(), value.clone());
+            }
+            ("oneOf" | "anyOf" | "allOf", _, value) => {
+                let all_of = target
+                    .entry("allOf")
+                    .or_insert_with(|| Value::Array(Vec::new()));
+                if let Value::Array(all_of) = all_of {
+                    all_of.push(serde_json::json!({ key.clone(): value.clone() }));
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -1,3 +1,5 @@
+pub(crate) mod extensions;
+
 pub(crate) mod uri {
     use std::fmt;
 
@@ -71,7 +73,16 @@ pub(crate) mod status {
 
     struct StatusVisitor;
 
-    impl Visitor<'_> for StatusVisitor {
+    /// A status deserialized from any representation supported by [`StatusVisitor`].
+    struct AnyStatus(Option<StatusCode>);
+
+    impl<'de> serde::Deserialize<'de> for AnyStatus {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_any(StatusVisitor).map(AnyStatus)
+        }
+    }
+
+    impl<'de> Visitor<'de> for StatusVisitor {
         type Value = Option<StatusCode>;
 
         fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
@@ -101,6 +112,33 @@ pub(crate) mod status {
             Ok(Some(status_code))
         }
 
+        fn visit_str<E: de::Error>(self, val: &str) -> Result<Self::Value, E> {
+            let val: u16 = val
+                .trim()
+                .parse()
+                .map_err(|_| de::Error::invalid_value(Unexpected::Str(val), &self))?;
+
+            self.visit_u16(val)
+        }
+
+        fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            if !crate::serde::extensions::is_reading_xml() {
+                return Err(de::Error::invalid_type(Unexpected::Map, &self));
+            }
+
+            // XML deserializers represent the text content of an element as `$text` member
+            match map.next_key::<String>()?.as_deref() {
+                Some("$text" | "$value") => {
+                    let status = map.next_value::<AnyStatus>()?;
+                    match map.next_key::<de::IgnoredAny>()? {
+                        None => Ok(status.0),
+                        Some(_) => Err(de::Error::invalid_type(Unexpected::Map, &self)),
+                    }
+                }
+                _ => Err(de::Error::invalid_type(Unexpected::Map, &self)),
+            }
+        }
+
         fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
             Ok(None)
         }
@@ -135,7 +173,89 @@ pub(crate) mod status {
         pub fn deserialize<'de, D: serde::Deserializer<'de>>(
             deserializer: D,
         ) -> Result<Option<http::StatusCode>, D::Error> {
-            deserializer.deserialize_u16(StatusVisitor)
+            // Some servers send the status as string, so accept any type
+            deserializer.deserialize_any(StatusVisitor)
+        }
+    }
+}
+
+pub(crate) mod methods {
+    use http::Method;
+    use serde::{de, ser::SerializeSeq, Deserialize};
+
+    pub fn serialize<S: serde::Serializer>(
+        value: &[Method],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(value.len()))?;
+        for method in value {
+            seq.serialize_element(method.as_str())?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Method>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|method| Method::from_bytes(method.as_bytes()).map_err(de::Error::custom))
+            .collect()
+    }
+}
+
+pub(crate) mod cow {
+    use std::{borrow::Cow, fmt};
+
+    use serde::de::{self, Visitor};
+
+    struct CowVisitor;
+
+    impl<'de> Visitor<'de> for CowVisitor {
+        type Value = Option<Cow<'de, str>>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "a string")
+        }
+
+        fn visit_borrowed_str<E: de::Error>(self, val: &'de str) -> Result<Self::Value, E> {
+            Ok(Some(Cow::Borrowed(val)))
+        }
+
+        fn visit_str<E: de::Error>(self, val: &str) -> Result<Self::Value, E> {
+            Ok(Some(Cow::Owned(val.to_string())))
+        }
+
+        fn visit_string<E: de::Error>(self, val: String) -> Result<Self::Value, E> {
+            Ok(Some(Cow::Owned(val)))
+        }
+
+        fn visit_some<D: serde::Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_str(self)
+        }
+
+        fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+    }
+
+    pub mod opt {
+        use std::borrow::Cow;
+
+        /// Deserializes an optional string, borrowing from the input if possible.
+        ///
+        /// Unlike the `Deserialize` implementation of `Cow`, this does not always allocate.
+        pub fn deserialize<'de: 'a, 'a, D: serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Cow<'a, str>>, D::Error> {
+            deserializer.deserialize_option(super::CowVisitor)
         }
     }
 }
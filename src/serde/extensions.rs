@@ -0,0 +1,791 @@
+//! Serialization of the flattened extensions field.
+//!
+//! Serde can only flatten structs and maps. To allow composing extensions
+//! from tuples of structs, the extensions are (de)serialized using an adapter
+//! which treats every tuple element as flattened into the same object.
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::{
+    de::{self, value::MapDeserializer, value::SeqDeserializer, IntoDeserializer, Visitor},
+    forward_to_deserialize_any,
+    ser::{self, Impossible, SerializeMap},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+#[cfg(feature = "xml")]
+thread_local! {
+    /// Whether an XML document is being read, see [`read_xml`].
+    static READING_XML: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Runs `read` with the workarounds for reading XML documents enabled.
+///
+/// XML has no typed values, so scalars are given as text and elements containing
+/// only text are represented as maps with a single `$text` member. These are only
+/// accepted while reading XML, other formats keep rejecting wrongly typed members.
+#[cfg(feature = "xml")]
+pub(crate) fn read_xml<T>(read: impl FnOnce() -> T) -> T {
+    struct Reset(bool);
+
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            READING_XML.set(self.0);
+        }
+    }
+
+    let _reset = Reset(READING_XML.replace(true));
+    read()
+}
+
+/// Returns true while an XML document is read using [`read_xml`].
+pub(crate) fn is_reading_xml() -> bool {
+    #[cfg(feature = "xml")]
+    return READING_XML.get();
+
+    #[cfg(not(feature = "xml"))]
+    false
+}
+
+pub fn serialize<Ext, S>(extensions: &Ext, serializer: S) -> Result<S::Ok, S::Error>
+where
+    Ext: Serialize,
+    S: Serializer,
+{
+    let mut map = serializer.serialize_map(None)?;
+    extensions.serialize(FlattenSerializer(&mut map))?;
+    map.end()
+}
+
+pub fn deserialize<'de, Ext, D>(deserializer: D) -> Result<Ext, D::Error>
+where
+    Ext: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    let content = buffer(deserializer)?;
+    Ext::deserialize(ContentDeserializer::new(&content))
+}
+
+/// Buffers the extension members.
+///
+/// While reading XML, the namespace declarations exposed as attribute members are dropped.
+fn buffer<'de, D>(deserializer: D) -> Result<Content, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let mut content = Content::deserialize(deserializer)?;
+    if let Content::Map(entries) = &mut content {
+        if is_reading_xml() {
+            entries.retain(
+                |(key, _)| !matches!(key, Content::String(key) if key.starts_with("@xmlns")),
+            );
+        }
+    }
+    Ok(content)
+}
+
+/// Deserializes the extensions, rejecting duplicate and unknown members.
+///
+/// Members are known if they are a field of an extension struct, or if the
+/// extensions accept arbitrary members (like maps).
+pub fn deserialize_strict<'de, Ext, D>(deserializer: D) -> Result<Ext, D::Error>
+where
+    Ext: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    let content = buffer(deserializer)?;
+    let Content::Map(entries) = &content else {
+        return Ext::deserialize(ContentDeserializer::new(&content));
+    };
+
+    let keys = entries
+        .iter()
+        .filter_map(|(key, _)| match key {
+            Content::String(key) => Some(key.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    for (i, key) in keys.iter().enumerate() {
+        if keys[..i].contains(key) {
+            return Err(de::Error::custom(format_args!("duplicate member `{key}`")));
+        }
+    }
+
+    let known = KnownMembers::default();
+    let extensions = Ext::deserialize(ContentDeserializer::tracked(&content, &known))?;
+
+    if !known.all.get() {
+        let fields = known.fields.borrow();
+        if let Some(key) = keys.iter().find(|key| !fields.contains(&key.as_str())) {
+            return Err(de::Error::custom(format_args!(
+                "unknown extension member `{key}`"
+            )));
+        }
+    }
+
+    Ok(extensions)
+}
+
+/// The members consumed while deserializing extensions.
+#[derive(Default)]
+struct KnownMembers {
+    fields: std::cell::RefCell<Vec<&'static str>>,
+    all: std::cell::Cell<bool>,
+}
+
+fn unsupported<E: ser::Error>(kind: &str) -> E {
+    E::custom(format_args!(
+        "can only flatten structs, maps and tuples as extensions (got {kind})"
+    ))
+}
+
+/// Serializer writing the members of structs, maps and tuple elements into a map.
+struct FlattenSerializer<'a, M>(&'a mut M);
+
+impl<'a, M> Serializer for FlattenSerializer<'a, M>
+where
+    M: SerializeMap,
+{
+    type Ok = ();
+    type Error = M::Error;
+
+    type SerializeSeq = Impossible<(), M::Error>;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Impossible<(), M::Error>;
+    type SerializeTupleVariant = Impossible<(), M::Error>;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Impossible<(), M::Error>;
+
+    fn serialize_bool(self, _: bool) -> Result<(), M::Error> {
+        Err(unsupported("a boolean"))
+    }
+
+    fn serialize_i8(self, _: i8) -> Result<(), M::Error> {
+        Err(unsupported("an integer"))
+    }
+
+    fn serialize_i16(self, _: i16) -> Result<(), M::Error> {
+        Err(unsupported("an integer"))
+    }
+
+    fn serialize_i32(self, _: i32) -> Result<(), M::Error> {
+        Err(unsupported("an integer"))
+    }
+
+    fn serialize_i64(self, _: i64) -> Result<(), M::Error> {
+        Err(unsupported("an integer"))
+    }
+
+    fn serialize_u8(self, _: u8) -> Result<(), M::Error> {
+        Err(unsupported("an integer"))
+    }
+
+    fn serialize_u16(self, _: u16) -> Result<(), M::Error> {
+        Err(unsupported("an integer"))
+    }
+
+    fn serialize_u32(self, _: u32) -> Result<(), M::Error> {
+        Err(unsupported("an integer"))
+    }
+
+    fn serialize_u64(self, _: u64) -> Result<(), M::Error> {
+        Err(unsupported("an integer"))
+    }
+
+    fn serialize_f32(self, _: f32) -> Result<(), M::Error> {
+        Err(unsupported("a float"))
+    }
+
+    fn serialize_f64(self, _: f64) -> Result<(), M::Error> {
+        Err(unsupported("a float"))
+    }
+
+    fn serialize_char(self, _: char) -> Result<(), M::Error> {
+        Err(unsupported("a char"))
+    }
+
+    fn serialize_str(self, _: &str) -> Result<(), M::Error> {
+        Err(unsupported("a string"))
+    }
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<(), M::Error> {
+        Err(unsupported("bytes"))
+    }
+
+    fn serialize_none(self) -> Result<(), M::Error> {
+        Ok(())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), M::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), M::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), M::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+    ) -> Result<(), M::Error> {
+        Err(unsupported("an enum"))
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), M::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), M::Error> {
+        self.0.serialize_entry(variant, value)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, M::Error> {
+        Err(unsupported("a sequence"))
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, M::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, M::Error> {
+        Err(unsupported("a tuple struct"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, M::Error> {
+        Err(unsupported("an enum"))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, M::Error> {
+        Ok(self)
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, M::Error> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, M::Error> {
+        Err(unsupported("an enum"))
+    }
+}
+
+impl<M> ser::SerializeTuple for FlattenSerializer<'_, M>
+where
+    M: SerializeMap,
+{
+    type Ok = ();
+    type Error = M::Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), M::Error> {
+        value.serialize(FlattenSerializer(&mut *self.0))
+    }
+
+    fn end(self) -> Result<(), M::Error> {
+        Ok(())
+    }
+}
+
+impl<M> ser::SerializeMap for FlattenSerializer<'_, M>
+where
+    M: SerializeMap,
+{
+    type Ok = ();
+    type Error = M::Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), M::Error> {
+        self.0.serialize_key(key)
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), M::Error> {
+        self.0.serialize_value(value)
+    }
+
+    fn end(self) -> Result<(), M::Error> {
+        Ok(())
+    }
+}
+
+impl<M> ser::SerializeStruct for FlattenSerializer<'_, M>
+where
+    M: SerializeMap,
+{
+    type Ok = ();
+    type Error = M::Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), M::Error> {
+        self.0.serialize_entry(key, value)
+    }
+
+    fn end(self) -> Result<(), M::Error> {
+        Ok(())
+    }
+}
+
+/// Buffered, self-describing copy of the flattened extension members.
+#[derive(Debug)]
+enum Content {
+    Bool(bool),
+    U64(u64),
+    I64(i64),
+    F64(f64),
+    Char(char),
+    String(String),
+    Bytes(Vec<u8>),
+    None,
+    Some(Box<Content>),
+    Unit,
+    Newtype(Box<Content>),
+    Seq(Vec<Content>),
+    Map(Vec<(Content, Content)>),
+}
+
+impl Content {
+    /// Returns the text content of an XML element, or this content otherwise.
+    ///
+    /// XML deserializers represent elements containing only text as map with
+    /// a single `$text` member.
+    fn unwrap_text(&self) -> &Content {
+        if !is_reading_xml() {
+            return self;
+        }
+
+        match self {
+            Content::Map(entries) => match entries.as_slice() {
+                [(Content::String(key), text @ Content::String(_))] if key == "$text" => text,
+                _ => self,
+            },
+            _ => self,
+        }
+    }
+
+    /// Returns the items of an array written as sequence of `i` elements.
+    ///
+    /// XML deserializers represent these as map with an `i` member for every item.
+    fn xml_items(&self) -> Option<Vec<&Content>> {
+        if !is_reading_xml() {
+            return None;
+        }
+
+        match self {
+            Content::Map(entries) if !entries.is_empty() => entries
+                .iter()
+                .map(|(key, value)| match key {
+                    Content::String(key) if key == "i" => Some(value),
+                    _ => None,
+                })
+                .collect(),
+            _ => None,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Content {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ContentVisitor)
+    }
+}
+
+struct ContentVisitor;
+
+impl<'de> Visitor<'de> for ContentVisitor {
+    type Value = Content;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "any value")
+    }
+
+    fn visit_bool<E: de::Error>(self, val: bool) -> Result<Content, E> {
+        Ok(Content::Bool(val))
+    }
+
+    fn visit_i64<E: de::Error>(self, val: i64) -> Result<Content, E> {
+        Ok(Content::I64(val))
+    }
+
+    fn visit_u64<E: de::Error>(self, val: u64) -> Result<Content, E> {
+        Ok(Content::U64(val))
+    }
+
+    fn visit_f64<E: de::Error>(self, val: f64) -> Result<Content, E> {
+        Ok(Content::F64(val))
+    }
+
+    fn visit_char<E: de::Error>(self, val: char) -> Result<Content, E> {
+        Ok(Content::Char(val))
+    }
+
+    fn visit_str<E: de::Error>(self, val: &str) -> Result<Content, E> {
+        Ok(Content::String(val.to_owned()))
+    }
+
+    fn visit_string<E: de::Error>(self, val: String) -> Result<Content, E> {
+        Ok(Content::String(val))
+    }
+
+    fn visit_bytes<E: de::Error>(self, val: &[u8]) -> Result<Content, E> {
+        Ok(Content::Bytes(val.to_owned()))
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, val: Vec<u8>) -> Result<Content, E> {
+        Ok(Content::Bytes(val))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Content, E> {
+        Ok(Content::None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Content, D::Error> {
+        Content::deserialize(deserializer).map(|content| Content::Some(Box::new(content)))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Content, E> {
+        Ok(Content::Unit)
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Content, D::Error> {
+        Content::deserialize(deserializer).map(|content| Content::Newtype(Box::new(content)))
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Content, A::Error> {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(Content::Seq(items))
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Content, A::Error> {
+        let mut entries = Vec::with_capacity(map.size_hint().unwrap_or(0));
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(Content::Map(entries))
+    }
+}
+
+/// Implements deserialization of scalars which are parsed if given as string.
+///
+/// XML has no typed scalars and buffers all values as strings, see [`read_xml`].
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident: $ty:ty),+) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+                match self.content {
+                    Content::String(val) if is_reading_xml() => match val.trim().parse::<$ty>() {
+                        Ok(parsed) => visitor.$visit(parsed),
+                        Err(_) => self.deserialize_any(visitor),
+                    },
+                    _ => self.deserialize_any(visitor),
+                }
+            }
+        )+
+    };
+}
+
+/// Deserializer reading from buffered content.
+///
+/// Tuples are deserialized by handing the same content to every element,
+/// so each element can pick the members it knows about.
+struct ContentDeserializer<'a, E> {
+    content: &'a Content,
+    known: Option<&'a KnownMembers>,
+    error: PhantomData<E>,
+}
+
+impl<'a, E> ContentDeserializer<'a, E> {
+    fn new(content: &'a Content) -> Self {
+        Self {
+            content: content.unwrap_text(),
+            known: None,
+            error: PhantomData,
+        }
+    }
+
+    /// Creates a deserializer recording which members of the content are consumed.
+    fn tracked(content: &'a Content, known: &'a KnownMembers) -> Self {
+        Self {
+            known: Some(known),
+            ..Self::new(content)
+        }
+    }
+}
+
+impl<'de, E> IntoDeserializer<'de, E> for ContentDeserializer<'_, E>
+where
+    E: de::Error,
+{
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de, 'a, E> IntoDeserializer<'de, E> for &'a Content
+where
+    E: de::Error,
+{
+    type Deserializer = ContentDeserializer<'a, E>;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        ContentDeserializer::new(self)
+    }
+}
+
+impl<E> ContentDeserializer<'_, E>
+where
+    E: de::Error,
+{
+    /// Visits the buffered content as is.
+    fn visit_content<'de, V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        if let Some(known) = self.known {
+            known.all.set(true);
+        }
+
+        match self.content {
+            Content::Bool(val) => visitor.visit_bool(*val),
+            Content::U64(val) => visitor.visit_u64(*val),
+            Content::I64(val) => visitor.visit_i64(*val),
+            Content::F64(val) => visitor.visit_f64(*val),
+            Content::Char(val) => visitor.visit_char(*val),
+            Content::String(val) => visitor.visit_str(val),
+            Content::Bytes(val) => visitor.visit_bytes(val),
+            Content::None => visitor.visit_none(),
+            Content::Some(content) => visitor.visit_some(ContentDeserializer::new(content)),
+            Content::Unit => visitor.visit_unit(),
+            Content::Newtype(content) => {
+                visitor.visit_newtype_struct(ContentDeserializer::new(content))
+            }
+            Content::Seq(items) => {
+                let mut seq = SeqDeserializer::new(items.iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            Content::Map(entries) => {
+                let entries = entries.iter().map(|(key, value)| (key, value));
+                let mut map = MapDeserializer::new(entries);
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+        }
+    }
+}
+
+impl<'de, E> Deserializer<'de> for ContentDeserializer<'_, E>
+where
+    E: de::Error,
+{
+    type Error = E;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        match self.content.xml_items() {
+            Some(items) => visit_items(items, visitor),
+            None => self.visit_content(visitor),
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        match self.content {
+            // An empty array is written as empty element
+            Content::Map(entries) if is_reading_xml() && entries.is_empty() => {
+                visit_items(Vec::new(), visitor)
+            }
+            Content::String(val) if is_reading_xml() && val.is_empty() => {
+                visit_items(Vec::new(), visitor)
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        self.visit_content(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        match self.content {
+            Content::None | Content::Unit => visitor.visit_none(),
+            Content::Some(content) => visitor.visit_some(ContentDeserializer::new(content)),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        match self.content {
+            // Unknown members are ignored when no extensions are expected
+            Content::Map(_) | Content::Unit => visitor.visit_unit(),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, E> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, E> {
+        match self.content {
+            Content::Newtype(content) => {
+                visitor.visit_newtype_struct(ContentDeserializer::new(content))
+            }
+            _ => visitor.visit_newtype_struct(self),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, E> {
+        match self.content {
+            Content::Map(_) => visitor.visit_seq(RepeatSeq {
+                content: self.content,
+                known: self.known,
+                remaining: len,
+                error: PhantomData,
+            }),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, E> {
+        match self.content {
+            Content::String(val) => visitor.visit_enum(val.as_str().into_deserializer()),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        mut self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, E> {
+        if let Some(known) = self.known.take() {
+            known.fields.borrow_mut().extend_from_slice(fields);
+        }
+
+        self.visit_content(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        visitor.visit_unit()
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool: bool,
+        deserialize_i8 => visit_i8: i8,
+        deserialize_i16 => visit_i16: i16,
+        deserialize_i32 => visit_i32: i32,
+        deserialize_i64 => visit_i64: i64,
+        deserialize_u8 => visit_u8: u8,
+        deserialize_u16 => visit_u16: u16,
+        deserialize_u32 => visit_u32: u32,
+        deserialize_u64 => visit_u64: u64,
+        deserialize_f32 => visit_f32: f32,
+        deserialize_f64 => visit_f64: f64
+    }
+
+    forward_to_deserialize_any! {
+        i128 u128 char str string bytes byte_buf tuple_struct identifier
+    }
+}
+
+/// Visits the given items as sequence.
+fn visit_items<'de, V, E>(items: Vec<&Content>, visitor: V) -> Result<V::Value, E>
+where
+    V: Visitor<'de>,
+    E: de::Error,
+{
+    let mut seq = SeqDeserializer::new(items.into_iter());
+    let value = visitor.visit_seq(&mut seq)?;
+    seq.end()?;
+    Ok(value)
+}
+
+/// Sequence access yielding the same content for every element of a tuple.
+struct RepeatSeq<'a, E> {
+    content: &'a Content,
+    known: Option<&'a KnownMembers>,
+    remaining: usize,
+    error: PhantomData<E>,
+}
+
+impl<'de, E> de::SeqAccess<'de> for RepeatSeq<'_, E>
+where
+    E: de::Error,
+{
+    type Error = E;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, E> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+        let deserializer = match self.known {
+            Some(known) => ContentDeserializer::tracked(self.content, known),
+            None => ContentDeserializer::new(self.content),
+        };
+        seed.deserialize(deserializer).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
@@ -0,0 +1,185 @@
+use http::StatusCode;
+
+use crate::{MergeExtensions, ProblemDetails};
+
+/// The severity of a problem, e.g. for alert routing in ops tooling.
+///
+/// Severities are ordered from [`Info`](Severity::Info) to [`Critical`](Severity::Critical),
+/// and serialized as lowercase strings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The problem is informational and requires no action.
+    Info,
+
+    /// The problem may require attention.
+    Warning,
+
+    /// The problem requires attention.
+    Error,
+
+    /// The problem requires immediate attention.
+    Critical,
+}
+
+impl Severity {
+    /// Returns the lowercase name of this severity, e.g. `warning`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+            Self::Critical => "critical",
+        }
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Severity {
+    type Err = UnknownSeverityError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "info" => Ok(Self::Info),
+            "warning" => Ok(Self::Warning),
+            "error" => Ok(Self::Error),
+            "critical" => Ok(Self::Critical),
+            _ => Err(UnknownSeverityError(s.to_string())),
+        }
+    }
+}
+
+/// Error returned when parsing an unknown [`Severity`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownSeverityError(String);
+
+impl std::fmt::Display for UnknownSeverityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown severity {}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownSeverityError {}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Severity {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Severity {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = std::borrow::Cow::<'de, str>::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Extension member carrying a [`Severity`] as `severity`.
+///
+/// This is added by [`ProblemDetails::with_severity`] and [`ProblemDetails::with_severity_from`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeverityExtension {
+    /// The severity of the problem.
+    pub severity: Severity,
+}
+
+impl MergeExtensions for SeverityExtension {
+    fn merge_extensions(&mut self, other: Self) {
+        *self = other;
+    }
+}
+
+/// A policy deciding the severity of problems based on their status.
+///
+/// This allows logging and metrics integrations to classify problems consistently.
+/// The policy is implemented for closures taking the status.
+///
+/// ```rust
+/// use http::StatusCode;
+/// use problem_details::{ProblemDetails, Severity};
+///
+/// let policy = |status: Option<StatusCode>| match status {
+///     Some(StatusCode::SERVICE_UNAVAILABLE) => Severity::Critical,
+///     _ => Severity::Info,
+/// };
+///
+/// let details = ProblemDetails::from_status_code(StatusCode::SERVICE_UNAVAILABLE)
+///     .with_severity_from(&policy);
+///
+/// assert_eq!(details.extensions.1.severity, Severity::Critical);
+/// ```
+pub trait SeverityPolicy {
+    /// Returns the severity of a problem with the given status.
+    fn severity(&self, status: Option<StatusCode>) -> Severity;
+}
+
+impl<F> SeverityPolicy for F
+where
+    F: Fn(Option<StatusCode>) -> Severity,
+{
+    fn severity(&self, status: Option<StatusCode>) -> Severity {
+        self(status)
+    }
+}
+
+/// The default severity policy, based on the [`ProblemKind`](crate::ProblemKind).
+///
+/// Server errors are [`Error`](Severity::Error), client errors are
+/// [`Warning`](Severity::Warning) and all other problems are [`Info`](Severity::Info).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultSeverityPolicy;
+
+impl SeverityPolicy for DefaultSeverityPolicy {
+    fn severity(&self, status: Option<StatusCode>) -> Severity {
+        match status {
+            Some(status) if status.is_server_error() => Severity::Error,
+            Some(status) if status.is_client_error() => Severity::Warning,
+            _ => Severity::Info,
+        }
+    }
+}
+
+impl<Ext> ProblemDetails<Ext> {
+    /// Builder-style method that adds the given severity as `severity` extension member.
+    ///
+    /// ```rust
+    /// use http::StatusCode;
+    /// use problem_details::{ProblemDetails, Severity};
+    ///
+    /// let details = ProblemDetails::from_status_code(StatusCode::INTERNAL_SERVER_ERROR)
+    ///     .with_severity(Severity::Critical);
+    ///
+    /// let json = serde_json::to_value(&details).unwrap();
+    /// assert_eq!(json["severity"], "critical");
+    /// ```
+    #[must_use]
+    pub fn with_severity(self, severity: Severity) -> ProblemDetails<(Ext, SeverityExtension)> {
+        self.extend_extensions(SeverityExtension { severity })
+    }
+
+    /// Builder-style method that adds the severity decided by the given policy
+    /// as `severity` extension member.
+    ///
+    /// See [`SeverityPolicy`] and [`DefaultSeverityPolicy`].
+    #[must_use]
+    pub fn with_severity_from(
+        self,
+        policy: &impl SeverityPolicy,
+    ) -> ProblemDetails<(Ext, SeverityExtension)> {
+        let severity = policy.severity(self.status);
+        self.with_severity(severity)
+    }
+}
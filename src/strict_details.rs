@@ -0,0 +1,118 @@
+use std::borrow::Cow;
+
+use http::{StatusCode, Uri};
+use serde::{Deserialize, Deserializer};
+
+use crate::{ProblemDetails, ProblemType};
+
+/// A problem details object which rejects unknown and duplicate members when deserialized.
+///
+/// By default, [`ProblemDetails`] ignores members not covered by its extensions, as
+/// required by the RFC for consumers. When validating your own documents, e.g. in tests
+/// or when checking a contract, it can be useful to reject them instead. Deserializing a
+/// `StrictProblemDetails` fails if
+///
+/// - a member is neither a standard member nor a field of the extensions, or
+/// - a member appears more than once.
+///
+/// Extensions accepting arbitrary members, like [`DynamicExtensions`](crate::DynamicExtensions)
+/// or maps, accept every member. Standard members are still checked for duplicates.
+///
+/// # Example
+///
+/// ```rust
+/// use problem_details::StrictProblemDetails;
+///
+/// #[derive(serde::Deserialize)]
+/// struct OutOfCredit {
+///     balance: u32,
+/// }
+///
+/// let details: StrictProblemDetails<OutOfCredit> =
+///     serde_json::from_str(r#"{"status":403,"balance":30}"#).unwrap();
+/// assert_eq!(details.0.extensions.balance, 30);
+///
+/// let result = serde_json::from_str::<StrictProblemDetails<OutOfCredit>>(
+///     r#"{"status":403,"balance":30,"accounts":["/account/12345"]}"#,
+/// );
+/// assert!(result.is_err());
+/// ```
+///
+/// Requires feature `serde`.
+#[derive(Clone, Debug, Default)]
+pub struct StrictProblemDetails<Ext = ()>(pub ProblemDetails<Ext>);
+
+impl<Ext> StrictProblemDetails<Ext> {
+    /// Returns the deserialized problem details.
+    pub fn into_inner(self) -> ProblemDetails<Ext> {
+        self.0
+    }
+}
+
+impl<Ext> From<ProblemDetails<Ext>> for StrictProblemDetails<Ext> {
+    fn from(value: ProblemDetails<Ext>) -> Self {
+        Self(value)
+    }
+}
+
+impl<Ext> From<StrictProblemDetails<Ext>> for ProblemDetails<Ext> {
+    fn from(value: StrictProblemDetails<Ext>) -> Self {
+        value.0
+    }
+}
+
+impl<'de, Ext> Deserialize<'de> for StrictProblemDetails<Ext>
+where
+    Ext: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let strict = Strict::deserialize(deserializer)?;
+
+        let mut details = ProblemDetails::new().with_extensions(strict.extensions);
+        details.r#type = strict.r#type;
+        details.status = strict.status;
+        details.title = strict.title;
+        details.detail = strict.detail;
+        details.instance = strict.instance;
+        Ok(Self(details))
+    }
+}
+
+#[cfg(feature = "json")]
+impl<Ext> StrictProblemDetails<Ext>
+where
+    Ext: serde::de::DeserializeOwned,
+{
+    /// Parses a problem details object from JSON, rejecting unknown and duplicate members.
+    ///
+    /// Requires feature `json`.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+}
+
+/// Mirrors the serialized form of [`ProblemDetails`] with strict extensions.
+#[derive(Deserialize)]
+struct Strict<Ext> {
+    #[serde(default)]
+    r#type: Option<ProblemType>,
+
+    #[serde(default, with = "crate::serde::status::opt")]
+    status: Option<StatusCode>,
+
+    #[serde(default)]
+    title: Option<Cow<'static, str>>,
+
+    #[serde(default)]
+    detail: Option<Cow<'static, str>>,
+
+    #[serde(default, with = "crate::serde::uri::opt")]
+    instance: Option<Uri>,
+
+    #[serde(
+        flatten,
+        deserialize_with = "crate::serde::extensions::deserialize_strict"
+    )]
+    #[serde(bound(deserialize = "Ext: Deserialize<'de>"))]
+    extensions: Ext,
+}
@@ -0,0 +1,108 @@
+use std::borrow::Cow;
+
+use http::{StatusCode, Uri};
+
+use crate::ProblemDetails;
+
+/// A reusable definition of a problem type.
+///
+/// A template defines the `type`, `status` and `title` of a problem once,
+/// and is instantiated per occurrence with the specific `detail`, `instance`
+/// and extensions. Templates can be defined as constants.
+///
+/// ```rust
+/// use http::{StatusCode, Uri};
+/// use problem_details::ProblemTemplate;
+///
+/// const OUT_OF_CREDIT: ProblemTemplate = ProblemTemplate::new(
+///     "https://example.com/probs/out-of-credit",
+///     StatusCode::FORBIDDEN,
+///     "You do not have enough credit.",
+/// )
+/// .with_documentation("https://example.com/docs/errors#out-of-credit");
+///
+/// let details = OUT_OF_CREDIT
+///     .with_detail("Your current balance is 30, but that costs 50.")
+///     .with_instance(Uri::from_static("/account/12345/msgs/abc"));
+///
+/// assert_eq!(details.status, Some(StatusCode::FORBIDDEN));
+/// assert_eq!(details.title.as_deref(), Some("You do not have enough credit."));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProblemTemplate {
+    r#type: &'static str,
+    status: StatusCode,
+    title: &'static str,
+    documentation: Option<&'static str>,
+}
+
+impl ProblemTemplate {
+    /// Creates a new problem template.
+    ///
+    /// The `type` must be a valid URI, otherwise instantiating the template panics
+    /// (like [`Uri::from_static`]).
+    pub const fn new(r#type: &'static str, status: StatusCode, title: &'static str) -> Self {
+        Self {
+            r#type,
+            status,
+            title,
+            documentation: None,
+        }
+    }
+
+    /// Builder-style method that sets a link to human-readable documentation of this problem type.
+    ///
+    /// The documentation link is not part of the problem details.
+    #[must_use]
+    pub const fn with_documentation(mut self, documentation: &'static str) -> Self {
+        self.documentation = Some(documentation);
+        self
+    }
+
+    /// Returns the type URI of this template.
+    pub const fn r#type(&self) -> &'static str {
+        self.r#type
+    }
+
+    /// Returns the status code of this template.
+    pub const fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Returns the title of this template.
+    pub const fn title(&self) -> &'static str {
+        self.title
+    }
+
+    /// Returns the documentation link of this template, if any.
+    pub const fn documentation(&self) -> Option<&'static str> {
+        self.documentation
+    }
+
+    /// Creates a new problem details object from this template.
+    #[must_use]
+    pub fn instantiate(&self) -> ProblemDetails {
+        ProblemDetails::new()
+            .with_type(Uri::from_static(self.r#type))
+            .with_status(self.status)
+            .with_title(self.title)
+    }
+
+    /// Creates a new problem details object from this template with the given `detail`.
+    #[must_use]
+    pub fn with_detail(&self, detail: impl Into<Cow<'static, str>>) -> ProblemDetails {
+        self.instantiate().with_detail(detail)
+    }
+
+    /// Creates a new problem details object from this template with the given `instance`.
+    #[must_use]
+    pub fn with_instance(&self, instance: impl Into<Uri>) -> ProblemDetails {
+        self.instantiate().with_instance(instance)
+    }
+}
+
+impl From<ProblemTemplate> for ProblemDetails {
+    fn from(value: ProblemTemplate) -> Self {
+        value.instantiate()
+    }
+}
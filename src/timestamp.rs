@@ -0,0 +1,136 @@
+use std::borrow::Cow;
+use std::time::SystemTime;
+
+use crate::{date, MergeExtensions, ProblemDetails};
+
+/// Extension member recording when a problem occurred, as RFC 3339 date-time in UTC.
+///
+/// The member is named `timestamp` by default, which can be changed using
+/// [`with_key`](Timestamp::with_key) to follow other API guidelines.
+///
+/// ```rust
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use problem_details::{ProblemDetails, Timestamp};
+///
+/// let occurred_at = UNIX_EPOCH + Duration::from_secs(784111777);
+///
+/// let details = ProblemDetails::new().with_occurred_at(occurred_at);
+/// let json = serde_json::to_value(&details).unwrap();
+/// assert_eq!(json["timestamp"], "1994-11-06T08:49:37Z");
+///
+/// let details = ProblemDetails::new()
+///     .with_occurred_at(Timestamp::from(occurred_at).with_key("occurredAt"));
+/// let json = serde_json::to_value(&details).unwrap();
+/// assert_eq!(json["occurredAt"], "1994-11-06T08:49:37Z");
+/// ```
+///
+/// With feature `time` or `chrono`, timestamps can also be created from
+/// `time::OffsetDateTime` or `chrono::DateTime` respectively.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Timestamp {
+    key: Cow<'static, str>,
+    time: SystemTime,
+}
+
+impl Timestamp {
+    /// The default name of the extension member.
+    pub const DEFAULT_KEY: &'static str = "timestamp";
+
+    /// Creates a timestamp for the given point in time.
+    pub fn new(time: SystemTime) -> Self {
+        Self {
+            key: Cow::Borrowed(Self::DEFAULT_KEY),
+            time,
+        }
+    }
+
+    /// Creates a timestamp for the current point in time.
+    pub fn now() -> Self {
+        Self::new(SystemTime::now())
+    }
+
+    /// Builder-style method that sets the name of the extension member.
+    #[must_use]
+    pub fn with_key(mut self, key: impl Into<Cow<'static, str>>) -> Self {
+        self.key = key.into();
+        self
+    }
+
+    /// Returns the name of the extension member.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Returns the recorded point in time.
+    pub fn time(&self) -> SystemTime {
+        self.time
+    }
+}
+
+/// Formats the timestamp as RFC 3339 date-time in UTC.
+impl std::fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        date::fmt_rfc3339(f, self.time)
+    }
+}
+
+impl From<SystemTime> for Timestamp {
+    fn from(time: SystemTime) -> Self {
+        Self::new(time)
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<time::OffsetDateTime> for Timestamp {
+    fn from(time: time::OffsetDateTime) -> Self {
+        Self::new(time.into())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz> From<chrono::DateTime<Tz>> for Timestamp
+where
+    Tz: chrono::TimeZone,
+{
+    fn from(time: chrono::DateTime<Tz>) -> Self {
+        Self::new(time.into())
+    }
+}
+
+impl MergeExtensions for Timestamp {
+    fn merge_extensions(&mut self, other: Self) {
+        *self = other;
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Timestamp {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(self.key(), &format_args!("{self}"))?;
+        map.end()
+    }
+}
+
+impl<Ext> ProblemDetails<Ext> {
+    /// Builder-style method that records the current point in time as `timestamp`.
+    ///
+    /// The timestamp is added next to the existing extensions, see [`Timestamp`].
+    #[must_use]
+    pub fn with_timestamp(self) -> ProblemDetails<(Ext, Timestamp)> {
+        self.with_occurred_at(Timestamp::now())
+    }
+
+    /// Builder-style method that records the given point in time as `timestamp`.
+    ///
+    /// The timestamp is added next to the existing extensions, see [`Timestamp`].
+    #[must_use]
+    pub fn with_occurred_at(self, time: impl Into<Timestamp>) -> ProblemDetails<(Ext, Timestamp)> {
+        self.extend_extensions(time.into())
+    }
+}
@@ -0,0 +1,43 @@
+//! Well-known problem type URIs.
+//!
+//! These statics can be used to reference standardized problem types instead
+//! of repeating their URIs as string literals.
+//!
+//! ```rust
+//! use http::StatusCode;
+//! use problem_details::{types, ProblemDetails};
+//!
+//! let details = ProblemDetails::from_status_code(StatusCode::BAD_REQUEST)
+//!     .with_type(types::DATE.clone())
+//!     .with_detail("The date header is not within the allowed skew");
+//!
+//! assert!(details.matches_type(&types::DATE));
+//! ```
+use std::sync::LazyLock;
+
+use http::Uri;
+
+use crate::ProblemType;
+
+/// `about:blank`, the default problem type.
+///
+/// Indicates that the problem has no additional semantics beyond that of the HTTP status code.
+/// See [RFC 9457, Section 4.2.1](https://www.rfc-editor.org/rfc/rfc9457.html#name-aboutblank).
+pub static ABOUT_BLANK: LazyLock<ProblemType> = LazyLock::new(ProblemType::default);
+
+/// `https://iana.org/assignments/http-problem-types#date`
+///
+/// Indicates that the `Date` header of the request was not acceptable.
+/// Registered in the [IANA HTTP Problem Types registry](https://www.iana.org/assignments/http-problem-types/)
+/// by [RFC 9457, Section 4.2.2](https://www.rfc-editor.org/rfc/rfc9457.html#name-registered-problem-types).
+pub static DATE: LazyLock<ProblemType> = LazyLock::new(|| {
+    Uri::from_static("https://iana.org/assignments/http-problem-types#date").into()
+});
+
+/// `https://iana.org/assignments/http-problem-types#quota-exceeded`
+///
+/// Indicates that the client exceeded a quota, as proposed by the IETF HTTPAPI
+/// working group draft on [RateLimit header fields](https://datatracker.ietf.org/doc/draft-ietf-httpapi-ratelimit-headers/).
+pub static QUOTA_EXCEEDED: LazyLock<ProblemType> = LazyLock::new(|| {
+    Uri::from_static("https://iana.org/assignments/http-problem-types#quota-exceeded").into()
+});
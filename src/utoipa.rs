@@ -0,0 +1,248 @@
+//! OpenAPI schemas for [`ProblemDetails`] using [`utoipa`]. Requires feature `utoipa`.
+//!
+//! With the `utoipa` feature enabled, [`ProblemDetails`] implements [`ToSchema`].
+//! Problems without extensions are described by a `ProblemDetails` component schema
+//! containing the standard members.
+//!
+//! Extension structs implementing [`ToSchema`] can be registered using the
+//! [`extension_schema!`](crate::extension_schema) macro. The schema of a problem with
+//! such extensions is then composed via `allOf` of the `ProblemDetails` schema
+//! and the extension schema, e.g. `ProblemDetails_OutOfCredit`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use problem_details::ProblemDetails;
+//! use utoipa::{PartialSchema, ToSchema};
+//!
+//! #[derive(serde::Serialize, ToSchema)]
+//! struct OutOfCredit {
+//!     balance: u32,
+//! }
+//!
+//! problem_details::extension_schema!(OutOfCredit);
+//!
+//! assert_eq!(ProblemDetails::<OutOfCredit>::name(), "ProblemDetails_OutOfCredit");
+//!
+//! let mut schemas = Vec::new();
+//! ProblemDetails::<OutOfCredit>::schemas(&mut schemas);
+//!
+//! let names = schemas.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>();
+//! assert_eq!(names, ["ProblemDetails", "OutOfCredit"]);
+//! ```
+use std::borrow::Cow;
+
+use utoipa::{
+    openapi::{
+        schema::{AllOfBuilder, ObjectBuilder, Schema, SchemaFormat, Type},
+        Ref, RefOr,
+    },
+    PartialSchema, ToSchema,
+};
+
+use crate::ProblemDetails;
+
+/// The name of the schema containing the standard members.
+const BASE_SCHEMA_NAME: &str = "ProblemDetails";
+
+/// Extensions which can be described in the schema of a [`ProblemDetails`].
+///
+/// This is implemented for `()` (no extensions) and for tuples of extensions.
+/// Extension structs implementing [`ToSchema`] can implement it using the
+/// [`extension_schema!`](crate::extension_schema) macro.
+pub trait ExtensionSchema {
+    /// Returns the schema of the extension members, or `None` if there are none.
+    fn extension_schema() -> Option<RefOr<Schema>>;
+
+    /// Returns the name used to identify problems with these extensions.
+    fn extension_name() -> Option<Cow<'static, str>>;
+
+    /// Collects the component schemas referenced by the extension schema.
+    fn extension_schemas(schemas: &mut Vec<(String, RefOr<Schema>)>) {
+        let _ = schemas;
+    }
+}
+
+impl ExtensionSchema for () {
+    fn extension_schema() -> Option<RefOr<Schema>> {
+        None
+    }
+
+    fn extension_name() -> Option<Cow<'static, str>> {
+        None
+    }
+}
+
+/// Implements [`ExtensionSchema`](crate::utoipa::ExtensionSchema) for the given extension types.
+///
+/// The types need to implement [`ToSchema`](::utoipa::ToSchema). Their schema is
+/// referenced by name, and registered as component schema. Requires feature `utoipa`.
+///
+/// See the [`utoipa`](crate::utoipa) module for an example.
+#[macro_export]
+macro_rules! extension_schema {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl $crate::utoipa::ExtensionSchema for $ty {
+                fn extension_schema() -> ::std::option::Option<
+                    $crate::__private::utoipa::openapi::RefOr<$crate::__private::utoipa::openapi::schema::Schema>
+                > {
+                    ::std::option::Option::Some(
+                        $crate::__private::utoipa::openapi::Ref::from_schema_name(
+                            <$ty as $crate::__private::utoipa::ToSchema>::name()
+                        ).into()
+                    )
+                }
+
+                fn extension_name() -> ::std::option::Option<::std::borrow::Cow<'static, str>> {
+                    ::std::option::Option::Some(<$ty as $crate::__private::utoipa::ToSchema>::name())
+                }
+
+                fn extension_schemas(
+                    schemas: &mut ::std::vec::Vec<(
+                        ::std::string::String,
+                        $crate::__private::utoipa::openapi::RefOr<$crate::__private::utoipa::openapi::schema::Schema>,
+                    )>,
+                ) {
+                    schemas.push((
+                        <$ty as $crate::__private::utoipa::ToSchema>::name().into(),
+                        <$ty as $crate::__private::utoipa::PartialSchema>::schema(),
+                    ));
+                    <$ty as $crate::__private::utoipa::ToSchema>::schemas(schemas);
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_extension_schema_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name),+> ExtensionSchema for ($($name,)+)
+        where
+            $($name: ExtensionSchema,)+
+        {
+            fn extension_schema() -> Option<RefOr<Schema>> {
+                let schemas = [$($name::extension_schema()),+]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>();
+
+                match schemas.len() {
+                    0 => None,
+                    1 => schemas.into_iter().next(),
+                    _ => Some(
+                        schemas
+                            .into_iter()
+                            .fold(AllOfBuilder::new(), AllOfBuilder::item)
+                            .into(),
+                    ),
+                }
+            }
+
+            fn extension_name() -> Option<Cow<'static, str>> {
+                let names = [$($name::extension_name()),+]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>();
+
+                match names.len() {
+                    0 => None,
+                    1 => names.into_iter().next(),
+                    _ => Some(names.join("_").into()),
+                }
+            }
+
+            fn extension_schemas(schemas: &mut Vec<(String, RefOr<Schema>)>) {
+                $($name::extension_schemas(schemas);)+
+            }
+        }
+    };
+}
+
+impl_extension_schema_for_tuple!(A);
+impl_extension_schema_for_tuple!(A, B);
+impl_extension_schema_for_tuple!(A, B, C);
+impl_extension_schema_for_tuple!(A, B, C, D);
+
+/// Returns the schema containing the standard members.
+fn base_schema() -> RefOr<Schema> {
+    let uri_reference = || {
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .format(Some(SchemaFormat::Custom("uri-reference".to_string())))
+    };
+
+    ObjectBuilder::new()
+        .description(Some("A problem details object as defined in RFC 9457."))
+        .property(
+            "type",
+            uri_reference()
+                .description(Some("A URI reference that identifies the problem type."))
+                .default(Some("about:blank".into())),
+        )
+        .property(
+            "status",
+            ObjectBuilder::new()
+                .schema_type(Type::Integer)
+                .minimum(Some(100))
+                .maximum(Some(599))
+                .description(Some(
+                    "The HTTP status code for this occurrence of the problem.",
+                )),
+        )
+        .property(
+            "title",
+            ObjectBuilder::new()
+                .schema_type(Type::String)
+                .description(Some("A short, human-readable summary of the problem type.")),
+        )
+        .property(
+            "detail",
+            ObjectBuilder::new()
+                .schema_type(Type::String)
+                .description(Some(
+                    "A human-readable explanation specific to this occurrence of the problem.",
+                )),
+        )
+        .property(
+            "instance",
+            uri_reference().description(Some(
+                "A URI reference that identifies the specific occurrence of the problem.",
+            )),
+        )
+        .into()
+}
+
+impl<Ext> PartialSchema for ProblemDetails<Ext>
+where
+    Ext: ExtensionSchema,
+{
+    fn schema() -> RefOr<Schema> {
+        match Ext::extension_schema() {
+            Some(extension_schema) => AllOfBuilder::new()
+                .item(Ref::from_schema_name(BASE_SCHEMA_NAME))
+                .item(extension_schema)
+                .into(),
+            None => base_schema(),
+        }
+    }
+}
+
+impl<Ext> ToSchema for ProblemDetails<Ext>
+where
+    Ext: ExtensionSchema,
+{
+    fn name() -> Cow<'static, str> {
+        match Ext::extension_name() {
+            Some(name) => format!("{BASE_SCHEMA_NAME}_{name}").into(),
+            None => BASE_SCHEMA_NAME.into(),
+        }
+    }
+
+    fn schemas(schemas: &mut Vec<(String, RefOr<Schema>)>) {
+        if Ext::extension_schema().is_some() {
+            schemas.push((BASE_SCHEMA_NAME.to_string(), base_schema()));
+        }
+        Ext::extension_schemas(schemas);
+    }
+}
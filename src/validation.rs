@@ -0,0 +1,175 @@
+use http::{header, response::Parts, StatusCode};
+
+use crate::{
+    dynamic_extensions::RESERVED_MEMBERS, problem_type::split_scheme, ProblemDetails, ProblemType,
+};
+
+/// The essences of the content types used by the problem formats of this crate.
+const PROBLEM_CONTENT_TYPES: [&str; 6] = [
+    "application/problem+json",
+    "application/problem+xml",
+    "application/concise-problem-details+cbor",
+    "application/yaml",
+    "text/html",
+    "text/plain",
+];
+
+/// A conformance warning returned by [`ProblemDetails::validate`].
+///
+/// Each warning corresponds to a recommendation of
+/// [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html) that is not met by the problem details.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValidationWarning {
+    /// The `type` member is not an absolute URI.
+    ///
+    /// See [https://www.rfc-editor.org/rfc/rfc9457.html#name-type]() for more information.
+    RelativeType(ProblemType),
+
+    /// The `status` member does not match the status code of the HTTP response.
+    ///
+    /// See [https://www.rfc-editor.org/rfc/rfc9457.html#name-status]() for more information.
+    StatusMismatch {
+        /// The status code of the problem details.
+        problem: StatusCode,
+        /// The status code of the HTTP response.
+        response: StatusCode,
+    },
+
+    /// The HTTP response does not use a problem details content type.
+    ContentTypeMismatch(Option<String>),
+
+    /// An extension uses the name of a member defined by the RFC.
+    ///
+    /// See [https://www.rfc-editor.org/rfc/rfc9457.html#name-extension-members]() for more information.
+    ReservedExtension(String),
+
+    /// The problem type is `about:blank`, but the `title` differs from the
+    /// reason phrase of the status code.
+    ///
+    /// See [https://www.rfc-editor.org/rfc/rfc9457.html#name-aboutblank]() for more information.
+    TitleMismatch {
+        /// The title of the problem details.
+        title: String,
+        /// The canonical reason phrase of the status code.
+        reason: &'static str,
+    },
+}
+
+impl std::fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RelativeType(r#type) => write!(f, "Type {type} is not an absolute uri"),
+            Self::StatusMismatch { problem, response } => write!(
+                f,
+                "Status {} does not match response status {}",
+                problem.as_u16(),
+                response.as_u16()
+            ),
+            Self::ContentTypeMismatch(Some(content_type)) => {
+                write!(
+                    f,
+                    "Content type {content_type} is not a problem details type"
+                )
+            }
+            Self::ContentTypeMismatch(None) => write!(f, "Content type is missing"),
+            Self::ReservedExtension(name) => {
+                write!(f, "Extension {name} uses a reserved member name")
+            }
+            Self::TitleMismatch { title, reason } => write!(
+                f,
+                "Title {title:?} does not match status reason {reason:?} for type about:blank"
+            ),
+        }
+    }
+}
+
+impl<Ext> ProblemDetails<Ext>
+where
+    Ext: serde::Serialize,
+{
+    /// Checks this problem details object for conformance with RFC 9457.
+    ///
+    /// Returns a list of warnings, which is empty if no issues were found.
+    /// This is mainly intended to be used in tests.
+    ///
+    /// ```rust
+    /// use http::{StatusCode, Uri};
+    /// use problem_details::{ProblemDetails, ValidationWarning};
+    ///
+    /// let details = ProblemDetails::from_status_code(StatusCode::NOT_FOUND)
+    ///     .with_type(Uri::from_static("/probs/not-found"));
+    ///
+    /// assert!(matches!(
+    ///     details.validate().as_slice(),
+    ///     [ValidationWarning::RelativeType(_)]
+    /// ));
+    /// ```
+    pub fn validate(&self) -> Vec<ValidationWarning> {
+        let mut warnings = Vec::new();
+
+        let r#type = self.effective_type();
+
+        if split_scheme(&r#type.to_string()).is_none() {
+            warnings.push(ValidationWarning::RelativeType(r#type.into_owned()));
+        } else if *r#type == ProblemType::default() {
+            let reason = self.status.as_ref().and_then(StatusCode::canonical_reason);
+            if let (Some(title), Some(reason)) = (self.title.as_deref(), reason) {
+                if title != reason {
+                    warnings.push(ValidationWarning::TitleMismatch {
+                        title: title.to_string(),
+                        reason,
+                    });
+                }
+            }
+        }
+
+        if let Ok(serde_json::Value::Object(extensions)) = serde_json::to_value(&self.extensions) {
+            warnings.extend(
+                extensions
+                    .keys()
+                    .filter(|key| RESERVED_MEMBERS.contains(&key.as_str()))
+                    .map(|key| ValidationWarning::ReservedExtension(key.clone())),
+            );
+        }
+
+        warnings
+    }
+
+    /// Checks this problem details object for conformance with RFC 9457 when
+    /// sent as part of a response with the given parts.
+    ///
+    /// In addition to the checks done by [`validate`](ProblemDetails::validate),
+    /// this checks that the status code and content type of the response match.
+    /// All content types of the formats supported by this crate are accepted, i.e.
+    /// JSON, XML, concise CBOR, YAML, HTML and plain text.
+    pub fn validate_against(&self, parts: &Parts) -> Vec<ValidationWarning> {
+        let mut warnings = self.validate();
+
+        if let Some(status) = self.status {
+            if status != parts.status {
+                warnings.push(ValidationWarning::StatusMismatch {
+                    problem: status,
+                    response: parts.status,
+                });
+            }
+        }
+
+        let content_type = parts
+            .headers
+            .get(header::CONTENT_TYPE)
+            .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned());
+        let is_problem_type = content_type.as_deref().is_some_and(|content_type| {
+            let essence = content_type.split(';').next().unwrap_or_default().trim();
+            PROBLEM_CONTENT_TYPES
+                .iter()
+                .any(|problem_type| essence.eq_ignore_ascii_case(problem_type))
+        });
+
+        if !is_problem_type {
+            warnings.push(ValidationWarning::ContentTypeMismatch(content_type));
+        }
+
+        warnings
+    }
+}
@@ -0,0 +1,242 @@
+//! Warp reply types for [`ProblemDetails`]. Requires feature `warp` (implies `json` and `http02`).
+//!
+//! With the `warp` feature enabled, [`ProblemDetails`] implements [`Reply`] using
+//! [`JsonProblemDetails`]. You can also return [`JsonProblemDetails`] to be specific.
+//! If you want to return XML, you can use [`XmlProblemDetails`] (requires feature `xml`).
+//! Concise problem details in CBOR are returned using `CborProblemDetails` (requires feature `cbor`).
+//! YAML is returned using `YamlProblemDetails` (requires feature `yaml`).
+//! Browsers can be served an HTML page using `HtmlProblemDetails` (requires feature `html`).
+//! As last resort, [`TextProblemDetails`] returns a plain text body.
+//!
+//! Requests not matched by any filter end in a [`Rejection`], which warp answers with a
+//! plain text response. Pass [`recover`] to [`Filter::recover`](::warp::Filter::recover)
+//! to answer the built-in rejections with problems instead.
+//!
+//! # Example
+//!
+//! ```rust
+//! use http::StatusCode;
+//! use problem_details::ProblemDetails;
+//! use warp::Filter;
+//!
+//! let routes = warp::path("teapot")
+//!     .and(warp::get())
+//!     .map(|| {
+//!         // always return a problem description
+//!         ProblemDetails::from_status_code(StatusCode::IM_A_TEAPOT)
+//!             .with_detail("short and stout")
+//!     })
+//!     .recover(problem_details::warp::recover);
+//! # let _routes = routes;
+//! ```
+use std::error::Error;
+
+use http::{HeaderMap, StatusCode};
+use warp::body::BodyDeserializeError;
+use warp::http::{header, HeaderValue};
+use warp::hyper::Body;
+use warp::reject::{
+    InvalidHeader, InvalidQuery, LengthRequired, MethodNotAllowed, MissingCookie, MissingHeader,
+    PayloadTooLarge, UnsupportedMediaType,
+};
+use warp::reply::{Reply, Response};
+use warp::Rejection;
+
+use crate::http02::{to_http02_header_map, to_http02_status_code};
+use crate::{JsonProblemDetails, ProblemDetails, TextProblemDetails};
+
+#[cfg(feature = "xml")]
+use crate::XmlProblemDetails;
+
+#[cfg(feature = "cbor")]
+use crate::CborProblemDetails;
+
+#[cfg(feature = "yaml")]
+use crate::YamlProblemDetails;
+
+#[cfg(feature = "html")]
+use crate::HtmlProblemDetails;
+
+impl<Ext> Reply for JsonProblemDetails<Ext>
+where
+    Ext: serde::Serialize + Send,
+{
+    fn into_response(self) -> Response {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = match Self(details).to_body_bytes() {
+            Ok(json) => json,
+            Err(_) => return warp::http::StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        };
+
+        problem_response(status_code, headers, Self::CONTENT_TYPE, content)
+    }
+}
+
+#[cfg(feature = "xml")]
+impl<Ext> Reply for XmlProblemDetails<Ext>
+where
+    Ext: serde::Serialize + Send,
+{
+    fn into_response(self) -> Response {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = match Self(details).to_body_bytes() {
+            Ok(xml) => xml,
+            Err(_) => return warp::http::StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        };
+
+        problem_response(status_code, headers, Self::CONTENT_TYPE, content)
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl<Ext> Reply for CborProblemDetails<Ext>
+where
+    Ext: serde::Serialize + Send,
+{
+    fn into_response(self) -> Response {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = match Self(details).to_body_vec() {
+            Ok(cbor) => cbor,
+            Err(_) => return warp::http::StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        };
+
+        problem_response(status_code, headers, Self::CONTENT_TYPE, content)
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl<Ext> Reply for YamlProblemDetails<Ext>
+where
+    Ext: serde::Serialize + Send,
+{
+    fn into_response(self) -> Response {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = match Self(details).to_body_string() {
+            Ok(yaml) => yaml,
+            Err(_) => return warp::http::StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        };
+
+        problem_response(status_code, headers, Self::CONTENT_TYPE, content)
+    }
+}
+
+#[cfg(feature = "html")]
+impl<Ext> Reply for HtmlProblemDetails<Ext>
+where
+    Ext: serde::Serialize + Send,
+{
+    fn into_response(self) -> Response {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = match Self(details).to_body_string() {
+            Ok(html) => html,
+            Err(_) => return warp::http::StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        };
+
+        problem_response(status_code, headers, Self::CONTENT_TYPE, content)
+    }
+}
+
+impl<Ext> Reply for TextProblemDetails<Ext>
+where
+    Ext: serde::Serialize + Send,
+{
+    fn into_response(self) -> Response {
+        let mut details = self.0.with_configured_default_type();
+        let headers = details.headers.take().unwrap_or_default();
+        let status_code = details.status_or_default();
+        let content = Self(details).to_body_string();
+
+        problem_response(status_code, headers, Self::CONTENT_TYPE, content)
+    }
+}
+
+impl<Ext> Reply for ProblemDetails<Ext>
+where
+    Ext: serde::Serialize + Send,
+{
+    fn into_response(self) -> Response {
+        JsonProblemDetails(self).into_response()
+    }
+}
+
+/// Converts the built-in rejections of warp into JSON problems.
+///
+/// Unmatched paths result in `404 Not Found`, unmatched methods in `405 Method Not Allowed`.
+/// Invalid query strings and bodies, missing or invalid headers and cookies, as well as
+/// body size and content type rejections use the status chosen by warp, with the reason
+/// as detail. Custom rejections are passed on, so they can be handled by another
+/// recovery filter.
+///
+/// ```rust
+/// use warp::Filter;
+///
+/// let routes = warp::path!("orders" / u32)
+///     .and(warp::get())
+///     .map(|id| format!("Order {id}"))
+///     .recover(problem_details::warp::recover);
+/// # let _routes = routes;
+/// ```
+pub async fn recover(rejection: Rejection) -> Result<JsonProblemDetails, Rejection> {
+    if rejection.is_not_found() {
+        return Ok(JsonProblemDetails(ProblemDetails::from_status_code(
+            StatusCode::NOT_FOUND,
+        )));
+    }
+
+    let details = if let Some(error) = rejection.find::<InvalidQuery>() {
+        client_error(StatusCode::BAD_REQUEST, error)
+    } else if let Some(error) = rejection.find::<BodyDeserializeError>() {
+        match error.source() {
+            Some(source) => client_error(StatusCode::BAD_REQUEST, source),
+            None => client_error(StatusCode::BAD_REQUEST, error),
+        }
+    } else if let Some(error) = rejection.find::<MissingHeader>() {
+        client_error(StatusCode::BAD_REQUEST, error)
+    } else if let Some(error) = rejection.find::<InvalidHeader>() {
+        client_error(StatusCode::BAD_REQUEST, error)
+    } else if let Some(error) = rejection.find::<MissingCookie>() {
+        client_error(StatusCode::BAD_REQUEST, error)
+    } else if let Some(error) = rejection.find::<LengthRequired>() {
+        client_error(StatusCode::LENGTH_REQUIRED, error)
+    } else if let Some(error) = rejection.find::<PayloadTooLarge>() {
+        client_error(StatusCode::PAYLOAD_TOO_LARGE, error)
+    } else if let Some(error) = rejection.find::<UnsupportedMediaType>() {
+        client_error(StatusCode::UNSUPPORTED_MEDIA_TYPE, error)
+    } else if rejection.find::<MethodNotAllowed>().is_some() {
+        // Checked last, as other filters of the same path may have rejected for another reason
+        ProblemDetails::from_status_code(StatusCode::METHOD_NOT_ALLOWED)
+    } else {
+        return Err(rejection);
+    };
+
+    Ok(JsonProblemDetails(details))
+}
+
+fn client_error(status: StatusCode, error: &dyn Error) -> ProblemDetails {
+    ProblemDetails::from_status_code(status).with_detail(error.to_string())
+}
+
+fn problem_response(
+    status_code: StatusCode,
+    headers: HeaderMap,
+    content_type: &'static str,
+    content: impl Into<Body>,
+) -> Response {
+    let mut response = Response::new(content.into());
+    *response.status_mut() = to_http02_status_code(status_code);
+    *response.headers_mut() = to_http02_header_map(&headers);
+    response
+        .headers_mut()
+        .insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    response
+}
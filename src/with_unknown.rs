@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::MergeExtensions;
+
+/// Typed extensions which keep all unknown extension members.
+///
+/// When deserializing, the members known to `Ext` are deserialized into
+/// [`known`](WithUnknown::known), and all other extension members are
+/// collected into [`unknown`](WithUnknown::unknown). Both are written when
+/// serializing, so foreign extensions survive a round-trip, e.g. in proxies.
+///
+/// `Ext` should be a struct; map-like extensions already capture all members.
+///
+/// ```rust
+/// use problem_details::{ProblemDetails, WithUnknown};
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Balance {
+///     balance: u32,
+/// }
+///
+/// let json = serde_json::json!({
+///     "status": 403,
+///     "balance": 30,
+///     "accounts": ["/account/12345"]
+/// });
+///
+/// let details: ProblemDetails<WithUnknown<Balance>> =
+///     serde_json::from_value(json.clone()).unwrap();
+///
+/// assert_eq!(details.extensions.known.balance, 30);
+/// assert!(details.extensions.unknown.contains_key("accounts"));
+/// assert_eq!(serde_json::to_value(&details).unwrap(), json);
+/// ```
+///
+/// Requires feature `json`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct WithUnknown<Ext> {
+    /// The typed extension members.
+    #[serde(flatten)]
+    pub known: Ext,
+
+    /// All extension members not known to `Ext`.
+    ///
+    /// The members are serialized sorted by key, so the output is stable.
+    #[serde(flatten, serialize_with = "serialize_sorted")]
+    pub unknown: HashMap<String, Value>,
+}
+
+impl<Ext> WithUnknown<Ext> {
+    /// Creates typed extensions without unknown members.
+    pub fn new(known: Ext) -> Self {
+        Self {
+            known,
+            unknown: HashMap::new(),
+        }
+    }
+
+    /// Returns the typed extensions, discarding the unknown members.
+    pub fn into_known(self) -> Ext {
+        self.known
+    }
+}
+
+impl<Ext> From<Ext> for WithUnknown<Ext> {
+    fn from(known: Ext) -> Self {
+        Self::new(known)
+    }
+}
+
+impl<Ext> std::ops::Deref for WithUnknown<Ext> {
+    type Target = Ext;
+
+    fn deref(&self) -> &Ext {
+        &self.known
+    }
+}
+
+impl<Ext> std::ops::DerefMut for WithUnknown<Ext> {
+    fn deref_mut(&mut self) -> &mut Ext {
+        &mut self.known
+    }
+}
+
+impl<Ext> MergeExtensions for WithUnknown<Ext>
+where
+    Ext: MergeExtensions,
+{
+    fn merge_extensions(&mut self, other: Self) {
+        self.known.merge_extensions(other.known);
+        self.unknown.merge_extensions(other.unknown);
+    }
+}
+
+fn serialize_sorted<S>(unknown: &HashMap<String, Value>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let mut members = unknown.iter().collect::<Vec<_>>();
+    members.sort_unstable_by_key(|(key, _)| *key);
+    serializer.collect_map(members)
+}
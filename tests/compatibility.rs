@@ -0,0 +1,57 @@
+//! The process-wide RFC 7807 compatibility mode changes all response representations.
+#![cfg(feature = "json")]
+
+use http::StatusCode;
+use problem_details::{CompatibilityMode, DynamicExtensions, JsonProblemDetails, ProblemDetails};
+
+fn details() -> ProblemDetails<DynamicExtensions> {
+    ProblemDetails::from_status_code(StatusCode::NOT_FOUND)
+        .with_extension("resource", serde_json::Value::Null)
+}
+
+#[tokio::test]
+async fn compatibility_mode() {
+    problem_details::set_compatibility_mode(CompatibilityMode::Rfc7807).unwrap();
+
+    let body = JsonProblemDetails::from(details())
+        .to_body_string()
+        .unwrap();
+    assert_eq!(
+        body,
+        r#"{"type":"about:blank","status":404,"title":"Not Found"}"#
+    );
+
+    #[cfg(feature = "axum")]
+    {
+        use axum::response::IntoResponse;
+
+        let response = details().into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(
+            body,
+            r#"{"type":"about:blank","status":404,"title":"Not Found"}"#
+        );
+    }
+
+    #[cfg(all(feature = "axum", feature = "xml"))]
+    {
+        use axum::response::IntoResponse;
+        use problem_details::XmlProblemDetails;
+
+        let response = XmlProblemDetails::from(details()).into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(
+            body,
+            concat!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+                r#"<problem xmlns="urn:ietf:rfc:7807">"#,
+                "<type>about:blank</type><status>404</status><title>Not Found</title>",
+                "</problem>"
+            )
+        );
+    }
+}
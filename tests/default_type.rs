@@ -0,0 +1,42 @@
+//! The process-wide default type is applied to responses by the framework integrations.
+#![cfg(feature = "json")]
+
+use http::{StatusCode, Uri};
+use problem_details::{JsonProblemDetails, ProblemDetails};
+
+#[tokio::test]
+async fn default_type() {
+    problem_details::set_default_type(Uri::from_static("https://example.com/probs/generic"))
+        .unwrap();
+
+    // Serializing the body directly leaves the problem unchanged
+    let body = JsonProblemDetails::from(ProblemDetails::from_status_code(StatusCode::NOT_FOUND))
+        .to_body_string()
+        .unwrap();
+    assert_eq!(body, r#"{"status":404,"title":"Not Found"}"#);
+
+    #[cfg(feature = "axum")]
+    {
+        use axum::response::IntoResponse;
+
+        let response = ProblemDetails::from_status_code(StatusCode::NOT_FOUND).into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(
+            body,
+            r#"{"type":"https://example.com/probs/generic","status":404,"title":"Not Found"}"#
+        );
+
+        let response = ProblemDetails::from_status_code(StatusCode::NOT_FOUND)
+            .with_type(Uri::from_static("https://example.com/probs/specific"))
+            .into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(
+            body,
+            r#"{"type":"https://example.com/probs/specific","status":404,"title":"Not Found"}"#
+        );
+    }
+}
@@ -0,0 +1,38 @@
+//! The process-wide extension filter applies to all response representations.
+#![cfg(feature = "json")]
+
+use http::StatusCode;
+use problem_details::{DynamicExtensions, ExtensionFilter, JsonProblemDetails, ProblemDetails};
+
+fn details() -> ProblemDetails<DynamicExtensions> {
+    ProblemDetails::from_status_code(StatusCode::INTERNAL_SERVER_ERROR)
+        .with_extension("trace_id", "abc123")
+        .with_extension("debug_sql", "SELECT * FROM users")
+}
+
+#[tokio::test]
+async fn extension_filter() {
+    problem_details::set_extension_filter(ExtensionFilter::deny(["debug_sql"])).unwrap();
+
+    let body = JsonProblemDetails::from(details())
+        .to_body_string()
+        .unwrap();
+    assert_eq!(
+        body,
+        r#"{"status":500,"title":"Internal Server Error","trace_id":"abc123"}"#
+    );
+
+    #[cfg(feature = "axum")]
+    {
+        use axum::response::IntoResponse;
+
+        let response = details().into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(
+            body,
+            r#"{"status":500,"title":"Internal Server Error","trace_id":"abc123"}"#
+        );
+    }
+}
@@ -0,0 +1,51 @@
+//! The process-wide HTML template renders HTML responses.
+#![cfg(feature = "html")]
+
+use http::StatusCode;
+use problem_details::{
+    escape_html, HtmlPage, HtmlProblemDetails, JsonProblemDetails, ProblemDetails,
+};
+
+fn details() -> ProblemDetails {
+    ProblemDetails::from_status_code(StatusCode::NOT_FOUND).with_detail("<missing>")
+}
+
+const PAGE: &str = "<h1>404 Not Found</h1><p>&lt;missing&gt;</p>";
+
+#[tokio::test]
+async fn html_template() {
+    let template = |page: &HtmlPage| {
+        format!(
+            "<h1>{} {}</h1><p>{}</p>",
+            page.status.as_u16(),
+            escape_html(&page.title),
+            escape_html(page.detail.as_deref().unwrap_or_default())
+        )
+    };
+    assert!(problem_details::set_html_template(template).is_ok());
+
+    let body = HtmlProblemDetails::from(details())
+        .to_body_string()
+        .unwrap();
+    assert_eq!(body, PAGE);
+
+    // Other representations are not affected
+    let body = JsonProblemDetails::from(details())
+        .to_body_string()
+        .unwrap();
+    assert_eq!(
+        body,
+        r#"{"status":404,"title":"Not Found","detail":"<missing>"}"#
+    );
+
+    #[cfg(feature = "axum")]
+    {
+        use axum::response::IntoResponse;
+
+        let response = HtmlProblemDetails::from(details()).into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, PAGE);
+    }
+}
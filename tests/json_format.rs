@@ -0,0 +1,39 @@
+//! The process-wide JSON format changes the layout of JSON responses.
+#![cfg(feature = "json")]
+
+use http::StatusCode;
+use problem_details::{DynamicExtensions, JsonFormat, JsonProblemDetails, ProblemDetails};
+
+fn details() -> ProblemDetails<DynamicExtensions> {
+    ProblemDetails::from_status_code(StatusCode::CONFLICT)
+        .with_extension("code", "E1")
+        .with_extension("zone", "eu")
+}
+
+const PRETTY: &str = r#"{
+  "status": 409,
+  "title": "Conflict",
+  "code": "E1",
+  "zone": "eu"
+}"#;
+
+#[tokio::test]
+async fn json_format() {
+    problem_details::set_json_format(JsonFormat::Pretty).unwrap();
+
+    let body = JsonProblemDetails::from(details())
+        .to_body_string()
+        .unwrap();
+    assert_eq!(body, PRETTY);
+
+    #[cfg(feature = "axum")]
+    {
+        use axum::response::IntoResponse;
+
+        let response = details().into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, PRETTY);
+    }
+}
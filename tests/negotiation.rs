@@ -0,0 +1,57 @@
+//! The process-wide content negotiation selects the format of negotiated responses.
+#![cfg(feature = "json")]
+
+use http::{header, HeaderMap, HeaderValue, StatusCode};
+use problem_details::{
+    AcceptedFormat, ContentNegotiation, JsonProblemDetails, ProblemDetails, ProblemFormat,
+};
+
+#[tokio::test]
+async fn content_negotiation() {
+    problem_details::set_content_negotiation(
+        ContentNegotiation::new()
+            .with_formats([ProblemFormat::Json, ProblemFormat::Text])
+            .with_default(ProblemFormat::Text),
+    )
+    .unwrap();
+
+    let mut headers = HeaderMap::new();
+    headers.insert(header::ACCEPT, HeaderValue::from_static("image/png"));
+    assert_eq!(
+        AcceptedFormat::from_headers(&headers),
+        AcceptedFormat(ProblemFormat::Text)
+    );
+
+    // Explicit JSON responses are not negotiated
+    let body = JsonProblemDetails::from(ProblemDetails::from_status_code(StatusCode::NOT_FOUND))
+        .to_body_string()
+        .unwrap();
+    assert_eq!(body, r#"{"status":404,"title":"Not Found"}"#);
+
+    #[cfg(feature = "axum")]
+    {
+        use axum::response::IntoResponse;
+        use problem_details::NegotiatedProblemDetails;
+
+        let response = NegotiatedProblemDetails::from_headers(
+            ProblemDetails::from_status_code(StatusCode::NOT_FOUND),
+            &headers,
+        )
+        .into_response();
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            ProblemFormat::Text.content_type()
+        );
+
+        headers.insert(header::ACCEPT, HeaderValue::from_static("application/json"));
+        let response = NegotiatedProblemDetails::from_headers(
+            ProblemDetails::from_status_code(StatusCode::NOT_FOUND),
+            &headers,
+        )
+        .into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, r#"{"status":404,"title":"Not Found"}"#);
+    }
+}
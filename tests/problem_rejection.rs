@@ -0,0 +1,77 @@
+//! The `#[problem_rejection]` attribute wraps extractors of other crates.
+#![cfg(feature = "axum-macros")]
+
+use axum::body::Body;
+use axum::extract::{Json, Query};
+use axum::routing::{get, post};
+use axum::Router;
+use http::{header, Request, StatusCode};
+use problem_details::axum::problem_rejection;
+use tower_service::Service;
+
+/// Query string whose rejections are problems.
+#[problem_rejection(type = "https://example.com/probs/invalid-query", status = 422)]
+pub struct ProblemQuery<T>(pub Query<T>);
+
+/// JSON body whose rejections are problems.
+#[problem_rejection(status = StatusCode::BAD_REQUEST, source = Body)]
+struct ProblemJson<T>(Json<T>);
+
+#[derive(serde::Deserialize)]
+struct Page {
+    page: u32,
+}
+
+async fn call(app: &mut Router, request: Request<Body>) -> (StatusCode, serde_json::Value) {
+    std::future::poll_fn(|cx| Service::<Request<Body>>::poll_ready(app, cx))
+        .await
+        .unwrap();
+    let response = app.call(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or_default())
+}
+
+#[tokio::test]
+async fn problem_rejection() {
+    let mut app =
+        Router::new()
+            .route(
+                "/query",
+                get(
+                    |ProblemQuery(Query(query)): ProblemQuery<Page>| async move {
+                        query.page.to_string()
+                    },
+                ),
+            )
+            .route(
+                "/json",
+                post(|ProblemJson(Json(value)): ProblemJson<Page>| async move {
+                    value.page.to_string()
+                }),
+            );
+
+    let request = Request::get("/query?page=1").body(Body::empty()).unwrap();
+    let (status, _) = call(&mut app, request).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let request = Request::get("/query?page=x").body(Body::empty()).unwrap();
+    let (status, body) = call(&mut app, request).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["type"], "https://example.com/probs/invalid-query");
+    assert_eq!(body["status"], 422);
+    assert_eq!(body["rejection"], "QueryRejection");
+    assert_eq!(body["source"], "request");
+
+    let request = Request::post("/json")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from("{"))
+        .unwrap();
+    let (status, body) = call(&mut app, request).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body.get("type").is_none());
+    assert_eq!(body["rejection"], "JsonRejection");
+    assert_eq!(body["source"], "body");
+}
@@ -0,0 +1,50 @@
+//! The process-wide XML format changes the layout of XML responses.
+#![cfg(feature = "xml")]
+
+use http::StatusCode;
+use problem_details::{ProblemDetails, XmlFormat, XmlProblemDetails};
+
+const LEGACY: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+    "<problem>",
+    "<status>400</status><title>Bad Request</title>",
+    "<roles>admin</roles><roles>editor</roles>",
+    "</problem>"
+);
+
+fn details() -> ProblemDetails<std::collections::HashMap<&'static str, Vec<&'static str>>> {
+    ProblemDetails::from_status_code(StatusCode::BAD_REQUEST)
+        .with_extensions([("roles", vec!["admin", "editor"])].into())
+}
+
+#[tokio::test]
+async fn xml_format() {
+    problem_details::set_xml_format(XmlFormat::Legacy).unwrap();
+
+    let body = XmlProblemDetails::from(details()).to_body_string().unwrap();
+    assert_eq!(body, LEGACY);
+
+    #[cfg(feature = "json")]
+    {
+        use problem_details::JsonProblemDetails;
+
+        let body = JsonProblemDetails::from(details())
+            .to_body_string()
+            .unwrap();
+        assert_eq!(
+            body,
+            r#"{"status":400,"title":"Bad Request","roles":["admin","editor"]}"#
+        );
+    }
+
+    #[cfg(feature = "axum")]
+    {
+        use axum::response::IntoResponse;
+
+        let response = XmlProblemDetails::from(details()).into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, LEGACY);
+    }
+}